@@ -0,0 +1,538 @@
+# German UI strings. Keys missing here fall back to English (en.ftl).
+
+app-title = Note Squirrel - { $note } ({ $vault })
+app-title-hidden = Note Squirrel
+no-note = Keine Notiz
+
+## Default date formats (strftime syntax)
+format-date = %d.%m.%Y
+format-time = %H:%M
+format-datetime = %d.%m.%Y %H:%M
+format-week = KW %V %G
+
+## Menus
+menu-file = Datei
+menu-view = Ansicht
+menu-help = Hilfe
+menu-keyboard-shortcuts = Tastenkürzel
+shortcuts-title = Tastenkürzel
+setup-title = Willkommen bei Note Squirrel
+setup-step = Schritt { $current } von { $total }
+setup-welcome = Richten wir alles ein
+setup-notes-folder-intro = Notizen sind einfache Markdown-Dateien in einem Ordner deiner Wahl. Er wird angelegt, falls er noch nicht existiert.
+setup-theme-intro = Wähle ein Design. Es lässt sich, wie jede Farbe, später in den Einstellungen ändern.
+setup-fonts-intro = Wähle die Schriftarten für Editor, Notizliste und Vorschau.
+setup-import-intro = Du hast schon Markdown-Notizen? Sie können jetzt in deinen Notizordner kopiert werden.
+setup-import-enabled = Vorhandenen Ordner mit Markdown-Dateien importieren
+setup-import-folder = Ordner
+setup-back = Zurück
+setup-next = Weiter
+setup-finish = Fertig
+setup-skip = Überspringen und Standards verwenden
+import-drop-hint = Markdown-Dateien oder Ordner zum Importieren ablegen
+import-conflicts-title = Importkonflikte
+import-conflicts-intro = Diese Dateien heißen wie bereits vorhandene Notizen:
+import-keep-both = Beide behalten
+import-replace = Vorhandene ersetzen
+import-merge = Im externen Tool zusammenführen
+import-skip = Überspringen
+import-apply-to-all = Erste Auswahl für alle übernehmen
+import-import = Importieren
+menu-tools = Werkzeuge
+menu-register-links = notesquirrel://-Links registrieren
+menu-settings = Einstellungen…
+menu-open-in-new-window = In neuem Fenster öffnen
+menu-new-folder = Neuer Ordner
+menu-note-history = Versionsverlauf
+menu-vaults = Tresore
+menu-manage-vaults = Tresore verwalten…
+menu-version-control = Versionskontrolle
+menu-git-not-a-repository = Der Notizordner ist kein Git-Repository.
+menu-git-enable = Notizen mit Git verfolgen
+menu-git-commit-now = Jetzt committen
+menu-git-log = Git-Log dieser Notiz
+menu-git-autocommit = Automatisch committen:
+menu-git-commit-minutes = Minuten zwischen Commits
+menu-git-last-commit = Letzter Commit: { $commit }
+menu-recently-closed = Zuletzt geschlossen
+menu-recently-closed-deleted = { $note } (gelöscht)
+menu-todays-note = Heutige Notiz
+menu-daily-notes-calendar = Kalender…
+menu-export-settings = Einstellungen exportieren…
+menu-import-settings = Einstellungen importieren…
+menu-zoom-in = Vergrößern
+menu-zoom-out = Verkleinern
+menu-reset-zoom = Zoom zurücksetzen
+menu-ui-scale = Skalierung: { $percent }%
+menu-show-sidebar = Seitenleiste anzeigen
+menu-show-preview = Vorschau anzeigen
+menu-focus-mode = Fokusmodus
+menu-always-on-top = Immer im Vordergrund
+menu-scratchpad = Notizblock
+menu-hide-notes = Notizen verbergen
+menu-zen-mode = Zen-Modus
+menu-present = Präsentieren
+menu-tasks = Aufgaben
+menu-global-search = Alle Notizen durchsuchen
+menu-unused-attachments = Unbenutzte Anhänge…
+menu-vault-statistics = Statistik…
+menu-vault-index = Notizindex…
+
+## Common buttons
+button-ok = OK
+button-apply = Übernehmen
+button-cancel = Abbrechen
+button-close = Schließen
+button-yes = Ja
+button-no = Nein
+
+## Sidebar
+sort-alphabetical = A-Z
+sort-recent = Zuletzt
+menu-serve-note = Notiz im Netzwerk bereitstellen
+menu-export-note = Notiz exportieren…
+menu-export-selection = Auswahl exportieren…
+menu-share-note = Teilen…
+menu-stop-serving = Bereitstellung von { $note } beenden
+serve-title = Notiz wird bereitgestellt
+serve-note = { $note } wird bereitgestellt unter:
+serve-copy-url = Kopieren
+serve-hint = Öffne diese Adresse auf einem Handy oder einem anderen Computer im selben Netzwerk. Die Seite lädt neu, wenn die Notiz gespeichert wird.
+serve-stop = Bereitstellung beenden
+profiler-title = Profiler
+profiler-frame = Frame: { $average } ms Schnitt, { $worst } ms Maximum
+profiler-parse = Markdown-Parsing: { $time } ms
+profiler-layout = Editor-Layout: { $time } ms
+profiler-io = Datei-I/O: { $reads } gelesen, { $writes } geschrieben, { $time } ms
+profiler-undo = Rückgängig-Verlauf: { $size } KiB
+editor-copy-as-html = Als HTML kopieren
+editor-table-paste-offer = Die eingefügten Zeilen sehen wie eine Tabelle aus.
+editor-table-paste-convert = In Tabelle umwandeln
+editor-highlighting = Hervorhebung läuft…
+preview-large-note = Diese Notiz ist { $size } MB groß und wird nicht automatisch in der Vorschau angezeigt.
+preview-render-anyway = Trotzdem anzeigen
+export-title = Notiz exportieren
+export-note = „{ $note }“ wird exportiert
+export-selection-title = Auswahl exportieren
+export-selection = Der markierte Text aus „{ $note }“ wird exportiert
+export-copy-html = Als HTML kopieren
+export-format = Format:
+export-file = Datei:
+export-button = Exportieren
+export-no-pandoc = Installiere pandoc oder setze pandoc_path in config.toml, um ODT, EPUB, reStructuredText und Org zu exportieren.
+share-title = Notiz teilen
+share-note = „{ $note }“ aus deiner Mail-App versenden
+share-attach-html = Als HTML anhängen
+share-attach-unsupported = Auf dieser Plattform kann deine Mail-App die Notiz nur als Text übernehmen.
+share-email = E-Mail
+status-words = { $words } Wörter
+status-word-goal-progress = { $words } / { $goal } Wörter ({ $percent } %)
+status-word-goal = Ziel…
+status-word-goal-label = Wortziel:
+status-word-goal-set = Ziel setzen
+status-word-goal-clear = Ziel entfernen
+status-word-goal-hint = Wird als word_goal im Front Matter der Notiz gespeichert.
+status-unclosed-fence = Nicht geschlossener ```-Block in Zeile { $line }
+breadcrumb-jump = Zu dieser Überschrift springen
+sidebar-loading-notes = Notizen werden geladen… { $done }/{ $total }
+sidebar-search-hint = Notizen durchsuchen, Notiz > Überschrift, @Überschrift
+
+## Presentation
+presentation-counter = { $current } / { $total }
+
+## Preview
+preview-placeholder = Beginne zu tippen, um deine gerenderten Notizen (Markdown) zu sehen...
+
+## Delete dialog
+delete-note-title = Notiz löschen
+delete-note-confirm = Möchtest du '{ $note }' wirklich löschen?
+external-link-title = Link öffnen
+external-link-confirm = Diese Adresse im Browser öffnen?
+external-link-open = Öffnen
+toast-note-deleted = '{ $note }' gelöscht
+toast-undo = Rückgängig
+toast-dismiss = Schließen
+switcher-hint = Strg loslassen zum Öffnen, Escape zum Abbrechen
+sidebar-tags = Tags
+sidebar-tag-filter = Mit Tag #{ $tag }
+sidebar-clear-tag-filter = Alle Notizen anzeigen
+sidebar-recently-deleted = Zuletzt gelöscht ({ $count })
+sidebar-recently-deleted-more = und { $count } ältere im Ordner .trash
+sidebar-restore-note = Notiz wiederherstellen
+sidebar-deleted-at = Gelöscht am { $time }
+notes-alias-match = auch bekannt als „{ $alias }“
+notes-new-folder = Neuer Ordner
+notes-new-note-here = Neue Notiz hier
+notes-new-folder-here = Neuer Ordner hier
+notes-rename-folder = Ordner umbenennen
+notes-move-to = Verschieben nach
+notes-top-level = Oberste Ebene
+preview-tag-hint = Nur Notizen mit diesem Tag anzeigen
+preview-copy-link = Link kopieren
+scratchpad-title = Notizblock
+scratchpad-hint = Wird automatisch gespeichert und erscheint nicht in der Notizliste
+scratchpad-close = Notizblock schließen
+
+## Error dialog
+config-errors-title = Konfigurationsfehler
+config-errors-intro = Beim Laden der Konfiguration sind folgende Fehler aufgetreten:
+
+## View modes
+view-mode-split = Editor und Vorschau
+view-mode-editor-only = Nur Editor
+view-mode-preview-only = Nur Vorschau
+split-side-by-side = Vorschau neben dem Editor
+split-stacked = Vorschau unter dem Editor
+
+## Themes
+theme-dark = Dunkel
+theme-light = Hell
+theme-high-contrast = Hoher Kontrast
+theme-colorblind-dark = Farbenblind-freundlich dunkel
+theme-colorblind-light = Farbenblind-freundlich hell
+
+## Find & replace
+find-replace-title = Suchen & Ersetzen
+find-label = Suchen:
+find-hint = Suchtext eingeben...
+replace-label = Ersetzen:
+replace-hint = Ersetzungstext eingeben...
+find-match-case = Groß-/Kleinschreibung
+find-regex = Regex
+find-no-matches = Keine Treffer
+find-match-position = { $current } von { $total }
+find-match-count = { $total } Treffer
+find-replace-all = Alle ersetzen
+find-replace = Ersetzen
+find-previous = Zurück
+find-next = Weiter
+find-scope-current = Diese Notiz
+find-scope-all = Alle Notizen
+find-all-notes-count = { $total } Treffer in { $notes } Notiz(en)
+find-note-replacements = { $count } Ersetzung(en)
+find-replace-all-notes = In allen Notizen ersetzen
+find-replaced-notes = In { $count } Notiz(en) ersetzt:
+find-undo-note = Rückgängig
+
+## Tasks
+global-search-title = Alle Notizen durchsuchen
+global-search-hint = Text, der in allen Notizen gesucht wird
+global-search-match-case = Groß-/Kleinschreibung beachten
+global-search-count = { $matches } Treffer in { $notes } Notiz(en)
+global-search-truncated = (nur die ersten werden angezeigt)
+global-search-none = Keine Notiz enthält diesen Text.
+
+note-conflict-title = Notiz anderswo geändert
+note-conflict-intro = „{ $note }“ wurde von einem anderen Programm geändert, während du sie bearbeitet hast. Deine Änderungen sind noch nicht gespeichert.
+note-conflict-more = Danach noch { $count } weitere Notiz(en).
+note-conflict-changed = { $count } Zeile(n) unterscheiden sich
+note-conflict-mine = Deine Version
+note-conflict-theirs = Version auf der Festplatte
+note-conflict-keep-mine = Meine behalten
+note-conflict-take-theirs = Ihre übernehmen
+note-conflict-merge = Von Hand zusammenführen
+note-conflict-merge-tool = Im Merge-Tool öffnen
+note-conflict-merge-hint = Bearbeite den Text unten zur Version, die bleiben soll, und entferne die Zeilen <<<<<<<, ======= und >>>>>>>.
+note-conflict-save-merged = Zusammengeführte speichern
+note-conflict-back = Zurück
+
+note-history-title = Verlauf von „{ $note }“
+note-history-none = Von dieser Notiz gibt es noch keine früheren Versionen. Schalte den Verlauf unter Einstellungen → Tresor ein, um sie zu behalten.
+note-history-select = Wähle links eine Version, um sie mit der Notiz zu vergleichen.
+note-history-snapshot = Version vom { $time }
+note-history-current = Aktuelle Version
+note-history-restore = Diese Version wiederherstellen
+note-history-read-failed = Diese Version konnte nicht gelesen werden
+
+vaults-title = Tresore
+vaults-active = Geöffnet
+vaults-open = Wechseln
+vaults-remove = Entfernen
+vaults-remove-hint = Den Ordner von der Liste nehmen; seine Notizen bleiben unberührt
+vaults-add-label = Notizordner hinzufügen:
+vaults-add-hint = Vollständiger Pfad zu einem Ordner
+vaults-add = Hinzufügen
+folder-picker-title = Ordner auswählen
+folder-picker-up = Nach oben
+folder-picker-empty = Keine Unterordner
+folder-picker-new-hint = Name des neuen Ordners
+folder-picker-create = Ordner erstellen
+folder-picker-choose = Diesen Ordner verwenden
+note-history-git-title = Git-Log von „{ $note }“
+note-history-no-commits = Noch kein Commit enthält diese Notiz.
+note-history-commit = Commit { $commit } vom { $time }
+git-autocommit-off = Aus
+git-autocommit-after-saving = Nach dem Speichern
+git-autocommit-interval = In festen Abständen
+tasks-title = Aufgaben
+tasks-filter-open = Offen
+tasks-filter-done = Erledigt
+tasks-filter-overdue = Überfällig
+tasks-filter-all = Alle
+tasks-refresh = Aktualisieren
+tasks-count = { $count } Aufgabe(n)
+tasks-none = Keine Aufgaben für diesen Filter.
+tasks-sort = Sortieren nach
+tasks-sort-note = Notiz
+tasks-sort-due = Fälligkeit
+tasks-sort-priority = Priorität
+task-priority-high = hoch
+task-priority-medium = mittel
+task-priority-low = niedrig
+
+## Reminders
+reminder-title = Aufgabe heute fällig
+reminder-body = { $task } ({ $note })
+
+## Daily notes calendar
+calendar-title = Kalender
+calendar-month = { $month } { $year }
+calendar-today = Heute
+calendar-hint = Unterstrichene Tage haben eine Tagesnotiz; an Tagen mit Punkt wurden Notizen angelegt oder bearbeitet. Doppelklicke einen Tag, um seine Tagesnotiz zu öffnen.
+calendar-open-daily-note = Tagesnotiz öffnen
+calendar-create-daily-note = Tagesnotiz anlegen
+calendar-created = Angelegt
+calendar-edited = Bearbeitet
+calendar-no-activity = An diesem Tag wurden keine Notizen angelegt oder bearbeitet.
+calendar-monday = Mo
+calendar-tuesday = Di
+calendar-wednesday = Mi
+calendar-thursday = Do
+calendar-friday = Fr
+calendar-saturday = Sa
+calendar-sunday = So
+calendar-january = Januar
+calendar-february = Februar
+calendar-march = März
+calendar-april = April
+calendar-may = Mai
+calendar-june = Juni
+calendar-july = Juli
+calendar-august = August
+calendar-september = September
+calendar-october = Oktober
+calendar-november = November
+calendar-december = Dezember
+
+## Vault statistics
+stats-title = Statistik
+stats-computing = Deine Notizen werden gezählt…
+stats-refresh = Aktualisieren
+stats-notes = Notizen
+stats-words = Wörter
+stats-activity = Aktivität (letzte 12 Wochen)
+stats-created = Erstellt
+stats-edited = Zuletzt bearbeitet
+stats-largest-notes = Größte Notizen
+stats-note-words = { $note } ({ $words } Wörter)
+stats-top-tags = Häufigste Tags
+stats-tag-count = #{ $tag } ({ $count })
+stats-no-tags = Noch keine #Tags.
+stats-writing = Schreiben (letzte 52 Wochen)
+stats-day-words = { $date }: { $words } Wörter geschrieben
+stats-words-today = Heute { $words } Wörter
+stats-current-streak = Aktuelle Serie: { $days } Tage
+stats-longest-streak = Längste Serie: { $days } Tage
+vault-index-title = Notizindex
+vault-index-note-description = Die Notiz „Vault Index“ verlinkt alle Notizen, nach #Tags gruppiert. Erneutes Schreiben aktualisiert sie.
+vault-index-write-note = Indexnotiz schreiben
+vault-index-csv-description = Oder exportiere die Liste der Notizen als CSV, mit Wortzahl, Änderungsdatum und Tags.
+vault-index-file = Datei:
+vault-index-export-csv = CSV exportieren
+vault-index-exported = { $count } Notizen nach { $path } exportiert
+
+## Unused attachments
+attachments-title = Unbenutzte Anhänge
+attachments-summary = { $count } nicht referenzierte Datei(en), insgesamt { $size }
+attachments-none-unused = Jeder Anhang wird von einer Notiz verwendet.
+attachments-select-all = Alle auswählen
+attachments-select-none = Auswahl aufheben
+attachments-refresh = Aktualisieren
+attachments-delete-selected = Auswahl löschen ({ $count })
+attachments-select-to-preview = Wähle eine Datei für die Vorschau aus.
+attachments-no-preview = Für diesen Dateityp ist keine Vorschau verfügbar.
+attachments-image-failed = Bild konnte nicht geladen werden.
+attachment-preview-close = Vorschau schließen
+attachment-preview-open = In Standard-App öffnen
+attachment-preview-rendering = Vorschau wird geladen…
+attachment-preview-image-failed = Bild konnte nicht geladen werden.
+attachment-preview-pdf-failed = Das PDF konnte nicht dargestellt werden: { $error }
+attachment-preview-no-pdftoppm = Für PDF-Vorschauen muss { $program } (aus poppler) in deinem PATH sein.
+
+## Settings
+settings-title = Einstellungen
+settings-tab-general = Allgemein
+settings-tab-editor = Editor
+settings-tab-preview = Vorschau-Stile
+settings-tab-keybindings = Tastenkürzel
+settings-tab-vault = Tresor
+settings-tab-templates = Vorlagen
+settings-language = Sprache
+settings-theme = Design
+settings-ui-scale = Skalierung
+settings-list-font = Schrift der Notizliste
+settings-list-font-size = Schriftgröße der Notizliste
+settings-editor-font = Editor-Schrift
+settings-editor-font-size = Editor-Schriftgröße
+settings-editor-max-line-width = Maximale Zeilenbreite
+settings-editor-max-line-width-hint = Zeichen, zentriert (0 = volle Breite)
+settings-focus-scope = Fokusmodus hebt hervor
+focus-scope-paragraph = Aktuellen Absatz
+focus-scope-sentence = Aktuellen Satz
+settings-autocorrect = Autokorrektur
+settings-autocorrect-enabled = Markdown-Fehler beim Tippen korrigieren
+settings-list-marker = Aufzählungszeichen:
+settings-editor-line-spacing = Zeilenabstand
+settings-zen-line-width = Zeilenbreite im Zen-Modus
+settings-zen-dim = Restlichen Bildschirm abdunkeln
+settings-block-spacing = Absatzabstand
+settings-preview-font = Vorschau-Schrift
+settings-preview-font-size = Vorschau-Schriftgröße
+settings-font-hint = Wähle eine eingebaute Schriftfamilie oder eine installierte Systemschrift. Schriften werden beim Übernehmen der Einstellungen neu geladen.
+settings-font-monospace = monospace (eingebaut)
+settings-font-proportional = proportional (eingebaut)
+settings-syntax-highlighting = Syntaxhervorhebung
+settings-highlight-rules = Eigene Hervorhebungsregeln (Regex)
+settings-highlight-text-color = Text
+settings-highlight-background = Hintergrund
+settings-highlight-remove = Regel entfernen
+settings-highlight-add = Regel hinzufügen
+settings-date-formats = Datumsformate
+settings-date-formats-hint = Verwendet strftime-Syntax, z. B. %d.%m.%Y oder %A, %e. %B. Leer lassen für die Vorgabe der Sprache.
+settings-format-date = Datum
+settings-format-time = Uhrzeit
+settings-format-datetime = Datum und Uhrzeit
+settings-format-week = Woche
+settings-keybindings-hint = Kürzel wie "Ctrl+Shift+F" oder "F3" eingeben. Ctrl entspricht unter macOS auch Cmd. Leer lassen, um die Belegung zu entfernen.
+settings-reset-keybindings = Standard wiederherstellen
+settings-global-hotkey = Systemweites Kürzel zum Ein- und Ausblenden
+settings-global-hotkey-hint = Holt NoteSquirrel aus jeder App nach vorne oder minimiert es, wenn es schon den Fokus hat. Unter Wayland nicht verfügbar. Leer lassen, um es auszuschalten.
+settings-keymap-preset = Vorlage
+settings-keymap-preset-apply = Vorlage anwenden
+keymap-preset-default = Standard
+keymap-preset-vscode = Wie VS Code
+keymap-preset-vim-lite = Vim-light
+keymap-preset-macos = macOS-typisch
+settings-notes-folder = Notizordner
+settings-confirm-delete = Vor dem Löschen einer Notiz nachfragen
+settings-confirm-external-links = Vor dem Öffnen von Weblinks aus der Vorschau nachfragen
+settings-privacy-screen-minutes = Notizen nach so vielen Minuten ohne Eingabe verbergen (0 = nie)
+settings-privacy-screen-on-focus-loss = Notizen verbergen, wenn das Fenster den Fokus verliert
+settings-delete-to-trash = Gelöschte Notizen in den Ordner .trash verschieben, statt sie zu entfernen
+settings-note-history-minutes = Höchstens alle so viele Minuten eine Version der Notiz sichern (0 = kein Verlauf)
+settings-note-history-limit = Gesicherte Versionen pro Notiz
+settings-task-reminders = Über heute fällige Aufgaben benachrichtigen
+settings-remember-note-views = Cursor- und Scrollposition jeder Notiz auch nach einem Neustart merken
+settings-notes-folder-hint = Die Notizen werden beim Übernehmen der Einstellungen aus dem neuen Ordner geladen.
+settings-note-extensions = Notizdateien:
+settings-new-note-extension = Neue Notizen:
+settings-note-extensions-hint = Durch Kommas getrennte Endungen der Dateien, die als Notizen aufgeführt werden, z. B. md, markdown, txt.
+settings-ignore-patterns = Ignorierte Dateien und Ordner, ein Muster pro Zeile:
+settings-ignore-patterns-hint = Passende Dateien und Ordner erscheinen nicht in der Notizliste, wie bei einer .gitignore. Ein Muster, das auf / endet, passt nur auf Ordner, und eines mit einem / an anderer Stelle gilt ab der obersten Ebene des Notizordners.
+settings-browse = Durchsuchen…
+settings-move-notes = Notizen in den neuen Ordner verschieben
+settings-move-notes-hint = Verschiebt Notizen, Unterordner, Anhänge, Papierkorb und Verlauf. Dateien, deren Namen im neuen Ordner schon vergeben sind, bleiben, wo sie sind.
+settings-new-note-pattern = Name neuer Notizen
+settings-new-note-pattern-hint = {n} steht für einen Zähler, {date}, {time} und {datetime} für den Erstellungszeitpunkt.
+settings-new-note-template = Vorlage für neue Notizen
+settings-new-note-template-hint = Optionale Markdown-Datei (relativ zum Notizordner), mit der neue Notizen beginnen. Vorlagen verwaltest du im Tab Vorlagen.
+settings-daily-note-format = Name der Tagesnotiz (strftime-Format)
+settings-daily-note-template = Vorlage für Tagesnotizen
+settings-daily-note-template-hint = Optionale Markdown-Datei für Tagesnotizen; ist sie leer, wird die Vorlage für neue Notizen verwendet. {date} ist der Tag der Notiz.
+templates-hint = Vorlagen sind Markdown-Dateien im Ordner { $folder } deines Notizordners.
+templates-none = Noch keine Vorlagen
+templates-new = Neue Vorlage
+templates-new-name = Vorlage
+templates-name = Name
+templates-rename = Umbenennen
+templates-edit = Bearbeiten
+templates-preview = Vorschau
+templates-variables-hint = Variablen: {{title}}, {{date}}, {{time}}, {{datetime}}, {{week}}, {{clipboard}} und {{cursor}} für die Startposition des Cursors.
+templates-save = Speichern
+templates-use-new = Für neue Notizen verwenden
+templates-use-daily = Für Tagesnotizen verwenden
+templates-delete = Löschen
+templates-delete-confirm = Diese Vorlage löschen?
+
+## Style names
+style-text = Text
+style-heading-marker = Überschriftszeichen
+style-heading = Überschrift { $level }
+style-code-fence = Codeblock
+style-code-fence-background = Codeblock-Hintergrund
+style-block-quote = Zitat
+style-list-item = Listeneintrag
+style-paragraph = Absatz
+style-strong = Fett
+style-emphasis = Kursiv
+style-strikethrough = Durchgestrichen
+style-inline-code = Inline-Code
+style-code-block = Codeblock
+style-list-bullet = Aufzählungszeichen
+style-code-block-background = Codeblock-Hintergrund
+
+## Settings export / import
+transfer-export-title = Einstellungen exportieren
+transfer-import-title = Einstellungen importieren
+transfer-export-description = Schreibt alle Einstellungen, einschließlich Design, Stile, Schriften und Tastenkürzel, in eine einzige Datei.
+transfer-import-description = Ersetzt die aktuellen Einstellungen durch die aus einer exportierten Einstellungsdatei.
+transfer-file = Datei:
+transfer-keep-notes-folder = Notizordner dieses Rechners beibehalten
+transfer-export = Exportieren
+transfer-import = Importieren
+transfer-exported = Einstellungen nach '{ $path }' exportiert
+transfer-imported = Einstellungen aus '{ $path }' importiert
+
+## Keybinding categories and actions
+category-file = Datei
+category-editing = Bearbeiten
+category-find-replace = Suchen & Ersetzen
+category-view = Ansicht
+category-navigation = Navigation
+action-new-note = Neue Notiz
+action-copy-note = Notiz kopieren (Editor nicht fokussiert)
+action-delete-note = Notiz löschen
+action-open-settings = Einstellungen
+action-undo = Rückgängig
+action-redo = Wiederherstellen
+action-insert-list-item = Listeneintrag einfügen
+action-insert-checkbox = Kontrollkästchen einfügen
+action-insert-date = Datum einfügen
+action-insert-time = Uhrzeit einfügen
+action-insert-footnote = Fußnote einfügen
+action-insert-table-of-contents = Inhaltsverzeichnis einfügen oder aktualisieren
+action-find-replace = Suchen & Ersetzen
+action-next-match = Nächster Treffer
+action-previous-match = Vorheriger Treffer
+action-replace-current = Aktuellen Treffer ersetzen
+action-replace-all = Alle Treffer ersetzen
+action-global-search = Alle Notizen durchsuchen
+action-zoom-in = Vergrößern
+action-zoom-out = Verkleinern
+action-reset-zoom = Zoom zurücksetzen
+action-toggle-sidebar = Seitenleiste ein-/ausblenden
+action-toggle-preview = Vorschau ein-/ausblenden
+action-zen-mode = Zen-Modus
+action-focus-mode = Fokusmodus
+action-open-in-new-window = Notiz in neuem Fenster öffnen
+action-show-shortcuts = Tastenkürzel
+action-present = Notiz als Folien präsentieren
+action-todays-note = Heutige Notiz öffnen
+action-daily-notes-calendar = Kalender der Tagesnotizen
+action-show-tasks = Aufgaben aus allen Notizen
+action-next-note = Nächste Notiz
+action-previous-note = Vorherige Notiz
+action-rename-note = Notiz umbenennen
+action-focus-note-search = Notizen durchsuchen
+action-focus-editor = Zum Editor wechseln
+action-reopen-closed-note = Geschlossene Notiz wieder öffnen
+action-note-history = Versionsverlauf der Notiz anzeigen
+action-next-recent-note = Nächste zuletzt verwendete Notiz
+action-previous-recent-note = Vorherige zuletzt verwendete Notiz
+action-always-on-top = Immer im Vordergrund umschalten
+action-toggle-scratchpad = Notizblock ein- oder ausblenden
+action-hide-notes = Notizen hinter dem Sichtschutz verbergen
+privacy-screen-title = Notizen verborgen
+privacy-screen-hint = Klicke oder drücke eine Taste, um sie wieder anzuzeigen
+action-paste-plain-text = Als reinen Text einfügen
+action-copy-as-html = Auswahl oder Notiz als HTML kopieren
@@ -0,0 +1,539 @@
+# English UI strings. Every key used by the app must be defined here;
+# other locales fall back to these for keys they don't translate.
+
+app-title = Note Squirrel - { $note } ({ $vault })
+app-title-hidden = Note Squirrel
+no-note = No Note
+
+## Default date formats (strftime syntax)
+format-date = %Y-%m-%d
+format-time = %H:%M
+format-datetime = %Y-%m-%d %H:%M
+format-week = %G-W%V
+
+## Menus
+menu-file = File
+menu-view = View
+menu-help = Help
+menu-keyboard-shortcuts = Keyboard Shortcuts
+shortcuts-title = Keyboard Shortcuts
+setup-title = Welcome to Note Squirrel
+setup-step = Step { $current } of { $total }
+setup-welcome = Let's get you set up
+setup-notes-folder-intro = Notes are plain markdown files in a folder of your choice. It will be created if it doesn't exist.
+setup-theme-intro = Pick a theme. You can change it, and every color, later in Settings.
+setup-fonts-intro = Pick the fonts for the editor, the note list, and the preview.
+setup-import-intro = Already have markdown notes? They can be copied into your notes folder now.
+setup-import-enabled = Import an existing folder of markdown files
+setup-import-folder = Folder
+setup-back = Back
+setup-next = Next
+setup-finish = Finish
+setup-skip = Skip and use defaults
+import-drop-hint = Drop markdown files or folders to import them
+import-conflicts-title = Import Conflicts
+import-conflicts-intro = These files have the same name as existing notes:
+import-keep-both = Keep both
+import-replace = Replace existing
+import-merge = Merge in external tool
+import-skip = Skip
+import-apply-to-all = Use first choice for all
+import-import = Import
+menu-tools = Tools
+menu-register-links = Register notesquirrel:// Links
+menu-settings = Settings…
+menu-open-in-new-window = Open in New Window
+menu-new-folder = New Folder
+menu-note-history = Note History
+menu-vaults = Vaults
+menu-manage-vaults = Manage Vaults…
+menu-version-control = Version Control
+menu-git-not-a-repository = The notes folder isn't a git repository.
+menu-git-enable = Track Notes with Git
+menu-git-commit-now = Commit Now
+menu-git-log = Git Log of This Note
+menu-git-autocommit = Commit automatically:
+menu-git-commit-minutes = Minutes between commits
+menu-git-last-commit = Last commit: { $commit }
+menu-recently-closed = Recently Closed
+menu-recently-closed-deleted = { $note } (deleted)
+menu-todays-note = Today's Note
+menu-daily-notes-calendar = Calendar…
+menu-export-settings = Export Settings…
+menu-import-settings = Import Settings…
+menu-zoom-in = Zoom In
+menu-zoom-out = Zoom Out
+menu-reset-zoom = Reset Zoom
+menu-ui-scale = UI scale: { $percent }%
+menu-show-sidebar = Show Sidebar
+menu-show-preview = Show Preview
+menu-focus-mode = Focus Mode
+menu-always-on-top = Always on Top
+menu-scratchpad = Scratchpad
+menu-hide-notes = Hide Notes
+menu-zen-mode = Zen Mode
+menu-present = Present
+menu-tasks = Tasks
+menu-global-search = Search All Notes
+menu-unused-attachments = Unused Attachments…
+menu-vault-statistics = Vault Statistics…
+menu-vault-index = Vault Index…
+
+## Common buttons
+button-ok = OK
+button-apply = Apply
+button-cancel = Cancel
+button-close = Close
+button-yes = Yes
+button-no = No
+
+## Sidebar
+sort-alphabetical = A-Z
+sort-recent = Recent
+menu-serve-note = Serve Note on Network
+menu-export-note = Export Note…
+menu-export-selection = Export Selection…
+menu-share-note = Share…
+menu-stop-serving = Stop Serving { $note }
+serve-title = Serving Note
+serve-note = Serving { $note } at:
+serve-copy-url = Copy
+serve-hint = Open this address on a phone or another computer on the same network. The page reloads when the note is saved.
+serve-stop = Stop Serving
+profiler-title = Profiler
+profiler-frame = Frame: { $average } ms avg, { $worst } ms worst
+profiler-parse = Markdown parse: { $time } ms
+profiler-layout = Editor layout: { $time } ms
+profiler-io = File I/O: { $reads } reads, { $writes } writes, { $time } ms
+profiler-undo = Undo history: { $size } KiB
+editor-copy-as-html = Copy as HTML
+editor-table-paste-offer = The pasted rows look like a table.
+editor-table-paste-convert = Convert to table
+editor-highlighting = Highlighting…
+preview-large-note = This note is { $size } MB, too large to preview automatically.
+preview-render-anyway = Render anyway
+export-title = Export Note
+export-note = Exporting “{ $note }”
+export-selection-title = Export Selection
+export-selection = Exporting the selected text from “{ $note }”
+export-copy-html = Copy as HTML
+export-format = Format:
+export-file = File:
+export-button = Export
+export-no-pandoc = Install pandoc, or set pandoc_path in config.toml, to export ODT, EPUB, reStructuredText, and Org.
+share-title = Share Note
+share-note = Email “{ $note }” from your mail app
+share-attach-html = Attach as HTML
+share-attach-unsupported = Your mail app can only be given the note as text on this platform.
+share-email = Email
+status-words = { $words } words
+status-word-goal-progress = { $words } / { $goal } words ({ $percent }%)
+status-word-goal = Goal…
+status-word-goal-label = Word goal:
+status-word-goal-set = Set Goal
+status-word-goal-clear = Remove Goal
+status-word-goal-hint = Saved as word_goal in the note's front matter.
+status-unclosed-fence = Unclosed ``` fence on line { $line }
+breadcrumb-jump = Jump to this heading
+sidebar-loading-notes = Loading notes… { $done }/{ $total }
+sidebar-search-hint = Search notes, note > heading, @heading
+
+## Presentation
+presentation-counter = { $current } / { $total }
+
+## Preview
+preview-placeholder = Start typing to see your rendered notes (markdown)...
+
+## Delete dialog
+delete-note-title = Delete Note
+delete-note-confirm = Are you sure you want to delete '{ $note }'?
+external-link-title = Open Link
+external-link-confirm = Open this address in your browser?
+external-link-open = Open
+toast-note-deleted = Deleted '{ $note }'
+toast-undo = Undo
+toast-dismiss = Dismiss
+switcher-hint = Release Ctrl to open, Escape to cancel
+sidebar-tags = Tags
+sidebar-tag-filter = Tagged #{ $tag }
+sidebar-clear-tag-filter = Show all notes
+sidebar-recently-deleted = Recently Deleted ({ $count })
+sidebar-recently-deleted-more = and { $count } older in the .trash folder
+sidebar-restore-note = Restore note
+sidebar-deleted-at = Deleted { $time }
+notes-alias-match = also known as “{ $alias }”
+notes-new-folder = New Folder
+notes-new-note-here = New Note Here
+notes-new-folder-here = New Folder Here
+notes-rename-folder = Rename Folder
+notes-move-to = Move To
+notes-top-level = Top Level
+preview-tag-hint = Show only notes with this tag
+preview-copy-link = Copy Link
+scratchpad-title = Scratchpad
+scratchpad-hint = Saved automatically, kept out of the notes list
+scratchpad-close = Close the scratchpad
+
+## Error dialog
+config-errors-title = Configuration Errors
+config-errors-intro = The following errors occurred while loading the configuration:
+
+## View modes
+view-mode-split = Editor and Preview
+view-mode-editor-only = Editor Only
+view-mode-preview-only = Preview Only
+split-side-by-side = Preview Beside Editor
+split-stacked = Preview Below Editor
+
+## Themes
+theme-dark = Dark
+theme-light = Light
+theme-high-contrast = High contrast
+theme-colorblind-dark = Colorblind-safe dark
+theme-colorblind-light = Colorblind-safe light
+
+## Find & replace
+find-replace-title = Find & Replace
+find-label = Find:
+find-hint = Enter search text...
+replace-label = Replace:
+replace-hint = Enter replacement text...
+find-match-case = Match case
+find-regex = Regex
+find-no-matches = No matches
+find-match-position = { $current } of { $total }
+find-match-count = { $total } matches
+find-replace-all = Replace All
+find-replace = Replace
+find-previous = Previous
+find-next = Next
+find-scope-current = This note
+find-scope-all = All notes
+find-all-notes-count = { $total } matches in { $notes } note(s)
+find-note-replacements = { $count } replacement(s)
+find-replace-all-notes = Replace in All Notes
+find-replaced-notes = Replaced in { $count } note(s):
+find-undo-note = Undo
+
+## Tasks
+global-search-title = Search All Notes
+global-search-hint = Text to find in every note
+global-search-match-case = Match case
+global-search-count = { $matches } match(es) in { $notes } note(s)
+global-search-truncated = (showing the first ones only)
+global-search-none = No notes contain this text.
+
+note-conflict-title = Note Changed Elsewhere
+note-conflict-intro = "{ $note }" was changed by another program while you were editing it. Your edits haven't been saved yet.
+note-conflict-more = { $count } more note(s) to resolve after this one.
+note-conflict-changed = { $count } line(s) differ
+note-conflict-mine = Your version
+note-conflict-theirs = Version on disk
+note-conflict-keep-mine = Keep Mine
+note-conflict-take-theirs = Take Theirs
+note-conflict-merge = Merge Manually
+note-conflict-merge-tool = Open in Merge Tool
+note-conflict-merge-hint = Edit the text below into the version to keep, removing the <<<<<<<, ======= and >>>>>>> lines.
+note-conflict-save-merged = Save Merged
+note-conflict-back = Back
+
+note-history-title = History of "{ $note }"
+note-history-none = This note has no earlier versions yet. Turn on history under Settings → Vault to keep them.
+note-history-select = Pick a version on the left to compare it with the note.
+note-history-snapshot = Version from { $time }
+note-history-current = Current version
+note-history-restore = Restore This Version
+note-history-read-failed = Couldn't read this version
+
+vaults-title = Vaults
+vaults-active = Open
+vaults-open = Switch
+vaults-remove = Remove
+vaults-remove-hint = Take this folder off the list; its notes aren't touched
+vaults-add-label = Add a notes folder:
+vaults-add-hint = Full path to a folder
+vaults-add = Add
+folder-picker-title = Choose a Folder
+folder-picker-up = Up
+folder-picker-empty = No subfolders
+folder-picker-new-hint = New folder name
+folder-picker-create = Create Folder
+folder-picker-choose = Use This Folder
+note-history-git-title = Git log of "{ $note }"
+note-history-no-commits = No commits include this note yet.
+note-history-commit = Commit { $commit } from { $time }
+git-autocommit-off = Off
+git-autocommit-after-saving = After saving
+git-autocommit-interval = On a timer
+tasks-title = Tasks
+tasks-filter-open = Open
+tasks-filter-done = Done
+tasks-filter-overdue = Overdue
+tasks-filter-all = All
+tasks-refresh = Refresh
+tasks-count = { $count } task(s)
+tasks-none = No tasks match this filter.
+tasks-sort = Sort by
+tasks-sort-note = Note
+tasks-sort-due = Due date
+tasks-sort-priority = Priority
+task-priority-high = high
+task-priority-medium = medium
+task-priority-low = low
+
+## Reminders
+reminder-title = Task due today
+reminder-body = { $task } ({ $note })
+
+## Daily notes calendar
+calendar-title = Calendar
+calendar-month = { $month } { $year }
+calendar-today = Today
+calendar-hint = Underlined days have a daily note; dotted days had notes created or edited. Double-click a day to open its daily note.
+calendar-open-daily-note = Open Daily Note
+calendar-create-daily-note = Create Daily Note
+calendar-created = Created
+calendar-edited = Edited
+calendar-no-activity = No notes were created or edited on this day.
+calendar-monday = Mo
+calendar-tuesday = Tu
+calendar-wednesday = We
+calendar-thursday = Th
+calendar-friday = Fr
+calendar-saturday = Sa
+calendar-sunday = Su
+calendar-january = January
+calendar-february = February
+calendar-march = March
+calendar-april = April
+calendar-may = May
+calendar-june = June
+calendar-july = July
+calendar-august = August
+calendar-september = September
+calendar-october = October
+calendar-november = November
+calendar-december = December
+
+## Vault statistics
+stats-title = Vault Statistics
+stats-computing = Counting your notes…
+stats-refresh = Refresh
+stats-notes = Notes
+stats-words = Words
+stats-activity = Activity (last 12 weeks)
+stats-created = Created
+stats-edited = Last edited
+stats-largest-notes = Largest notes
+stats-note-words = { $note } ({ $words } words)
+stats-top-tags = Most-used tags
+stats-tag-count = #{ $tag } ({ $count })
+stats-no-tags = No #tags yet.
+stats-writing = Writing (last 52 weeks)
+stats-day-words = { $date }: { $words } words written
+stats-words-today = { $words } words today
+stats-current-streak = Current streak: { $days } days
+stats-longest-streak = Longest streak: { $days } days
+vault-index-title = Vault Index
+vault-index-note-description = The “Vault Index” note links to every note, grouped by #tag. Writing it again refreshes it.
+vault-index-write-note = Write Index Note
+vault-index-csv-description = Or export the list of notes as CSV, with word counts, modification dates, and tags.
+vault-index-file = File:
+vault-index-export-csv = Export CSV
+vault-index-exported = Exported { $count } notes to { $path }
+
+## Unused attachments
+attachments-title = Unused Attachments
+attachments-summary = { $count } unreferenced file(s), { $size } total
+attachments-none-unused = Every attachment is referenced by a note.
+attachments-select-all = Select All
+attachments-select-none = Select None
+attachments-refresh = Refresh
+attachments-delete-selected = Delete Selected ({ $count })
+attachments-select-to-preview = Select a file to preview it.
+attachments-no-preview = No preview available for this file type.
+attachments-image-failed = Could not load image.
+attachment-preview-close = Close the preview
+attachment-preview-open = Open in Default App
+attachment-preview-rendering = Loading preview…
+attachment-preview-image-failed = Could not load image.
+attachment-preview-pdf-failed = Could not render the PDF: { $error }
+attachment-preview-no-pdftoppm = Previewing PDFs needs { $program } (from poppler) on your PATH.
+
+## Settings
+settings-title = Settings
+settings-tab-general = General
+settings-tab-editor = Editor
+settings-tab-preview = Preview Styles
+settings-tab-keybindings = Keybindings
+settings-tab-vault = Vault
+settings-tab-templates = Templates
+settings-language = Language
+settings-theme = Theme
+settings-ui-scale = UI scale
+settings-list-font = Note list font
+settings-list-font-size = Note list font size
+settings-editor-font = Editor font
+settings-editor-font-size = Editor font size
+settings-editor-max-line-width = Max line width
+settings-editor-max-line-width-hint = characters, centered (0 = full width)
+settings-focus-scope = Focus mode keeps
+focus-scope-paragraph = Current paragraph
+focus-scope-sentence = Current sentence
+settings-autocorrect = Autocorrect
+settings-autocorrect-enabled = Fix markdown slips while typing
+settings-list-marker = List bullet:
+settings-editor-line-spacing = Line spacing
+settings-zen-line-width = Zen mode line width
+settings-zen-dim = Dim the rest of the screen
+settings-block-spacing = Block spacing
+settings-preview-font = Preview font
+settings-preview-font-size = Preview font size
+settings-font-hint = Pick a built-in family or any font installed on the system. Fonts reload as soon as the settings are applied.
+settings-font-monospace = monospace (built-in)
+settings-font-proportional = proportional (built-in)
+settings-syntax-highlighting = Syntax highlighting
+settings-highlight-rules = Custom highlight rules (regex)
+settings-highlight-text-color = Text
+settings-highlight-background = Background
+settings-highlight-remove = Remove rule
+settings-highlight-add = Add Rule
+settings-date-formats = Date formats
+settings-date-formats-hint = Uses strftime syntax, e.g. %Y-%m-%d or %A, %B %e. Leave empty to use the language's default.
+settings-format-date = Date
+settings-format-time = Time
+settings-format-datetime = Date and time
+settings-format-week = Week
+settings-keybindings-hint = Write chords like "Ctrl+Shift+F" or "F3". Ctrl also matches Cmd on macOS. Leave empty to unbind.
+settings-reset-keybindings = Reset to Defaults
+settings-global-hotkey = System-wide show/hide shortcut
+settings-global-hotkey-hint = Brings NoteSquirrel to the front from any app, or minimizes it when it already has focus. Not available on Wayland. Leave empty to turn it off.
+settings-keymap-preset = Preset
+settings-keymap-preset-apply = Apply Preset
+keymap-preset-default = Default
+keymap-preset-vscode = VS Code-like
+keymap-preset-vim-lite = Vim-lite
+keymap-preset-macos = macOS conventional
+settings-notes-folder = Notes folder
+settings-confirm-delete = Ask before deleting a note
+settings-confirm-external-links = Ask before opening web links from the preview
+settings-privacy-screen-minutes = Hide notes after this many idle minutes (0 = never)
+settings-privacy-screen-on-focus-loss = Hide notes when the window loses focus
+settings-delete-to-trash = Move deleted notes to the .trash folder instead of removing them
+settings-note-history-minutes = Keep a snapshot of a note at most every this many minutes (0 = no history)
+settings-note-history-limit = Snapshots kept per note
+settings-task-reminders = Notify me about tasks due today
+settings-remember-note-views = Remember each note's cursor and scroll position after restarting
+settings-notes-folder-hint = Notes are reloaded from the new folder when the settings are applied.
+settings-note-extensions = Note files:
+settings-new-note-extension = New notes:
+settings-note-extensions-hint = Comma-separated extensions of the files listed as notes, e.g. md, markdown, txt.
+settings-ignore-patterns = Ignored files and folders, one pattern per line:
+settings-ignore-patterns-hint = Matching files and folders are left out of the notes list, as in a .gitignore. A pattern ending in / only matches folders, and one with a / elsewhere matches from the top of the notes folder.
+settings-browse = Browse…
+settings-move-notes = Move the notes into the new folder
+settings-move-notes-hint = Moves the notes, subfolders, attachments, trash and history. Files whose names are already taken in the new folder stay where they are.
+settings-new-note-pattern = New note name
+settings-new-note-pattern-hint = Use {n} for a counter, and {date}, {time}, or {datetime} for the creation time.
+settings-new-note-template = New note template
+settings-new-note-template-hint = Optional markdown file, relative to the notes folder, that new notes start from. Manage templates on the Templates tab.
+settings-daily-note-format = Daily note name (strftime format)
+settings-daily-note-template = Daily note template
+settings-daily-note-template-hint = Optional markdown file for daily notes; the new-note template is used when empty. {date} is the note's day.
+templates-hint = Templates are markdown files in the { $folder } folder of your notes folder.
+templates-none = No templates yet
+templates-new = New Template
+templates-new-name = Template
+templates-name = Name
+templates-rename = Rename
+templates-edit = Edit
+templates-preview = Preview
+templates-variables-hint = Variables: {{title}}, {{date}}, {{time}}, {{datetime}}, {{week}}, {{clipboard}}, and {{cursor}} for where the cursor starts.
+templates-save = Save
+templates-use-new = Use for New Notes
+templates-use-daily = Use for Daily Notes
+templates-delete = Delete
+templates-delete-confirm = Delete this template?
+
+## Style names
+style-text = Text
+style-heading-marker = Heading marker
+style-heading = Heading { $level }
+style-code-fence = Code fence
+style-code-fence-background = Code fence background
+style-block-quote = Block quote
+style-list-item = List item
+style-paragraph = Paragraph
+style-strong = Strong
+style-emphasis = Emphasis
+style-strikethrough = Strikethrough
+style-inline-code = Inline code
+style-code-block = Code block
+style-list-bullet = List bullet
+style-code-block-background = Code block background
+
+## Settings export / import
+transfer-export-title = Export Settings
+transfer-import-title = Import Settings
+transfer-export-description = Writes all settings, including theme, styles, fonts, and keybindings, to a single file.
+transfer-import-description = Replaces the current settings with the ones in an exported settings file.
+transfer-file = File:
+transfer-keep-notes-folder = Keep this machine's notes folder
+transfer-export = Export
+transfer-import = Import
+transfer-exported = Settings exported to '{ $path }'
+transfer-imported = Settings imported from '{ $path }'
+
+## Keybinding categories and actions
+category-file = File
+category-editing = Editing
+category-find-replace = Find & Replace
+category-view = View
+category-navigation = Navigation
+action-new-note = New note
+action-copy-note = Copy note (editor unfocused)
+action-delete-note = Delete note
+action-open-settings = Settings
+action-undo = Undo
+action-redo = Redo
+action-insert-list-item = Insert list item
+action-insert-checkbox = Insert checkbox item
+action-insert-date = Insert date
+action-insert-time = Insert time
+action-insert-footnote = Insert footnote
+action-insert-table-of-contents = Insert or refresh table of contents
+action-find-replace = Find & replace
+action-next-match = Next match
+action-previous-match = Previous match
+action-replace-current = Replace current match
+action-replace-all = Replace all matches
+action-global-search = Search all notes
+action-zoom-in = Zoom in
+action-zoom-out = Zoom out
+action-reset-zoom = Reset zoom
+action-toggle-sidebar = Show/hide sidebar
+action-toggle-preview = Show/hide preview
+action-zen-mode = Zen mode
+action-focus-mode = Focus mode
+action-open-in-new-window = Open note in new window
+action-show-shortcuts = Keyboard shortcuts
+action-present = Present note as slides
+action-todays-note = Open today's note
+action-daily-notes-calendar = Daily notes calendar
+action-show-tasks = Tasks across all notes
+action-next-note = Next note
+action-previous-note = Previous note
+action-rename-note = Rename note
+action-focus-note-search = Search notes
+action-focus-editor = Focus the editor
+action-reopen-closed-note = Reopen closed note
+action-note-history = Show note history
+action-next-recent-note = Next recently used note
+action-previous-recent-note = Previous recently used note
+action-always-on-top = Toggle always on top
+action-toggle-scratchpad = Show or hide the scratchpad
+action-hide-notes = Hide notes behind the privacy screen
+privacy-screen-title = Notes hidden
+privacy-screen-hint = Click or press a key to show them again
+action-paste-plain-text = Paste as plain text
+action-copy-as-html = Copy selection or note as HTML
@@ -1,10 +1,80 @@
 use eframe::egui;
 
-use crate::notes_list::{NotesList, SortOrder};
+use crate::notes_list::{HeadingJump, NotesList, NotesListAction, SortOrder};
 use crate::editor::Editor;
 use crate::rendered_view::RenderedView;
-use crate::config::{Config, ConfigLoadResult};
-use crate::find_replace::{FindReplace, FindReplaceAction};
+use crate::config::{
+    Config, ConfigLoadResult, ConfigWatcher, GitAutocommit, SplitOrientation, VaultSession, ViewMode, MAX_SIDEBAR_WIDTH, MAX_SPLIT_RATIO, MAX_UI_SCALE,
+    MIN_SIDEBAR_WIDTH, MIN_SPLIT_RATIO, MIN_UI_SCALE, UI_SCALE_STEP,
+};
+use crate::i18n::{self, tr, tr_args};
+use crate::keymap::{Action, Keymap};
+use crate::find_replace::{FindReplace, FindReplaceAction, FindScope};
+use crate::attachment_preview::{self, AttachmentPreview, AttachmentPreviewAction};
+use crate::attachments::{AttachmentsReport, AttachmentsAction};
+use crate::settings::{SettingsDialog, SettingsAction};
+use crate::settings_transfer::{SettingsTransfer, SettingsTransferAction, TransferMode};
+use crate::note_window::{NoteWindow, NoteWindowAction};
+use crate::shortcuts_sheet::ShortcutsSheet;
+use crate::note_import::{self, ConflictChoice, ImportAction, ImportDialog, PendingImport};
+use crate::uri_scheme::{self, DeepLink};
+use crate::link_forwarding::LinkListener;
+use crate::setup_wizard::{SetupWizard, SetupWizardAction};
+use crate::presentation::{Presentation, PresentationAction};
+use crate::vault_stats::{StatsAction, StatsDialog};
+use crate::vault_index::{self, VaultIndex, VaultIndexAction};
+use crate::file_manager::{self, FileManager};
+use crate::daily_notes::{CalendarAction, CalendarPicker};
+use crate::tasks::{self, TasksAction, TasksPanel};
+use crate::global_search::{GlobalSearch, GlobalSearchAction};
+use crate::reminders::ReminderScheduler;
+use crate::task_calendar::CalendarSync;
+use crate::note_switcher::{NoteSwitcher, NoteSwitcherAction};
+use crate::scratchpad::{Scratchpad, ScratchpadAction};
+use crate::headings;
+use crate::note_server::{NoteServer, NoteServerAction};
+use crate::note_export::{ExportFormat, NoteExport, NoteExportAction};
+use crate::note_share::{self, NoteShare, NoteShareAction};
+use crate::opml;
+use crate::pandoc;
+use crate::merge_tool::{MergeEvent, MergeJob, MergeQueue};
+use crate::note_conflict::{ConflictAction, ConflictDialog};
+use crate::note_history::{HistoryAction, HistoryPanel, HistorySource};
+use crate::version_control::VersionControl;
+use crate::vault_switcher::{VaultAction, VaultSwitcher};
+use crate::note_template;
+use crate::word_goal;
+use crate::toc;
+use crate::autocorrect;
+use crate::note_views::{NoteView, NoteViews};
+use crate::automation_api::{ApiCommand, ApiResponse, AutomationApi};
+use crate::system_hotkey::SystemHotkey;
+use crate::privacy_screen::PrivacyScreen;
+use crate::docx_export;
+use crate::html_export;
+use crate::profiler::{ProfileSample, Profiler};
+
+/// How many notes the sidebar's Recently Deleted section lists; the rest stay in the trash.
+const RECENTLY_DELETED_SHOWN: usize = 20;
+/// How long the "note deleted" toast offers to undo.
+const UNDO_DELETE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(6);
+
+/// A note that was just deleted, kept so the deletion can be undone.
+struct DeletedNote {
+    name: String,
+    content: String,
+    deleted_at: std::time::Instant,
+}
+
+/// How many closed notes File → Recently Closed remembers.
+const RECENTLY_CLOSED_LIMIT: usize = 10;
+
+/// A note that was switched away from, closed in its own window, or deleted. Deleted notes
+/// keep their content so reopening them recreates the file.
+struct ClosedNote {
+    name: String,
+    deleted_content: Option<String>,
+}
 
 #[allow(dead_code)]
 pub struct AppFrame {
@@ -12,29 +82,120 @@ pub struct AppFrame {
     pub editor: Editor,
     pub rendered_view: RenderedView,
     pub show_delete_confirmation: bool,
+    /// A web link clicked in the preview, waiting for the user to confirm opening it.
+    pending_external_link: Option<String>,
     pub config: Config,
     pub error_dialog_errors: Vec<String>,
     pub show_error_dialog: bool,
     pub find_replace: FindReplace,
+    pub attachments_report: AttachmentsReport,
+    attachment_preview: AttachmentPreview,
+    pub settings_dialog: SettingsDialog,
+    pub settings_transfer: SettingsTransfer,
+    pub shortcuts_sheet: ShortcutsSheet,
+    pub import_dialog: ImportDialog,
+    pub setup_wizard: SetupWizard,
+    pub keymap: Keymap,
+    config_watcher: Option<ConfigWatcher>,
     last_window_title: String,
+    window_fitted_to_monitor: bool,
+    zen_mode: bool,
+    note_windows: Vec<NoteWindow>,
+    uri_registration_status: Option<Result<String, String>>,
+    link_listener: Option<LinkListener>,
+    recently_deleted: Option<DeletedNote>,
+    /// Most recently closed last.
+    recently_closed: Vec<ClosedNote>,
+    note_switcher: NoteSwitcher,
+    scratchpad: Scratchpad,
+    presentation: Presentation,
+    stats_dialog: StatsDialog,
+    note_views: NoteViews,
+    /// The goal being typed into the status bar's word goal menu.
+    word_goal_draft: usize,
+    vault_index: VaultIndex,
+    calendar_picker: CalendarPicker,
+    tasks_panel: TasksPanel,
+    global_search: GlobalSearch,
+    note_history: HistoryPanel,
+    version_control: VersionControl,
+    vault_switcher: VaultSwitcher,
+    reminders: Option<ReminderScheduler>,
+    task_calendar: Option<CalendarSync>,
+    note_server: NoteServer,
+    note_export: NoteExport,
+    note_share: NoteShare,
+    automation_api: AutomationApi,
+    system_hotkey: SystemHotkey,
+    privacy_screen: PrivacyScreen,
+    merge_queue: MergeQueue,
+    conflict_dialog: ConflictDialog,
+    profiler: Profiler,
+    next_note_window_serial: u64,
 }
 
 impl AppFrame {
     pub fn new() -> Self {
-        let ConfigLoadResult { config, errors } = Config::load();
+        let ConfigLoadResult { config, errors, first_run } = Config::load();
         let mut app_frame = Self {
             notes_list: NotesList::new(&config),
+            version_control: VersionControl::new(&config.notes_folder),
             editor: Editor::new(&config),
             rendered_view: RenderedView::new(&config),
+            presentation: Presentation::new(&config),
+            scratchpad: Scratchpad::new(&config),
             show_delete_confirmation: false,
+            pending_external_link: None,
+            settings_dialog: SettingsDialog::new(&config),
             config,
             error_dialog_errors: errors,
             show_error_dialog: false,
             find_replace: FindReplace::new(),
+            attachments_report: AttachmentsReport::new(),
+            attachment_preview: AttachmentPreview::new(),
+            settings_transfer: SettingsTransfer::new(),
+            shortcuts_sheet: ShortcutsSheet::new(),
+            import_dialog: ImportDialog::new(),
+            setup_wizard: SetupWizard::new(),
+            config_watcher: None,
+            keymap: Keymap::default(),
             last_window_title: String::new(),
+            window_fitted_to_monitor: false,
+            zen_mode: false,
+            note_windows: Vec::new(),
+            uri_registration_status: None,
+            link_listener: None,
+            recently_deleted: None,
+            recently_closed: Vec::new(),
+            note_switcher: NoteSwitcher::new(),
+            stats_dialog: StatsDialog::new(),
+            note_views: NoteViews::new(),
+            word_goal_draft: 1000,
+            vault_index: VaultIndex::new(),
+            calendar_picker: CalendarPicker::new(),
+            tasks_panel: TasksPanel::new(),
+            global_search: GlobalSearch::new(),
+            note_history: HistoryPanel::new(),
+            vault_switcher: VaultSwitcher::new(),
+            reminders: None,
+            task_calendar: None,
+            note_server: NoteServer::new(),
+            note_export: NoteExport::new(),
+            note_share: NoteShare::new(),
+            automation_api: AutomationApi::new(),
+            system_hotkey: SystemHotkey::new(),
+            privacy_screen: PrivacyScreen::new(),
+            merge_queue: MergeQueue::new(),
+            conflict_dialog: ConflictDialog::new(),
+            profiler: Profiler::new(),
+            next_note_window_serial: 0,
         };
 
-        app_frame.load_notes();
+        if first_run {
+            app_frame.setup_wizard.open(&app_frame.config);
+        } else {
+            app_frame.load_notes();
+        }
         app_frame
     }
 
@@ -42,23 +203,229 @@ impl AppFrame {
         let (loaded_fonts, font_errors) = self.config.setup_fonts(ctx);
         self.config.loaded_fonts = loaded_fonts;
         self.error_dialog_errors.extend(font_errors);
+        self.propagate_config();
         if !self.error_dialog_errors.is_empty() {
             self.show_error_dialog = true;
         }
     }
 
+    pub fn apply_theme(&self, ctx: &egui::Context) {
+        self.config.theme.apply(ctx);
+    }
+
+    /// Scales the whole UI on top of the display's native pixels-per-point.
+    pub fn apply_ui_scale(&self, ctx: &egui::Context) {
+        ctx.options_mut(|o| o.zoom_with_keyboard = false);
+        ctx.set_zoom_factor(self.config.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE));
+    }
+
+    fn change_ui_scale(&mut self, ui_scale: f32, ctx: &egui::Context) {
+        let ui_scale = (ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE) * 100.0).round() / 100.0;
+        if ui_scale != self.config.ui_scale {
+            self.config.ui_scale = ui_scale;
+            self.apply_ui_scale(ctx);
+            self.save_config();
+        }
+    }
+
+    pub fn apply_config(&mut self, config: Config, ctx: &egui::Context) {
+        self.config = config;
+        self.apply_theme(ctx);
+        self.apply_ui_scale(ctx);
+        self.setup_fonts_and_collect_errors(ctx);
+    }
+
+    /// Viewport settings for launching with the saved window geometry.
+    pub fn initial_viewport(&self) -> egui::ViewportBuilder {
+        let window = &self.config.window;
+        let mut viewport = egui::ViewportBuilder::default()
+            .with_inner_size([window.width, window.height])
+            .with_maximized(window.maximized);
+        if let (Some(x), Some(y)) = (window.x, window.y) {
+            viewport = viewport.with_position([x, y]);
+        }
+        if window.always_on_top {
+            viewport = viewport.with_window_level(egui::WindowLevel::AlwaysOnTop);
+        }
+        viewport
+    }
+
+    /// When launched on a different display than last time, shrinks the restored window to fit
+    /// and moves it back on screen if the saved position would leave it out of view.
+    fn fit_window_to_monitor(&mut self, ctx: &egui::Context) {
+        if self.window_fitted_to_monitor {
+            return;
+        }
+        let Some(monitor_size) = ctx.input(|i| i.viewport().monitor_size) else {
+            return;
+        };
+        self.window_fitted_to_monitor = true;
+
+        let window = &self.config.window;
+        let Some([saved_width, saved_height]) = window.monitor_size else {
+            return;
+        };
+        let zoom = ctx.zoom_factor();
+        let monitor = monitor_size * zoom;
+        if (saved_width - monitor.x).abs() < 1.0 && (saved_height - monitor.y).abs() < 1.0 {
+            return;
+        }
+
+        let size = egui::vec2(window.width.min(monitor.x * 0.9), window.height.min(monitor.y * 0.9));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size / zoom));
+
+        let off_screen = window.x.is_some_and(|x| x + size.x > monitor.x || x < 0.0)
+            || window.y.is_some_and(|y| y + size.y > monitor.y || y < 0.0);
+        if off_screen {
+            let centered = ((monitor - size) * 0.5).max(egui::Vec2::ZERO);
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition((centered / zoom).to_pos2()));
+        }
+    }
+
+    /// Records the current window geometry so it can be saved on exit. The size and position
+    /// are only updated while the window isn't maximized or fullscreen, so leaving either restores them.
+    fn track_window_state(&mut self, ctx: &egui::Context) {
+        let zoom = ctx.zoom_factor();
+        let (maximized, fullscreen, inner_rect, outer_rect, monitor_size) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.maximized, viewport.fullscreen, viewport.inner_rect, viewport.outer_rect, viewport.monitor_size)
+        });
+
+        let window = &mut self.config.window;
+        if let Some(size) = monitor_size {
+            window.monitor_size = Some([size.x * zoom, size.y * zoom]);
+        }
+        if fullscreen == Some(true) {
+            return;
+        }
+        let Some(maximized) = maximized else {
+            return;
+        };
+        window.maximized = maximized;
+        if maximized {
+            return;
+        }
+        if let Some(rect) = inner_rect {
+            window.width = rect.width() * zoom;
+            window.height = rect.height() * zoom;
+        }
+        if let Some(rect) = outer_rect {
+            window.x = Some(rect.min.x * zoom);
+            window.y = Some(rect.min.y * zoom);
+        }
+    }
+
+    pub fn start_config_watcher(&mut self, ctx: &egui::Context) {
+        self.config_watcher = Some(ConfigWatcher::spawn(ctx.clone()));
+    }
+
+    pub fn start_note_watcher(&mut self, ctx: &egui::Context) {
+        self.notes_list.watch_for_changes(ctx);
+    }
+
+    /// Starts taking the `notesquirrel://` links later launches of the app hand over.
+    pub fn start_link_listener(&mut self, ctx: &egui::Context) {
+        match LinkListener::spawn(ctx) {
+            Ok(listener) => self.link_listener = Some(listener),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    pub fn handle_config_reload(&mut self, ctx: &egui::Context) {
+        let changed = self.config_watcher.as_ref().is_some_and(|w| w.poll_changed());
+        if !changed {
+            return;
+        }
+
+        match Config::reload() {
+            Ok(mut config) => {
+                config.loaded_fonts = self.config.loaded_fonts.clone();
+                config.keep_session_state(&self.config);
+                if config != self.config {
+                    self.apply_config(config, ctx);
+                }
+            }
+            Err(e) => {
+                self.error_dialog_errors.push(e);
+                self.show_error_dialog = true;
+            }
+        }
+    }
+
+    fn propagate_config(&mut self) {
+        i18n::set_language(self.config.language);
+        let (keymap, keymap_errors) = Keymap::from_overrides(&self.config.keymap);
+        self.keymap = keymap;
+        self.error_dialog_errors.extend(keymap_errors);
+        let highlight_errors = self.editor.set_config(&self.config);
+        self.error_dialog_errors.extend(highlight_errors);
+        self.rendered_view.set_config(&self.config);
+        let scratchpad_errors = self.scratchpad.set_config(&self.config);
+        self.error_dialog_errors.extend(scratchpad_errors);
+        for window in &mut self.note_windows {
+            window.set_config(&self.config);
+        }
+        let folder = self.notes_list.get_file_manager().notes_dir().to_path_buf();
+        let current = self.notes_list.get_current_note_name().to_string();
+        if self.notes_list.set_config(&self.config) {
+            // Only which files are notes changed; keep the open note open.
+            if folder == self.config.notes_folder {
+                self.config.set_current_session(VaultSession {
+                    note: current,
+                    cursor: self.editor.get_cursor_position(),
+                    scroll_offset: self.editor.get_scroll_offset(),
+                });
+            }
+            self.load_notes();
+        }
+        self.note_views.set_persist(&self.config.notes_folder, self.config.remember_note_views);
+        self.update_reminders();
+    }
+
+    /// Starts, restarts, or stops the due-task reminders and the task calendar file to match the
+    /// config and notes folder.
+    fn update_reminders(&mut self) {
+        if !self.config.task_reminders {
+            self.reminders = None;
+        } else if !self.reminders.as_ref().is_some_and(|r| r.watches(&self.config.notes_folder)) {
+            self.reminders = Some(ReminderScheduler::spawn(&self.config));
+        }
+        match &self.config.tasks_calendar {
+            None => self.task_calendar = None,
+            Some(path) if !self.task_calendar.as_ref().is_some_and(|c| c.watches(&self.config.notes_folder, path)) => {
+                self.task_calendar = Some(CalendarSync::spawn(&self.config, path));
+            }
+            Some(_) => {}
+        }
+    }
+
     pub fn load_notes(&mut self) {
+        self.save_note_views();
+        self.config.remember_vault();
         self.notes_list.load_notes();
-        if let Some(ref name) = self.config.last_open_note
-            && let Some(index) = self.notes_list.find_note_index(name) {
-                self.notes_list.switch_to_note(index);
-            }
+        self.note_views = NoteViews::load(&self.config.notes_folder, self.config.remember_note_views);
+        self.recently_closed.clear();
+        self.update_reminders();
+        let session = self.config.current_session().cloned();
+        let restored = session
+            .as_ref()
+            .and_then(|session| self.notes_list.find_note_index(&session.note))
+            .is_some_and(|index| self.notes_list.switch_to_note(index));
         self.editor.load_notes(&self.notes_list);
+        self.note_switcher.clear();
+        self.note_switcher.note_opened(self.notes_list.get_current_note_name());
+        if restored && let Some(session) = session {
+            self.editor.restore_position(session.cursor, session.scroll_offset);
+        }
     }
 
     pub fn update_window_title(&mut self, ctx: &egui::Context) {
-        let note_name = self.notes_list.get_current_note_name();
-        let title = format!("Note Squirrel - {}", note_name);
+        let title = if self.privacy_screen.is_active() {
+            tr("app-title-hidden").to_string()
+        } else {
+            let vault = Config::vault_name(&self.config.notes_folder);
+            tr_args("app-title", &[("note", &self.notes_list.get_current_note_name()), ("vault", &vault)])
+        };
 
         if title != self.last_window_title {
             ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
@@ -73,91 +440,200 @@ impl AppFrame {
     }
 
     pub fn handle_global_shortcuts(&mut self, ctx: &egui::Context) {
-        ctx.input_mut(|i| {
-            if i.consume_key(egui::Modifiers::CTRL, egui::Key::N)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::N)
-            {
-                self.create_new_note();
-            }
+        let actions = ctx.input_mut(|i| self.keymap.triggered_actions(i));
 
-            if (i.consume_key(egui::Modifiers::CTRL, egui::Key::C)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::C))
-                && !i.focused
-            {
-                self.editor.copy_to_clipboard();
-            }
+        for action in actions {
+            self.run_action(action, ctx);
+        }
+    }
 
-            if i.consume_key(egui::Modifiers::CTRL, egui::Key::D)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::D)
-            {
-                self.show_delete_confirmation = true;
+    fn run_action(&mut self, action: Action, ctx: &egui::Context) {
+        match action {
+            Action::NewNote => self.create_new_note(""),
+            Action::CopyNote => {
+                // With the editor focused, the chord copies its selection as usual.
+                if !self.editor.has_focus(ctx) {
+                    self.editor.copy_to_clipboard();
+                }
             }
-
-            if i.consume_key(egui::Modifiers::CTRL, egui::Key::F)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::F)
-            {
-                self.find_replace.toggle_dialog();
+            Action::DeleteNote => {
+                if self.config.confirm_delete {
+                    self.show_delete_confirmation = true;
+                } else {
+                    self.delete_current_note();
+                }
             }
-
-            if (i.consume_key(egui::Modifiers::CTRL, egui::Key::Z)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::Z))
-                && self.editor.undo()
-            {
-                self.notes_list.save_current_content(self.editor.get_text());
+            Action::OpenSettings => self.settings_dialog.toggle_dialog(&self.config),
+            Action::Undo if self.scratchpad.has_focus(ctx) => {
+                self.scratchpad.undo();
             }
-
-            if (i.consume_key(egui::Modifiers::CTRL, egui::Key::Y)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::Y))
-                && self.editor.redo()
-            {
-                self.notes_list.save_current_content(self.editor.get_text());
+            Action::Redo if self.scratchpad.has_focus(ctx) => {
+                self.scratchpad.redo();
             }
-
-            if i.consume_key(egui::Modifiers::NONE, egui::Key::F3)
-                && self.find_replace.show_dialog
-            {
-                self.find_replace.next_match();
+            Action::Undo => {
+                if self.editor.undo() {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
             }
-
-            if i.consume_key(egui::Modifiers::SHIFT, egui::Key::F3)
-                && self.find_replace.show_dialog
-            {
-                self.find_replace.previous_match();
+            Action::Redo => {
+                if self.editor.redo() {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
             }
-
-            if (i.consume_key(egui::Modifiers::CTRL, egui::Key::Comma)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::Comma))
-                && self.editor.insert_list_entry(None)
-            {
-                self.notes_list.save_current_content(self.editor.get_text());
+            Action::InsertListItem => {
+                if self.editor.insert_list_entry(None) {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+            }
+            Action::InsertCheckbox => {
+                if self.editor.insert_checkbox_entry(None) {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+            }
+            Action::InsertDate => {
+                let date = self.config.formats.format_date(&chrono::Local::now());
+                if self.editor.insert_at_cursor(&date) {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+            }
+            Action::InsertTime => {
+                let time = self.config.formats.format_time(&chrono::Local::now());
+                if self.editor.insert_at_cursor(&time) {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+            }
+            Action::InsertFootnote => {
+                if self.editor.insert_footnote() {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+            }
+            Action::InsertTableOfContents => {
+                if self.editor.insert_table_of_contents() {
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+            }
+            Action::PastePlainText => self.editor.paste_plain_text(ctx),
+            Action::CopyAsHtml => self.editor.copy_as_html(),
+            Action::FindReplace => self.find_replace.toggle_dialog(),
+            Action::NextMatch => self.run_find_replace_action_if_open(FindReplaceAction::NextMatch),
+            Action::PreviousMatch => self.run_find_replace_action_if_open(FindReplaceAction::PreviousMatch),
+            Action::ReplaceCurrent => self.run_find_replace_action_if_open(FindReplaceAction::ReplaceCurrent),
+            Action::ReplaceAll => self.run_find_replace_action_if_open(FindReplaceAction::ReplaceAll),
+            Action::ZoomIn => self.change_ui_scale(self.config.ui_scale + UI_SCALE_STEP, ctx),
+            Action::ZoomOut => self.change_ui_scale(self.config.ui_scale - UI_SCALE_STEP, ctx),
+            Action::ResetZoom => self.change_ui_scale(1.0, ctx),
+            Action::ToggleSidebar => self.config.layout.sidebar_visible = !self.config.layout.sidebar_visible,
+            Action::TogglePreview => self.config.layout.view_mode = self.config.layout.view_mode.toggle_preview(),
+            Action::ZenMode => self.set_zen_mode(!self.zen_mode, ctx),
+            Action::FocusMode => self.editor.toggle_focus_mode(),
+            Action::OpenInNewWindow => self.open_current_note_in_window(),
+            Action::ShowShortcuts => self.shortcuts_sheet.toggle_dialog(),
+            Action::Present => self.start_presentation(ctx),
+            Action::TodaysNote => self.open_daily_note(chrono::Local::now().date_naive()),
+            Action::DailyNotesCalendar => self.toggle_calendar(),
+            Action::ShowTasks => self.toggle_tasks_panel(),
+            Action::GlobalSearch => self.toggle_global_search(),
+            Action::NoteHistory => self.toggle_note_history(HistorySource::Snapshots),
+            Action::NextNote => self.switch_to_adjacent_note(1),
+            Action::PreviousNote => self.switch_to_adjacent_note(-1),
+            Action::RenameNote => {
+                self.config.layout.sidebar_visible = true;
+                self.notes_list.rename_current_note();
             }
+            Action::FocusNoteSearch => {
+                self.config.layout.sidebar_visible = true;
+                self.notes_list.focus_search();
+            }
+            Action::FocusEditor => self.editor.request_focus(),
+            Action::NextRecentNote | Action::PreviousRecentNote => {
+                // Keep Ctrl+Tab from also moving keyboard focus to the next widget.
+                ctx.memory_mut(|m| m.move_focus(egui::FocusDirection::None));
+                let step = if action == Action::NextRecentNote { 1 } else { -1 };
+                self.note_switcher.cycle(
+                    step,
+                    self.keymap.chord_for(action),
+                    self.notes_list.get_current_note_name(),
+                    self.notes_list.get_note_names(),
+                );
+            }
+            Action::AlwaysOnTop => self.toggle_always_on_top(ctx),
+            Action::ToggleScratchpad => self.toggle_scratchpad(),
+            Action::HideNotes => self.privacy_screen.hide(),
+            Action::ReopenClosedNote => {
+                if let Some(closed) = self.recently_closed.last() {
+                    let name = closed.name.clone();
+                    self.reopen_closed_note(&name);
+                }
+            }
+        }
+    }
 
-            if (i.consume_key(egui::Modifiers::CTRL, egui::Key::Period)
-                || i.consume_key(egui::Modifiers::MAC_CMD, egui::Key::Period))
-                && self.editor.insert_checkbox_entry(None)
-            {
-                self.notes_list.save_current_content(self.editor.get_text());
+    /// Shows the current note as slides, fullscreen.
+    fn start_presentation(&mut self, ctx: &egui::Context) {
+        self.presentation.start(self.editor.get_text(), &self.config);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
+    }
+
+    fn handle_presentation(&mut self, ui: &mut egui::Ui) {
+        if let PresentationAction::Exit = self.presentation.render(ui, self.keymap.chord_for(Action::Present)) {
+            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.zen_mode));
+        }
+    }
+
+    fn toggle_scratchpad(&mut self) {
+        self.scratchpad.toggle_panel();
+        if !self.scratchpad.show_panel {
+            self.editor.request_focus();
+        }
+    }
+
+    pub fn handle_scratchpad(&mut self, ctx: &egui::Context) {
+        match self.scratchpad.render(ctx) {
+            ScratchpadAction::Close => self.editor.request_focus(),
+            ScratchpadAction::SaveFailed => {
+                self.error_dialog_errors.push("Failed to save the scratchpad".to_string());
+                self.show_error_dialog = true;
             }
-        });
+            ScratchpadAction::None => {}
+        }
+    }
+
+    fn toggle_always_on_top(&mut self, ctx: &egui::Context) {
+        let window = &mut self.config.window;
+        window.always_on_top = !window.always_on_top;
+        let level = if window.always_on_top { egui::WindowLevel::AlwaysOnTop } else { egui::WindowLevel::Normal };
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+        self.save_config();
+    }
+
+    /// Zen mode goes fullscreen and shows only the editor, as a centered column.
+    fn set_zen_mode(&mut self, zen_mode: bool, ctx: &egui::Context) {
+        self.zen_mode = zen_mode;
+        self.editor.set_zen_mode(zen_mode);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(zen_mode));
+    }
+
+    fn run_find_replace_action_if_open(&mut self, action: FindReplaceAction) {
+        if self.find_replace.show_dialog {
+            self.run_find_replace_action(action);
+        }
     }
 
     pub fn render_delete_confirmation_dialog(&mut self, ctx: &egui::Context) {
         if self.show_delete_confirmation {
-            egui::Window::new("Delete Note")
+            egui::Window::new(tr("delete-note-title"))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
                 .show(ctx, |ui| {
-                    ui.label(format!(
-                        "Are you sure you want to delete '{}'?",
-                        self.notes_list.get_current_note_name()
-                    ));
+                    ui.label(tr_args("delete-note-confirm", &[("note", &self.notes_list.get_current_note_name())]));
                     ui.horizontal(|ui| {
-                        if ui.button("Yes").clicked() || ui.input(|i| i.key_pressed(egui::Key::Y)) {
+                        if ui.button(tr("button-yes")).clicked() || ui.input(|i| i.key_pressed(egui::Key::Y)) {
                             self.delete_current_note();
                             self.show_delete_confirmation = false;
                         }
-                        if ui.button("No").clicked() || ui.input(|i| i.key_pressed(egui::Key::N)) {
+                        let cancel = ui.input(|i| i.key_pressed(egui::Key::N) || i.key_pressed(egui::Key::Escape));
+                        if ui.button(tr("button-no")).clicked() || cancel {
                             self.show_delete_confirmation = false;
                         }
                     });
@@ -165,14 +641,98 @@ impl AppFrame {
         }
     }
 
+    /// Asks before opening a web link clicked in the preview, showing the full address.
+    pub fn render_external_link_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(url) = &self.pending_external_link else {
+            return;
+        };
+        let mut close = false;
+        egui::Window::new(tr("external-link-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr("external-link-confirm"));
+                ui.add(egui::Label::new(egui::RichText::new(url).monospace()).wrap());
+                ui.horizontal(|ui| {
+                    if ui.button(tr("external-link-open")).clicked() {
+                        if let Err(e) = webbrowser::open(url) {
+                            self.error_dialog_errors.push(format!("Failed to open link: {}", e));
+                            self.show_error_dialog = true;
+                        }
+                        close = true;
+                    }
+                    if ui.button(tr("preview-copy-link")).clicked() {
+                        ctx.copy_text(url.clone());
+                        close = true;
+                    }
+                    if ui.button(tr("button-cancel")).clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        close = true;
+                    }
+                });
+            });
+        if close {
+            self.pending_external_link = None;
+        }
+    }
+
+    /// Shows "Note deleted — Undo" in the corner for a few seconds after a deletion.
+    pub fn render_undo_delete_toast(&mut self, ctx: &egui::Context) {
+        let Some(deleted) = &self.recently_deleted else {
+            return;
+        };
+        let remaining = UNDO_DELETE_TIMEOUT.saturating_sub(deleted.deleted_at.elapsed());
+        if remaining.is_zero() {
+            self.recently_deleted = None;
+            return;
+        }
+        ctx.request_repaint_after(remaining);
+
+        let mut undo = false;
+        let mut dismiss = false;
+        egui::Area::new(egui::Id::new("undo_delete_toast"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr_args("toast-note-deleted", &[("note", &deleted.name)]));
+                        undo = ui.button(tr("toast-undo")).clicked();
+                        dismiss = ui.small_button("✕").on_hover_text(tr("toast-dismiss")).clicked();
+                    });
+                });
+            });
+
+        if undo {
+            self.undo_delete();
+        } else if dismiss {
+            self.recently_deleted = None;
+        }
+    }
+
+    fn undo_delete(&mut self) {
+        let Some(deleted) = self.recently_deleted.take() else {
+            return;
+        };
+        self.notes_list.save_current_content(self.editor.get_text());
+        let previous = self.notes_list.get_current_note_name().to_string();
+        if self.notes_list.restore_note(&deleted.name, &deleted.content) {
+            self.editor.load_notes(&self.notes_list);
+            self.track_note_switch(previous);
+        } else {
+            self.error_dialog_errors.push(format!("Failed to restore note '{}'", deleted.name));
+            self.show_error_dialog = true;
+        }
+    }
+
     pub fn render_error_dialog(&mut self, ctx: &egui::Context) {
         if self.show_error_dialog {
-            egui::Window::new("Configuration Errors")
+            egui::Window::new(tr("config-errors-title"))
                 .collapsible(false)
                 .resizable(true)
                 .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
                 .show(ctx, |ui| {
-                    ui.label("The following errors occurred while loading the configuration:");
+                    ui.label(tr("config-errors-intro"));
                     ui.separator();
 
                     egui::ScrollArea::vertical()
@@ -185,7 +745,8 @@ impl AppFrame {
 
                     ui.separator();
                     ui.horizontal(|ui| {
-                        if ui.button("OK").clicked() {
+                        let dismiss = ui.input(|i| i.key_pressed(egui::Key::Escape));
+                        if ui.button(tr("button-ok")).clicked() || dismiss {
                             self.show_error_dialog = false;
                             self.error_dialog_errors.clear();
                         }
@@ -195,12 +756,25 @@ impl AppFrame {
     }
 
     pub fn handle_find_replace(&mut self, ctx: &egui::Context) {
-        let action = self.find_replace.render(ctx);
+        let action = self.find_replace.render(ctx, &self.keymap);
+        self.run_find_replace_action(action);
+
+        // Update matches if dialog is shown
+        if self.find_replace.show_dialog {
+            self.update_editor_matches();
+        } else {
+            self.editor.clear_matches();
+        }
+    }
 
+    fn run_find_replace_action(&mut self, action: FindReplaceAction) {
         match action {
             FindReplaceAction::UpdateMatches => {
                 self.find_replace.update_matches(self.editor.get_text());
                 self.update_editor_matches();
+                if self.find_replace.scope == FindScope::AllNotes {
+                    self.count_matches_in_all_notes();
+                }
             }
             FindReplaceAction::NextMatch => {
                 self.find_replace.next_match();
@@ -219,6 +793,7 @@ impl AppFrame {
                     self.update_editor_matches();
                 }
             }
+            FindReplaceAction::ReplaceAll if self.find_replace.scope == FindScope::AllNotes => self.replace_in_all_notes(),
             FindReplaceAction::ReplaceAll => {
                 let mut text = self.editor.get_text().to_string();
                 let count = self.find_replace.replace_all(&mut text);
@@ -229,139 +804,1802 @@ impl AppFrame {
                     self.update_editor_matches();
                 }
             }
+            FindReplaceAction::UndoNote(note) => self.undo_replace_in_note(&note),
             FindReplaceAction::None => {}
         }
-
-        // Update matches if dialog is shown
-        if self.find_replace.show_dialog {
-            self.update_editor_matches();
-        } else {
-            self.editor.clear_matches();
-        }
     }
 
-    fn update_editor_matches(&mut self) {
-        let ranges = self.find_replace.get_match_ranges();
-        let current = self.find_replace.current_match_index;
-        self.editor.set_match_ranges(ranges, current);
+    /// Every note is read first so the counts, and the replace that follows, don't miss any.
+    fn count_matches_in_all_notes(&mut self) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        self.notes_list.load_all_content();
+        self.find_replace.update_note_match_counts(self.notes_list.notes_with_content());
     }
 
-    pub fn render_main_layout(&mut self, ui: &mut egui::Ui) {
-        egui::Panel::left("sidebar_panel")
-            .exact_size(200.0)
-            .show_inside(ui, |ui| {
-                ui.horizontal(|ui| {
-                    let is_alpha = self.notes_list.get_sort_order() == &SortOrder::Alphabetical;
-                    let is_recent = self.notes_list.get_sort_order() == &SortOrder::LastModified;
-                    if ui.selectable_label(is_alpha, "A-Z").clicked() {
-                        self.notes_list.set_sort_order(SortOrder::Alphabetical);
-                    }
-                    if ui.selectable_label(is_recent, "Recent").clicked() {
-                        self.notes_list.set_sort_order(SortOrder::LastModified);
-                    }
-                });
-                ui.horizontal(|ui| {
-                    let icon_size = egui::vec2(16.0, 16.0);
-                    let (rect, _) = ui.allocate_exact_size(icon_size, egui::Sense::hover());
-                    if ui.is_rect_visible(rect) {
-                        let painter = ui.painter();
-                        let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(170, 170, 170));
-                        let center = rect.center() - egui::vec2(1.5, 1.5);
-                        painter.circle_stroke(center, 4.5, stroke);
-                        let h0 = center + egui::vec2(3.2, 3.2);
-                        painter.line_segment([h0, h0 + egui::vec2(3.0, 3.0)], stroke);
-                    }
-                    ui.text_edit_singleline(self.notes_list.get_search_text_mut());
-                });
-                ui.separator();
-
-                let inner = ui.available_size();
-                ui.allocate_ui_with_layout(inner, egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                    egui::ScrollArea::vertical()
-                        .auto_shrink([false, false])
-                        .id_salt("notes_list_scroll")
-                        .show(ui, |ui| {
-                            if let Some(switch_to_index) = self.notes_list.render(ui) {
-                                self.switch_to_note(switch_to_index);
-                            }
-                        });
-                });
-            });
-
-        self.render_editor_and_preview(ui);
+    fn replace_in_all_notes(&mut self) {
+        self.count_matches_in_all_notes();
+        let current = self.notes_list.get_current_note_name().to_string();
+        let notes: Vec<String> = self.find_replace.note_match_counts.iter().map(|(note, _)| note.clone()).collect();
+        let mut replaced = Vec::new();
+        for note in notes {
+            let Some(before) = self.notes_list.get_note_content(&note).map(str::to_string) else {
+                continue;
+            };
+            let mut text = before.clone();
+            if self.find_replace.replace_all_in(&mut text) == 0 {
+                continue;
+            }
+            // The note in the editor gets the change as an undo step there too.
+            if note == current {
+                self.editor.set_text_with_undo(&text);
+            }
+            self.notes_list.save_note_content(&note, &text);
+            replaced.push((note, before));
+        }
+        self.find_replace.replaced_notes = replaced;
+        self.find_replace.update_matches(self.editor.get_text());
+        self.update_editor_matches();
+        self.count_matches_in_all_notes();
     }
 
-    fn render_editor_and_preview(&mut self, ui: &mut egui::Ui) {
-        egui::CentralPanel::default().show_inside(ui, |ui| {
-            ui.columns(2, |columns| {
-                columns[0].vertical(|ui| {
-                    let inner = ui.available_size();
-                    ui.allocate_ui_with_layout(inner, egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                        if self.editor.render(ui) {
-                            self.notes_list.save_current_content(self.editor.get_text());
-                        }
-                    });
-                });
-
-                columns[1].vertical(|ui| {
-                    let inner = ui.available_size();
-                    ui.allocate_ui_with_layout(inner, egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                        if let Some(checkbox_toggles) = self.rendered_view.render(ui, self.editor.get_text())
-                            && !checkbox_toggles.is_empty() {
-                                for line in checkbox_toggles {
-                                    self.editor.toggle_checkbox_at_line(line);
-                                }
-                                self.notes_list.save_current_content(self.editor.get_text());
-                            }
-                    });
-                });
-            });
-        });
+    fn undo_replace_in_note(&mut self, note: &str) {
+        let Some(position) = self.find_replace.replaced_notes.iter().position(|(name, _)| name == note) else {
+            return;
+        };
+        let (note, before) = self.find_replace.replaced_notes.remove(position);
+        if note == self.notes_list.get_current_note_name() {
+            self.editor.set_text_with_undo(&before);
+        }
+        self.notes_list.save_note_content(&note, &before);
+        self.find_replace.update_matches(self.editor.get_text());
+        self.update_editor_matches();
+        self.count_matches_in_all_notes();
     }
 
-    fn create_new_note(&mut self) {
-        if let Some(_new_note_name) = self.notes_list.create_new_note() {
-            self.editor.set_text("");
+    /// Applies the choices from the first-run wizard, creates the notes folder, and imports
+    /// the existing notes the user pointed it at.
+    pub fn handle_setup_wizard(&mut self, ctx: &egui::Context) {
+        if let SetupWizardAction::Finish { mut config, import_from } = self.setup_wizard.render(ctx) {
+            config.keep_session_state(&self.config);
+            self.apply_config(*config, ctx);
+            self.load_notes();
+            self.save_config();
+            if let Some(folder) = import_from {
+                self.import_paths(&[folder]);
+            }
         }
     }
 
-    fn delete_current_note(&mut self) {
-        if self.notes_list.delete_current_note() {
-            self.editor.set_text(self.notes_list.get_current_content());
+    pub fn handle_settings(&mut self, ctx: &egui::Context) {
+        if let SettingsAction::Apply { mut config, move_notes } = self.settings_dialog.render(ctx) {
+            config.keep_session_state(&self.config);
+            config.save_blocked = false;
+            if move_notes && config.notes_folder != self.config.notes_folder {
+                self.move_notes_to(*config, ctx);
+            } else {
+                self.apply_config(*config, ctx);
+            }
+            self.save_config();
         }
     }
 
-    fn switch_to_note(&mut self, index: usize) {
+    /// Applies a config with a new notes folder after moving the notes into it, and reopens the
+    /// note that was open there.
+    fn move_notes_to(&mut self, config: Config, ctx: &egui::Context) {
+        let from = self.config.notes_folder.clone();
+        let session = VaultSession {
+            note: self.notes_list.get_current_note_name().to_string(),
+            cursor: self.editor.get_cursor_position(),
+            scroll_offset: self.editor.get_scroll_offset(),
+        };
         self.notes_list.save_current_content(self.editor.get_text());
-        if self.notes_list.switch_to_note(index) {
-            self.editor.set_text(self.notes_list.get_current_content());
-            self.config.last_open_note = Some(self.notes_list.get_current_note_name().to_string());
-            self.save_config();
+        self.notes_list.flush_writes();
+        self.save_note_views();
+        // Switching the list over first writes its caches to the old folder, so they move too.
+        self.notes_list.set_config(&config);
+        match file_manager::move_notes_folder(&from, &config.notes_folder) {
+            Ok(left_behind) if !left_behind.is_empty() => {
+                self.error_dialog_errors.push(format!(
+                    "{} items were left in '{}' because the new notes folder already has files with their names",
+                    left_behind.len(),
+                    from.display()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => self.error_dialog_errors.push(e),
         }
+        self.apply_config(config, ctx);
+        self.config.set_current_session(session);
+        self.load_notes();
     }
 
-}
-
-impl Default for AppFrame {
-    fn default() -> Self {
-        Self::new()
+    pub fn handle_settings_transfer(&mut self, ctx: &egui::Context) {
+        match self.settings_transfer.render(ctx) {
+            SettingsTransferAction::Export(path) => {
+                let status = self
+                    .config
+                    .export_bundle(&path)
+                    .map(|_| tr_args("transfer-exported", &[("path", &path.display())]));
+                self.settings_transfer.set_status(status);
+            }
+            SettingsTransferAction::Import { path, keep_notes_folder } => match Config::import_bundle(&path) {
+                Ok(mut config) => {
+                    if keep_notes_folder {
+                        config.notes_folder = self.config.notes_folder.clone();
+                    }
+                    config.keep_session_state(&self.config);
+                    self.apply_config(config, ctx);
+                    self.save_config();
+                    self.settings_transfer.set_status(Ok(tr_args("transfer-imported", &[("path", &path.display())])));
+                }
+                Err(e) => self.settings_transfer.set_status(Err(e)),
+            },
+            SettingsTransferAction::None => {}
+        }
     }
-}
 
-impl eframe::App for AppFrame {
-    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+    pub fn render_menu_bar(&mut self, ui: &mut egui::Ui) {
+        egui::Panel::top("menu_bar_panel").show_inside(ui, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button(tr("menu-file"), |ui| {
+                    let settings_button = egui::Button::new(tr("menu-settings"))
+                        .shortcut_text(self.keymap.chord_text(Action::OpenSettings));
+                    if ui.add(settings_button).clicked() {
+                        self.settings_dialog.open(&self.config);
+                        ui.close();
+                    }
+                    let new_window_button = egui::Button::new(tr("menu-open-in-new-window"))
+                        .shortcut_text(self.keymap.chord_text(Action::OpenInNewWindow));
+                    if ui.add(new_window_button).clicked() {
+                        self.open_current_note_in_window();
+                        ui.close();
+                    }
+                    ui.menu_button(tr("menu-vaults"), |ui| {
+                        let mut switch_to = None;
+                        for folder in &self.config.vaults {
+                            let is_active = *folder == self.config.notes_folder;
+                            let button = egui::Button::selectable(is_active, Config::vault_name(folder));
+                            if ui.add(button).on_hover_text(folder.display().to_string()).clicked() && !is_active {
+                                switch_to = Some(folder.clone());
+                            }
+                        }
+                        ui.separator();
+                        if ui.button(tr("menu-manage-vaults")).clicked() {
+                            self.vault_switcher.show_dialog = true;
+                            ui.close();
+                        }
+                        if let Some(folder) = switch_to {
+                            self.open_vault(folder);
+                            ui.close();
+                        }
+                    });
+                    if ui.button(tr("menu-new-folder")).clicked() {
+                        self.notes_list.create_folder("");
+                        ui.close();
+                    }
+                    let history_button = egui::Button::new(tr("menu-note-history")).shortcut_text(self.keymap.chord_text(Action::NoteHistory));
+                    if ui.add(history_button).clicked() {
+                        self.toggle_note_history(HistorySource::Snapshots);
+                        ui.close();
+                    }
+                    ui.add_enabled_ui(!self.recently_closed.is_empty(), |ui| {
+                        ui.menu_button(tr("menu-recently-closed"), |ui| {
+                            let mut reopen = None;
+                            for (position, closed) in self.recently_closed.iter().rev().enumerate() {
+                                let label = if closed.deleted_content.is_some() {
+                                    tr_args("menu-recently-closed-deleted", &[("note", &closed.name)])
+                                } else {
+                                    closed.name.clone()
+                                };
+                                let mut button = egui::Button::new(label);
+                                if position == 0 {
+                                    button = button.shortcut_text(self.keymap.chord_text(Action::ReopenClosedNote));
+                                }
+                                if ui.add(button).clicked() {
+                                    reopen = Some(closed.name.clone());
+                                }
+                            }
+                            if let Some(name) = reopen {
+                                self.reopen_closed_note(&name);
+                                ui.close();
+                            }
+                        });
+                    });
+                    if let Some(note) = self.note_server.served_note() {
+                        if ui.button(tr_args("menu-stop-serving", &[("note", &note)])).clicked() {
+                            self.note_server.stop();
+                            ui.close();
+                        }
+                    } else if ui.button(tr("menu-serve-note")).clicked() {
+                        self.serve_current_note();
+                        ui.close();
+                    }
+                    if ui.button(tr("menu-export-note")).clicked() {
+                        self.open_note_export(None);
+                        ui.close();
+                    }
+                    let selection = self.editor.selected_text();
+                    if ui.add_enabled(selection.is_some(), egui::Button::new(tr("menu-export-selection"))).clicked() {
+                        self.open_note_export(selection);
+                        ui.close();
+                    }
+                    if ui.button(tr("menu-share-note")).clicked() {
+                        self.note_share.open(self.notes_list.get_current_note_name());
+                        ui.close();
+                    }
+                    ui.separator();
+                    let todays_note_button = egui::Button::new(tr("menu-todays-note"))
+                        .shortcut_text(self.keymap.chord_text(Action::TodaysNote));
+                    if ui.add(todays_note_button).clicked() {
+                        self.open_daily_note(chrono::Local::now().date_naive());
+                        ui.close();
+                    }
+                    let calendar_button = egui::Button::new(tr("menu-daily-notes-calendar"))
+                        .shortcut_text(self.keymap.chord_text(Action::DailyNotesCalendar));
+                    if ui.add(calendar_button).clicked() {
+                        self.toggle_calendar();
+                        ui.close();
+                    }
+                    ui.separator();
+                    if ui.button(tr("menu-export-settings")).clicked() {
+                        self.settings_transfer.open(TransferMode::Export);
+                        ui.close();
+                    }
+                    if ui.button(tr("menu-import-settings")).clicked() {
+                        self.settings_transfer.open(TransferMode::Import);
+                        ui.close();
+                    }
+                });
+                ui.menu_button(tr("menu-view"), |ui| {
+                    for (action, label) in [
+                        (Action::ZoomIn, tr("menu-zoom-in")),
+                        (Action::ZoomOut, tr("menu-zoom-out")),
+                        (Action::ResetZoom, tr("menu-reset-zoom")),
+                    ] {
+                        let button = egui::Button::new(label).shortcut_text(self.keymap.chord_text(action));
+                        if ui.add(button).clicked() {
+                            let ctx = ui.ctx().clone();
+                            self.run_action(action, &ctx);
+                        }
+                    }
+                    ui.label(tr_args("menu-ui-scale", &[("percent", &(self.config.ui_scale * 100.0).round())]));
+                    ui.separator();
+                    let sidebar_button = egui::Button::selectable(self.config.layout.sidebar_visible, tr("menu-show-sidebar"))
+                        .shortcut_text(self.keymap.chord_text(Action::ToggleSidebar));
+                    if ui.add(sidebar_button).clicked() {
+                        self.config.layout.sidebar_visible = !self.config.layout.sidebar_visible;
+                    }
+                    let preview_button = egui::Button::selectable(self.config.layout.view_mode.shows_preview(), tr("menu-show-preview"))
+                        .shortcut_text(self.keymap.chord_text(Action::TogglePreview));
+                    if ui.add(preview_button).clicked() {
+                        self.config.layout.view_mode = self.config.layout.view_mode.toggle_preview();
+                    }
+                    let focus_button = egui::Button::selectable(self.editor.is_focus_mode(), tr("menu-focus-mode"))
+                        .shortcut_text(self.keymap.chord_text(Action::FocusMode));
+                    if ui.add(focus_button).clicked() {
+                        self.editor.toggle_focus_mode();
+                    }
+                    let on_top_button = egui::Button::selectable(self.config.window.always_on_top, tr("menu-always-on-top"))
+                        .shortcut_text(self.keymap.chord_text(Action::AlwaysOnTop));
+                    if ui.add(on_top_button).clicked() {
+                        let ctx = ui.ctx().clone();
+                        self.toggle_always_on_top(&ctx);
+                    }
+                    let scratchpad_button = egui::Button::selectable(self.scratchpad.show_panel, tr("menu-scratchpad"))
+                        .shortcut_text(self.keymap.chord_text(Action::ToggleScratchpad));
+                    if ui.add(scratchpad_button).clicked() {
+                        self.toggle_scratchpad();
+                        ui.close();
+                    }
+                    let hide_button = egui::Button::new(tr("menu-hide-notes")).shortcut_text(self.keymap.chord_text(Action::HideNotes));
+                    if ui.add(hide_button).clicked() {
+                        self.privacy_screen.hide();
+                        ui.close();
+                    }
+                    let tasks_button = egui::Button::new(tr("menu-tasks")).shortcut_text(self.keymap.chord_text(Action::ShowTasks));
+                    if ui.add(tasks_button).clicked() {
+                        self.toggle_tasks_panel();
+                        ui.close();
+                    }
+                    let search_button = egui::Button::new(tr("menu-global-search")).shortcut_text(self.keymap.chord_text(Action::GlobalSearch));
+                    if ui.add(search_button).clicked() {
+                        self.toggle_global_search();
+                        ui.close();
+                    }
+                    let present_button = egui::Button::new(tr("menu-present")).shortcut_text(self.keymap.chord_text(Action::Present));
+                    if ui.add(present_button).clicked() {
+                        let ctx = ui.ctx().clone();
+                        self.start_presentation(&ctx);
+                        ui.close();
+                    }
+                    let zen_button = egui::Button::new(tr("menu-zen-mode")).shortcut_text(self.keymap.chord_text(Action::ZenMode));
+                    if ui.add(zen_button).clicked() {
+                        let ctx = ui.ctx().clone();
+                        self.set_zen_mode(true, &ctx);
+                        ui.close();
+                    }
+                    ui.separator();
+                    for mode in ViewMode::ALL {
+                        if ui.radio_value(&mut self.config.layout.view_mode, mode, mode.label()).clicked() {
+                            ui.close();
+                        }
+                    }
+                    ui.separator();
+                    ui.add_enabled_ui(self.config.layout.view_mode == ViewMode::Split, |ui| {
+                        for orientation in SplitOrientation::ALL {
+                            if ui.radio_value(&mut self.config.layout.split_orientation, orientation, orientation.label()).clicked() {
+                                ui.close();
+                            }
+                        }
+                    });
+                });
+                ui.menu_button(tr("menu-tools"), |ui| {
+                    if ui.button(tr("menu-unused-attachments")).clicked() {
+                        self.open_attachments_report();
+                        ui.close();
+                    }
+                    if ui.button(tr("menu-vault-statistics")).clicked() {
+                        let ctx = ui.ctx().clone();
+                        self.open_vault_stats(&ctx);
+                        ui.close();
+                    }
+                    if ui.button(tr("menu-vault-index")).clicked() {
+                        self.vault_index.open();
+                        ui.close();
+                    }
+                    if uri_scheme::CAN_REGISTER && ui.button(tr("menu-register-links")).clicked() {
+                        let status = uri_scheme::register();
+                        if let Err(e) = &status {
+                            self.error_dialog_errors.push(e.clone());
+                            self.show_error_dialog = true;
+                        }
+                        self.uri_registration_status = Some(status);
+                    }
+                    if let Some(Ok(message)) = &self.uri_registration_status {
+                        ui.weak(message);
+                    }
+                });
+                ui.menu_button(tr("menu-version-control"), |ui| self.render_version_control_menu(ui));
+                ui.menu_button(tr("menu-help"), |ui| {
+                    let shortcuts_button = egui::Button::new(tr("menu-keyboard-shortcuts"))
+                        .shortcut_text(self.keymap.chord_text(Action::ShowShortcuts));
+                    if ui.add(shortcuts_button).clicked() {
+                        self.shortcuts_sheet.show_dialog = true;
+                        ui.close();
+                    }
+                });
+            });
+        });
+    }
+
+    fn render_version_control_menu(&mut self, ui: &mut egui::Ui) {
+        if !self.version_control.is_repository() {
+            ui.weak(tr("menu-git-not-a-repository"));
+            if ui.button(tr("menu-git-enable")).clicked() {
+                self.notes_list.flush_writes();
+                if let Err(e) = self.version_control.enable() {
+                    self.error_dialog_errors.push(e);
+                    self.show_error_dialog = true;
+                }
+                ui.close();
+            }
+            return;
+        }
+        if ui.button(tr("menu-git-commit-now")).clicked() {
+            self.notes_list.save_current_content(self.editor.get_text());
+            self.notes_list.flush_writes();
+            self.version_control.commit_now(self.notes_list.last_change());
+            ui.close();
+        }
+        if ui.button(tr("menu-git-log")).clicked() {
+            self.toggle_note_history(HistorySource::Commits);
+            ui.close();
+        }
+        ui.separator();
+        ui.label(tr("menu-git-autocommit"));
+        let mut changed = false;
+        for mode in GitAutocommit::ALL {
+            changed |= ui.radio_value(&mut self.config.git_autocommit, mode, mode.label()).changed();
+        }
+        if self.config.git_autocommit == GitAutocommit::Interval {
+            ui.horizontal(|ui| {
+                ui.label(tr("menu-git-commit-minutes"));
+                changed |= ui.add(egui::DragValue::new(&mut self.config.git_commit_minutes).range(1..=1440)).changed();
+            });
+        }
+        if changed {
+            self.save_config();
+        }
+        if let Some(summary) = self.version_control.last_commit_summary() {
+            ui.separator();
+            ui.weak(tr_args("menu-git-last-commit", &[("commit", &summary)]));
+        }
+    }
+
+    /// Commits the notes folder when `git_autocommit` calls for it, and reports failed commits.
+    pub fn handle_version_control(&mut self, ctx: &egui::Context) {
+        if self.version_control.dir() != self.config.notes_folder {
+            self.version_control = VersionControl::new(&self.config.notes_folder);
+        }
+        if let Some(e) = self.version_control.poll(ctx) {
+            self.error_dialog_errors.push(e);
+            self.show_error_dialog = true;
+        }
+        let last_change = self.notes_list.last_change();
+        if self.version_control.commit_due(ctx, self.config.git_autocommit, self.config.git_commit_minutes, last_change) {
+            self.notes_list.flush_writes();
+            self.version_control.commit_now(last_change);
+        }
+    }
+
+    pub fn handle_attachments_report(&mut self, ctx: &egui::Context) {
+        match self.attachments_report.render(ctx) {
+            AttachmentsAction::Refresh => self.refresh_attachments_report(),
+            AttachmentsAction::DeleteSelected => {
+                let file_manager = self.notes_list.get_file_manager();
+                for path in self.attachments_report.selected_paths() {
+                    if !file_manager.delete_attachment(&path) {
+                        eprintln!("Failed to delete attachment: {}", path.display());
+                    }
+                }
+                self.refresh_attachments_report();
+            }
+            AttachmentsAction::None => {}
+        }
+    }
+
+    fn serve_current_note(&mut self) {
+        let note = self.notes_list.get_current_note_name().to_string();
+        let Some(content) = self.notes_list.get_note_content(&note) else {
+            return;
+        };
+        if let Err(e) = self.note_server.start(&note, content, self.config.serve_port) {
+            self.error_dialog_errors.push(e);
+            self.show_error_dialog = true;
+        }
+    }
+
+    fn render_profiler(&mut self, ctx: &egui::Context) {
+        if !self.config.show_profiler {
+            return;
+        }
+        let sample = ProfileSample {
+            parse: self.rendered_view.last_parse_duration(),
+            layout: self.editor.last_layout_duration(),
+            io: self.notes_list.io_stats(),
+            undo_bytes: self.editor.undo_memory(),
+        };
+        self.profiler.render(ctx, &sample);
+        self.profiler.end_frame();
+        // Keep the numbers moving while the overlay is up.
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    /// Picks up notes read in the background or changed by other programs, and reports failed
+    /// reads and saves.
+    fn handle_note_io(&mut self, ctx: &egui::Context) {
+        let errors = self.notes_list.poll_io();
+        if !errors.is_empty() {
+            self.error_dialog_errors.extend(errors);
+            self.show_error_dialog = true;
+        }
+        if self.notes_list.take_current_reloaded() {
+            self.editor.set_text_with_undo(self.notes_list.get_current_content());
+        }
+        for conflict in self.notes_list.take_conflicts() {
+            self.conflict_dialog.push(conflict);
+        }
+        if self.notes_list.loading_progress().is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+    }
+
+    /// Keeps the served page in step with the note's saved content.
+    pub fn handle_note_server(&mut self, ctx: &egui::Context) {
+        if let Some(content) = self.note_server.served_note().and_then(|note| self.notes_list.get_note_content(note)) {
+            self.note_server.update(content);
+        }
+        match self.note_server.render(ctx) {
+            NoteServerAction::Stop => self.note_server.stop(),
+            NoteServerAction::None => {}
+        }
+    }
+
+    /// Registers the system-wide show/hide shortcut to match the config.
+    pub fn handle_system_hotkey(&mut self, ctx: &egui::Context) {
+        if let Err(e) = self.system_hotkey.sync(&self.config, ctx) {
+            self.error_dialog_errors.push(e);
+            self.show_error_dialog = true;
+        }
+    }
+
+    /// Starts or stops the automation API to match the config, and carries out the requests
+    /// scripts have sent since the last frame.
+    pub fn handle_automation_api(&mut self, ctx: &egui::Context) {
+        if let Err(e) = self.automation_api.sync(&self.config, ctx) {
+            self.error_dialog_errors.push(e);
+            self.show_error_dialog = true;
+        }
+        for request in self.automation_api.poll() {
+            let response = match &request.command {
+                ApiCommand::ListNotes => ApiResponse::json_list(self.notes_list.get_note_names().iter().map(String::as_str)),
+                ApiCommand::ReadNote(name) => match self.notes_list.load_note_now(name) {
+                    Some(content) => ApiResponse::markdown(content),
+                    None => ApiResponse::not_found(&format!("No note named '{}'", name)),
+                },
+                ApiCommand::WriteNote { name, content } => self.write_note_from_api(name, content),
+                ApiCommand::AppendToNote { name, text } => {
+                    // The open note's latest text is in the editor, which may be ahead of the list.
+                    let existing = if name == self.notes_list.get_current_note_name() {
+                        Some(self.editor.get_text().to_string())
+                    } else if self.notes_list.find_note_index(name).is_some() {
+                        self.notes_list.load_note_now(name).map(str::to_string)
+                    } else {
+                        Some(String::new())
+                    };
+                    match existing {
+                        Some(mut content) => {
+                            if !content.is_empty() && !content.ends_with('\n') {
+                                content.push('\n');
+                            }
+                            content.push_str(text);
+                            self.write_note_from_api(name, &content)
+                        }
+                        None => ApiResponse::error(500, &format!("Failed to read note '{}'", name)),
+                    }
+                }
+                ApiCommand::Search(query) => ApiResponse::json_list(self.notes_list.search_notes(query)),
+            };
+            request.respond(response);
+        }
+    }
+
+    fn write_note_from_api(&mut self, name: &str, content: &str) -> ApiResponse {
+        if self.notes_list.find_note_index(name).is_none() {
+            if note_template::sanitize_note_path(name) != name {
+                return ApiResponse::error(400, &format!("'{}' is not a valid note name", name));
+            }
+            if !self.notes_list.add_note(name, content) {
+                return ApiResponse::error(500, &format!("Failed to create note '{}'", name));
+            }
+            return ApiResponse::no_content();
+        }
+        self.replace_note_content(name, content);
+        ApiResponse::no_content()
+    }
+
+    /// Opens the export dialog for the current note, or for `selection` from it.
+    fn open_note_export(&mut self, selection: Option<String>) {
+        let note = self.notes_list.get_current_note_name().to_string();
+        self.note_export.open(&note, selection, pandoc::version(&self.config.pandoc_path));
+    }
+
+    pub fn handle_note_share(&mut self, ctx: &egui::Context) {
+        let NoteShareAction::Email { attach_html } = self.note_share.render(ctx) else {
+            return;
+        };
+        let note = self.notes_list.get_current_note_name();
+        if let Err(e) = note_share::email(note, self.editor.get_text(), attach_html) {
+            self.error_dialog_errors.push(e);
+            self.show_error_dialog = true;
+        }
+    }
+
+    pub fn handle_note_export(&mut self, ctx: &egui::Context) {
+        let (format, path) = match self.note_export.render(ctx) {
+            NoteExportAction::None => return,
+            NoteExportAction::CopyHtml => {
+                let selection = self.note_export.selection().unwrap_or_default().to_string();
+                self.editor.copy_markdown_as_html(selection);
+                return;
+            }
+            NoteExportAction::Export { format, path } => (format, path),
+        };
+        let note = self.notes_list.get_current_note_name().to_string();
+        let markdown = self.note_export.selection().unwrap_or(self.editor.get_text());
+        let notes_dir = self.notes_list.get_file_manager().notes_dir();
+        let result = match (format, format.pandoc_format()) {
+            (_, Some(pandoc_format)) => {
+                pandoc::from_markdown(&self.config.pandoc_path, markdown, pandoc_format, &note, notes_dir, &path)
+            }
+            (ExportFormat::Docx, None) => docx_export::write_docx(markdown, &note, notes_dir, &path),
+            (ExportFormat::Opml, None) => std::fs::write(&path, opml::from_markdown(&note, markdown))
+                .map_err(|e| format!("Failed to export to '{}': {}", path.display(), e)),
+            (_, None) => std::fs::write(&path, html_export::html_document(&note, markdown))
+                .map_err(|e| format!("Failed to export to '{}': {}", path.display(), e)),
+        };
+        match result {
+            Ok(()) => self.note_export.show_dialog = false,
+            Err(e) => {
+                self.error_dialog_errors.push(e);
+                self.show_error_dialog = true;
+            }
+        }
+    }
+
+    pub fn handle_vault_stats(&mut self, ctx: &egui::Context) {
+        if let StatsAction::Refresh = self.stats_dialog.render(ctx) {
+            self.open_vault_stats(ctx);
+        }
+    }
+
+    pub fn handle_vault_index(&mut self, ctx: &egui::Context) {
+        match self.vault_index.render(ctx) {
+            VaultIndexAction::None => {}
+            VaultIndexAction::WriteNote => self.write_index_note(),
+            VaultIndexAction::ExportCsv(path) => {
+                let notes = vault_index::list_notes(self.notes_list.get_file_manager());
+                let status = std::fs::write(&path, vault_index::to_csv(&notes))
+                    .map(|()| tr_args("vault-index-exported", &[("count", &notes.len()), ("path", &path.display())]))
+                    .map_err(|e| format!("Failed to export to '{}': {}", path.display(), e));
+                self.vault_index.set_status(status);
+            }
+        }
+    }
+
+    /// Writes the index note, or refreshes it if it was generated before, and opens it.
+    fn write_index_note(&mut self) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        let name = vault_index::INDEX_NOTE;
+        let file_manager = self.notes_list.get_file_manager();
+        let notes = vault_index::list_notes(file_manager);
+        let content = vault_index::index_note(&notes, &chrono::Local::now(), &self.config.formats);
+
+        let written = if self.notes_list.find_note_index(name).is_none() {
+            self.notes_list.add_note(name, &content).then_some(()).ok_or_else(|| format!("Failed to create note '{}'", name))
+        } else if vault_index::is_generated(&file_manager.read_note_content(name)) {
+            self.replace_note_content(name, &content);
+            Ok(())
+        } else {
+            Err(format!("A note named '{}' already exists; rename it to generate the index", name))
+        };
+        if let Err(e) = written {
+            self.error_dialog_errors.push(e);
+            self.show_error_dialog = true;
+            return;
+        }
+        if let Some(index) = self.notes_list.find_note_index(name) {
+            self.switch_to_note(index);
+            self.notes_list.reveal_current();
+        }
+    }
+
+    fn open_vault_stats(&mut self, ctx: &egui::Context) {
+        self.notes_list.save_writing_activity();
+        self.stats_dialog.open(FileManager::new(&self.config), ctx);
+    }
+
+    fn open_attachments_report(&mut self) {
+        self.refresh_attachments_report();
+        self.attachments_report.open();
+    }
+
+    fn refresh_attachments_report(&mut self) {
+        self.notes_list.load_all_content();
+        let file_manager = self.notes_list.get_file_manager();
+        let contents = self.notes_list.get_all_content();
+        self.attachments_report.refresh(
+            &file_manager.attachments_dir(),
+            file_manager.list_attachments(),
+            contents.iter().map(|c| c.as_str()),
+        );
+    }
+
+    fn update_editor_matches(&mut self) {
+        let ranges = self.find_replace.get_match_ranges();
+        let current = self.find_replace.current_match_index;
+        self.editor.set_match_ranges(ranges, current);
+    }
+
+    pub fn render_main_layout(&mut self, ui: &mut egui::Ui) {
+        if self.zen_mode {
+            egui::CentralPanel::default().show_inside(ui, |ui| {
+                let full_rect = ui.available_rect_before_wrap();
+                self.render_editor_in(ui, full_rect);
+                ui.advance_cursor_after_rect(full_rect);
+            });
+            return;
+        }
+
+        self.render_status_bar(ui);
+        if self.config.layout.sidebar_visible {
+            self.render_sidebar(ui);
+        }
+        self.render_attachment_preview(ui);
+
+        self.render_editor_and_preview(ui);
+    }
+
+    /// The side pane previewing the image or PDF linked at the editor's cursor or clicked in the
+    /// preview.
+    fn render_attachment_preview(&mut self, ui: &mut egui::Ui) {
+        let text = self.editor.get_text();
+        let link = self.editor.get_cursor_position().and_then(|cursor| attachment_preview::link_at(text, cursor));
+        self.attachment_preview.follow_cursor(link, self.notes_list.get_file_manager().notes_dir());
+
+        if let AttachmentPreviewAction::OpenExternally(path) = self.attachment_preview.render(ui)
+            && let Err(e) = webbrowser::open(&path.to_string_lossy())
+        {
+            self.error_dialog_errors.push(format!("Failed to open '{}': {}", path.display(), e));
+            self.show_error_dialog = true;
+        }
+    }
+
+    /// The current note's word count along the bottom of the window, with progress towards its
+    /// word goal when it has one.
+    fn render_status_bar(&mut self, ui: &mut egui::Ui) {
+        let text = self.editor.get_text();
+        let words = word_goal::count_words(text);
+        let goal = word_goal::word_goal(text);
+        let unclosed_fence = self.config.autocorrect_markdown.then(|| autocorrect::unclosed_fence(text)).flatten();
+        let mut new_goal = None;
+        let mut go_to_fence = false;
+
+        egui::Panel::bottom("status_bar").show_inside(ui, |ui| {
+            ui.horizontal(|ui| {
+                match goal {
+                    Some(goal) if goal > 0 => {
+                        let progress = words as f32 / goal as f32;
+                        let percent = (progress * 100.0).round() as usize;
+                        let label = tr_args("status-word-goal-progress", &[("words", &words), ("goal", &goal), ("percent", &percent)]);
+                        ui.add(egui::ProgressBar::new(progress.min(1.0)).desired_width(240.0).text(label));
+                    }
+                    _ => {
+                        ui.label(tr_args("status-words", &[("words", &words)]));
+                    }
+                }
+                ui.menu_button(tr("status-word-goal"), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("status-word-goal-label"));
+                        ui.add(egui::DragValue::new(&mut self.word_goal_draft).range(1..=1_000_000).speed(10));
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("status-word-goal-set")).clicked() {
+                            new_goal = Some(Some(self.word_goal_draft));
+                            ui.close();
+                        }
+                        if goal.is_some() && ui.button(tr("status-word-goal-clear")).clicked() {
+                            new_goal = Some(None);
+                            ui.close();
+                        }
+                    });
+                    ui.weak(tr("status-word-goal-hint"));
+                });
+                if let Some(line) = unclosed_fence {
+                    ui.separator();
+                    let warning = egui::RichText::new(tr_args("status-unclosed-fence", &[("line", &line)])).color(ui.visuals().warn_fg_color);
+                    go_to_fence = ui.link(warning).clicked();
+                }
+            });
+        });
+
+        if go_to_fence && let Some(line) = unclosed_fence {
+            self.editor.go_to_line(line - 1);
+        }
+        if let Some(goal) = new_goal {
+            let text = word_goal::set_word_goal(self.editor.get_text(), goal);
+            self.editor.set_text_with_undo(&text);
+            self.notes_list.save_current_content(self.editor.get_text());
+        }
+    }
+
+    fn render_sidebar(&mut self, ui: &mut egui::Ui) {
+        let sidebar_width = self.config.layout.sidebar_width.clamp(MIN_SIDEBAR_WIDTH, MAX_SIDEBAR_WIDTH);
+        let response = egui::Panel::left("sidebar_panel")
+            .resizable(true)
+            .default_size(sidebar_width)
+            .min_size(MIN_SIDEBAR_WIDTH)
+            .max_size(MAX_SIDEBAR_WIDTH)
+            .show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let is_alpha = self.notes_list.get_sort_order() == &SortOrder::Alphabetical;
+                    let is_recent = self.notes_list.get_sort_order() == &SortOrder::LastModified;
+                    if ui.selectable_label(is_alpha, tr("sort-alphabetical")).clicked() {
+                        self.notes_list.set_sort_order(SortOrder::Alphabetical);
+                    }
+                    if ui.selectable_label(is_recent, tr("sort-recent")).clicked() {
+                        self.notes_list.set_sort_order(SortOrder::LastModified);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let icon_size = egui::vec2(16.0, 16.0);
+                    let (rect, _) = ui.allocate_exact_size(icon_size, egui::Sense::hover());
+                    if ui.is_rect_visible(rect) {
+                        let painter = ui.painter();
+                        let stroke = egui::Stroke::new(1.5, self.config.theme.palette().search_icon);
+                        let center = rect.center() - egui::vec2(1.5, 1.5);
+                        painter.circle_stroke(center, 4.5, stroke);
+                        let h0 = center + egui::vec2(3.2, 3.2);
+                        painter.line_segment([h0, h0 + egui::vec2(3.0, 3.0)], stroke);
+                    }
+                    let search = egui::TextEdit::singleline(self.notes_list.get_search_text_mut()).hint_text(tr("sidebar-search-hint"));
+                    let response = ui.add(search);
+                    if self.notes_list.take_search_focus_request() {
+                        response.request_focus();
+                    }
+                    if response.lost_focus()
+                        && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                        && let Some(jump) = self.notes_list.first_heading_match()
+                    {
+                        self.jump_to_heading(jump);
+                    }
+                });
+                self.render_tag_bar(ui);
+                self.render_recently_deleted(ui);
+                if let Some(tag) = self.notes_list.tag_filter().map(str::to_string) {
+                    ui.horizontal(|ui| {
+                        let label = tr_args("sidebar-tag-filter", &[("tag", &tag)]);
+                        ui.label(egui::RichText::new(label).color(self.config.theme.palette().tag));
+                        if ui.small_button("✕").on_hover_text(tr("sidebar-clear-tag-filter")).clicked() {
+                            self.notes_list.set_tag_filter(None);
+                        }
+                    });
+                }
+                if let Some((done, total)) = self.notes_list.loading_progress() {
+                    ui.add(
+                        egui::ProgressBar::new(done as f32 / total as f32)
+                            .text(tr_args("sidebar-loading-notes", &[("done", &done), ("total", &total)])),
+                    );
+                }
+                ui.separator();
+
+                let inner = ui.available_size();
+                ui.allocate_ui_with_layout(inner, egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                    egui::ScrollArea::vertical()
+                        .auto_shrink([false, false])
+                        .id_salt("notes_list_scroll")
+                        .show(ui, |ui| {
+                            match self.notes_list.render(ui) {
+                                NotesListAction::None => {}
+                                NotesListAction::SwitchTo(index) => self.switch_to_note(index),
+                                NotesListAction::JumpToHeading(jump) => self.jump_to_heading(jump),
+                                NotesListAction::NewNoteIn(folder) => self.create_new_note(&folder),
+                            }
+                        });
+                });
+            });
+
+        self.config.layout.sidebar_width = response.response.rect.width();
+    }
+
+    /// Every tag in the vault, collapsed by default; clicking one shows only the notes with it.
+    fn render_tag_bar(&mut self, ui: &mut egui::Ui) {
+        let tags: Vec<(String, usize)> =
+            self.notes_list.all_tags().into_iter().map(|(tag, count)| (tag.to_string(), count)).collect();
+        if tags.is_empty() {
+            return;
+        }
+        let selected_tag = self.notes_list.tag_filter().map(str::to_lowercase);
+        let mut clicked = None;
+        let shown = egui::CollapsingHeader::new(tr("sidebar-tags")).id_salt("sidebar_tags").show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (tag, count) in tags {
+                    let selected = selected_tag.as_deref() == Some(tag.as_str());
+                    let label = egui::RichText::new(format!("#{} {}", tag, count)).color(self.config.theme.palette().tag);
+                    if ui.selectable_label(selected, label).clicked() {
+                        clicked = Some((!selected).then_some(tag));
+                    }
+                }
+            });
+        });
+        // Tags only show for notes read so far, so the rest are read once the list is open.
+        if shown.body_returned.is_some() {
+            self.notes_list.load_all_content_in_background();
+        }
+        if let Some(tag) = clicked {
+            self.notes_list.set_tag_filter(tag);
+        }
+    }
+
+    /// The notes in the trash, newest first and collapsed by default, each with a button to
+    /// restore it.
+    fn render_recently_deleted(&mut self, ui: &mut egui::Ui) {
+        let trashed = self.notes_list.trashed_notes();
+        if trashed.is_empty() {
+            return;
+        }
+        let mut restore = None;
+        let title = tr_args("sidebar-recently-deleted", &[("count", &trashed.len())]);
+        egui::CollapsingHeader::new(title).id_salt("sidebar_recently_deleted").show(ui, |ui| {
+            for trashed in trashed.iter().take(RECENTLY_DELETED_SHOWN) {
+                ui.horizontal(|ui| {
+                    if ui.small_button("↺").on_hover_text(tr("sidebar-restore-note")).clicked() {
+                        restore = Some(trashed.file.clone());
+                    }
+                    let response = ui.add(egui::Label::new(&trashed.name).truncate());
+                    if let Some(deleted) = trashed.deleted {
+                        let deleted: chrono::DateTime<chrono::Local> = deleted.into();
+                        response.on_hover_text(tr_args("sidebar-deleted-at", &[("time", &deleted.format("%Y-%m-%d %H:%M"))]));
+                    }
+                });
+            }
+            if trashed.len() > RECENTLY_DELETED_SHOWN {
+                ui.weak(tr_args("sidebar-recently-deleted-more", &[("count", &(trashed.len() - RECENTLY_DELETED_SHOWN))]));
+            }
+        });
+        if let Some(file) = restore {
+            self.restore_trashed_note(&file);
+        }
+    }
+
+    /// Takes a note out of the trash and opens it.
+    fn restore_trashed_note(&mut self, file: &str) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        let previous = self.notes_list.get_current_note_name().to_string();
+        let Some(name) = self.notes_list.restore_trashed_note(file) else {
+            self.error_dialog_errors.push(format!("Failed to restore note '{}' from the trash", file));
+            self.show_error_dialog = true;
+            return;
+        };
+        if self.recently_deleted.as_ref().is_some_and(|d| d.name == name) {
+            self.recently_deleted = None;
+        }
+        self.recently_closed.retain(|closed| closed.name != name || closed.deleted_content.is_none());
+        self.editor.load_notes(&self.notes_list);
+        self.track_note_switch(previous);
+        self.notes_list.reveal_current();
+        self.config.set_current_session(VaultSession {
+            note: name,
+            ..Default::default()
+        });
+        self.save_config();
+    }
+
+    fn render_editor_and_preview(&mut self, ui: &mut egui::Ui) {
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            let full_rect = ui.available_rect_before_wrap();
+            match self.config.layout.view_mode {
+                ViewMode::Split => {
+                    let ratio = self.config.layout.split_ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+                    let mut editor_rect = full_rect;
+                    let mut preview_rect = full_rect;
+                    let (divider, spacing) = match self.config.layout.split_orientation {
+                        SplitOrientation::SideBySide => {
+                            let spacing = ui.spacing().item_spacing.x;
+                            editor_rect.set_width((full_rect.width() - spacing) * ratio);
+                            preview_rect.min.x = editor_rect.max.x + spacing;
+                            (editor_rect.max.x, spacing)
+                        }
+                        SplitOrientation::Stacked => {
+                            let spacing = ui.spacing().item_spacing.y;
+                            editor_rect.set_height((full_rect.height() - spacing) * ratio);
+                            preview_rect.min.y = editor_rect.max.y + spacing;
+                            (editor_rect.max.y, spacing)
+                        }
+                    };
+
+                    self.render_editor_in(ui, editor_rect);
+                    self.render_preview_in(ui, preview_rect);
+                    self.render_splitter(ui, full_rect, divider, spacing);
+                }
+                ViewMode::EditorOnly => self.render_editor_in(ui, full_rect),
+                ViewMode::PreviewOnly => self.render_preview_in(ui, full_rect),
+            }
+            ui.advance_cursor_after_rect(full_rect);
+        });
+    }
+
+    /// The divider between the editor and preview, at `position` along the split axis. Dragging it
+    /// sets `split_ratio`; double-clicking resets it.
+    fn render_splitter(&mut self, ui: &mut egui::Ui, full_rect: egui::Rect, position: f32, spacing: f32) {
+        let stacked = self.config.layout.split_orientation == SplitOrientation::Stacked;
+        let (divider, grab_margin) = if stacked {
+            (egui::Rect::from_x_y_ranges(full_rect.x_range(), position..=position + spacing), egui::vec2(0.0, 3.0))
+        } else {
+            (egui::Rect::from_x_y_ranges(position..=position + spacing, full_rect.y_range()), egui::vec2(3.0, 0.0))
+        };
+        let response = ui.interact(divider.expand2(grab_margin), ui.id().with("editor_preview_splitter"), egui::Sense::click_and_drag());
+
+        if response.double_clicked() {
+            self.config.layout.split_ratio = 0.5;
+        } else if response.dragged()
+            && let Some(pointer) = response.interact_pointer_pos()
+        {
+            let ratio = if stacked {
+                (pointer.y - full_rect.min.y - spacing / 2.0) / (full_rect.height() - spacing)
+            } else {
+                (pointer.x - full_rect.min.x - spacing / 2.0) / (full_rect.width() - spacing)
+            };
+            self.config.layout.split_ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+        }
+
+        if response.hovered() || response.dragged() {
+            let cursor = if stacked { egui::CursorIcon::ResizeVertical } else { egui::CursorIcon::ResizeHorizontal };
+            ui.ctx().set_cursor_icon(cursor);
+        }
+        let stroke = if response.hovered() || response.dragged() {
+            ui.visuals().widgets.hovered.fg_stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        if stacked {
+            ui.painter().hline(full_rect.x_range(), divider.center().y, stroke);
+        } else {
+            ui.painter().vline(divider.center().x, full_rect.y_range(), stroke);
+        }
+    }
+
+    fn render_editor_in(&mut self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let builder = egui::UiBuilder::new().max_rect(rect).layout(egui::Layout::top_down(egui::Align::LEFT));
+        ui.scope_builder(builder, |ui| {
+            if !self.zen_mode {
+                self.render_breadcrumb(ui);
+            }
+            if self.editor.render(ui) {
+                self.notes_list.save_current_content(self.editor.get_text());
+            }
+        });
+    }
+
+    /// The headings enclosing the editor cursor, e.g. "Project › Meetings › 2025-06-12". Clicking
+    /// one jumps to it. Nothing is shown while the cursor is above the first heading.
+    fn render_breadcrumb(&mut self, ui: &mut egui::Ui) {
+        let text = self.editor.get_text();
+        let cursor_line = self
+            .editor
+            .get_cursor_position()
+            .map(|cursor| text.chars().take(cursor).filter(|&c| c == '\n').count())
+            .unwrap_or(0);
+        let note_headings = self.notes_list.get_current_headings();
+        let path = headings::heading_path(note_headings, cursor_line);
+        if path.is_empty() {
+            return;
+        }
+
+        let mut clicked = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 4.0;
+            for (position, &ordinal) in path.iter().enumerate() {
+                if position > 0 {
+                    ui.weak("›");
+                }
+                let heading = &note_headings[ordinal];
+                let label = egui::RichText::new(&heading.text).small();
+                if ui.add(egui::Button::new(label).frame(false)).on_hover_text(tr("breadcrumb-jump")).clicked() {
+                    clicked = Some((heading.line, ordinal));
+                }
+            }
+        });
+        ui.separator();
+
+        if let Some((line, ordinal)) = clicked {
+            self.editor.go_to_line(line);
+            self.rendered_view.scroll_to_heading(ordinal);
+        }
+    }
+
+    fn render_preview_in(&mut self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let builder = egui::UiBuilder::new().max_rect(rect).layout(egui::Layout::top_down(egui::Align::LEFT));
+        ui.scope_builder(builder, |ui| {
+            if let Some(checkbox_toggles) = self.rendered_view.render(ui, self.editor.get_text())
+                && !checkbox_toggles.is_empty() {
+                    for line in checkbox_toggles {
+                        self.editor.toggle_checkbox_at_line(line);
+                    }
+                    self.notes_list.save_current_content(self.editor.get_text());
+                }
+        });
+        if let Some(tag) = self.rendered_view.take_clicked_tag() {
+            self.notes_list.set_tag_filter(Some(tag));
+            self.config.layout.sidebar_visible = true;
+        }
+        if let Some(link) = self.rendered_view.take_clicked_attachment() {
+            match attachment_preview::resolve(self.notes_list.get_file_manager().notes_dir(), &link) {
+                Some(path) => self.attachment_preview.show(path),
+                None => {
+                    self.error_dialog_errors.push(format!("Attachment '{}' doesn't exist", link));
+                    self.show_error_dialog = true;
+                }
+            }
+        }
+        if let Some(anchor) = self.rendered_view.take_clicked_anchor() {
+            let headings = self.notes_list.get_current_headings();
+            if let Some(ordinal) = toc::anchors(headings).iter().position(|a| *a == anchor) {
+                let jump = HeadingJump {
+                    index: self.notes_list.get_current_note_index(),
+                    line: headings[ordinal].line,
+                    ordinal,
+                };
+                self.jump_to_heading(jump);
+            }
+        }
+        if let Some(url) = self.rendered_view.take_clicked_external_link() {
+            self.pending_external_link = Some(url);
+        }
+        if let Some(note) = self.rendered_view.take_clicked_note() {
+            match self.notes_list.resolve_note(&note) {
+                Some(index) => {
+                    self.switch_to_note(index);
+                    self.notes_list.reveal_current();
+                }
+                None => {
+                    self.error_dialog_errors.push(format!("Note '{}' doesn't exist", note));
+                    self.show_error_dialog = true;
+                }
+            }
+        }
+    }
+
+    fn open_current_note_in_window(&mut self) {
+        let note = self.notes_list.get_current_note_name().to_string();
+        let Some(content) = self.notes_list.get_note_content(&note) else {
+            return;
+        };
+        let window = NoteWindow::new(&note, content, &self.config, self.next_note_window_serial);
+        self.next_note_window_serial += 1;
+        self.note_windows.push(window);
+    }
+
+    /// Brings every editor up to date with the notes list, so a note open in several windows
+    /// shows the same text everywhere. Windows whose note was deleted or renamed are closed.
+    fn sync_open_notes(&mut self) {
+        if !self.note_windows.is_empty() {
+            let current = self.notes_list.get_current_content();
+            if self.editor.get_text() != current {
+                self.editor.set_text(current);
+            }
+        }
+
+        let notes_list = &self.notes_list;
+        let mut closed_windows = Vec::new();
+        self.note_windows.retain_mut(|window| match notes_list.get_note_content(&window.note) {
+            Some(content) if window.is_open() => {
+                window.sync_content(content);
+                true
+            }
+            Some(_) => {
+                closed_windows.push(window.note.clone());
+                false
+            }
+            None => false,
+        });
+        for note in closed_windows {
+            if note != self.notes_list.get_current_note_name() {
+                self.remember_closed_note(note, None);
+            }
+        }
+    }
+
+    pub fn handle_note_windows(&mut self, ctx: &egui::Context) {
+        for window in &mut self.note_windows {
+            if let NoteWindowAction::Changed(content) = window.render(ctx, &mut self.privacy_screen) {
+                self.notes_list.save_note_content(&window.note, &content);
+            }
+        }
+    }
+
+    /// Imports markdown files or folders dropped onto the window. Files named like an existing
+    /// note are held back for the conflict dialog; the rest are copied in right away.
+    pub fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("file_drop_overlay")));
+            let rect = ctx.content_rect();
+            painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(rect.center(), egui::Align2::CENTER_CENTER, tr("import-drop-hint"), egui::FontId::proportional(20.0), egui::Color32::WHITE);
+        }
+
+        let paths: Vec<std::path::PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|file| file.path.clone()).collect());
+        if !paths.is_empty() {
+            self.import_paths(&paths);
+        }
+    }
+
+    fn import_paths(&mut self, paths: &[std::path::PathBuf]) {
+        let mut taken = self.notes_list.get_note_names().to_vec();
+        let mut ready = Vec::new();
+        let mut conflicts = Vec::new();
+        let with_pandoc = pandoc::version(&self.config.pandoc_path).is_some();
+        for source in note_import::collect_importable_files(paths, with_pandoc) {
+            let Some(name) = note_import::note_name_for(&source) else {
+                continue;
+            };
+            if taken.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                conflicts.push(PendingImport { source, name, choice: ConflictChoice::KeepBoth });
+            } else {
+                taken.push(name.clone());
+                ready.push((source, name));
+            }
+        }
+
+        self.import_notes(ready);
+        if !conflicts.is_empty() {
+            self.import_dialog.open(conflicts, !self.config.merge_tool.trim().is_empty());
+        }
+    }
+
+    pub fn handle_import_dialog(&mut self, ctx: &egui::Context) {
+        let ImportAction::Resolve(pending) = self.import_dialog.render(ctx) else {
+            return;
+        };
+
+        let mut taken = self.notes_list.get_note_names().to_vec();
+        let mut imports = Vec::new();
+        for PendingImport { source, name, choice } in pending {
+            match choice {
+                ConflictChoice::KeepBoth => {
+                    let name = note_import::unique_name(&name, &taken);
+                    taken.push(name.clone());
+                    imports.push((source, name));
+                }
+                ConflictChoice::Replace => {
+                    let existing = taken.iter().find(|n| n.eq_ignore_ascii_case(&name)).cloned();
+                    imports.push((source, existing.unwrap_or(name)));
+                }
+                ConflictChoice::Merge => {
+                    let existing = taken.iter().find(|n| n.eq_ignore_ascii_case(&name)).cloned().unwrap_or(name);
+                    let incoming = self.read_import_source(&source, &existing);
+                    match (self.notes_list.load_note_now(&existing).map(str::to_string), incoming) {
+                        (Some(local), Ok(incoming)) => self.merge_queue.push(MergeJob { note: existing, local, incoming }),
+                        (None, _) => self.error_dialog_errors.push(format!("Failed to read note '{}' for merging", existing)),
+                        (_, Err(e)) => self.error_dialog_errors.push(e),
+                    }
+                }
+                ConflictChoice::Skip => {}
+            }
+        }
+        self.show_error_dialog |= !self.error_dialog_errors.is_empty();
+        self.import_notes(imports);
+    }
+
+    /// A file being imported as markdown, converted from OPML, or through pandoc when it's
+    /// another format.
+    fn read_import_source(&self, source: &std::path::Path, name: &str) -> Result<String, String> {
+        if let Some(format) = pandoc::import_format(source) {
+            let notes_dir = self.notes_list.get_file_manager().notes_dir();
+            return pandoc::to_markdown(&self.config.pandoc_path, source, format, notes_dir, name);
+        }
+        let content = std::fs::read_to_string(source).map_err(|e| format!("Failed to import '{}': {}", source.display(), e))?;
+        if opml::is_opml(source) {
+            return opml::to_markdown(&content).map_err(|e| format!("Failed to import '{}': {}", source.display(), e));
+        }
+        Ok(content)
+    }
+
+    /// Saves what the merge tool produced once the user closes it, and starts the next merge.
+    pub fn handle_merges(&mut self, ctx: &egui::Context) {
+        if !self.merge_queue.is_busy() {
+            return;
+        }
+        for event in self.merge_queue.poll(&self.config.merge_tool) {
+            match event {
+                MergeEvent::Merged { note, content } => self.replace_note_content(&note, &content),
+                MergeEvent::Failed(e) => {
+                    self.error_dialog_errors.push(e);
+                    self.show_error_dialog = true;
+                }
+            }
+        }
+        if self.merge_queue.is_busy() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+    }
+
+    /// Asks what to do with edits that clashed with another program's changes to a note.
+    pub fn handle_conflict_dialog(&mut self, ctx: &egui::Context) {
+        let has_merge_tool = !self.config.merge_tool.trim().is_empty();
+        match self.conflict_dialog.render(ctx, &self.config.theme.palette(), has_merge_tool) {
+            ConflictAction::KeepMine(conflict) => self.replace_note_content(&conflict.note, &conflict.mine),
+            ConflictAction::Merged { note, content } => self.replace_note_content(&note, &content),
+            ConflictAction::MergeTool(conflict) => self.merge_queue.push(MergeJob {
+                note: conflict.note,
+                local: conflict.mine,
+                incoming: conflict.theirs,
+            }),
+            ConflictAction::TakeTheirs | ConflictAction::None => {}
+        }
+    }
+
+    /// Saves new content for an existing note, updating the editor when it's the current one.
+    fn replace_note_content(&mut self, name: &str, content: &str) {
+        if name == self.notes_list.get_current_note_name() {
+            self.editor.set_text_with_undo(content);
+        }
+        self.notes_list.save_note_content(name, content);
+    }
+
+    /// Copies the files into the notes folder under the given names, reloads the list, and
+    /// opens the first note that was imported.
+    fn import_notes(&mut self, imports: Vec<(std::path::PathBuf, String)>) {
+        if imports.is_empty() {
+            return;
+        }
+        self.notes_list.save_current_content(self.editor.get_text());
+
+        let mut first_imported = None;
+        for (source, name) in imports {
+            let file_manager = self.notes_list.get_file_manager();
+            let imported = if pandoc::import_format(&source).is_some() || opml::is_opml(&source) {
+                self.read_import_source(&source, &name).and_then(|markdown| {
+                    file_manager
+                        .write_note(&name, &markdown)
+                        .map_err(|e| format!("Failed to import '{}': {}", source.display(), e))
+                })
+            } else {
+                file_manager.import_note(&source, &name)
+            };
+            match imported {
+                Ok(()) => {
+                    first_imported.get_or_insert(name);
+                }
+                Err(e) => {
+                    self.error_dialog_errors.push(e);
+                    self.show_error_dialog = true;
+                }
+            }
+        }
+
+        let previous_note = self.notes_list.get_current_note_name().to_string();
+        self.notes_list.load_notes();
+        let open_note = first_imported.unwrap_or(previous_note);
+        if let Some(index) = self.notes_list.find_note_index(&open_note) {
+            self.notes_list.switch_to_note(index);
+        }
+        self.editor.load_notes(&self.notes_list);
+        self.config.set_current_session(VaultSession {
+            note: self.notes_list.get_current_note_name().to_string(),
+            ..Default::default()
+        });
+        self.save_config();
+    }
+
+    /// Switches to another notes folder, from the vault switcher or the command line, first
+    /// saving the open note and where it was left off.
+    pub fn open_vault(&mut self, folder: std::path::PathBuf) {
+        if folder == self.config.notes_folder {
+            return;
+        }
+        self.notes_list.save_current_content(self.editor.get_text());
+        self.config.set_current_session(VaultSession {
+            note: self.notes_list.get_current_note_name().to_string(),
+            cursor: self.editor.get_cursor_position(),
+            scroll_offset: self.editor.get_scroll_offset(),
+        });
+        self.notes_list.flush_writes();
+        self.config.notes_folder = folder;
+        if self.notes_list.set_config(&self.config) {
+            self.load_notes();
+        }
+        self.save_config();
+    }
+
+    pub fn handle_vault_switcher(&mut self, ctx: &egui::Context) {
+        match self.vault_switcher.render(ctx, &self.config.vaults, &self.config.notes_folder) {
+            VaultAction::Open(folder) => self.open_vault(folder),
+            VaultAction::Add(folder) => {
+                if !self.config.vaults.contains(&folder) {
+                    self.config.vaults.push(folder.clone());
+                }
+                self.open_vault(folder);
+                self.save_config();
+            }
+            VaultAction::Remove(folder) => {
+                self.config.vaults.retain(|vault| *vault != folder);
+                self.save_config();
+            }
+            VaultAction::None => {}
+        }
+    }
+
+    /// Opens the notes of links another launch of the app handed over, and brings the window
+    /// forward.
+    fn handle_forwarded_links(&mut self, ctx: &egui::Context) {
+        let Some(listener) = &self.link_listener else {
+            return;
+        };
+        let links = listener.poll();
+        if links.is_empty() {
+            return;
+        }
+        for link in links {
+            self.notes_list.save_current_content(self.editor.get_text());
+            let previous = self.notes_list.get_current_note_name().to_string();
+            let errors = self.error_dialog_errors.len();
+            self.handle_launch_link(link);
+            self.track_note_switch(previous);
+            self.show_error_dialog |= self.error_dialog_errors.len() > errors;
+        }
+        self.save_config();
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    }
+
+    /// Opens the note named on the command line or by a `notesquirrel://` link.
+    pub fn handle_launch_link(&mut self, link: Result<DeepLink, String>) {
+        match link {
+            Ok(DeepLink::Note(name)) => {
+                let (opened, template_error) = self.notes_list.open_or_create_note(&name);
+                if opened {
+                    self.editor.load_notes(&self.notes_list);
+                    self.place_template_cursor();
+                    self.config.set_current_session(VaultSession {
+                        note: self.notes_list.get_current_note_name().to_string(),
+                        ..Default::default()
+                    });
+                } else {
+                    self.error_dialog_errors.push(format!("Failed to open note '{}'", name));
+                }
+                self.error_dialog_errors.extend(template_error);
+            }
+            Err(e) => self.error_dialog_errors.push(e),
+        }
+    }
+
+    pub fn handle_note_switcher(&mut self, ctx: &egui::Context) {
+        if let NoteSwitcherAction::Open(note) = self.note_switcher.render(ctx)
+            && let Some(index) = self.notes_list.find_note_index(&note)
+        {
+            self.switch_to_note(index);
+            self.notes_list.reveal_current();
+        }
+    }
+
+    pub fn handle_calendar_picker(&mut self, ctx: &egui::Context) {
+        match self.calendar_picker.render(ctx) {
+            CalendarAction::Open(day) => self.open_daily_note(day),
+            CalendarAction::OpenNote(note) => {
+                if let Some(index) = self.notes_list.find_note_index(&note) {
+                    self.switch_to_note(index);
+                    self.notes_list.reveal_current();
+                }
+            }
+            CalendarAction::None => {}
+        }
+    }
+
+    fn toggle_calendar(&mut self) {
+        let days_with_notes = self.notes_list.daily_note_days();
+        self.calendar_picker.toggle_dialog(self.notes_list.get_file_manager(), days_with_notes);
+    }
+
+    pub fn handle_tasks_panel(&mut self, ctx: &egui::Context) {
+        match self.tasks_panel.render(ctx, chrono::Local::now().date_naive()) {
+            TasksAction::Refresh => {
+                self.notes_list.load_all_content();
+                self.tasks_panel.refresh(self.notes_list.notes_with_content());
+            }
+            TasksAction::Toggle { note, line } => {
+                if note == self.notes_list.get_current_note_name() {
+                    if let Some(content) = tasks::toggle_task_line(self.editor.get_text(), line) {
+                        self.editor.set_text_with_undo(&content);
+                        self.notes_list.save_current_content(self.editor.get_text());
+                    }
+                } else if let Some(content) = self.notes_list.get_note_content(&note).and_then(|c| tasks::toggle_task_line(c, line)) {
+                    self.notes_list.save_note_content(&note, &content);
+                }
+                self.tasks_panel.refresh(self.notes_list.notes_with_content());
+            }
+            TasksAction::OpenNote(note) => {
+                if let Some(index) = self.notes_list.find_note_index(&note) {
+                    self.switch_to_note(index);
+                }
+            }
+            TasksAction::None => {}
+        }
+    }
+
+    pub fn handle_global_search(&mut self, ctx: &egui::Context) {
+        match self.global_search.render(ctx, &self.config.theme.palette()) {
+            GlobalSearchAction::Search => {
+                self.notes_list.save_current_content(self.editor.get_text());
+                self.global_search.search(self.notes_list.notes_with_content());
+            }
+            GlobalSearchAction::OpenNote(note) => {
+                if let Some(index) = self.notes_list.find_note_index(&note) {
+                    self.switch_to_note(index);
+                }
+            }
+            GlobalSearchAction::Open { note, line, range } => {
+                if let Some(index) = self.notes_list.find_note_index(&note) {
+                    if index != self.notes_list.get_current_note_index() {
+                        self.switch_to_note(index);
+                    }
+                    self.editor.select_in_line(line, range);
+                }
+            }
+            GlobalSearchAction::None => {}
+        }
+    }
+
+    /// Earlier versions of the current note, compared with the editor's text.
+    pub fn handle_note_history(&mut self, ctx: &egui::Context) {
+        let action = self.note_history.render(
+            ctx,
+            &self.config.theme.palette(),
+            self.notes_list.get_file_manager(),
+            self.notes_list.get_current_note_name(),
+            self.editor.get_text(),
+        );
+        if let HistoryAction::Restore { note, content } = action {
+            // Restoring is undone by restoring the snapshot of the version it replaces.
+            self.notes_list.save_current_content(self.editor.get_text());
+            self.notes_list.snapshot_note(&note);
+            self.replace_note_content(&note, &content);
+            self.notes_list.flush_writes();
+            self.note_history.load(&note, self.notes_list.get_file_manager());
+        }
+    }
+
+    fn toggle_note_history(&mut self, source: HistorySource) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        let note = self.notes_list.get_current_note_name();
+        self.note_history.toggle_dialog(source, note, self.notes_list.get_file_manager());
+    }
+
+    /// Opens the search across every note, reading any notes not loaded yet so none are missed.
+    fn toggle_global_search(&mut self) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        self.notes_list.load_all_content();
+        self.global_search.search(self.notes_list.notes_with_content());
+        self.global_search.toggle_dialog();
+    }
+
+    fn toggle_tasks_panel(&mut self) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        self.notes_list.load_all_content();
+        self.tasks_panel.refresh(self.notes_list.notes_with_content());
+        self.tasks_panel.toggle_dialog();
+    }
+
+    fn open_daily_note(&mut self, day: chrono::NaiveDate) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        let previous = self.notes_list.get_current_note_name().to_string();
+        let (opened, template_error) = self.notes_list.open_or_create_daily_note(day);
+        if opened {
+            self.editor.set_text(self.notes_list.get_current_content());
+            self.place_template_cursor();
+            self.track_note_switch(previous);
+            self.config.set_current_session(VaultSession {
+                note: self.notes_list.get_current_note_name().to_string(),
+                ..Default::default()
+            });
+            self.save_config();
+        } else {
+            self.error_dialog_errors.push(format!("Failed to open the daily note for {}", day));
+            self.show_error_dialog = true;
+        }
+        if let Some(error) = template_error {
+            self.error_dialog_errors.push(error);
+            self.show_error_dialog = true;
+        }
+    }
+
+    fn create_new_note(&mut self, folder: &str) {
+        let previous = self.notes_list.get_current_note_name().to_string();
+        let (new_note_name, template_error) = self.notes_list.create_new_note(folder);
+        if new_note_name.is_some() {
+            self.editor.set_text(self.notes_list.get_current_content());
+            self.notes_list.reveal_current();
+            self.place_template_cursor();
+            self.track_note_switch(previous);
+        }
+        if let Some(error) = template_error {
+            self.error_dialog_errors.push(error);
+            self.show_error_dialog = true;
+        }
+    }
+
+    /// Moves the cursor to the template's `{{cursor}}` in a note that was just created.
+    fn place_template_cursor(&mut self) {
+        if let Some(cursor) = self.notes_list.take_template_cursor() {
+            self.editor.restore_position(Some(cursor), 0.0);
+            self.editor.request_focus();
+        }
+    }
+
+    fn delete_current_note(&mut self) {
+        let name = self.notes_list.get_current_note_name().to_string();
+        let content = self.editor.get_text().to_string();
+        if self.notes_list.delete_current_note() {
+            self.editor.set_text(self.notes_list.get_current_content());
+            self.remember_closed_note(name.clone(), Some(content.clone()));
+            self.track_note_switch(name.clone());
+            self.recently_deleted = Some(DeletedNote {
+                name,
+                content,
+                deleted_at: std::time::Instant::now(),
+            });
+        }
+    }
+
+    fn switch_to_adjacent_note(&mut self, step: isize) {
+        if let Some(index) = self.notes_list.adjacent_note_index(step) {
+            self.switch_to_note(index);
+            self.notes_list.reveal_current();
+        }
+    }
+
+    fn switch_to_note(&mut self, index: usize) {
+        self.notes_list.save_current_content(self.editor.get_text());
+        let previous = self.notes_list.get_current_note_name().to_string();
+        if self.notes_list.switch_to_note(index) {
+            self.editor.set_text(self.notes_list.get_current_content());
+            self.track_note_switch(previous);
+            self.config.set_current_session(VaultSession {
+                note: self.notes_list.get_current_note_name().to_string(),
+                ..Default::default()
+            });
+            self.save_config();
+        }
+    }
+
+    /// Opens the note a heading is in and scrolls the editor and preview to the heading.
+    fn jump_to_heading(&mut self, jump: HeadingJump) {
+        if self.notes_list.get_current_note_index() != jump.index {
+            self.switch_to_note(jump.index);
+        }
+        self.editor.go_to_line(jump.line);
+        self.rendered_view.scroll_to_heading(jump.ordinal);
+    }
+
+    /// Opens a note from File → Recently Closed, recreating it first if it was deleted.
+    fn reopen_closed_note(&mut self, name: &str) {
+        let Some(position) = self.recently_closed.iter().rposition(|c| c.name == name) else {
+            return;
+        };
+        let closed = self.recently_closed.remove(position);
+
+        if let Some(index) = self.notes_list.find_note_index(&closed.name) {
+            self.switch_to_note(index);
+        } else if let Some(content) = closed.deleted_content {
+            self.notes_list.save_current_content(self.editor.get_text());
+            let previous = self.notes_list.get_current_note_name().to_string();
+            if self.notes_list.restore_note(&closed.name, &content) {
+                if self.recently_deleted.as_ref().is_some_and(|d| d.name == closed.name) {
+                    self.recently_deleted = None;
+                }
+                self.editor.load_notes(&self.notes_list);
+                self.track_note_switch(previous);
+                self.notes_list.reveal_current();
+                self.config.set_current_session(VaultSession {
+                    note: closed.name,
+                    ..Default::default()
+                });
+                self.save_config();
+            } else {
+                self.error_dialog_errors.push(format!("Failed to restore note '{}'", closed.name));
+                self.show_error_dialog = true;
+            }
+        } else {
+            self.error_dialog_errors.push(format!("Note '{}' no longer exists", closed.name));
+            self.show_error_dialog = true;
+        }
+    }
+
+    /// Call after the editor has moved from `previous` to another note.
+    fn track_note_switch(&mut self, previous: String) {
+        let current = self.notes_list.get_current_note_name().to_string();
+        if previous != current {
+            self.note_views.remember(&previous, self.current_view());
+            let view = self.note_views.get(&current).unwrap_or_default();
+            self.editor.restore_position(view.cursor, view.editor_scroll);
+            self.rendered_view.restore_scroll(view.preview_scroll);
+        }
+        if previous != current && self.notes_list.find_note_index(&previous).is_some() {
+            self.remember_closed_note(previous, None);
+        }
+        self.recently_closed.retain(|c| c.name != current);
+        self.note_switcher.note_opened(&current);
+    }
+
+    /// The cursor and scroll positions in the open note. Right after a switch, and until the
+    /// next frame, these still belong to the note that was open before.
+    fn current_view(&self) -> NoteView {
+        NoteView {
+            cursor: self.editor.get_cursor_position(),
+            editor_scroll: self.editor.get_scroll_offset(),
+            preview_scroll: self.rendered_view.get_scroll_offset(),
+        }
+    }
+
+    /// Writes the note views to the notes folder when `remember_note_views` is on, dropping
+    /// those of notes that are gone.
+    fn save_note_views(&mut self) {
+        let current = self.notes_list.get_current_note_name().to_string();
+        if !current.is_empty() {
+            self.note_views.remember(&current, self.current_view());
+        }
+        let notes_list = &self.notes_list;
+        self.note_views.retain(|note| notes_list.find_note_index(note).is_some());
+        if let Err(e) = self.note_views.save() {
+            eprintln!("{}", e);
+        }
+    }
+
+    fn remember_closed_note(&mut self, name: String, deleted_content: Option<String>) {
+        if name.is_empty() {
+            return;
+        }
+        self.recently_closed.retain(|c| c.name != name);
+        self.recently_closed.push(ClosedNote { name, deleted_content });
+        if self.recently_closed.len() > RECENTLY_CLOSED_LIMIT {
+            self.recently_closed.remove(0);
+        }
+    }
+}
+
+impl Default for AppFrame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl eframe::App for AppFrame {
+    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
         let ctx = ui.ctx().clone();
 
+        if self.setup_wizard.show_dialog {
+            self.handle_setup_wizard(&ctx);
+            return;
+        }
+
         if ctx.input(|i| i.viewport().close_requested()) {
-            self.config.last_open_note = Some(self.notes_list.get_current_note_name().to_string());
+            self.config.set_current_session(VaultSession {
+                note: self.notes_list.get_current_note_name().to_string(),
+                cursor: self.editor.get_cursor_position(),
+                scroll_offset: self.editor.get_scroll_offset(),
+            });
             self.save_config();
+            self.save_note_views();
+            self.notes_list.flush_writes();
+        }
+
+        self.privacy_screen.update(&ctx, self.config.privacy_screen_minutes, self.config.privacy_screen_on_focus_loss);
+        if self.privacy_screen.is_active() {
+            self.update_window_title(&ctx);
+            self.handle_note_windows(&ctx);
+            self.handle_automation_api(&ctx);
+            self.privacy_screen.render(ui);
+            return;
+        }
+
+        if self.presentation.active {
+            self.handle_presentation(ui);
+            return;
         }
 
+        self.profiler.begin_frame();
+        self.fit_window_to_monitor(&ctx);
+        self.track_window_state(&ctx);
+        self.handle_config_reload(&ctx);
+        self.handle_note_io(&ctx);
         self.update_window_title(&ctx);
         self.handle_global_shortcuts(&ctx);
+        self.handle_note_switcher(&ctx);
         self.render_delete_confirmation_dialog(&ctx);
+        self.render_external_link_confirmation(&ctx);
         self.render_error_dialog(&ctx);
+        self.render_undo_delete_toast(&ctx);
         self.handle_find_replace(&ctx);
+        self.handle_attachments_report(&ctx);
+        self.handle_vault_stats(&ctx);
+        self.handle_vault_index(&ctx);
+        self.handle_calendar_picker(&ctx);
+        self.handle_tasks_panel(&ctx);
+        self.handle_global_search(&ctx);
+        self.handle_note_history(&ctx);
+        self.handle_version_control(&ctx);
+        self.handle_vault_switcher(&ctx);
+        self.handle_note_server(&ctx);
+        self.handle_note_export(&ctx);
+        self.handle_note_share(&ctx);
+        self.handle_automation_api(&ctx);
+        self.handle_forwarded_links(&ctx);
+        self.handle_system_hotkey(&ctx);
+        self.handle_settings(&ctx);
+        self.handle_settings_transfer(&ctx);
+        self.shortcuts_sheet.render(&ctx, &self.keymap);
+        self.handle_import_dialog(&ctx);
+        self.handle_conflict_dialog(&ctx);
+        self.handle_merges(&ctx);
+        self.handle_dropped_files(&ctx);
+        if !self.zen_mode {
+            self.render_menu_bar(ui);
+        }
+        self.sync_open_notes();
         self.render_main_layout(ui);
+        self.handle_scratchpad(&ctx);
+        self.handle_note_windows(&ctx);
+        self.render_profiler(&ctx);
     }
 }
\ No newline at end of file
@@ -0,0 +1,255 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::OnceLock;
+use std::thread;
+
+use eframe::egui;
+use regex::Regex;
+
+use crate::attachments::{self, IMAGE_EXTENSIONS};
+use crate::i18n::{tr, tr_args};
+
+/// Renders a PDF's first page; part of poppler, and looked up on the `PATH`.
+const PDFTOPPM: &str = "pdftoppm";
+/// Width in pixels the first page of a PDF is rendered at.
+const PDF_PAGE_WIDTH: u32 = 800;
+
+/// `[text](dest)` or `![alt](dest)`, with an optional `"title"`.
+fn link_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r#"!?\[[^\]\n]*\]\(\s*<?([^)>\s]+)>?(?:\s+"[^"\n]*")?\s*\)"#).expect("link regex is valid")
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AttachmentKind {
+    Image,
+    Pdf,
+}
+
+fn attachment_kind(path: &Path) -> Option<AttachmentKind> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        Some(AttachmentKind::Image)
+    } else if extension == "pdf" {
+        Some(AttachmentKind::Pdf)
+    } else {
+        None
+    }
+}
+
+/// Whether `dest` is a link to a local image or PDF, which the preview pane can show.
+pub fn is_previewable(dest: &str) -> bool {
+    let dest = strip_file_scheme(dest);
+    !dest.contains("://") && attachment_kind(Path::new(dest)).is_some()
+}
+
+fn strip_file_scheme(dest: &str) -> &str {
+    dest.strip_prefix("file://").or_else(|| dest.strip_prefix("file:")).unwrap_or(dest)
+}
+
+/// The destination of the link or image the character position `cursor` is in, if any.
+pub fn link_at(text: &str, cursor: usize) -> Option<&str> {
+    let byte = text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i);
+    let line_start = text[..byte].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[byte..].find('\n').map_or(text.len(), |i| byte + i);
+    let line = &text[line_start..line_end];
+    let column = byte - line_start;
+    link_regex()
+        .captures_iter(line)
+        .find(|capture| capture.get(0).is_some_and(|link| link.start() <= column && column <= link.end()))
+        .and_then(|capture| capture.get(1))
+        .map(|dest| dest.as_str())
+}
+
+/// The local image or PDF a link points to, relative to `notes_dir`, if it exists.
+pub fn resolve(notes_dir: &Path, dest: &str) -> Option<PathBuf> {
+    if !is_previewable(dest) {
+        return None;
+    }
+    let path = notes_dir.join(strip_file_scheme(dest).replace("%20", " "));
+    path.is_file().then_some(path)
+}
+
+enum Preview {
+    /// An image that's read when the pane next shows.
+    Unloaded,
+    /// The first page of a PDF, being rendered to a PNG by `pdftoppm`.
+    Rendering(mpsc::Receiver<Result<PathBuf, String>>),
+    Ready(egui::TextureHandle),
+    Failed(String),
+}
+
+/// A side panel showing the image or PDF linked where the editor's cursor is, or the one last
+/// clicked in the preview, without leaving the app.
+pub struct AttachmentPreview {
+    current: Option<(PathBuf, Preview)>,
+    /// The link the editor's cursor was last in, so it's only resolved when it changes.
+    cursor_link: Option<String>,
+    /// The attachment the pane was closed on; it stays closed until the cursor leaves its link.
+    dismissed: Option<PathBuf>,
+}
+
+pub enum AttachmentPreviewAction {
+    None,
+    OpenExternally(PathBuf),
+}
+
+impl AttachmentPreview {
+    pub fn new() -> Self {
+        Self {
+            current: None,
+            cursor_link: None,
+            dismissed: None,
+        }
+    }
+
+    /// Shows the attachment linked at the editor's cursor, if it's a different one.
+    pub fn follow_cursor(&mut self, link: Option<&str>, notes_dir: &Path) {
+        if self.cursor_link.as_deref() == link {
+            return;
+        }
+        self.cursor_link = link.map(str::to_string);
+        let Some(path) = link.and_then(|link| resolve(notes_dir, link)) else {
+            self.dismissed = None;
+            return;
+        };
+        if self.dismissed.as_ref() != Some(&path) {
+            self.show(path);
+        }
+    }
+
+    pub fn show(&mut self, path: PathBuf) {
+        self.dismissed = None;
+        if self.current.as_ref().is_some_and(|(current, _)| *current == path) {
+            return;
+        }
+        let preview = match attachment_kind(&path) {
+            Some(AttachmentKind::Pdf) => Preview::Rendering(render_pdf_page(path.clone())),
+            _ => Preview::Unloaded,
+        };
+        self.current = Some((path, preview));
+    }
+
+    pub fn close(&mut self) {
+        self.dismissed = self.current.take().map(|(path, _)| path);
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui) -> AttachmentPreviewAction {
+        let mut action = AttachmentPreviewAction::None;
+        let mut close = false;
+        let Some((path, preview)) = &mut self.current else {
+            return action;
+        };
+        poll_preview(ui.ctx(), path, preview);
+
+        egui::Panel::right("attachment_preview")
+            .resizable(true)
+            .default_size(280.0)
+            .show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+                    ui.strong(name).on_hover_text(path.display().to_string());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("✕").on_hover_text(tr("attachment-preview-close")).clicked() {
+                            close = true;
+                        }
+                    });
+                });
+                if let Ok(metadata) = std::fs::metadata(&path) {
+                    ui.weak(attachments::format_size(metadata.len()));
+                }
+                if ui.button(tr("attachment-preview-open")).clicked() {
+                    action = AttachmentPreviewAction::OpenExternally(path.clone());
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical().id_salt("attachment_preview_scroll").show(ui, |ui| match preview {
+                    Preview::Unloaded | Preview::Rendering(_) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(tr("attachment-preview-rendering"));
+                        });
+                    }
+                    Preview::Ready(texture) => {
+                        let size = texture.size_vec2();
+                        let scale = (ui.available_width() / size.x).min(1.0);
+                        ui.image((texture.id(), size * scale));
+                    }
+                    Preview::Failed(message) => {
+                        ui.label(message.as_str());
+                    }
+                });
+            });
+
+        if close {
+            self.close();
+        }
+        action
+    }
+}
+
+/// Loads an image once the pane first shows it, and picks up a PDF page once it's rendered.
+fn poll_preview(ctx: &egui::Context, path: &Path, preview: &mut Preview) {
+    let image = match preview {
+        Preview::Unloaded => Ok(path.to_path_buf()),
+        Preview::Rendering(receiver) => match receiver.try_recv() {
+            Ok(page) => page,
+            Err(_) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+        },
+        Preview::Ready(_) | Preview::Failed(_) => return,
+    };
+    *preview = match image {
+        Ok(image) => {
+            let texture = attachments::load_image_texture(ctx, &image);
+            if image != path {
+                let _ = std::fs::remove_file(&image);
+            }
+            match texture {
+                Some(texture) => Preview::Ready(texture),
+                None => Preview::Failed(tr("attachment-preview-image-failed").to_string()),
+            }
+        }
+        Err(error) => Preview::Failed(error),
+    };
+}
+
+/// Renders the PDF's first page to a PNG in the temp folder on a background thread.
+fn render_pdf_page(pdf: PathBuf) -> mpsc::Receiver<Result<PathBuf, String>> {
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+    let render = RENDERS.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let prefix = std::env::temp_dir().join(format!("notesquirrel-pdf-preview-{}-{}", std::process::id(), render));
+        let output = Command::new(PDFTOPPM)
+            .args(["-png", "-singlefile", "-f", "1", "-l", "1", "-scale-to-x"])
+            .arg(PDF_PAGE_WIDTH.to_string())
+            .args(["-scale-to-y", "-1"])
+            .arg(&pdf)
+            .arg(&prefix)
+            .output();
+        let result = match output {
+            Ok(output) if output.status.success() => Ok(prefix.with_extension("png")),
+            Ok(output) => Err(tr_args(
+                "attachment-preview-pdf-failed",
+                &[("error", &String::from_utf8_lossy(&output.stderr).trim())],
+            )
+            .to_string()),
+            Err(_) => Err(tr_args("attachment-preview-no-pdftoppm", &[("program", &PDFTOPPM)]).to_string()),
+        };
+        let _ = sender.send(result);
+    });
+    receiver
+}
+
+impl Default for AttachmentPreview {
+    fn default() -> Self {
+        Self::new()
+    }
+}
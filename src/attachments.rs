@@ -0,0 +1,218 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::i18n::{tr, tr_args};
+
+pub const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+pub struct UnusedAttachment {
+    pub path: PathBuf,
+    pub relative_name: String,
+    pub size_bytes: u64,
+    pub selected: bool,
+}
+
+pub struct AttachmentsReport {
+    pub show_dialog: bool,
+    pub unused: Vec<UnusedAttachment>,
+    preview_index: Option<usize>,
+    preview_texture: Option<(PathBuf, egui::TextureHandle)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AttachmentsAction {
+    None,
+    Refresh,
+    DeleteSelected,
+}
+
+impl AttachmentsReport {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            unused: Vec::new(),
+            preview_index: None,
+            preview_texture: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.show_dialog = true;
+    }
+
+    /// Rebuilds the list of attachments whose file name is not mentioned in any note.
+    pub fn refresh<'a>(&mut self, attachments_dir: &Path, attachments: Vec<PathBuf>, note_contents: impl Iterator<Item = &'a str> + Clone) {
+        self.unused = attachments
+            .into_iter()
+            .filter_map(|path| {
+                let relative_name = path
+                    .strip_prefix(attachments_dir)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let file_name = path.file_name()?.to_str()?.to_string();
+                let encoded_name = file_name.replace(' ', "%20");
+
+                let referenced = note_contents.clone().any(|content| {
+                    content.contains(&file_name) || content.contains(&encoded_name)
+                });
+
+                if referenced {
+                    None
+                } else {
+                    let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    Some(UnusedAttachment { path, relative_name, size_bytes, selected: false })
+                }
+            })
+            .collect();
+        self.preview_index = None;
+        self.preview_texture = None;
+    }
+
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        self.unused.iter().filter(|a| a.selected).map(|a| a.path.clone()).collect()
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> AttachmentsAction {
+        let mut action = AttachmentsAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+
+        egui::Window::new(tr("attachments-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(560.0, 380.0))
+            .show(ctx, |ui| {
+                let total_size: u64 = self.unused.iter().map(|a| a.size_bytes).sum();
+                ui.label(tr_args("attachments-summary", &[
+                    ("count", &self.unused.len()),
+                    ("size", &format_size(total_size)),
+                ]));
+                ui.separator();
+
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(280.0);
+                        egui::ScrollArea::vertical()
+                            .max_height(260.0)
+                            .id_salt("unused_attachments_scroll")
+                            .show(ui, |ui| {
+                                if self.unused.is_empty() {
+                                    ui.label(tr("attachments-none-unused"));
+                                }
+                                for (index, attachment) in self.unused.iter_mut().enumerate() {
+                                    ui.horizontal(|ui| {
+                                        ui.checkbox(&mut attachment.selected, "");
+                                        let is_previewed = self.preview_index == Some(index);
+                                        let label = format!("{} ({})", attachment.relative_name, format_size(attachment.size_bytes));
+                                        if ui.selectable_label(is_previewed, label).clicked() {
+                                            self.preview_index = Some(index);
+                                        }
+                                    });
+                                }
+                            });
+                    });
+
+                    ui.separator();
+
+                    ui.vertical(|ui| {
+                        self.render_preview(ui);
+                    });
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("attachments-select-all")).clicked() {
+                        for attachment in &mut self.unused {
+                            attachment.selected = true;
+                        }
+                    }
+                    if ui.button(tr("attachments-select-none")).clicked() {
+                        for attachment in &mut self.unused {
+                            attachment.selected = false;
+                        }
+                    }
+                    if ui.button(tr("attachments-refresh")).clicked() {
+                        action = AttachmentsAction::Refresh;
+                    }
+
+                    let selected_count = self.unused.iter().filter(|a| a.selected).count();
+                    let delete_button = egui::Button::new(tr_args("attachments-delete-selected", &[("count", &selected_count)]));
+                    if ui.add_enabled(selected_count > 0, delete_button).clicked() {
+                        action = AttachmentsAction::DeleteSelected;
+                    }
+                });
+            });
+
+        if !open {
+            self.show_dialog = false;
+            self.preview_texture = None;
+        }
+
+        action
+    }
+
+    fn render_preview(&mut self, ui: &mut egui::Ui) {
+        let Some(attachment) = self.preview_index.and_then(|i| self.unused.get(i)) else {
+            ui.label(tr("attachments-select-to-preview"));
+            return;
+        };
+
+        let path = attachment.path.clone();
+        let is_image = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+
+        if !is_image {
+            ui.label(tr("attachments-no-preview"));
+            return;
+        }
+
+        let needs_load = self.preview_texture.as_ref().is_none_or(|(p, _)| p != &path);
+        if needs_load {
+            self.preview_texture = load_image_texture(ui.ctx(), &path).map(|t| (path.clone(), t));
+        }
+
+        match &self.preview_texture {
+            Some((_, texture)) => {
+                let max_size = egui::vec2(240.0, 240.0);
+                let size = texture.size_vec2();
+                let scale = (max_size.x / size.x).min(max_size.y / size.y).min(1.0);
+                ui.image((texture.id(), size * scale));
+            }
+            None => {
+                ui.label(tr("attachments-image-failed"));
+            }
+        }
+    }
+}
+
+impl Default for AttachmentsReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn load_image_texture(ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
+    let image = image::open(path).ok()?.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    Some(ctx.load_texture(path.to_string_lossy(), color_image, egui::TextureOptions::LINEAR))
+}
+
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::config::ListMarker;
+
+/// A task checkbox with no space after its marker: `-[ ]`, `*[x]`.
+fn checkbox_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^(\s*[-*+])\[[ xX]\]").expect("checkbox regex is valid"))
+}
+
+/// A heading of level 2 or more with no space after its `#`s. A single `#` is left alone, since
+/// it's usually a `#tag`.
+fn heading_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^(\s*#{2,6})[^#\s]").expect("heading regex is valid"))
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// `line` with its slips fixed, and the character column where a space went in, if one did.
+fn correct_line(line: &str, marker: ListMarker) -> Option<(String, Option<usize>)> {
+    let mut corrected = line.to_string();
+    let mut inserted = None;
+
+    if let Some(prefix) = checkbox_regex().captures(line).or_else(|| heading_regex().captures(line)).and_then(|c| c.get(1)) {
+        corrected.insert(prefix.end(), ' ');
+        inserted = Some(line[..prefix.end()].chars().count());
+    }
+
+    // `- item` with another marker; a line of only markers and spaces is a thematic break.
+    let trimmed = corrected.trim_start();
+    let indent = corrected.len() - trimmed.len();
+    let is_break = trimmed.chars().all(|c| matches!(c, '-' | '*' | '+' | ' '));
+    if let Some(typed) = trimmed.chars().next().filter(|c| matches!(c, '-' | '*' | '+'))
+        && trimmed[1..].starts_with(' ')
+        && typed != marker.marker()
+        && !is_break
+    {
+        corrected.replace_range(indent..indent + 1, &marker.marker().to_string());
+    }
+
+    (corrected != line).then_some((corrected, inserted))
+}
+
+/// Fixes the line holding the character position `cursor`, unless it's inside a code block.
+/// Returns the corrected text and where the cursor moves to.
+pub fn correct(text: &str, cursor: usize, marker: ListMarker) -> Option<(String, usize)> {
+    let byte = text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i);
+    let line_start = text[..byte].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[byte..].find('\n').map_or(text.len(), |i| byte + i);
+    let line = &text[line_start..line_end];
+    let in_code_block = text[..line_start].lines().filter(|line| is_fence(line)).count() % 2 == 1;
+    if in_code_block || is_fence(line) {
+        return None;
+    }
+
+    let (corrected, inserted) = correct_line(line, marker)?;
+    let column = text[line_start..byte].chars().count();
+    let cursor = match inserted {
+        Some(inserted) if inserted <= column => cursor + 1,
+        _ => cursor,
+    };
+    Some((format!("{}{}{}", &text[..line_start], corrected, &text[line_end..]), cursor))
+}
+
+/// The 1-based line of a ``` fence that's never closed, if the text has one.
+pub fn unclosed_fence(text: &str) -> Option<usize> {
+    let mut open = None;
+    for (index, line) in text.lines().enumerate() {
+        if is_fence(line) {
+            open = if open.is_some() { None } else { Some(index + 1) };
+        }
+    }
+    open
+}
@@ -0,0 +1,371 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::config::Config;
+
+/// How long the server thread sleeps when no connection is waiting.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a request waits for the UI thread to answer before giving up.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Largest request body accepted, to keep a runaway script from filling memory.
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Something a script asked the running app to do.
+pub enum ApiCommand {
+    ListNotes,
+    ReadNote(String),
+    /// Replaces the note's content, creating the note if it doesn't exist.
+    WriteNote { name: String, content: String },
+    /// Adds text to the end of the note, creating the note if it doesn't exist.
+    AppendToNote { name: String, text: String },
+    Search(String),
+}
+
+pub struct ApiResponse {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl ApiResponse {
+    pub fn json_list<'a>(items: impl IntoIterator<Item = &'a str>) -> Self {
+        let items: Vec<String> = items.into_iter().map(json_string).collect();
+        Self {
+            status: 200,
+            content_type: "application/json",
+            body: format!("[{}]", items.join(",")),
+        }
+    }
+
+    pub fn markdown(content: &str) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/markdown; charset=utf-8",
+            body: content.to_string(),
+        }
+    }
+
+    pub fn no_content() -> Self {
+        Self::error(204, "")
+    }
+
+    pub fn not_found(message: &str) -> Self {
+        Self::error(404, message)
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: message.to_string(),
+        }
+    }
+}
+
+/// A command waiting for the UI thread, which owns the notes, to carry it out.
+pub struct ApiRequest {
+    pub command: ApiCommand,
+    reply: Sender<ApiResponse>,
+}
+
+impl ApiRequest {
+    pub fn respond(self, response: ApiResponse) {
+        self.reply.send(response).ok();
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct ApiSettings {
+    port: u16,
+    token: String,
+}
+
+/// Listens on localhost from a background thread and hands requests to the UI thread. Stops
+/// when dropped.
+struct RunningApi {
+    requests: Receiver<ApiRequest>,
+    stop: Arc<AtomicBool>,
+}
+
+impl RunningApi {
+    fn spawn(settings: &ApiSettings, ctx: &egui::Context) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", settings.port))?;
+        listener.set_nonblocking(true)?;
+
+        let (sender, requests) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let token = settings.token.clone();
+        let ctx = ctx.clone();
+
+        thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(e) = respond(stream, &token, &sender, &ctx) {
+                            eprintln!("Automation API request failed: {}", e);
+                        }
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+                    Err(e) => {
+                        eprintln!("Automation API stopped accepting connections: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self { requests, stop })
+    }
+}
+
+impl Drop for RunningApi {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The opt-in local HTTP API for scripts: list, read, write, and append to notes, and search.
+/// Only reachable from this machine, and refuses requests from web pages.
+pub struct AutomationApi {
+    running: Option<RunningApi>,
+    /// What the server was last started, or failed to start, with; `None` while disabled.
+    settings: Option<ApiSettings>,
+}
+
+impl AutomationApi {
+    pub fn new() -> Self {
+        Self {
+            running: None,
+            settings: None,
+        }
+    }
+
+    /// Starts, restarts, or stops the server to match the config. A server that fails to start
+    /// isn't retried until its settings change.
+    pub fn sync(&mut self, config: &Config, ctx: &egui::Context) -> Result<(), String> {
+        let wanted = config.automation_api.then(|| ApiSettings {
+            port: config.automation_api_port,
+            token: config.automation_api_token.clone(),
+        });
+        if wanted == self.settings {
+            return Ok(());
+        }
+        self.running = None;
+        self.settings = wanted;
+        if let Some(settings) = &self.settings {
+            let server = RunningApi::spawn(settings, ctx)
+                .map_err(|e| format!("Failed to start the automation API on port {}: {}", settings.port, e))?;
+            self.running = Some(server);
+        }
+        Ok(())
+    }
+
+    /// Requests that have arrived since the last call. Each must be answered with `respond`.
+    pub fn poll(&self) -> Vec<ApiRequest> {
+        self.running.as_ref().map(|api| api.requests.try_iter().collect()).unwrap_or_default()
+    }
+}
+
+impl Default for AutomationApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn respond(mut stream: TcpStream, token: &str, requests: &Sender<ApiRequest>, ctx: &egui::Context) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let response = match read_request(&mut stream)? {
+        Some(request) => route(request, token, requests, ctx),
+        None => ApiResponse::error(400, "Malformed request"),
+    };
+
+    let reason = match response.status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        500 => "Internal Server Error",
+        _ => "Service Unavailable",
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+/// Reads the request line, headers, and body. `None` when the request can't be understood;
+/// a body over `MAX_BODY_BYTES` or that isn't UTF-8 is left out.
+fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<HttpRequest>> {
+    let mut data = Vec::new();
+    let mut buffer = [0u8; 8192];
+    let header_end = loop {
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        let read = stream.read(&mut buffer)?;
+        if read == 0 || data.len() > 64 * 1024 {
+            return Ok(None);
+        }
+        data.extend_from_slice(&buffer[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let mut request = HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body: None,
+    };
+
+    let length: usize = request.header("Content-Length").and_then(|length| length.parse().ok()).unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Ok(Some(request));
+    }
+    let mut body = data[header_end + 4..].to_vec();
+    while body.len() < length {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&buffer[..read]);
+    }
+    body.truncate(length);
+    request.body = String::from_utf8(body).ok();
+    Ok(Some(request))
+}
+
+/// Checks the request, turns it into a command, and waits for the UI thread's answer.
+fn route(request: HttpRequest, token: &str, requests: &Sender<ApiRequest>, ctx: &egui::Context) -> ApiResponse {
+    // Browsers send `Origin` on cross-site requests, and a page that rebinds its own domain to
+    // 127.0.0.1 still sends its own `Host`; neither comes from a local script.
+    let host = request.header("Host").map_or("", |host| host.split_once(':').map_or(host, |(name, _port)| name));
+    if request.header("Origin").is_some() || !matches!(host, "127.0.0.1" | "localhost") {
+        return ApiResponse::error(403, "Requests from web pages are not allowed");
+    }
+    if !token.is_empty() && request.header("Authorization") != Some(format!("Bearer {}", token).as_str()) {
+        return ApiResponse::error(401, "Missing or wrong token");
+    }
+
+    let note = request.path.strip_prefix("/notes/").map(percent_decode).filter(|name| !name.is_empty());
+    let command = match (request.method.as_str(), request.path.as_str(), note) {
+        ("GET", "/notes", _) => ApiCommand::ListNotes,
+        ("GET", "/search", _) => {
+            let query = request
+                .query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("q="))
+                .map(|query| percent_decode(&query.replace('+', " ")))
+                .unwrap_or_default();
+            ApiCommand::Search(query)
+        }
+        ("GET", _, Some(name)) => ApiCommand::ReadNote(name),
+        ("PUT" | "POST", _, Some(name)) => {
+            let Some(body) = request.body else {
+                return ApiResponse::error(413, "The body must be UTF-8 text of at most 16 MiB");
+            };
+            if request.method == "PUT" {
+                ApiCommand::WriteNote { name, content: body }
+            } else {
+                ApiCommand::AppendToNote { name, text: body }
+            }
+        }
+        (_, "/notes" | "/search", _) | (_, _, Some(_)) => return ApiResponse::error(405, "Method not allowed"),
+        _ => return ApiResponse::not_found("Unknown endpoint"),
+    };
+
+    let (reply, answer) = mpsc::channel();
+    if requests.send(ApiRequest { command, reply }).is_err() {
+        return ApiResponse::error(503, "NoteSquirrel is shutting down");
+    }
+    ctx.request_repaint();
+    answer
+        .recv_timeout(REPLY_TIMEOUT)
+        .unwrap_or_else(|_| ApiResponse::error(503, "NoteSquirrel did not answer in time"))
+}
+
+/// Decodes `%XX` escapes in a URL path or query value.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%' && i + 2 < bytes.len())
+            .then(|| &bytes[i + 1..i + 3])
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
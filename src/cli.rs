@@ -0,0 +1,178 @@
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+
+use crate::config::{Config, ConfigLoadResult};
+use crate::file_manager::FileManager;
+use crate::note_template::{self, sanitize_file_name, sanitize_note_path};
+use crate::task_calendar;
+use crate::uri_scheme::{DeepLink, SCHEME};
+
+const USAGE: &str = "\
+Usage:
+  NoteSquirrel [VAULT] [NOTE]            Open the app, optionally at a notes folder and note
+  NoteSquirrel [VAULT] --new [--title T] Create a note from standard input and print its name
+  NoteSquirrel [VAULT] --cat NOTE        Print a note to standard output
+  NoteSquirrel [VAULT] --tasks-ics       Print open tasks with due dates as an iCalendar file
+  NoteSquirrel notesquirrel://note/NAME  Open the app at a note
+
+Options:
+  -h, --help     Show this help
+  -V, --version  Show the version";
+
+/// What to do for the command-line arguments NoteSquirrel was started with.
+pub enum Command {
+    /// Start the app, optionally switching notes folder and opening a note.
+    Open {
+        vault: Option<PathBuf>,
+        link: Option<Result<DeepLink, String>>,
+    },
+    NewNote {
+        vault: Option<PathBuf>,
+        title: Option<String>,
+    },
+    Cat {
+        vault: Option<PathBuf>,
+        note: String,
+    },
+    TasksIcs {
+        vault: Option<PathBuf>,
+    },
+    Help,
+    Version,
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut link = None;
+        let mut new_note = false;
+        let mut title = None;
+        let mut cat = None;
+        let mut tasks_ics = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Command::Help),
+                "-V" | "--version" => return Ok(Command::Version),
+                "--new" => new_note = true,
+                "--title" => title = Some(args.next().ok_or("--title needs a value")?),
+                "--cat" => cat = Some(args.next().ok_or("--cat needs a note name")?),
+                "--tasks-ics" => tasks_ics = true,
+                _ if arg.starts_with(&format!("{}://", SCHEME)) => link = Some(DeepLink::parse(&arg)),
+                _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'", arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let vault = positional.next().map(|path| std::path::absolute(&path).unwrap_or_else(|_| PathBuf::from(path)));
+        let note = positional.next();
+        if let Some(extra) = positional.next() {
+            return Err(format!("Unexpected argument '{}'", extra));
+        }
+
+        if tasks_ics {
+            return match (new_note, cat, note) {
+                (false, None, None) => Ok(Command::TasksIcs { vault }),
+                (_, _, Some(extra)) => Err(format!("Unexpected argument '{}'", extra)),
+                _ => Err("--tasks-ics can't be used with --new or --cat".to_string()),
+            };
+        }
+
+        match (new_note, cat) {
+            (true, Some(_)) => Err("--new and --cat can't be used together".to_string()),
+            (true, None) if note.is_some() => Err("Use --title to name a note created with --new".to_string()),
+            (true, None) => Ok(Command::NewNote { vault, title }),
+            (false, Some(_)) if note.is_some() => Err("Give the note to print after --cat".to_string()),
+            (false, Some(note)) => Ok(Command::Cat { vault, note }),
+            (false, None) => {
+                let link = link.or_else(|| note.map(|name| Ok(DeepLink::Note(sanitize_note_path(&name)))));
+                Ok(Command::Open { vault, link })
+            }
+        }
+    }
+
+    /// Runs commands that don't need a window and returns the exit code, or `None` for `Open`.
+    pub fn run_without_window(&self) -> Option<i32> {
+        let result = match self {
+            Command::Open { .. } => return None,
+            Command::Help => {
+                println!("{}", USAGE);
+                Ok(())
+            }
+            Command::Version => {
+                println!("NoteSquirrel {}", env!("CARGO_PKG_VERSION"));
+                Ok(())
+            }
+            Command::NewNote { vault, title } => new_note(vault.as_ref(), title.as_deref()),
+            Command::Cat { vault, note } => cat(vault.as_ref(), note),
+            Command::TasksIcs { vault } => {
+                print!("{}", task_calendar::notes_folder_ics(&load_config(vault.as_ref())));
+                Ok(())
+            }
+        };
+        Some(match result {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        })
+    }
+
+    pub fn usage() -> &'static str {
+        USAGE
+    }
+}
+
+fn load_config(vault: Option<&PathBuf>) -> Config {
+    let ConfigLoadResult { mut config, errors, .. } = Config::load();
+    for error in errors {
+        eprintln!("{}", error);
+    }
+    if let Some(vault) = vault {
+        config.notes_folder = vault.clone();
+    }
+    config
+}
+
+fn new_note(vault: Option<&PathBuf>, title: Option<&str>) -> Result<(), String> {
+    let config = load_config(vault);
+    let file_manager = FileManager::new(&config);
+    let now = chrono::Local::now();
+    let existing = file_manager.list_note_names();
+
+    let name = match title {
+        Some(title) => sanitize_file_name(title),
+        None => note_template::new_note_name(&config.new_note_pattern, &existing, &now, &config.formats),
+    };
+    if existing.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+        return Err(format!("A note named '{}' already exists", name));
+    }
+
+    let mut content = String::new();
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        stdin.lock().read_to_string(&mut content).map_err(|e| format!("Failed to read standard input: {}", e))?;
+    }
+    if content.is_empty() {
+        content = note_template::take_cursor(&note_template::new_note_content(&config, &name, &now)?).0;
+    }
+
+    if !file_manager.create_note(&name, &content) {
+        return Err(format!("Failed to create note '{}' in {}", name, config.notes_folder.display()));
+    }
+    println!("{}", name);
+    Ok(())
+}
+
+fn cat(vault: Option<&PathBuf>, note: &str) -> Result<(), String> {
+    let config = load_config(vault);
+    let file_manager = FileManager::new(&config);
+    if !file_manager.list_note_names().iter().any(|n| n == note) {
+        return Err(format!("No note named '{}' in {}", note, config.notes_folder.display()));
+    }
+    print!("{}", file_manager.read_note_content(note));
+    Ok(())
+}
@@ -1,15 +1,26 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
 use egui::{Color32, FontId, FontDefinitions, FontData, FontFamily};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use std::collections::BTreeMap;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+
+use crate::i18n::{tr, Language};
+use crate::keymap::Action;
+use crate::theme::ThemePreset;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarkdownStyle {
     pub font_size: f32,
     pub color: [u8; 3],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarkdownStyles {
     pub h1: MarkdownStyle,
     pub h2: MarkdownStyle,
@@ -25,12 +36,220 @@ pub struct MarkdownStyles {
     pub code_block: MarkdownStyle,
     pub code_block_background: [u8; 3],
     pub list_bullet: MarkdownStyle,
+    /// Editor line height as a multiple of the font's natural line height.
+    #[serde(default = "default_editor_line_spacing")]
+    pub editor_line_spacing: f32,
+    /// Vertical gap in points between paragraphs, lists, and quotes in the preview;
+    /// headings and code blocks get twice this.
+    #[serde(default = "default_block_spacing")]
+    pub block_spacing: f32,
+}
+
+pub fn default_editor_line_spacing() -> f32 {
+    1.0
+}
+
+pub fn default_block_spacing() -> f32 {
+    4.0
+}
+
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+
+pub const MIN_SPLIT_RATIO: f32 = 0.15;
+pub const MAX_SPLIT_RATIO: f32 = 0.85;
+pub const MIN_SIDEBAR_WIDTH: f32 = 120.0;
+pub const MAX_SIDEBAR_WIDTH: f32 = 600.0;
+
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 3.0;
+pub const UI_SCALE_STEP: f32 = 0.1;
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_zen_line_width() -> u32 {
+    80
+}
+
+fn default_zen_dim_surroundings() -> bool {
+    true
+}
+
+fn default_confirm_delete() -> bool {
+    true
+}
+
+fn default_delete_to_trash() -> bool {
+    true
+}
+
+fn default_note_extensions() -> Vec<String> {
+    vec!["md".to_string()]
+}
+
+fn default_new_note_extension() -> String {
+    "md".to_string()
+}
+
+fn default_note_history_limit() -> usize {
+    50
+}
+
+fn default_git_commit_minutes() -> u32 {
+    15
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_serve_port() -> u16 {
+    8765
+}
+
+fn default_automation_api_port() -> u16 {
+    8766
+}
+
+fn default_pandoc_path() -> String {
+    "pandoc".to_string()
+}
+
+fn default_large_note_threshold_kb() -> usize {
+    1024
+}
+
+fn default_daily_note_format() -> String {
+    crate::note_template::DEFAULT_DAILY_NOTE_FORMAT.to_string()
+}
+
+fn default_new_note_pattern() -> String {
+    "Note {n}".to_string()
+}
+
+const SETTINGS_BUNDLE_KEY: &str = "notesquirrel_settings";
+const SETTINGS_BUNDLE_VERSION: i64 = 1;
+
+/// Upgrades applied in order to configs older than `CURRENT_CONFIG_VERSION`;
+/// entry `n` migrates a version `n` file to version `n + 1`.
+const MIGRATIONS: [fn(&mut toml::Table); 2] = [migrate_v0_to_v1, migrate_v1_to_v2];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorStyles {
+    pub text: [u8; 3],
+    pub heading_marker: [u8; 3],
+    pub h1: [u8; 3],
+    pub h2: [u8; 3],
+    pub h3: [u8; 3],
+    pub h4: [u8; 3],
+    pub h5: [u8; 3],
+    pub h6: [u8; 3],
+    pub code: [u8; 3],
+    pub code_background: [u8; 3],
+    pub quote: [u8; 3],
+    pub list: [u8; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
+    #[serde(default)]
+    pub config_version: u32,
     pub notes_folder: PathBuf,
+    /// Notes folders to switch between from File → Vaults, `notes_folder` among them once it has
+    /// been opened.
+    #[serde(default)]
+    pub vaults: Vec<PathBuf>,
+    /// Extensions, without the dot, of the files in the notes folder that are listed as notes.
+    #[serde(default = "default_note_extensions")]
+    pub note_extensions: Vec<String>,
+    /// The extension new notes are created with; always listed as notes too.
+    #[serde(default = "default_new_note_extension")]
+    pub new_note_extension: String,
+    /// Gitignore-style patterns for files and folders in the notes folder to leave out of the
+    /// notes list, e.g. `.obsidian/` or `*.tmp`.
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
     pub editor_font_size: f32,
+    /// Widest the editor's text column may grow, in characters; 0 fills the pane.
+    #[serde(default)]
+    pub editor_max_line_width: u32,
+    /// Text column width in characters while in zen mode; 0 fills the screen.
+    #[serde(default = "default_zen_line_width")]
+    pub zen_line_width: u32,
+    /// Whether zen mode darkens the screen on either side of the text column.
+    #[serde(default = "default_zen_dim_surroundings")]
+    pub zen_dim_surroundings: bool,
+    #[serde(default)]
+    pub focus_scope: FocusScope,
+    /// Fix common markdown slips on the line being typed, e.g. `-[ ]` → `- [ ]`.
+    #[serde(default)]
+    pub autocorrect_markdown: bool,
+    /// The bullet autocorrect and pasted lists use for list items.
+    #[serde(default)]
+    pub list_marker: ListMarker,
+    /// Ask before deleting a note; deletions can be undone from a toast either way.
+    #[serde(default = "default_confirm_delete")]
+    pub confirm_delete: bool,
+    /// Ask before opening a web link clicked in the preview.
+    #[serde(default)]
+    pub confirm_external_links: bool,
+    /// Cover the notes after this many minutes without input; `0` never does.
+    #[serde(default)]
+    pub privacy_screen_minutes: u32,
+    /// Cover the notes whenever the main window loses focus, as when the screen locks.
+    #[serde(default)]
+    pub privacy_screen_on_focus_loss: bool,
+    /// Move deleted notes into the notes folder's `.trash` folder instead of removing them.
+    #[serde(default = "default_delete_to_trash")]
+    pub delete_to_trash: bool,
+    /// Snapshot a note into the notes folder's `.history` folder when a save replaces a version
+    /// at least this many minutes newer than its last snapshot; `0` keeps no history.
+    #[serde(default)]
+    pub note_history_minutes: u32,
+    /// How many snapshots are kept per note; older ones are deleted.
+    #[serde(default = "default_note_history_limit")]
+    pub note_history_limit: usize,
+    /// Commit the notes folder automatically when it's a git repository.
+    #[serde(default)]
+    pub git_autocommit: GitAutocommit,
+    /// Minutes between commits when `git_autocommit` is `interval`.
+    #[serde(default = "default_git_commit_minutes")]
+    pub git_commit_minutes: u32,
+    /// Send a desktop notification for open tasks due today (`📅 YYYY-MM-DD`). Off unless
+    /// chosen, since it rescans the notes folder every minute.
+    #[serde(default)]
+    pub task_reminders: bool,
+    /// Keep each note's cursor and scroll positions in the notes folder across restarts.
+    #[serde(default)]
+    pub remember_note_views: bool,
+    /// iCalendar file kept up to date with open tasks that have a due date.
+    #[serde(default)]
+    pub tasks_calendar: Option<PathBuf>,
+    /// Port for File → Serve Note; 0 picks a free one.
+    #[serde(default = "default_serve_port")]
+    pub serve_port: u16,
+    /// Show the debug overlay with frame, parse, layout, and file I/O timings.
+    #[serde(default)]
+    pub show_profiler: bool,
+    /// Notes bigger than this many KiB get no syntax highlighting and ask before rendering a preview.
+    #[serde(default = "default_large_note_threshold_kb")]
+    pub large_note_threshold_kb: usize,
+    /// The pandoc executable used for importing and exporting DOCX, ODT, EPUB, RST, and org files.
+    #[serde(default = "default_pandoc_path")]
+    pub pandoc_path: String,
+    /// Run the local HTTP API for scripts on 127.0.0.1.
+    #[serde(default)]
+    pub automation_api: bool,
+    #[serde(default = "default_automation_api_port")]
+    pub automation_api_port: u16,
+    /// When set, API requests must send `Authorization: Bearer <token>`.
+    #[serde(default)]
+    pub automation_api_token: String,
+    /// System-wide shortcut that raises the window or minimizes it again, e.g. `Ctrl+Alt+Space`.
+    /// Empty turns it off.
+    #[serde(default)]
+    pub global_hotkey: String,
+    /// Command for an external merge tool, with `{local}`, `{incoming}`, and `{merged}` standing
+    /// for the files. Empty turns merging off.
+    #[serde(default)]
+    pub merge_tool: String,
     pub list_font_size: f32,
     pub rendered_font_size: f32,
     pub editor_font_family: String,
@@ -38,18 +257,299 @@ pub struct Config {
     pub rendered_font_family: String,
     pub markdown_styles: MarkdownStyles,
     #[serde(default)]
-    pub last_open_note: Option<String>,
+    pub editor_styles: EditorStyles,
+    #[serde(default)]
+    pub theme: ThemePreset,
+    #[serde(default)]
+    pub keymap: BTreeMap<Action, String>,
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    #[serde(default)]
+    pub language: Language,
+    /// File name for new notes; supports `{n}`, `{date}`, `{time}`, and `{datetime}`.
+    #[serde(default = "default_new_note_pattern")]
+    pub new_note_pattern: String,
+    /// Optional markdown file whose contents start every new note.
+    #[serde(default)]
+    pub new_note_template: Option<PathBuf>,
+    /// strftime-style name for daily notes, e.g. `%Y-%m-%d`.
+    #[serde(default = "default_daily_note_format")]
+    pub daily_note_format: String,
+    /// Optional template for daily notes; falls back to `new_note_template`.
+    #[serde(default)]
+    pub daily_note_template: Option<PathBuf>,
+    #[serde(default)]
+    pub formats: DateFormats,
+    #[serde(default)]
+    pub highlight_rules: Vec<HighlightRule>,
+    /// Last open note and editor position for each notes folder, keyed by folder path.
+    #[serde(default)]
+    pub sessions: BTreeMap<String, VaultSession>,
+    #[serde(default)]
+    pub window: WindowState,
+    #[serde(default)]
+    pub layout: LayoutState,
     #[serde(skip)]
     pub loaded_fonts: LoadedFonts,
+    /// Set when the file on disk could not be parsed, so saving doesn't clobber it before it's fixed.
+    #[serde(skip)]
+    pub save_blocked: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ConfigLoadResult {
     pub config: Config,
     pub errors: Vec<String>,
+    /// No config file existed yet. Nothing is written until the first-run setup is done.
+    pub first_run: bool,
+}
+
+/// strftime-style formats for dates shown or inserted by the app. An empty format uses the
+/// default for the interface language.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DateFormats {
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub time: String,
+    #[serde(default)]
+    pub datetime: String,
+    #[serde(default)]
+    pub week: String,
+}
+
+impl DateFormats {
+    pub fn format_date(&self, when: &DateTime<Local>) -> String {
+        format_with_fallback(when, &self.date, tr("format-date"))
+    }
+
+    pub fn format_time(&self, when: &DateTime<Local>) -> String {
+        format_with_fallback(when, &self.time, tr("format-time"))
+    }
+
+    pub fn format_datetime(&self, when: &DateTime<Local>) -> String {
+        format_with_fallback(when, &self.datetime, tr("format-datetime"))
+    }
+
+    pub fn format_week(&self, when: &DateTime<Local>) -> String {
+        format_with_fallback(when, &self.week, tr("format-week"))
+    }
+
+    /// Returns an error message if `format` isn't a valid strftime-style format.
+    pub fn validate(format: &str) -> Result<(), String> {
+        if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+            Err(format!("'{}' is not a valid date format", format))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn format_with_fallback(when: &DateTime<Local>, format: &str, default_format: &str) -> String {
+    let format = if format.trim().is_empty() || DateFormats::validate(format).is_err() {
+        default_format
+    } else {
+        format
+    };
+    when.format(format).to_string()
+}
+
+/// Extra editor highlighting for text matching a regex, e.g. `TODO:` or ticket IDs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
+    #[serde(default)]
+    pub background: Option<[u8; 3]>,
+}
+
+/// Where the user left off in a notes folder.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct VaultSession {
+    pub note: String,
+    /// Cursor position as a character index into the note.
+    #[serde(default)]
+    pub cursor: Option<usize>,
+    #[serde(default)]
+    pub scroll_offset: f32,
+}
+
+/// Window geometry in logical points, saved on exit and restored on launch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub x: Option<f32>,
+    #[serde(default)]
+    pub y: Option<f32>,
+    #[serde(default)]
+    pub maximized: bool,
+    /// Size of the monitor the window was last on, used to refit the window when the display changes.
+    #[serde(default)]
+    pub monitor_size: Option<[f32; 2]>,
+    /// Keep the main window above other windows.
+    #[serde(default)]
+    pub always_on_top: bool,
+}
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            width: 1200.0,
+            height: 800.0,
+            x: None,
+            y: None,
+            maximized: false,
+            monitor_size: None,
+            always_on_top: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewMode {
+    #[default]
+    Split,
+    EditorOnly,
+    PreviewOnly,
+}
+
+impl ViewMode {
+    pub const ALL: [ViewMode; 3] = [ViewMode::Split, ViewMode::EditorOnly, ViewMode::PreviewOnly];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ViewMode::Split => tr("view-mode-split"),
+            ViewMode::EditorOnly => tr("view-mode-editor-only"),
+            ViewMode::PreviewOnly => tr("view-mode-preview-only"),
+        }
+    }
+
+    pub fn shows_preview(&self) -> bool {
+        *self != ViewMode::EditorOnly
+    }
+
+    /// Hides the preview if it's showing, otherwise brings it back next to the editor.
+    pub fn toggle_preview(&self) -> ViewMode {
+        if self.shows_preview() { ViewMode::EditorOnly } else { ViewMode::Split }
+    }
+}
+
+/// Whether split view puts the preview beside the editor or below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitOrientation {
+    #[default]
+    SideBySide,
+    /// Editor above the preview, for portrait monitors and narrow windows.
+    Stacked,
+}
+
+impl SplitOrientation {
+    pub const ALL: [SplitOrientation; 2] = [SplitOrientation::SideBySide, SplitOrientation::Stacked];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SplitOrientation::SideBySide => tr("split-side-by-side"),
+            SplitOrientation::Stacked => tr("split-stacked"),
+        }
+    }
+}
+
+/// How much of the text around the cursor stays fully visible in focus mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusScope {
+    #[default]
+    Paragraph,
+    Sentence,
+}
+
+impl FocusScope {
+    pub const ALL: [FocusScope; 2] = [FocusScope::Paragraph, FocusScope::Sentence];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FocusScope::Paragraph => tr("focus-scope-paragraph"),
+            FocusScope::Sentence => tr("focus-scope-sentence"),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+/// When the notes folder's git repository gets commits without asking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitAutocommit {
+    #[default]
+    Off,
+    /// Once saving has paused for a little while.
+    AfterSaving,
+    /// Every `git_commit_minutes` while there are changes.
+    Interval,
+}
+
+impl GitAutocommit {
+    pub const ALL: [GitAutocommit; 3] = [GitAutocommit::Off, GitAutocommit::AfterSaving, GitAutocommit::Interval];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitAutocommit::Off => tr("git-autocommit-off"),
+            GitAutocommit::AfterSaving => tr("git-autocommit-after-saving"),
+            GitAutocommit::Interval => tr("git-autocommit-interval"),
+        }
+    }
+}
+
+/// Bullet used for list items when autocorrect normalizes them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListMarker {
+    #[default]
+    Dash,
+    Asterisk,
+    Plus,
+}
+
+impl ListMarker {
+    pub const ALL: [ListMarker; 3] = [ListMarker::Dash, ListMarker::Asterisk, ListMarker::Plus];
+
+    pub fn marker(&self) -> char {
+        match self {
+            ListMarker::Dash => '-',
+            ListMarker::Asterisk => '*',
+            ListMarker::Plus => '+',
+        }
+    }
+}
+
+/// Panel arrangement of the main window, restored on launch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutState {
+    pub sidebar_width: f32,
+    pub sidebar_visible: bool,
+    /// Fraction of the central area given to the editor in split view.
+    pub split_ratio: f32,
+    pub view_mode: ViewMode,
+    #[serde(default)]
+    pub split_orientation: SplitOrientation,
+}
+
+impl Default for LayoutState {
+    fn default() -> Self {
+        Self {
+            sidebar_width: 200.0,
+            sidebar_visible: true,
+            split_ratio: 0.5,
+            view_mode: ViewMode::Split,
+            split_orientation: SplitOrientation::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct LoadedFonts {
     pub editor_loaded: bool,
     pub list_loaded: bool,
@@ -63,31 +563,62 @@ impl Default for Config {
         let default_mono_font = "monospace".to_string();
 
         Self {
+            config_version: CURRENT_CONFIG_VERSION,
             notes_folder: home_dir.join("local-notes"),
+            vaults: Vec::new(),
+            note_extensions: default_note_extensions(),
+            new_note_extension: default_new_note_extension(),
+            ignore_patterns: Vec::new(),
             editor_font_size: 14.0,
+            editor_max_line_width: 0,
+            zen_line_width: default_zen_line_width(),
+            zen_dim_surroundings: default_zen_dim_surroundings(),
+            focus_scope: FocusScope::default(),
+            autocorrect_markdown: false,
+            list_marker: ListMarker::default(),
+            confirm_delete: default_confirm_delete(),
+            confirm_external_links: false,
+            privacy_screen_minutes: 0,
+            privacy_screen_on_focus_loss: false,
+            delete_to_trash: default_delete_to_trash(),
+            note_history_minutes: 0,
+            note_history_limit: default_note_history_limit(),
+            git_autocommit: GitAutocommit::default(),
+            git_commit_minutes: default_git_commit_minutes(),
+            task_reminders: false,
+            remember_note_views: false,
+            tasks_calendar: None,
+            serve_port: default_serve_port(),
+            show_profiler: false,
+            large_note_threshold_kb: default_large_note_threshold_kb(),
+            pandoc_path: default_pandoc_path(),
+            automation_api: false,
+            automation_api_port: default_automation_api_port(),
+            automation_api_token: String::new(),
+            global_hotkey: String::new(),
+            merge_tool: String::new(),
             list_font_size: 14.0,
             rendered_font_size: 14.0,
             editor_font_family: default_mono_font.clone(),
             list_font_family: default_mono_font.clone(),
             rendered_font_family: default_mono_font.clone(),
-            markdown_styles: MarkdownStyles {
-                h1: MarkdownStyle { font_size: 24.0, color: [255, 220, 100] },
-                h2: MarkdownStyle { font_size: 20.0, color: [220, 255, 180] },
-                h3: MarkdownStyle { font_size: 18.0, color: [180, 220, 255] },
-                h4: MarkdownStyle { font_size: 16.0, color: [255, 180, 220] },
-                h5: MarkdownStyle { font_size: 14.0, color: [220, 180, 255] },
-                h6: MarkdownStyle { font_size: 12.0, color: [255, 255, 180] },
-                paragraph: MarkdownStyle { font_size: 14.0, color: [240, 240, 240] },
-                strong: MarkdownStyle { font_size: 14.0, color: [255, 255, 255] },
-                emphasis: MarkdownStyle { font_size: 14.0, color: [220, 180, 255] },
-                strikethrough: MarkdownStyle { font_size: 14.0, color: [150, 150, 150] },
-                code_inline: MarkdownStyle { font_size: 14.0, color: [200, 80, 20] },
-                code_block: MarkdownStyle { font_size: 12.0, color: [150, 120, 200] },
-                code_block_background: [40, 40, 50],
-                list_bullet: MarkdownStyle { font_size: 14.0, color: [60, 120, 200] },
-            },
-            last_open_note: None,
+            markdown_styles: ThemePreset::Dark.markdown_styles(),
+            editor_styles: ThemePreset::Dark.editor_styles(),
+            theme: ThemePreset::Dark,
+            keymap: BTreeMap::new(),
+            ui_scale: default_ui_scale(),
+            language: Language::default(),
+            new_note_pattern: default_new_note_pattern(),
+            new_note_template: None,
+            daily_note_format: default_daily_note_format(),
+            daily_note_template: None,
+            formats: DateFormats::default(),
+            highlight_rules: Vec::new(),
+            sessions: BTreeMap::new(),
+            window: WindowState::default(),
+            layout: LayoutState::default(),
             loaded_fonts: LoadedFonts::default(),
+            save_blocked: false,
         }
     }
 }
@@ -130,21 +661,34 @@ impl Config {
 
         for path in font_paths {
             if let Ok(font_data) = fs::read(&path) {
-                fonts.font_data.insert(
-                    family_key.to_owned(),
-                    FontData::from_owned(font_data).into()
-                );
-                fonts.families.insert(
-                    FontFamily::Name(family_key.into()),
-                    vec![family_key.to_owned()]
-                );
+                Self::insert_font(fonts, family_key, font_data);
                 return Ok(());
             }
         }
 
+        let installed = Self::find_installed_font_files()
+            .into_iter()
+            .find(|path| path.file_stem().and_then(|s| s.to_str()).is_some_and(|s| s.eq_ignore_ascii_case(font_name)));
+        if let Some(path) = installed
+            && let Ok(font_data) = fs::read(&path) {
+                Self::insert_font(fonts, family_key, font_data);
+                return Ok(());
+            }
+
         Err("Font file not found in system paths".to_string())
     }
 
+    fn insert_font(fonts: &mut FontDefinitions, family_key: &str, font_data: Vec<u8>) {
+        fonts.font_data.insert(
+            family_key.to_owned(),
+            FontData::from_owned(font_data).into()
+        );
+        fonts.families.insert(
+            FontFamily::Name(family_key.into()),
+            vec![family_key.to_owned()]
+        );
+    }
+
     fn get_system_font_paths(font_name: &str) -> Vec<PathBuf> {
         let mut paths = Vec::new();
 
@@ -231,53 +775,85 @@ impl Config {
             _ => {}
         }
 
-        #[cfg(target_os = "windows")]
-        {
-            let system_paths = [
-                PathBuf::from("C:/Windows/Fonts/"),
-                PathBuf::from("C:/Windows/System32/Fonts/"),
-            ];
-            for base_path in &system_paths {
-                for variation in &font_variations {
-                    paths.push(base_path.join(variation));
+        for base_path in Self::get_system_font_dirs() {
+            for variation in &font_variations {
+                paths.push(base_path.join(variation));
+
+                #[cfg(target_os = "linux")]
+                {
+                    paths.push(base_path.join("truetype").join(variation));
+                    paths.push(base_path.join("opentype").join(variation));
+                    paths.push(base_path.join("TTF").join(variation));
+                    paths.push(base_path.join("OTF").join(variation));
                 }
             }
         }
 
+        paths
+    }
+
+    fn get_system_font_dirs() -> Vec<PathBuf> {
+        #[cfg(target_os = "windows")]
+        let dirs = vec![
+            PathBuf::from("C:/Windows/Fonts/"),
+            PathBuf::from("C:/Windows/System32/Fonts/"),
+        ];
+
         #[cfg(target_os = "macos")]
-        {
-            let system_paths = [
-                PathBuf::from("/System/Library/Fonts/"),
-                PathBuf::from("/Library/Fonts/"),
-                PathBuf::from(std::env::home_dir().unwrap_or_default()).join("Library/Fonts/"),
-            ];
-            for base_path in &system_paths {
-                for variation in &font_variations {
-                    paths.push(base_path.join(variation));
-                }
-            }
-        }
+        let dirs = vec![
+            PathBuf::from("/System/Library/Fonts/"),
+            PathBuf::from("/Library/Fonts/"),
+            std::env::home_dir().unwrap_or_default().join("Library/Fonts/"),
+        ];
 
         #[cfg(target_os = "linux")]
-        {
-            let system_paths = [
-                PathBuf::from("/usr/share/fonts/"),
-                PathBuf::from("/usr/local/share/fonts/"),
-                std::env::home_dir().unwrap_or_default().join(".fonts/"),
-                std::env::home_dir().unwrap_or_default().join(".local/share/fonts/"),
-            ];
-            for base_path in &system_paths {
-                for variation in &font_variations {
-                    paths.push(base_path.join(variation));
-                    paths.push(base_path.join("truetype").join(variation));
-                    paths.push(base_path.join("opentype").join(variation));
-                    paths.push(base_path.join("TTF").join(variation));
-                    paths.push(base_path.join("OTF").join(variation));
+        let dirs = vec![
+            PathBuf::from("/usr/share/fonts/"),
+            PathBuf::from("/usr/local/share/fonts/"),
+            std::env::home_dir().unwrap_or_default().join(".fonts/"),
+            std::env::home_dir().unwrap_or_default().join(".local/share/fonts/"),
+        ];
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        let dirs = Vec::new();
+
+        dirs
+    }
+
+    fn collect_font_files(dir: &Path, files: &mut Vec<PathBuf>) {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    Self::collect_font_files(&path, files);
+                } else if path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("ttf") || e.eq_ignore_ascii_case("otf"))
+                {
+                    files.push(path);
                 }
             }
         }
+    }
 
-        paths
+    fn find_installed_font_files() -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for dir in Self::get_system_font_dirs() {
+            Self::collect_font_files(&dir, &mut files);
+        }
+        files
+    }
+
+    /// Names of the .ttf/.otf files installed in the system font folders, for the font pickers.
+    pub fn list_installed_fonts() -> Vec<String> {
+        let mut names: Vec<String> = Self::find_installed_font_files()
+            .iter()
+            .filter_map(|path| path.file_stem()?.to_str().map(|s| s.to_string()))
+            .collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        names.dedup();
+        names
     }
 
     pub fn get_editor_font_id(&self, size: f32) -> FontId {
@@ -329,49 +905,187 @@ impl Config {
         let config_path = Self::get_config_path();
         let mut errors = Vec::new();
 
-        let config = if config_path.exists() {
+        let first_run = !config_path.exists();
+        let config = if !first_run {
             match fs::read_to_string(&config_path) {
                 Ok(content) => {
-                    match toml::from_str(&content) {
-                        Ok(config) => config,
+                    match Self::parse_and_migrate(&content) {
+                        Ok((config, migrated_from)) => {
+                            if let Some(old_version) = migrated_from {
+                                let backup_path = config_path.with_extension(format!("toml.v{}.bak", old_version));
+                                if let Err(e) = fs::copy(&config_path, &backup_path) {
+                                    errors.push(format!("Failed to back up config before migrating: {}", e));
+                                } else if let Err(e) = config.save() {
+                                    errors.push(format!("Failed to save migrated config: {}", e));
+                                }
+                            }
+                            config
+                        }
                         Err(e) => {
-                            errors.push(format!("Failed to parse config file: {}", e));
-                            let default_config = Self::default();
-                            if let Err(e) = default_config.save() {
-                                errors.push(format!("Failed to save default config: {}", e));
+                            let backup_path = config_path.with_extension("toml.bak");
+                            match fs::copy(&config_path, &backup_path) {
+                                Ok(_) => errors.push(format!(
+                                    "{}. Using default settings; your file was left untouched and a copy was saved to '{}'.",
+                                    e,
+                                    backup_path.display()
+                                )),
+                                Err(copy_error) => errors.push(format!(
+                                    "{}. Using default settings; your file was left untouched (backup failed: {}).",
+                                    e, copy_error
+                                )),
+                            }
+                            Self {
+                                save_blocked: true,
+                                ..Self::default()
                             }
-                            default_config
                         }
                     }
                 }
                 Err(e) => {
-                    errors.push(format!("Failed to read config file: {}", e));
-                    let default_config = Self::default();
-                    if let Err(e) = default_config.save() {
-                        errors.push(format!("Failed to save default config: {}", e));
+                    errors.push(format!("Failed to read config file: {}. Using default settings.", e));
+                    Self {
+                        save_blocked: true,
+                        ..Self::default()
                     }
-                    default_config
                 }
             }
         } else {
-            if let Some(parent) = config_path.parent()
-                && !parent.exists()
-                && let Err(e) = fs::create_dir_all(parent) {
-                    errors.push(format!("Failed to create config directory '{}': {}", parent.display(), e));
-                }
-            let default_config = Self::default();
-            if let Err(e) = default_config.save() {
-                errors.push(format!("Failed to save default config: {}", e));
-            }
-            default_config
+            Self::default()
+        };
+
+        ConfigLoadResult { config, errors, first_run }
+    }
+
+    /// Re-reads the config file without touching it on disk, for hot-reloading.
+    pub fn reload() -> Result<Config, String> {
+        let content = fs::read_to_string(Self::get_config_path())
+            .map_err(|e| format!("Failed to read config file: {}", e))?;
+        Self::parse_and_migrate(&content).map(|(config, _)| config)
+    }
+
+    /// Parses config text, upgrading older versions and filling any missing keys from the defaults.
+    /// Returns the version the file was migrated from, if a migration was needed.
+    fn parse_and_migrate(content: &str) -> Result<(Config, Option<u32>), String> {
+        let table: toml::Table = toml::from_str(content)
+            .map_err(|e| format!("Failed to parse config file: {}", e))?;
+        Self::migrate_table(table)
+    }
+
+    fn migrate_table(mut table: toml::Table) -> Result<(Config, Option<u32>), String> {
+        let version = table
+            .get("config_version")
+            .and_then(|v| v.as_integer())
+            .unwrap_or(0) as u32;
+
+        if version > CURRENT_CONFIG_VERSION {
+            return Err(format!(
+                "Config file version {} is newer than this version of NoteSquirrel supports ({})",
+                version, CURRENT_CONFIG_VERSION
+            ));
+        }
+
+        for (from_version, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            migration(&mut table);
+            table.insert("config_version".to_string(), toml::Value::Integer(from_version as i64 + 1));
+        }
+
+        if let Ok(toml::Value::Table(defaults)) = toml::Value::try_from(Self::default()) {
+            fill_missing_keys(&mut table, &defaults);
+        }
+
+        let config = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("Failed to parse config file: {}", e))?;
+        let migrated_from = (version < CURRENT_CONFIG_VERSION).then_some(version);
+        Ok((config, migrated_from))
+    }
+
+    /// Copies the session state that isn't edited through settings (open notes, vaults, window,
+    /// and layout) from the running config, so applying new settings doesn't reset it.
+    pub fn keep_session_state(&mut self, current: &Config) {
+        self.sessions = current.sessions.clone();
+        self.vaults = current.vaults.clone();
+        self.window = current.window.clone();
+        self.layout = current.layout.clone();
+    }
+
+    /// Adds the open notes folder to `vaults` if it isn't there yet.
+    pub fn remember_vault(&mut self) {
+        if !self.vaults.contains(&self.notes_folder) {
+            self.vaults.push(self.notes_folder.clone());
+        }
+    }
+
+    /// What a vault is called in the switcher and the window title: its folder's name.
+    pub fn vault_name(folder: &Path) -> String {
+        folder.file_name().map_or_else(|| folder.display().to_string(), |name| name.to_string_lossy().into_owned())
+    }
+
+    fn session_key(&self) -> String {
+        self.notes_folder.display().to_string()
+    }
+
+    pub fn current_session(&self) -> Option<&VaultSession> {
+        self.sessions.get(&self.session_key())
+    }
+
+    pub fn set_current_session(&mut self, session: VaultSession) {
+        self.sessions.insert(self.session_key(), session);
+    }
+
+    /// Writes every setting except runtime state into a single portable file.
+    pub fn export_bundle(&self, path: &Path) -> Result<(), String> {
+        let mut config = self.clone();
+        config.sessions.clear();
+        config.vaults.clear();
+        config.window = WindowState::default();
+        config.layout = LayoutState::default();
+        let config_table = match toml::Value::try_from(&config) {
+            Ok(toml::Value::Table(table)) => table,
+            Ok(_) => return Err("Failed to serialize settings".to_string()),
+            Err(e) => return Err(format!("Failed to serialize settings: {}", e)),
         };
 
-        ConfigLoadResult { config, errors }
+        let mut header = toml::Table::new();
+        header.insert("format_version".to_string(), toml::Value::Integer(SETTINGS_BUNDLE_VERSION));
+        header.insert("app_version".to_string(), toml::Value::String(env!("CARGO_PKG_VERSION").to_string()));
+
+        let mut bundle = toml::Table::new();
+        bundle.insert(SETTINGS_BUNDLE_KEY.to_string(), toml::Value::Table(header));
+        bundle.insert("config".to_string(), toml::Value::Table(config_table));
+
+        let content = toml::to_string_pretty(&bundle).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+    }
+
+    /// Reads a file written by `export_bundle`, migrating it if it came from an older version.
+    pub fn import_bundle(path: &Path) -> Result<Config, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        let mut bundle: toml::Table = toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))?;
+
+        let format_version = bundle
+            .get(SETTINGS_BUNDLE_KEY)
+            .and_then(|header| header.get("format_version"))
+            .and_then(|v| v.as_integer())
+            .ok_or_else(|| format!("'{}' is not a NoteSquirrel settings file", path.display()))?;
+        if format_version > SETTINGS_BUNDLE_VERSION {
+            return Err(format!("'{}' was exported by a newer version of NoteSquirrel", path.display()));
+        }
+
+        match bundle.remove("config") {
+            Some(toml::Value::Table(table)) => Self::migrate_table(table).map(|(config, _)| config),
+            _ => Err(format!("'{}' does not contain any settings", path.display())),
+        }
     }
 
     pub fn save(&self) -> Result<(), String> {
         let config_path = Self::get_config_path();
 
+        if self.save_blocked {
+            return Err(format!("'{}' could not be parsed; not overwriting it until it is fixed", config_path.display()));
+        }
+
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
         }
@@ -381,7 +1095,7 @@ impl Config {
         Ok(())
     }
 
-    fn get_config_path() -> PathBuf {
+    pub fn get_config_path() -> PathBuf {
         let home_dir = std::env::home_dir().unwrap_or_else(|| PathBuf::from("."));
 
         #[cfg(target_os = "linux")]
@@ -400,9 +1114,103 @@ impl Config {
     }
 }
 
+/// Polls the config file's modification time on a background thread and wakes the UI when it changes.
+pub struct ConfigWatcher {
+    receiver: mpsc::Receiver<()>,
+}
+
+impl ConfigWatcher {
+    pub fn spawn(ctx: egui::Context) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let config_path = Config::get_config_path();
+
+        thread::spawn(move || {
+            let mut last_modified = Self::modified_time(&config_path);
+            loop {
+                thread::sleep(Duration::from_secs(1));
+                let modified = Self::modified_time(&config_path);
+                if modified != last_modified {
+                    last_modified = modified;
+                    if sender.send(()).is_err() {
+                        break;
+                    }
+                    ctx.request_repaint();
+                }
+            }
+        });
+
+        Self { receiver }
+    }
+
+    pub fn poll_changed(&self) -> bool {
+        self.receiver.try_iter().count() > 0
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+}
+
 impl MarkdownStyle {
     pub fn to_color32(&self) -> Color32 {
-        Color32::from_rgb(self.color[0], self.color[1], self.color[2])
+        rgb(self.color)
     }
 
-}
\ No newline at end of file
+}
+
+impl EditorStyles {
+    pub fn heading(&self, level: usize) -> Color32 {
+        let color = match level {
+            1 => self.h1,
+            2 => self.h2,
+            3 => self.h3,
+            4 => self.h4,
+            5 => self.h5,
+            _ => self.h6,
+        };
+        rgb(color)
+    }
+}
+
+impl Default for EditorStyles {
+    fn default() -> Self {
+        ThemePreset::Dark.editor_styles()
+    }
+}
+
+pub fn rgb(color: [u8; 3]) -> Color32 {
+    Color32::from_rgb(color[0], color[1], color[2])
+}
+
+/// Unversioned configs share the v1 layout; new keys are filled from defaults, so this only stamps the version.
+fn migrate_v0_to_v1(_table: &mut toml::Table) {}
+
+fn fill_missing_keys(table: &mut toml::Table, defaults: &toml::Table) {
+    for (key, default_value) in defaults {
+        match (table.get_mut(key), default_value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(default_table)) => {
+                fill_missing_keys(existing, default_table);
+            }
+            (None, _) => {
+                table.insert(key.clone(), default_value.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Moves the single `last_open_note` into the per-folder `sessions` table.
+fn migrate_v1_to_v2(table: &mut toml::Table) {
+    let Some(toml::Value::String(note)) = table.remove("last_open_note") else {
+        return;
+    };
+    let Some(folder) = table.get("notes_folder").and_then(|v| v.as_str()).map(str::to_string) else {
+        return;
+    };
+
+    let mut session = toml::Table::new();
+    session.insert("note".to_string(), toml::Value::String(note));
+    let mut sessions = toml::Table::new();
+    sessions.insert(folder, toml::Value::Table(session));
+    table.insert("sessions".to_string(), toml::Value::Table(sessions));
+}
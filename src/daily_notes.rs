@@ -0,0 +1,233 @@
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate};
+use eframe::egui;
+
+use crate::file_manager::FileManager;
+use crate::i18n::{tr, tr_args};
+
+/// When a note file was created and last modified, as local dates.
+struct NoteDates {
+    name: String,
+    created: Option<NaiveDate>,
+    modified: Option<NaiveDate>,
+}
+
+/// Month calendar of the vault: days with a daily note are underlined, days on which notes were
+/// created or edited get a dot, and picking a day lists those notes.
+pub struct CalendarPicker {
+    pub show_dialog: bool,
+    /// First day of the month being shown.
+    month: NaiveDate,
+    selected_day: NaiveDate,
+    days_with_notes: Vec<NaiveDate>,
+    note_dates: Vec<NoteDates>,
+}
+
+pub enum CalendarAction {
+    None,
+    /// Open or create the daily note for this day.
+    Open(NaiveDate),
+    OpenNote(String),
+}
+
+impl CalendarPicker {
+    pub fn new() -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            show_dialog: false,
+            month: first_of_month(today),
+            selected_day: today,
+            days_with_notes: Vec::new(),
+            note_dates: Vec::new(),
+        }
+    }
+
+    pub fn toggle_dialog(&mut self, file_manager: &FileManager, days_with_notes: Vec<NaiveDate>) {
+        self.show_dialog = !self.show_dialog;
+        if self.show_dialog {
+            let today = Local::now().date_naive();
+            self.month = first_of_month(today);
+            self.selected_day = today;
+            self.days_with_notes = days_with_notes;
+            let local_date = |time: std::time::SystemTime| DateTime::<Local>::from(time).date_naive();
+            self.note_dates = file_manager
+                .list_note_names()
+                .into_iter()
+                .map(|name| NoteDates {
+                    created: file_manager.get_note_created_time(&name).map(local_date),
+                    modified: file_manager.get_note_modified_time(&name).map(local_date),
+                    name,
+                })
+                .collect();
+        }
+    }
+
+    /// Notes created on `day`, and notes edited on `day` that were created earlier.
+    fn notes_on(&self, day: NaiveDate) -> (Vec<&str>, Vec<&str>) {
+        let created = self.note_dates.iter().filter(|n| n.created == Some(day)).map(|n| n.name.as_str()).collect();
+        let edited = self
+            .note_dates
+            .iter()
+            .filter(|n| n.modified == Some(day) && n.created != Some(day))
+            .map(|n| n.name.as_str())
+            .collect();
+        (created, edited)
+    }
+
+    fn has_activity(&self, day: NaiveDate) -> bool {
+        self.note_dates.iter().any(|n| n.created == Some(day) || n.modified == Some(day))
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> CalendarAction {
+        let mut action = CalendarAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let today = Local::now().date_naive();
+        let mut open = true;
+
+        egui::Window::new(tr("calendar-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.small_button("◀").clicked() {
+                        self.month = self.month - Months::new(1);
+                    }
+                    ui.strong(tr_args(
+                        "calendar-month",
+                        &[("month", &month_name(self.month.month())), ("year", &self.month.year())],
+                    ));
+                    if ui.small_button("▶").clicked() {
+                        self.month = self.month + Months::new(1);
+                    }
+                    if ui.button(tr("calendar-today")).clicked() {
+                        action = CalendarAction::Open(today);
+                    }
+                });
+                ui.separator();
+
+                egui::Grid::new("calendar_grid").num_columns(7).spacing([4.0, 4.0]).show(ui, |ui| {
+                    for weekday in WEEKDAY_KEYS {
+                        ui.weak(tr(weekday));
+                    }
+                    ui.end_row();
+
+                    let leading_blanks = self.month.weekday().num_days_from_monday();
+                    for _ in 0..leading_blanks {
+                        ui.label("");
+                    }
+                    let mut column = leading_blanks;
+                    for day in self.month.iter_days().take_while(|d| d.month() == self.month.month()) {
+                        let mut text = egui::RichText::new(day.day().to_string());
+                        if self.days_with_notes.contains(&day) {
+                            text = text.strong().underline();
+                        }
+                        if day == today {
+                            text = text.color(ui.visuals().hyperlink_color);
+                        }
+                        let button = egui::Button::selectable(day == self.selected_day, text).min_size(egui::vec2(28.0, 22.0));
+                        let response = ui.add(button);
+                        if self.has_activity(day) {
+                            let dot = egui::pos2(response.rect.center().x, response.rect.bottom() - 3.0);
+                            ui.painter().circle_filled(dot, 1.8, ui.visuals().selection.bg_fill);
+                        }
+                        if response.double_clicked() {
+                            action = CalendarAction::Open(day);
+                        } else if response.clicked() {
+                            self.selected_day = day;
+                        }
+                        column += 1;
+                        if column.is_multiple_of(7) {
+                            ui.end_row();
+                        }
+                    }
+                });
+
+                ui.separator();
+                self.render_day(ui, &mut action);
+                ui.separator();
+                ui.weak(tr("calendar-hint"));
+            });
+
+        if !open || !matches!(action, CalendarAction::None) {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+
+    fn render_day(&self, ui: &mut egui::Ui, action: &mut CalendarAction) {
+        let day = self.selected_day;
+        ui.horizontal(|ui| {
+            ui.strong(day.format("%Y-%m-%d").to_string());
+            let daily_label = if self.days_with_notes.contains(&day) {
+                tr("calendar-open-daily-note")
+            } else {
+                tr("calendar-create-daily-note")
+            };
+            if ui.button(daily_label).clicked() {
+                *action = CalendarAction::Open(day);
+            }
+        });
+
+        let (created, edited) = self.notes_on(day);
+        if created.is_empty() && edited.is_empty() {
+            ui.weak(tr("calendar-no-activity"));
+            return;
+        }
+        egui::ScrollArea::vertical().max_height(160.0).id_salt("calendar_day_notes").show(ui, |ui| {
+            for (heading, notes) in [(tr("calendar-created"), created), (tr("calendar-edited"), edited)] {
+                if notes.is_empty() {
+                    continue;
+                }
+                ui.label(egui::RichText::new(heading).small().weak());
+                for note in notes {
+                    if ui.link(note).clicked() {
+                        *action = CalendarAction::OpenNote(note.to_string());
+                    }
+                }
+            }
+        });
+    }
+}
+
+const WEEKDAY_KEYS: [&str; 7] = [
+    "calendar-monday",
+    "calendar-tuesday",
+    "calendar-wednesday",
+    "calendar-thursday",
+    "calendar-friday",
+    "calendar-saturday",
+    "calendar-sunday",
+];
+
+fn month_name(month: u32) -> &'static str {
+    match month {
+        1 => tr("calendar-january"),
+        2 => tr("calendar-february"),
+        3 => tr("calendar-march"),
+        4 => tr("calendar-april"),
+        5 => tr("calendar-may"),
+        6 => tr("calendar-june"),
+        7 => tr("calendar-july"),
+        8 => tr("calendar-august"),
+        9 => tr("calendar-september"),
+        10 => tr("calendar-october"),
+        11 => tr("calendar-november"),
+        _ => tr("calendar-december"),
+    }
+}
+
+fn first_of_month(day: NaiveDate) -> NaiveDate {
+    day.with_day(1).unwrap_or(day)
+}
+
+impl Default for CalendarPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,98 @@
+/// Delimiters tried in order: tabs from spreadsheets, then CSV with commas or semicolons.
+const DELIMITERS: [char; 3] = ['\t', ',', ';'];
+
+/// A markdown table for text that looks like rows of tab- or comma-separated values: at least two
+/// rows with the same number of fields, two or more each. The first row becomes the header,
+/// columns are padded to line up, and columns of numbers are right-aligned.
+pub fn to_markdown_table(text: &str) -> Option<String> {
+    let text = text.replace("\r\n", "\n");
+    let text = text.trim_matches('\n');
+    if !text.contains('\n') {
+        return None;
+    }
+    let rows = DELIMITERS.iter().find_map(|&delimiter| {
+        let rows = parse_rows(text, delimiter)?;
+        let columns = rows[0].len();
+        (rows.len() >= 2 && columns >= 2 && rows.iter().all(|row| row.len() == columns)).then_some(rows)
+    })?;
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.trim().replace('|', "\\|").replace('\n', " ")).collect())
+        .collect();
+    let columns = cells[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| cells.iter().map(|row| row[column].chars().count()).max().unwrap_or(0).max(3))
+        .collect();
+    let numeric: Vec<bool> = (0..columns)
+        .map(|column| {
+            let mut values = cells[1..].iter().map(|row| row[column].as_str()).filter(|cell| !cell.is_empty()).peekable();
+            values.peek().is_some() && values.all(is_number)
+        })
+        .collect();
+
+    let line = |row: &[String]| {
+        let padded: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| {
+                if numeric[column] {
+                    format!("{:>width$}", cell, width = widths[column])
+                } else {
+                    format!("{:<width$}", cell, width = widths[column])
+                }
+            })
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+    let separator: Vec<String> = (0..columns)
+        .map(|column| {
+            let dashes = "-".repeat(widths[column] - usize::from(numeric[column]));
+            if numeric[column] { format!("{}:", dashes) } else { dashes }
+        })
+        .collect();
+
+    let mut lines = vec![line(&cells[0]), format!("| {} |", separator.join(" | "))];
+    lines.extend(cells[1..].iter().map(|row| line(row)));
+    Some(lines.join("\n") + "\n")
+}
+
+/// Splits `text` into rows of fields, with CSV quoting: a field in double quotes can hold the
+/// delimiter, line breaks, and `""` for a quote. `None` if a quote is never closed.
+fn parse_rows(text: &str, delimiter: char) -> Option<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => row.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    row.push(field);
+    rows.push(row);
+    Some(rows)
+}
+
+fn is_number(cell: &str) -> bool {
+    let cell = cell.trim_start_matches(['-', '+', '$', '€', '£']).trim_end_matches('%');
+    !cell.is_empty() && cell.replace([',', '_'], "").parse::<f64>().is_ok()
+}
@@ -0,0 +1,591 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use zip::write::SimpleFileOptions;
+
+use crate::html_export::escape_html;
+
+/// Widest an image may be on the page: 6 inches, in EMUs.
+const MAX_IMAGE_WIDTH: u64 = 6 * 914_400;
+/// EMUs per pixel at 96 DPI.
+const EMU_PER_PIXEL: u64 = 9_525;
+
+const RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Writes `markdown` to `dest` as a Word document without needing pandoc. Headings, lists,
+/// tables, block quotes, code, links, and local images (relative to `notes_dir`) keep their
+/// structure; raw HTML is left out.
+pub fn write_docx(markdown: &str, title: &str, notes_dir: &Path, dest: &Path) -> Result<(), String> {
+    let mut writer = DocxWriter::new(notes_dir);
+    writer.convert(markdown);
+    writer.package(title, dest).map_err(|e| format!("Failed to export to '{}': {}", dest.display(), e))
+}
+
+#[derive(Default)]
+struct RunStyle {
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    /// Relationship ID of the link the run is in.
+    link: Option<String>,
+}
+
+struct Image {
+    file_name: String,
+    data: Vec<u8>,
+}
+
+struct DocxWriter<'a> {
+    notes_dir: &'a Path,
+    body: String,
+    /// Runs of the paragraph being built, and its properties.
+    runs: String,
+    paragraph_properties: Option<String>,
+    style: RunStyle,
+    /// Numbering ID of each list the text is in.
+    lists: Vec<usize>,
+    /// `(ordered, start)` for each numbering instance, indexed by ID - 1.
+    numberings: Vec<(bool, u64)>,
+    quote_depth: usize,
+    /// Label of a footnote definition whose first paragraph hasn't started yet.
+    footnote: Option<String>,
+    code_block: Option<String>,
+    table_rows: Vec<Vec<String>>,
+    table_head: bool,
+    /// Alt text and destination of the image being read.
+    image: Option<(String, String)>,
+    /// Link targets; the n-th is relationship `rIdLinkn`, as images are `rIdImagen`.
+    links: Vec<String>,
+    images: Vec<Image>,
+}
+
+impl<'a> DocxWriter<'a> {
+    fn new(notes_dir: &'a Path) -> Self {
+        Self {
+            notes_dir,
+            body: String::new(),
+            runs: String::new(),
+            paragraph_properties: None,
+            style: RunStyle::default(),
+            lists: Vec::new(),
+            numberings: Vec::new(),
+            quote_depth: 0,
+            footnote: None,
+            code_block: None,
+            table_rows: Vec::new(),
+            table_head: false,
+            image: None,
+            links: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+
+    fn convert(&mut self, markdown: &str) {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_TASKLISTS);
+
+        for event in Parser::new_ext(markdown, options) {
+            if let Some((alt, _)) = &mut self.image {
+                match event {
+                    Event::End(TagEnd::Image) => self.end_image(),
+                    Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                    _ => {}
+                }
+                continue;
+            }
+            if let Some(code) = &mut self.code_block {
+                match event {
+                    Event::End(TagEnd::CodeBlock) => self.end_code_block(),
+                    Event::Text(text) => code.push_str(&text),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match event {
+                Event::Start(tag) => self.start(tag),
+                Event::End(tag) => self.end(tag),
+                Event::Text(text) => self.text(&text, ""),
+                Event::Code(text) => self.text(&text, "<w:rStyle w:val=\"CodeChar\"/>"),
+                Event::SoftBreak => self.text(" ", ""),
+                Event::HardBreak => self.runs.push_str("<w:r><w:br/></w:r>"),
+                Event::Rule => {
+                    self.finish_paragraph();
+                    self.body.push_str(
+                        "<w:p><w:pPr><w:pBdr><w:bottom w:val=\"single\" w:sz=\"6\" w:space=\"1\" w:color=\"auto\"/></w:pBdr></w:pPr></w:p>",
+                    );
+                }
+                Event::TaskListMarker(checked) => self.text(if checked { "☒ " } else { "☐ " }, ""),
+                Event::FootnoteReference(label) => self.text(&format!("[{}]", label), "<w:vertAlign w:val=\"superscript\"/>"),
+                Event::Html(_) | Event::InlineHtml(_) | Event::InlineMath(_) | Event::DisplayMath(_) => {}
+            }
+        }
+        self.finish_paragraph();
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => {
+                // A tight list item's text arrives without a paragraph of its own, so the item
+                // opens one; a loose item's first paragraph reuses it.
+                if self.paragraph_properties.is_none() || !self.runs.is_empty() {
+                    self.begin_paragraph(self.block_properties());
+                }
+                if let Some(label) = self.footnote.take() {
+                    self.text(&format!("[{}] ", label), "<w:vertAlign w:val=\"superscript\"/>");
+                }
+            }
+            Tag::Heading { level, .. } => {
+                let level = heading_number(level);
+                self.begin_paragraph(format!("<w:pStyle w:val=\"Heading{}\"/>", level));
+            }
+            Tag::BlockQuote(_) => {
+                self.finish_paragraph();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.finish_paragraph();
+                self.code_block = Some(String::new());
+            }
+            Tag::List(start) => {
+                self.finish_paragraph();
+                self.numberings.push((start.is_some(), start.unwrap_or(1)));
+                self.lists.push(self.numberings.len());
+            }
+            Tag::Item => {
+                let numbering = self.lists.last().copied().unwrap_or(0);
+                let level = self.lists.len().saturating_sub(1);
+                self.begin_paragraph(format!(
+                    "<w:pStyle w:val=\"ListParagraph\"/><w:numPr><w:ilvl w:val=\"{}\"/><w:numId w:val=\"{}\"/></w:numPr>",
+                    level, numbering
+                ));
+            }
+            Tag::Table(_) => {
+                self.finish_paragraph();
+                self.table_rows.clear();
+            }
+            Tag::TableHead => {
+                self.table_head = true;
+                self.table_rows.push(Vec::new());
+            }
+            Tag::TableRow => self.table_rows.push(Vec::new()),
+            Tag::TableCell => {
+                self.runs.clear();
+                if self.table_head {
+                    self.style.bold += 1;
+                }
+            }
+            Tag::Emphasis => self.style.italic += 1,
+            Tag::Strong => self.style.bold += 1,
+            Tag::Strikethrough => self.style.strike += 1,
+            Tag::Link { dest_url, .. } => {
+                if dest_url.starts_with('#') {
+                    return;
+                }
+                self.links.push(dest_url.to_string());
+                self.style.link = Some(format!("rIdLink{}", self.links.len()));
+            }
+            Tag::Image { dest_url, .. } => self.image = Some((String::new(), dest_url.to_string())),
+            Tag::FootnoteDefinition(label) => {
+                self.finish_paragraph();
+                self.footnote = Some(label.to_string());
+            }
+            Tag::HtmlBlock | Tag::MetadataBlock(_) | Tag::DefinitionList | Tag::DefinitionListTitle | Tag::DefinitionListDefinition => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::FootnoteDefinition => self.finish_paragraph(),
+            TagEnd::BlockQuote(_) => {
+                self.finish_paragraph();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::List(_) => {
+                self.finish_paragraph();
+                self.lists.pop();
+            }
+            TagEnd::TableHead => {
+                self.table_head = false;
+            }
+            TagEnd::TableCell => {
+                if self.table_head {
+                    self.style.bold = self.style.bold.saturating_sub(1);
+                }
+                let cell = std::mem::take(&mut self.runs);
+                if let Some(row) = self.table_rows.last_mut() {
+                    row.push(cell);
+                }
+            }
+            TagEnd::Table => self.end_table(),
+            TagEnd::Emphasis => self.style.italic = self.style.italic.saturating_sub(1),
+            TagEnd::Strong => self.style.bold = self.style.bold.saturating_sub(1),
+            TagEnd::Strikethrough => self.style.strike = self.style.strike.saturating_sub(1),
+            TagEnd::Link => self.style.link = None,
+            _ => {}
+        }
+    }
+
+    /// Paragraph properties for body text at the current nesting: indented under a list item,
+    /// or quoted.
+    fn block_properties(&self) -> String {
+        if !self.lists.is_empty() {
+            format!("<w:pStyle w:val=\"ListParagraph\"/><w:ind w:left=\"{}\"/>", 720 * self.lists.len())
+        } else if self.quote_depth > 0 {
+            format!("<w:pStyle w:val=\"Quote\"/><w:ind w:left=\"{}\"/>", 720 * self.quote_depth)
+        } else {
+            String::new()
+        }
+    }
+
+    fn begin_paragraph(&mut self, properties: String) {
+        self.finish_paragraph();
+        self.paragraph_properties = Some(properties);
+    }
+
+    fn finish_paragraph(&mut self) {
+        let Some(properties) = self.paragraph_properties.take() else {
+            return;
+        };
+        let runs = std::mem::take(&mut self.runs);
+        self.body.push_str(&paragraph(&properties, &runs));
+    }
+
+    fn text(&mut self, text: &str, extra_properties: &str) {
+        if self.paragraph_properties.is_none() && self.table_rows.is_empty() {
+            self.paragraph_properties = Some(self.block_properties());
+        }
+        let mut properties = String::new();
+        if self.style.link.is_some() {
+            properties.push_str("<w:rStyle w:val=\"Hyperlink\"/>");
+        } else {
+            properties.push_str(extra_properties);
+        }
+        if self.style.bold > 0 {
+            properties.push_str("<w:b/>");
+        }
+        if self.style.italic > 0 {
+            properties.push_str("<w:i/>");
+        }
+        if self.style.strike > 0 {
+            properties.push_str("<w:strike/>");
+        }
+        let run = run(&properties, text);
+        match &self.style.link {
+            Some(id) => self.runs.push_str(&format!("<w:hyperlink r:id=\"{}\">{}</w:hyperlink>", id, run)),
+            None => self.runs.push_str(&run),
+        }
+    }
+
+    fn end_code_block(&mut self) {
+        let Some(code) = self.code_block.take() else {
+            return;
+        };
+        let runs = code
+            .trim_end_matches('\n')
+            .split('\n')
+            .map(|line| run("", line))
+            .collect::<Vec<_>>()
+            .join("<w:r><w:br/></w:r>");
+        self.body.push_str(&paragraph("<w:pStyle w:val=\"Code\"/>", &runs));
+    }
+
+    fn end_table(&mut self) {
+        let rows = std::mem::take(&mut self.table_rows);
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        self.body.push_str("<w:tbl><w:tblPr><w:tblStyle w:val=\"TableGrid\"/><w:tblW w:w=\"0\" w:type=\"auto\"/></w:tblPr><w:tblGrid>");
+        for _ in 0..columns {
+            self.body.push_str("<w:gridCol/>");
+        }
+        self.body.push_str("</w:tblGrid>");
+        for row in rows {
+            self.body.push_str("<w:tr>");
+            for column in 0..columns {
+                let cell = row.get(column).map_or("", String::as_str);
+                self.body.push_str(&format!("<w:tc>{}</w:tc>", paragraph("", cell)));
+            }
+            self.body.push_str("</w:tr>");
+        }
+        self.body.push_str("</w:tbl>");
+        // Word needs a paragraph between consecutive tables, and after a table ending the body.
+        self.body.push_str("<w:p/>");
+    }
+
+    fn end_image(&mut self) {
+        let Some((alt, dest)) = self.image.take() else {
+            return;
+        };
+        match self.embed_image(&alt, &dest) {
+            Some(drawing) => {
+                if self.paragraph_properties.is_none() && self.table_rows.is_empty() {
+                    self.paragraph_properties = Some(self.block_properties());
+                }
+                self.runs.push_str(&drawing);
+            }
+            // Remote or unreadable images become their alt text, linked to the source.
+            None => {
+                let link = self.style.link.clone();
+                if link.is_none() {
+                    self.links.push(dest);
+                    self.style.link = Some(format!("rIdLink{}", self.links.len()));
+                }
+                self.text(if alt.is_empty() { "image" } else { &alt }, "");
+                self.style.link = link;
+            }
+        }
+    }
+
+    /// Adds a local PNG, JPEG, or GIF to the package and returns the run that shows it.
+    fn embed_image(&mut self, alt: &str, dest: &str) -> Option<String> {
+        if dest.contains("://") {
+            return None;
+        }
+        let path = self.notes_dir.join(dest.strip_prefix("file:").unwrap_or(dest));
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !matches!(extension.as_str(), "png" | "jpg" | "jpeg" | "gif") {
+            return None;
+        }
+        let (width, height) = image::image_dimensions(&path).ok()?;
+        let data = std::fs::read(&path).ok()?;
+
+        let mut cx = width as u64 * EMU_PER_PIXEL;
+        let mut cy = height as u64 * EMU_PER_PIXEL;
+        if cx > MAX_IMAGE_WIDTH {
+            cy = cy * MAX_IMAGE_WIDTH / cx;
+            cx = MAX_IMAGE_WIDTH;
+        }
+        let id = self.images.len() + 1;
+        let file_name = format!("image{}.{}", id, extension);
+        let drawing = format!(
+            "<w:r><w:drawing><wp:inline distT=\"0\" distB=\"0\" distL=\"0\" distR=\"0\">\
+             <wp:extent cx=\"{cx}\" cy=\"{cy}\"/><wp:docPr id=\"{id}\" name=\"Picture {id}\" descr=\"{alt}\"/>\
+             <a:graphic xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\">\
+             <a:graphicData uri=\"http://schemas.openxmlformats.org/drawingml/2006/picture\">\
+             <pic:pic xmlns:pic=\"http://schemas.openxmlformats.org/drawingml/2006/picture\">\
+             <pic:nvPicPr><pic:cNvPr id=\"{id}\" name=\"{file_name}\"/><pic:cNvPicPr/></pic:nvPicPr>\
+             <pic:blipFill><a:blip r:embed=\"rIdImage{id}\"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill>\
+             <pic:spPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"{cx}\" cy=\"{cy}\"/></a:xfrm>\
+             <a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom></pic:spPr>\
+             </pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>",
+            alt = xml_text(alt),
+        );
+        self.images.push(Image { file_name, data });
+        Some(drawing)
+    }
+
+    fn package(&self, title: &str, dest: &Path) -> zip::result::ZipResult<()> {
+        let mut zip = zip::ZipWriter::new(File::create(dest)?);
+        let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        zip.start_file("[Content_Types].xml", options)?;
+        zip.write_all(CONTENT_TYPES.as_bytes())?;
+        zip.start_file("_rels/.rels", options)?;
+        zip.write_all(PACKAGE_RELATIONSHIPS.as_bytes())?;
+        zip.start_file("docProps/core.xml", options)?;
+        zip.write_all(core_properties(title).as_bytes())?;
+        zip.start_file("word/document.xml", options)?;
+        zip.write_all(self.document().as_bytes())?;
+        zip.start_file("word/styles.xml", options)?;
+        zip.write_all(STYLES.as_bytes())?;
+        zip.start_file("word/numbering.xml", options)?;
+        zip.write_all(self.numbering().as_bytes())?;
+        zip.start_file("word/_rels/document.xml.rels", options)?;
+        zip.write_all(self.document_relationships().as_bytes())?;
+        for image in &self.images {
+            zip.start_file(format!("word/media/{}", image.file_name), options)?;
+            zip.write_all(&image.data)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    fn document(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+             xmlns:r=\"{RELATIONSHIPS}\" \
+             xmlns:wp=\"http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing\">\
+             <w:body>{}<w:sectPr><w:pgSz w:w=\"12240\" w:h=\"15840\"/>\
+             <w:pgMar w:top=\"1440\" w:right=\"1440\" w:bottom=\"1440\" w:left=\"1440\" w:header=\"720\" w:footer=\"720\" w:gutter=\"0\"/>\
+             </w:sectPr></w:body></w:document>",
+            self.body
+        )
+    }
+
+    /// A bulleted and a numbered list definition, and an instance for every list so numbering
+    /// restarts with each one.
+    fn numbering(&self) -> String {
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <w:numbering xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">{}{}",
+            abstract_numbering(0, false),
+            abstract_numbering(1, true)
+        );
+        for (index, (ordered, start)) in self.numberings.iter().enumerate() {
+            xml.push_str(&format!("<w:num w:numId=\"{}\"><w:abstractNumId w:val=\"{}\"/>", index + 1, *ordered as u8));
+            if *ordered {
+                for level in 0..9 {
+                    xml.push_str(&format!(
+                        "<w:lvlOverride w:ilvl=\"{}\"><w:startOverride w:val=\"{}\"/></w:lvlOverride>",
+                        level, start
+                    ));
+                }
+            }
+            xml.push_str("</w:num>");
+        }
+        xml.push_str("</w:numbering>");
+        xml
+    }
+
+    fn document_relationships(&self) -> String {
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+             <Relationship Id=\"rIdStyles\" Type=\"{RELATIONSHIPS}/styles\" Target=\"styles.xml\"/>\
+             <Relationship Id=\"rIdNumbering\" Type=\"{RELATIONSHIPS}/numbering\" Target=\"numbering.xml\"/>"
+        );
+        for (index, target) in self.links.iter().enumerate() {
+            xml.push_str(&format!(
+                "<Relationship Id=\"rIdLink{}\" Type=\"{RELATIONSHIPS}/hyperlink\" Target=\"{}\" TargetMode=\"External\"/>",
+                index + 1,
+                xml_text(target)
+            ));
+        }
+        for (index, image) in self.images.iter().enumerate() {
+            xml.push_str(&format!(
+                "<Relationship Id=\"rIdImage{}\" Type=\"{RELATIONSHIPS}/image\" Target=\"media/{}\"/>",
+                index + 1,
+                image.file_name
+            ));
+        }
+        xml.push_str("</Relationships>");
+        xml
+    }
+}
+
+fn heading_number(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn paragraph(properties: &str, runs: &str) -> String {
+    if properties.is_empty() {
+        format!("<w:p>{}</w:p>", runs)
+    } else {
+        format!("<w:p><w:pPr>{}</w:pPr>{}</w:p>", properties, runs)
+    }
+}
+
+fn run(properties: &str, text: &str) -> String {
+    let properties = if properties.is_empty() { String::new() } else { format!("<w:rPr>{}</w:rPr>", properties) };
+    let text = text.replace('\t', "    ");
+    format!("<w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r>", properties, xml_text(&text))
+}
+
+/// Escaped text with the control characters XML doesn't allow removed.
+fn xml_text(text: &str) -> String {
+    let allowed: String = text.chars().filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r')).collect();
+    escape_html(&allowed)
+}
+
+fn abstract_numbering(id: usize, ordered: bool) -> String {
+    const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+    let mut xml = format!("<w:abstractNum w:abstractNumId=\"{}\"><w:multiLevelType w:val=\"hybridMultilevel\"/>", id);
+    for level in 0..9 {
+        let (format, text) = if ordered {
+            ("decimal", format!("%{}.", level + 1))
+        } else {
+            ("bullet", BULLETS[level % BULLETS.len()].to_string())
+        };
+        xml.push_str(&format!(
+            "<w:lvl w:ilvl=\"{}\"><w:start w:val=\"1\"/><w:numFmt w:val=\"{}\"/><w:lvlText w:val=\"{}\"/><w:lvlJc w:val=\"left\"/>\
+             <w:pPr><w:ind w:left=\"{}\" w:hanging=\"360\"/></w:pPr></w:lvl>",
+            level,
+            format,
+            text,
+            720 * (level + 1)
+        ));
+    }
+    xml.push_str("</w:abstractNum>");
+    xml
+}
+
+fn core_properties(title: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\"><dc:title>{}</dc:title><dc:creator>NoteSquirrel</dc:creator></cp:coreProperties>",
+        xml_text(title)
+    )
+}
+
+const CONTENT_TYPES: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>
+<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+<Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+<Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+<Default Extension=\"png\" ContentType=\"image/png\"/>\
+<Default Extension=\"jpg\" ContentType=\"image/jpeg\"/>\
+<Default Extension=\"jpeg\" ContentType=\"image/jpeg\"/>\
+<Default Extension=\"gif\" ContentType=\"image/gif\"/>\
+<Override PartName=\"/word/document.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/>\
+<Override PartName=\"/word/styles.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml\"/>\
+<Override PartName=\"/word/numbering.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml\"/>\
+<Override PartName=\"/docProps/core.xml\" ContentType=\"application/vnd.openxmlformats-package.core-properties+xml\"/>\
+</Types>";
+
+const PACKAGE_RELATIONSHIPS: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>
+<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"word/document.xml\"/>\
+<Relationship Id=\"rId2\" Type=\"http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties\" Target=\"docProps/core.xml\"/>\
+</Relationships>";
+
+/// Word's built-in style names, so the headings show up in the navigation pane and restyling
+/// the document in Word works as usual.
+const STYLES: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>
+<w:styles xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii=\"Calibri\" w:hAnsi=\"Calibri\" w:eastAsia=\"Calibri\" w:cs=\"Calibri\"/>\
+<w:sz w:val=\"22\"/><w:szCs w:val=\"22\"/></w:rPr></w:rPrDefault>\
+<w:pPrDefault><w:pPr><w:spacing w:after=\"160\" w:line=\"259\" w:lineRule=\"auto\"/></w:pPr></w:pPrDefault></w:docDefaults>\
+<w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/><w:qFormat/></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading1\"><w:name w:val=\"heading 1\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:keepNext/><w:spacing w:before=\"360\" w:after=\"120\"/><w:outlineLvl w:val=\"0\"/></w:pPr><w:rPr><w:b/><w:sz w:val=\"36\"/><w:szCs w:val=\"36\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading2\"><w:name w:val=\"heading 2\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:keepNext/><w:spacing w:before=\"240\" w:after=\"80\"/><w:outlineLvl w:val=\"1\"/></w:pPr><w:rPr><w:b/><w:sz w:val=\"30\"/><w:szCs w:val=\"30\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading3\"><w:name w:val=\"heading 3\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:keepNext/><w:spacing w:before=\"240\" w:after=\"80\"/><w:outlineLvl w:val=\"2\"/></w:pPr><w:rPr><w:b/><w:sz w:val=\"26\"/><w:szCs w:val=\"26\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading4\"><w:name w:val=\"heading 4\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:keepNext/><w:spacing w:before=\"200\" w:after=\"60\"/><w:outlineLvl w:val=\"3\"/></w:pPr><w:rPr><w:b/><w:i/><w:sz w:val=\"24\"/><w:szCs w:val=\"24\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading5\"><w:name w:val=\"heading 5\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:keepNext/><w:spacing w:before=\"200\" w:after=\"60\"/><w:outlineLvl w:val=\"4\"/></w:pPr><w:rPr><w:b/><w:sz w:val=\"22\"/><w:szCs w:val=\"22\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading6\"><w:name w:val=\"heading 6\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:keepNext/><w:spacing w:before=\"200\" w:after=\"60\"/><w:outlineLvl w:val=\"5\"/></w:pPr><w:rPr><w:i/><w:sz w:val=\"22\"/><w:szCs w:val=\"22\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Quote\"><w:name w:val=\"Quote\"/><w:basedOn w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:pBdr><w:left w:val=\"single\" w:sz=\"18\" w:space=\"8\" w:color=\"BFBFBF\"/></w:pBdr></w:pPr><w:rPr><w:i/><w:color w:val=\"595959\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"ListParagraph\"><w:name w:val=\"List Paragraph\"/><w:basedOn w:val=\"Normal\"/><w:qFormat/>\
+<w:pPr><w:spacing w:after=\"60\"/><w:ind w:left=\"720\"/></w:pPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Code\"><w:name w:val=\"Code\"/><w:basedOn w:val=\"Normal\"/>\
+<w:pPr><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"F0F0F0\"/><w:spacing w:after=\"160\" w:line=\"240\" w:lineRule=\"auto\"/></w:pPr>\
+<w:rPr><w:rFonts w:ascii=\"Consolas\" w:hAnsi=\"Consolas\" w:cs=\"Consolas\"/><w:sz w:val=\"20\"/><w:szCs w:val=\"20\"/></w:rPr></w:style>\
+<w:style w:type=\"character\" w:styleId=\"CodeChar\"><w:name w:val=\"Code Char\"/>\
+<w:rPr><w:rFonts w:ascii=\"Consolas\" w:hAnsi=\"Consolas\" w:cs=\"Consolas\"/><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"F0F0F0\"/></w:rPr></w:style>\
+<w:style w:type=\"character\" w:styleId=\"Hyperlink\"><w:name w:val=\"Hyperlink\"/><w:rPr><w:color w:val=\"0563C1\"/><w:u w:val=\"single\"/></w:rPr></w:style>\
+<w:style w:type=\"table\" w:styleId=\"TableGrid\"><w:name w:val=\"Table Grid\"/><w:tblPr><w:tblBorders>\
+<w:top w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/><w:left w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/>\
+<w:bottom w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/><w:right w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/>\
+<w:insideH w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/><w:insideV w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/>\
+</w:tblBorders><w:tblCellMar><w:left w:w=\"108\" w:type=\"dxa\"/><w:right w:w=\"108\" w:type=\"dxa\"/></w:tblCellMar></w:tblPr></w:style>\
+</w:styles>";
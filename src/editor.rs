@@ -2,8 +2,21 @@ use eframe::egui;
 use egui::{Color32, ScrollArea};
 use arboard::Clipboard;
 
-use crate::notes_list::NotesList;
-use crate::config::Config;
+use crate::notes_list::{NoteDrag, NotesList};
+use regex::Regex;
+
+use crate::config::{rgb, Config, EditorStyles, FocusScope, HighlightRule};
+use crate::tags;
+use crate::tasks;
+use crate::toc;
+use crate::theme::ThemePalette;
+use crate::html_export::markdown_to_html;
+use crate::delimited_table::to_markdown_table;
+use crate::autocorrect;
+use crate::footnotes;
+use crate::html_to_markdown::html_to_markdown;
+use crate::pasted_lists;
+use crate::i18n::tr;
 
 pub struct Editor {
     markdown_text: String,
@@ -16,11 +29,140 @@ pub struct Editor {
     redo_stack: Vec<String>,
     cursor_override: Option<egui::text::CCursorRange>,
     current_cursor_pos: Option<usize>,
+    /// Selected character range, when the selection isn't empty.
+    current_selection: Option<std::ops::Range<usize>>,
+    scroll_offset: f32,
+    scroll_override: Option<f32>,
+    /// Scroll the cursor to the top of the editor on the next render.
+    scroll_to_cursor: bool,
     text_edit_id: Option<egui::Id>,
     cached_layout_text: String,
     cached_layout_matches: Vec<(usize, usize)>,
     cached_layout_current_match: Option<usize>,
     cached_layout_job: Option<egui::text::LayoutJob>,
+    /// Bumped whenever `cached_layout_job` is rebuilt.
+    layout_generation: u64,
+    /// `cached_layout_job` laid out, reused while nothing that affects the layout changes.
+    cached_galley: Option<(GalleyKey, std::sync::Arc<egui::Galley>)>,
+    /// How long highlighting and laying out the text took the last time it wasn't cached, for the
+    /// profiler overlay.
+    last_layout_duration: std::time::Duration,
+    pending_highlight: Option<PendingHighlight>,
+    /// The paste queued this frame is plain text and shouldn't be converted from HTML.
+    plain_paste: bool,
+    /// Rows of delimited text that were just pasted, with the offer to turn them into a table.
+    table_paste_offer: Option<TablePasteOffer>,
+    highlight_rules: Vec<CompiledHighlightRule>,
+    zen_mode: bool,
+    focus_mode: bool,
+}
+
+struct TablePasteOffer {
+    /// Character index where the pasted text starts.
+    start: usize,
+    pasted: String,
+    table: String,
+    /// Byte length of the note right after the paste; any other edit withdraws the offer.
+    text_len: Option<usize>,
+}
+
+/// Everything besides the text that decides how the cached layout job is laid out.
+#[derive(PartialEq)]
+struct GalleyKey {
+    layout_generation: u64,
+    wrap_width: f32,
+    focus_cursor: Option<usize>,
+    pixels_per_point: f32,
+}
+
+/// Notes bigger than this are highlighted on a worker thread, showing plain text until it's done.
+const BACKGROUND_HIGHLIGHT_BYTES: usize = 128 * 1024;
+
+/// Highlighting running on a worker thread; it sends back the text it highlighted with the job.
+struct PendingHighlight {
+    receiver: std::sync::mpsc::Receiver<(String, egui::text::LayoutJob)>,
+}
+
+/// How much of their color dimmed text keeps in focus mode.
+const FOCUS_DIM_FACTOR: f32 = 0.35;
+
+/// Space the text edit leaves between its frame and the text.
+const TEXT_EDIT_MARGIN: egui::Vec2 = egui::vec2(4.0, 2.0);
+
+/// A user highlight rule from config with its regex compiled.
+#[derive(Clone)]
+pub struct CompiledHighlightRule {
+    regex: Regex,
+    color: Option<Color32>,
+    background: Option<Color32>,
+}
+
+/// Everything the layouter needs to style editor text.
+#[derive(Clone)]
+struct EditorLook {
+    font_id: egui::FontId,
+    font_size: f32,
+    styles: EditorStyles,
+    palette: ThemePalette,
+    highlight_rules: Vec<CompiledHighlightRule>,
+    /// Row height to use per point of font size, or `None` for the font's natural spacing.
+    line_height_per_point: Option<f32>,
+}
+
+/// Compiles the configured highlight rules, skipping and reporting any with an invalid pattern.
+pub fn compile_highlight_rules(rules: &[HighlightRule]) -> (Vec<CompiledHighlightRule>, Vec<String>) {
+    let mut compiled = Vec::new();
+    let mut errors = Vec::new();
+    for rule in rules {
+        match Regex::new(&rule.pattern) {
+            Ok(regex) => compiled.push(CompiledHighlightRule {
+                regex,
+                color: rule.color.map(rgb),
+                background: rule.background.map(rgb),
+            }),
+            Err(e) => errors.push(format!("Highlight rule '{}' is not a valid regex: {}", rule.pattern, e)),
+        }
+    }
+    (compiled, errors)
+}
+
+/// Byte range of the run of non-blank lines around byte `pos`.
+fn paragraph_range(text: &str, pos: usize) -> (usize, usize) {
+    let mut start = 0;
+    let mut end = text.len();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let line_end = offset + line.len();
+        if line.trim().is_empty() {
+            if line_end <= pos {
+                start = line_end;
+            } else if offset > pos {
+                end = offset;
+                break;
+            }
+        }
+        offset = line_end;
+    }
+    (start, end)
+}
+
+/// Byte range of the sentence around byte `pos`, without crossing paragraph boundaries.
+fn sentence_range(text: &str, pos: usize) -> (usize, usize) {
+    let (paragraph_start, paragraph_end) = paragraph_range(text, pos);
+    let paragraph = &text[paragraph_start..paragraph_end];
+    let relative = pos - paragraph_start;
+    let is_terminator = |c: char| matches!(c, '.' | '!' | '?');
+
+    let start = paragraph[..relative]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| is_terminator(*c))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let end = paragraph[relative..]
+        .char_indices()
+        .find(|(_, c)| is_terminator(*c))
+        .map_or(paragraph.len(), |(i, c)| relative + i + c.len_utf8());
+    (paragraph_start + start, paragraph_start + end)
 }
 
 impl Editor {
@@ -36,20 +178,150 @@ impl Editor {
             redo_stack: Vec::new(),
             cursor_override: None,
             current_cursor_pos: None,
+            current_selection: None,
+            scroll_offset: 0.0,
+            scroll_override: None,
+            scroll_to_cursor: false,
             text_edit_id: None,
             cached_layout_text: String::new(),
             cached_layout_matches: Vec::new(),
             cached_layout_current_match: None,
             cached_layout_job: None,
+            layout_generation: 0,
+            cached_galley: None,
+            last_layout_duration: std::time::Duration::ZERO,
+            pending_highlight: None,
+            plain_paste: false,
+            table_paste_offer: None,
+            highlight_rules: compile_highlight_rules(&config.highlight_rules).0,
+            zen_mode: false,
+            focus_mode: false,
         }
     }
 
+    /// Returns any highlight rules that couldn't be compiled.
+    pub fn set_config(&mut self, config: &Config) -> Vec<String> {
+        self.config = config.clone();
+        self.cached_layout_job = None;
+        let (highlight_rules, errors) = compile_highlight_rules(&config.highlight_rules);
+        self.highlight_rules = highlight_rules;
+        errors
+    }
+
+    /// In zen mode the text column uses `zen_line_width` instead of `editor_max_line_width`.
+    pub fn set_zen_mode(&mut self, zen_mode: bool) {
+        self.zen_mode = zen_mode;
+        self.should_focus = true;
+    }
+
+    /// Gives the editor keyboard focus on the next frame.
+    pub fn request_focus(&mut self) {
+        self.should_focus = true;
+    }
+
+    pub fn has_focus(&self, ctx: &egui::Context) -> bool {
+        self.text_edit_id.is_some_and(|id| ctx.memory(|m| m.has_focus(id)))
+    }
+
+    pub fn toggle_focus_mode(&mut self) {
+        self.focus_mode = !self.focus_mode;
+    }
+
+    pub fn is_focus_mode(&self) -> bool {
+        self.focus_mode
+    }
+
     pub fn load_notes(&mut self, notes_list: &NotesList) {
         self.markdown_text = notes_list.get_current_content().to_string();
         self.undo_stack.clear();
         self.redo_stack.clear();
     }
 
+    pub fn get_cursor_position(&self) -> Option<usize> {
+        self.current_cursor_pos
+    }
+
+    pub fn get_scroll_offset(&self) -> f32 {
+        self.scroll_offset
+    }
+
+    /// Puts the cursor and scroll position back where they were when the note was last open.
+    pub fn restore_position(&mut self, cursor: Option<usize>, scroll_offset: f32) {
+        if let Some(cursor) = cursor {
+            let cursor = cursor.min(self.markdown_text.chars().count());
+            self.cursor_override = Some(egui::text::CCursorRange::one(egui::text::CCursor::new(cursor)));
+            self.current_cursor_pos = Some(cursor);
+        }
+        self.scroll_override = Some(scroll_offset.max(0.0));
+    }
+
+    /// Moves the cursor to the start of `line` and scrolls it to the top of the editor.
+    pub fn go_to_line(&mut self, line: usize) {
+        let cursor = self.markdown_text.split_inclusive('\n').take(line).map(|l| l.chars().count()).sum();
+        self.go_to_char(cursor);
+    }
+
+    /// Selects the bytes `range` of zero-based `line`, scrolling the selection into view.
+    pub fn select_in_line(&mut self, line: usize, range: std::ops::Range<usize>) {
+        let line_start: usize = self.markdown_text.split_inclusive('\n').take(line).map(str::len).sum();
+        let char_index = |byte: usize| self.markdown_text.get(..line_start + byte).map_or(0, |text| text.chars().count());
+        let (start, end) = (char_index(range.start), char_index(range.end));
+        self.go_to_char(end);
+        self.cursor_override = Some(egui::text::CCursorRange::two(egui::text::CCursor::new(start), egui::text::CCursor::new(end)));
+    }
+
+    fn go_to_char(&mut self, cursor: usize) {
+        self.cursor_override = Some(egui::text::CCursorRange::one(egui::text::CCursor::new(cursor)));
+        self.current_cursor_pos = Some(cursor);
+        self.scroll_to_cursor = true;
+        self.should_focus = true;
+    }
+
+    /// Fixes slips on the line being typed, as a separate undo step so undo brings back exactly
+    /// what was typed.
+    fn autocorrect(&mut self) {
+        let Some(cursor) = self.current_cursor_pos else {
+            return;
+        };
+        let Some((text, cursor)) = autocorrect::correct(&self.markdown_text, cursor, self.config.list_marker) else {
+            return;
+        };
+        self.undo_stack.push(std::mem::replace(&mut self.markdown_text, text));
+        self.cursor_override = Some(egui::text::CCursorRange::one(egui::text::CCursor::new(cursor)));
+        self.current_cursor_pos = Some(cursor);
+    }
+
+    /// Adds the next numbered footnote reference at the cursor, with its definition at the end of
+    /// the note, and moves the cursor to the definition.
+    pub fn insert_footnote(&mut self) -> bool {
+        let cursor = self.current_cursor_pos.unwrap_or_else(|| self.markdown_text.chars().count());
+        let (text, definition) = footnotes::insert_footnote(&self.markdown_text, cursor);
+        self.set_text_with_undo(&text);
+        self.go_to_char(definition);
+        true
+    }
+
+    /// Adds a table of contents linking to the note's headings, or refreshes the one already there.
+    pub fn insert_table_of_contents(&mut self) -> bool {
+        let cursor = self.current_cursor_pos.unwrap_or_else(|| self.markdown_text.chars().count());
+        let (text, after) = toc::insert_or_refresh(&self.markdown_text, cursor);
+        if text == self.markdown_text {
+            return false;
+        }
+        self.set_text_with_undo(&text);
+        self.go_to_char(after);
+        true
+    }
+
+    pub fn last_layout_duration(&self) -> std::time::Duration {
+        self.last_layout_duration
+    }
+
+    /// Bytes of text held by the undo and redo history.
+    pub fn undo_memory(&self) -> usize {
+        self.undo_stack.iter().chain(&self.redo_stack).map(String::len).sum()
+    }
+
     pub fn get_text(&self) -> &str {
         &self.markdown_text
     }
@@ -92,6 +364,205 @@ impl Editor {
         }
     }
 
+    /// Inserts text at the cursor, or at the end of the note if the editor hasn't had a cursor yet.
+    /// Puts the selection, or the whole note when nothing is selected, on the clipboard as HTML for
+    /// pasting into web editors and email. The markdown goes along as the plain text version.
+    pub fn copy_as_html(&mut self) {
+        let markdown = self.selected_text().unwrap_or_else(|| self.markdown_text.clone());
+        self.copy_markdown_as_html(markdown);
+    }
+
+    /// Puts `markdown` on the clipboard as HTML, with the markdown as the plain text version.
+    pub fn copy_markdown_as_html(&mut self, markdown: String) {
+        let html = markdown_to_html(&markdown);
+        if let Some(clipboard) = &mut self.clipboard
+            && let Err(e) = clipboard.set().html(html, Some(markdown))
+        {
+            eprintln!("Failed to copy as HTML: {}", e);
+        }
+    }
+
+    /// The selected text, if any is selected.
+    pub fn selected_text(&self) -> Option<String> {
+        let range = self.current_selection.as_ref()?;
+        Some(self.markdown_text.chars().skip(range.start).take(range.len()).collect())
+    }
+
+    /// Pastes the clipboard's plain text, skipping the HTML to markdown conversion.
+    pub fn paste_plain_text(&mut self, ctx: &egui::Context) {
+        let Some(text) = self.clipboard.as_mut().and_then(|clipboard| clipboard.get_text().ok()) else {
+            return;
+        };
+        self.plain_paste = true;
+        ctx.input_mut(|i| i.events.push(egui::Event::Paste(text.replace("\r\n", "\n"))));
+    }
+
+    /// When the editor is about to receive a paste and the clipboard also holds HTML, as it does
+    /// when copying from a browser or word processor, swaps the pasted text for that HTML converted
+    /// to markdown so headings, lists, links, and tables survive. Returns whether it did.
+    fn convert_html_paste(&mut self, ui: &egui::Ui) -> bool {
+        let focused = self.text_edit_id.is_some_and(|id| ui.memory(|m| m.has_focus(id)));
+        let pasting = ui.input(|i| i.events.iter().any(|event| matches!(event, egui::Event::Paste(_))));
+        if !focused || !pasting {
+            return false;
+        }
+        let Some(html) = self.clipboard.as_mut().and_then(|clipboard| clipboard.get().html().ok()) else {
+            return false;
+        };
+        let markdown = html_to_markdown(&html);
+        if markdown.trim().is_empty() {
+            return false;
+        }
+        ui.ctx().input_mut(|i| {
+            for event in &mut i.events {
+                if let egui::Event::Paste(text) = event {
+                    *text = markdown.clone();
+                }
+            }
+        });
+        true
+    }
+
+    /// When the text being pasted is rows of tab- or comma-separated values, pastes it as is and
+    /// remembers it so it can be turned into a table afterwards. Returns whether it did.
+    fn offer_table_paste(&mut self, ui: &egui::Ui) -> bool {
+        let focused = self.text_edit_id.is_some_and(|id| ui.memory(|m| m.has_focus(id)));
+        let pasted = ui.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.replace("\r\n", "\n")),
+                _ => None,
+            })
+        });
+        let Some(pasted) = pasted.filter(|_| focused) else {
+            return false;
+        };
+        let Some(table) = to_markdown_table(&pasted) else {
+            return false;
+        };
+        ui.ctx().input_mut(|i| {
+            for event in &mut i.events {
+                if let egui::Event::Paste(text) = event {
+                    *text = pasted.clone();
+                }
+            }
+        });
+        let start = match &self.current_selection {
+            Some(range) => range.start,
+            None => self.current_cursor_pos.unwrap_or_else(|| self.markdown_text.chars().count()),
+        };
+        self.table_paste_offer = Some(TablePasteOffer {
+            start,
+            pasted,
+            table,
+            text_len: None,
+        });
+        true
+    }
+
+    /// Swaps bullets like `•` and `◦`, and `1)` numbering, in the text being pasted for markdown
+    /// list items, as when pasting a list copied as plain text from Word or Slack.
+    fn normalize_list_paste(&self, ui: &egui::Ui) {
+        let focused = self.text_edit_id.is_some_and(|id| ui.memory(|m| m.has_focus(id)));
+        if !focused {
+            return;
+        }
+        ui.ctx().input_mut(|i| {
+            for event in &mut i.events {
+                if let egui::Event::Paste(text) = event
+                    && let Some(list) = pasted_lists::normalize(&text.replace("\r\n", "\n"), self.config.list_marker.marker())
+                {
+                    *text = list;
+                }
+            }
+        });
+    }
+
+    /// Shows "Convert to table" in the corner of the editor after delimited rows were pasted.
+    fn render_table_paste_offer(&mut self, ui: &egui::Ui) {
+        let Some(offer) = &mut self.table_paste_offer else {
+            return;
+        };
+        match offer.text_len {
+            None => offer.text_len = Some(self.markdown_text.len()),
+            Some(len) if len != self.markdown_text.len() => {
+                self.table_paste_offer = None;
+                return;
+            }
+            Some(_) => {}
+        }
+
+        let mut convert = false;
+        let mut dismiss = ui.input(|i| i.key_pressed(egui::Key::Escape));
+        egui::Area::new(egui::Id::new("table_paste_offer"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::RIGHT_BOTTOM)
+            .fixed_pos(ui.clip_rect().right_bottom() + egui::vec2(-16.0, -16.0))
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("editor-table-paste-offer"));
+                        convert = ui.button(tr("editor-table-paste-convert")).clicked();
+                        dismiss |= ui.small_button("✕").on_hover_text(tr("toast-dismiss")).clicked();
+                    });
+                });
+            });
+
+        if convert {
+            self.convert_table_paste();
+        } else if dismiss {
+            self.table_paste_offer = None;
+        }
+    }
+
+    /// Replaces the pasted rows with the table, as long as they're still where they were pasted.
+    fn convert_table_paste(&mut self) {
+        let Some(offer) = self.table_paste_offer.take() else {
+            return;
+        };
+        let start = self.markdown_text.char_indices().nth(offer.start).map_or(self.markdown_text.len(), |(i, _)| i);
+        let end = start + offer.pasted.len();
+        if self.markdown_text.get(start..end) != Some(offer.pasted.as_str()) {
+            return;
+        }
+        // A table only parses when it starts on a line of its own.
+        let table = if start > 0 && !self.markdown_text[..start].ends_with('\n') {
+            format!("\n{}", offer.table)
+        } else {
+            offer.table
+        };
+
+        let mut text = self.markdown_text.clone();
+        text.replace_range(start..end, &table);
+        self.set_text_with_undo(&text);
+        let cursor = offer.start + table.chars().count();
+        self.cursor_override = Some(egui::text::CCursorRange::one(egui::text::CCursor::new(cursor)));
+        self.current_cursor_pos = Some(cursor);
+        self.should_focus = true;
+    }
+
+    pub fn insert_at_cursor(&mut self, text: &str) -> bool {
+        let char_pos = self.current_cursor_pos.unwrap_or_else(|| self.markdown_text.chars().count());
+        self.insert_at(char_pos, text)
+    }
+
+    /// Inserts `text` at the character position `char_pos` and puts the cursor after it.
+    fn insert_at(&mut self, char_pos: usize, text: &str) -> bool {
+        let byte_pos = self
+            .markdown_text
+            .char_indices()
+            .nth(char_pos)
+            .map_or(self.markdown_text.len(), |(i, _)| i);
+
+        self.undo_stack.push(self.markdown_text.clone());
+        self.redo_stack.clear();
+        self.markdown_text.insert_str(byte_pos, text);
+
+        let new_cursor_pos = char_pos + text.chars().count();
+        self.cursor_override = Some(egui::text::CCursorRange::one(egui::text::CCursor::new(new_cursor_pos)));
+        self.current_cursor_pos = Some(new_cursor_pos);
+        true
+    }
+
     pub fn insert_list_entry(&mut self, cursor_pos: Option<usize>) -> bool {
         let pos = cursor_pos.or(self.current_cursor_pos).unwrap_or(self.markdown_text.len());
         let line_start = self.markdown_text[..pos].rfind('\n').map_or(0, |p| p + 1);
@@ -218,27 +689,51 @@ impl Editor {
         let mut changed = false;
 
         ui.allocate_ui_with_layout(inner, egui::Layout::top_down(egui::Align::LEFT), |ui| {
-            ScrollArea::vertical()
+            if self.zen_mode && self.config.zen_dim_surroundings {
+                self.dim_outside_column(ui);
+            }
+            let mut scroll_area = ScrollArea::vertical()
                 .auto_shrink([false, false])
-                .id_salt("editor_scroll")
-                .show(ui, |ui| {
-                    changed = self.render_syntax_highlighted_editor(ui);
-                });
+                .id_salt("editor_scroll");
+            if let Some(offset) = self.scroll_override.take() {
+                scroll_area = scroll_area.vertical_scroll_offset(offset);
+            }
+            let output = scroll_area.show(ui, |ui| {
+                changed = self.render_syntax_highlighted_editor(ui);
+            });
+            self.scroll_offset = output.state.offset.y;
         });
 
         changed
     }
 
-    fn build_layout_job(text: &str, match_ranges: &[(usize, usize)], current_match: Option<usize>, font_id: &egui::FontId, editor_font_size: f32) -> egui::text::LayoutJob {
+    fn dim_outside_column(&self, ui: &egui::Ui) {
+        let rect = ui.max_rect();
+        let font_id = self.config.get_editor_font_id(self.config.editor_font_size);
+        let column_width = self.column_width(ui, &font_id).min(rect.width());
+        let margin = (rect.width() - column_width) / 2.0;
+        if margin <= 0.0 {
+            return;
+        }
+        let shade = Color32::from_black_alpha(90);
+        let left = egui::Rect::from_min_max(rect.min, egui::pos2(rect.min.x + margin, rect.max.y));
+        let right = egui::Rect::from_min_max(egui::pos2(rect.max.x - margin, rect.min.y), rect.max);
+        ui.painter().rect_filled(left, 0.0, shade);
+        ui.painter().rect_filled(right, 0.0, shade);
+    }
+
+    fn build_layout_job(text: &str, match_ranges: &[(usize, usize)], current_match: Option<usize>, look: &EditorLook) -> egui::text::LayoutJob {
         let mut job = egui::text::LayoutJob::default();
+        let EditorLook { font_id, font_size: editor_font_size, styles, palette, highlight_rules, line_height_per_point } = look;
+        let editor_font_size = *editor_font_size;
 
         let lines: Vec<&str> = text.lines().collect();
         for (i, line) in lines.iter().enumerate() {
-            Self::highlight_markdown_line_static(line, &mut job, font_id.clone(), editor_font_size);
+            Self::highlight_markdown_line_static(line, &mut job, font_id.clone(), editor_font_size, styles);
             if i < lines.len() - 1 {
                 job.append("\n", 0.0, egui::TextFormat {
                     font_id: font_id.clone(),
-                    color: Color32::from_rgb(200, 200, 200),
+                    color: rgb(styles.text),
                     ..Default::default()
                 });
             }
@@ -247,69 +742,266 @@ impl Editor {
         if text.ends_with('\n') {
             job.append("\n", 0.0, egui::TextFormat {
                 font_id: font_id.clone(),
-                color: Color32::from_rgb(200, 200, 200),
+                color: rgb(styles.text),
                 ..Default::default()
             });
         }
 
-        Self::apply_match_highlighting(&mut job, match_ranges, current_match);
+        if let Some(per_point) = *line_height_per_point {
+            for section in &mut job.sections {
+                section.format.line_height = Some(section.format.font_id.size * per_point);
+            }
+        }
+
+        Self::apply_tag_highlighting(&mut job, text, palette);
+        Self::apply_highlight_rules(&mut job, text, highlight_rules);
+        Self::apply_overdue_highlighting(&mut job, text, palette);
+        Self::apply_task_metadata_highlighting(&mut job, text, palette);
+        Self::apply_match_highlighting(&mut job, match_ranges, current_match, palette);
         job
     }
 
+    /// The text in a single style, for notes too big to highlight while typing.
+    fn plain_layout_job(text: &str, look: &EditorLook) -> egui::text::LayoutJob {
+        let format = egui::text::TextFormat {
+            font_id: look.font_id.clone(),
+            color: rgb(look.styles.text),
+            line_height: look.line_height_per_point.map(|per_point| look.font_id.size * per_point),
+            ..Default::default()
+        };
+        egui::text::LayoutJob::single_section(text.to_string(), format)
+    }
+
+    /// Starts highlighting the current text on a worker thread, unless one is already running;
+    /// a run that finishes on outdated text starts another.
+    fn start_background_highlight(&mut self, look: &EditorLook) {
+        if self.pending_highlight.is_some() {
+            return;
+        }
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let text = self.markdown_text.clone();
+        let match_ranges = self.match_ranges.clone();
+        let current_match = self.current_match;
+        let look = look.clone();
+        std::thread::spawn(move || {
+            let job = Self::build_layout_job(&text, &match_ranges, current_match, &look);
+            sender.send((text, job)).ok();
+        });
+        self.pending_highlight = Some(PendingHighlight { receiver });
+    }
+
+    /// Swaps in a finished background highlight if it still matches the text.
+    fn adopt_background_highlight(&mut self, look: &EditorLook) {
+        let Some(pending) = &self.pending_highlight else {
+            return;
+        };
+        let Ok((text, job)) = pending.receiver.try_recv() else {
+            return;
+        };
+        self.pending_highlight = None;
+        if text == self.markdown_text && text == self.cached_layout_text {
+            self.cached_layout_job = Some(job);
+            self.layout_generation += 1;
+        } else {
+            self.start_background_highlight(look);
+        }
+    }
+
+    /// Width of the centered writing column, or infinity when no maximum line width is set.
+    fn column_width(&self, ui: &egui::Ui, font_id: &egui::FontId) -> f32 {
+        let max_line_width = if self.zen_mode {
+            self.config.zen_line_width
+        } else {
+            self.config.editor_max_line_width
+        };
+        if max_line_width == 0 {
+            return f32::INFINITY;
+        }
+        let sample = "0".repeat(max_line_width as usize);
+        let text_width = ui.painter().layout_no_wrap(sample, font_id.clone(), Color32::PLACEHOLDER).size().x;
+        text_width + 2.0 * ui.spacing().button_padding.x
+    }
+
     fn render_syntax_highlighted_editor(&mut self, ui: &mut egui::Ui) -> bool {
         use egui::TextEdit;
 
+        if !std::mem::take(&mut self.plain_paste) && !self.convert_html_paste(ui) && !self.offer_table_paste(ui) {
+            self.normalize_list_paste(ui);
+        }
+
         let font_id = self.config.get_editor_font_id(self.config.editor_font_size);
-        let editor_font_size = self.config.editor_font_size;
+        let line_spacing = self.config.markdown_styles.editor_line_spacing;
+        let line_height_per_point = (line_spacing > 0.0 && line_spacing != 1.0).then(|| {
+            let natural = ui.painter().layout_no_wrap("M".to_string(), font_id.clone(), Color32::PLACEHOLDER).size().y;
+            natural / font_id.size * line_spacing
+        });
+        let look = EditorLook {
+            font_id: font_id.clone(),
+            font_size: self.config.editor_font_size,
+            styles: self.config.editor_styles.clone(),
+            palette: self.config.theme.palette(),
+            highlight_rules: self.highlight_rules.clone(),
+            line_height_per_point,
+        };
 
         if self.cached_layout_job.is_none()
             || self.cached_layout_text != self.markdown_text
             || self.cached_layout_matches != self.match_ranges
             || self.cached_layout_current_match != self.current_match
         {
-            let job = Self::build_layout_job(&self.markdown_text, &self.match_ranges, self.current_match, &font_id, editor_font_size);
+            let started = std::time::Instant::now();
+            let size = self.markdown_text.len();
+            let job = if size > self.config.large_note_threshold_kb * 1024 {
+                Self::plain_layout_job(&self.markdown_text, &look)
+            } else if size > BACKGROUND_HIGHLIGHT_BYTES {
+                self.start_background_highlight(&look);
+                Self::plain_layout_job(&self.markdown_text, &look)
+            } else {
+                Self::build_layout_job(&self.markdown_text, &self.match_ranges, self.current_match, &look)
+            };
+            self.last_layout_duration = started.elapsed();
             self.cached_layout_text = self.markdown_text.clone();
             self.cached_layout_matches = self.match_ranges.clone();
             self.cached_layout_current_match = self.current_match;
             self.cached_layout_job = Some(job);
+            self.layout_generation += 1;
         }
+        self.adopt_background_highlight(&look);
 
-        let cached_job = self.cached_layout_job.clone().unwrap();
-        let cached_text = self.cached_layout_text.clone();
-        let match_ranges = self.match_ranges.clone();
+        let available = ui.available_size();
+        let column_width = self.column_width(ui, &font_id).min(available.x);
+
+        // The layouter borrows the cache instead of copying it, so an unchanged note costs no
+        // allocations per frame. Only text typed this frame gets a fresh job.
+        let cached_job = self.cached_layout_job.as_ref();
+        let cached_text = &self.cached_layout_text;
+        let match_ranges = &self.match_ranges;
         let current_match = self.current_match;
+        let layout_generation = self.layout_generation;
+        let cached_galley = &mut self.cached_galley;
+        let layout_duration = &mut self.last_layout_duration;
+        let focus_cursor = if self.focus_mode { self.current_cursor_pos } else { None };
+        let focus_scope = self.config.focus_scope;
 
         let mut layouter = |ui: &egui::Ui, string: &dyn egui::TextBuffer, wrap_width: f32| {
             let s = string.as_str();
-            let mut job = if s == cached_text {
-                cached_job.clone()
-            } else {
-                Self::build_layout_job(s, &match_ranges, current_match, &font_id, editor_font_size)
+            let cached = cached_job.filter(|_| s == cached_text.as_str());
+            let key = GalleyKey {
+                layout_generation,
+                wrap_width,
+                focus_cursor,
+                pixels_per_point: ui.ctx().pixels_per_point(),
+            };
+            if cached.is_some()
+                && let Some((cached_key, galley)) = cached_galley.as_ref()
+                && *cached_key == key
+            {
+                return galley.clone();
+            }
+
+            let started = std::time::Instant::now();
+            let mut job = match cached {
+                Some(job) => job.clone(),
+                None if s.len() > BACKGROUND_HIGHLIGHT_BYTES => Self::plain_layout_job(s, &look),
+                None => Self::build_layout_job(s, match_ranges, current_match, &look),
             };
+            if let Some(cursor) = focus_cursor {
+                Self::apply_focus_dimming(&mut job, s, cursor, focus_scope);
+            }
             job.wrap.max_width = wrap_width;
-            ui.painter().layout_job(job)
+            let galley = ui.painter().layout_job(job);
+            if cached.is_some() {
+                *cached_galley = Some((key, galley.clone()));
+                *layout_duration += started.elapsed();
+            } else {
+                *layout_duration = started.elapsed();
+            }
+            galley
         };
 
-        let previous_text = self.markdown_text.clone();
-
         let text_edit = TextEdit::multiline(&mut self.markdown_text)
             .font(font_id.clone())
             .lock_focus(true)
             .layouter(&mut layouter);
 
-        let response = ui.add_sized(ui.available_size(), text_edit);
+        let margin = ((available.x - column_width) / 2.0).max(0.0);
+        let response = ui
+            .horizontal_top(|ui| {
+                ui.add_space(margin);
+                ui.add_sized([column_width, available.y], text_edit)
+            })
+            .inner;
 
         self.text_edit_id = Some(response.id);
 
+        if self.pending_highlight.is_some() {
+            let corner = ui.clip_rect().right_top() + egui::vec2(-22.0, 6.0);
+            ui.put(egui::Rect::from_min_size(corner, egui::vec2(16.0, 16.0)), egui::Spinner::new())
+                .on_hover_text(tr("editor-highlighting"));
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(50));
+        }
+
         if let Some(state) = egui::TextEdit::load_state(ui.ctx(), response.id)
             && let Some(cursor) = state.cursor.char_range()
         {
             self.current_cursor_pos = Some(cursor.primary.index);
+            let (start, end) = (cursor.primary.index.min(cursor.secondary.index), cursor.primary.index.max(cursor.secondary.index));
+            self.current_selection = (start < end).then_some(start..end);
+        }
+
+        // Ctrl+click on a footnote jumps between its reference and its definition.
+        let footnote_jump = (response.clicked() && ui.input(|i| i.modifiers.command))
+            .then(|| footnotes::partner(&self.markdown_text, self.current_cursor_pos?))
+            .flatten();
+
+        if self.focus_mode && self.current_cursor_pos != focus_cursor {
+            ui.ctx().request_repaint();
         }
 
-        if let Some(cursor_range) = self.cursor_override.take()
-            && let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), response.id)
+        if self.scroll_to_cursor
+            && let Some(cursor) = self.current_cursor_pos
         {
+            // The text edit only scrolls to the cursor when the user moves it, so lay the text
+            // out again to find where the cursor ends up.
+            let galley = layouter(ui, &self.markdown_text, response.rect.width() - 2.0 * TEXT_EDIT_MARGIN.x);
+            let cursor_rect = galley.pos_from_cursor(egui::text::CCursor::new(cursor));
+            ui.scroll_to_rect(cursor_rect.translate(response.rect.min.to_vec2() + TEXT_EDIT_MARGIN), Some(egui::Align::TOP));
+            self.scroll_to_cursor = false;
+        }
+
+        // A note dragged from the sidebar is linked where it's dropped, with a caret showing where
+        // that will be while it's dragged over the text.
+        let mut dropped_note = None;
+        if let Some(pointer) = ui.input(|i| i.pointer.hover_pos())
+            && response.dnd_hover_payload::<NoteDrag>().is_some()
+        {
+            let galley = layouter(ui, &self.markdown_text, response.rect.width() - 2.0 * TEXT_EDIT_MARGIN.x);
+            let origin = response.rect.min + TEXT_EDIT_MARGIN;
+            let cursor = galley.cursor_from_pos(pointer - origin);
+            let caret = galley.pos_from_cursor(cursor).translate(origin.to_vec2());
+            ui.painter().vline(caret.left(), caret.y_range(), ui.visuals().text_cursor.stroke);
+            if let Some(note) = response.dnd_release_payload::<NoteDrag>() {
+                dropped_note = Some((cursor.index, note.0.clone()));
+            }
+        }
+
+        let mut copy_as_html = false;
+        response.context_menu(|ui| {
+            if ui.button(tr("editor-copy-as-html")).clicked() {
+                copy_as_html = true;
+                ui.close();
+            }
+        });
+        if copy_as_html {
+            self.copy_as_html();
+        }
+        self.render_table_paste_offer(ui);
+        if let Some(partner) = footnote_jump {
+            self.go_to_char(partner);
+        }
+
+        if let Some(cursor_range) = self.cursor_override.take() {
+            let mut state = egui::TextEdit::load_state(ui.ctx(), response.id).unwrap_or_default();
             state.cursor.set_char_range(Some(cursor_range));
             state.store(ui.ctx(), response.id);
         }
@@ -319,41 +1011,50 @@ impl Editor {
             self.should_focus = false;
         }
 
+        let dropped = dropped_note.is_some_and(|(at, note)| self.insert_at(at, &format!("[[{}]]", note)));
+        if dropped {
+            self.should_focus = true;
+        }
+
+        // The layout cache still holds the text from before this frame's edit.
         let changed = response.changed() && response.has_focus();
-        if changed && self.markdown_text != previous_text {
-            self.undo_stack.push(previous_text);
+        if changed && self.markdown_text != self.cached_layout_text {
+            self.undo_stack.push(self.cached_layout_text.clone());
             self.redo_stack.clear();
+            if self.config.autocorrect_markdown {
+                self.autocorrect();
+            }
         }
 
-        changed
+        changed || dropped
     }
 
-    fn highlight_markdown_line_static(line: &str, job: &mut egui::text::LayoutJob, font_id: egui::FontId, font_size: f32) {
+    fn highlight_markdown_line_static(line: &str, job: &mut egui::text::LayoutJob, font_id: egui::FontId, font_size: f32, styles: &EditorStyles) {
         let trimmed = line.trim_start();
 
         if trimmed.starts_with("######") {
-            Self::add_header_text_static(line, 6, Color32::from_rgb(255, 255, 180), 14.0, job, font_id.clone(), font_size);
+            Self::add_header_text_static(line, 6, styles.heading(6), job, font_id.clone(), styles);
         } else if trimmed.starts_with("#####") {
-            Self::add_header_text_static(line, 5, Color32::from_rgb(220, 180, 255), 16.0, job, font_id.clone(), font_size);
+            Self::add_header_text_static(line, 5, styles.heading(5), job, font_id.clone(), styles);
         } else if trimmed.starts_with("####") {
-            Self::add_header_text_static(line, 4, Color32::from_rgb(255, 180, 220), 18.0, job, font_id.clone(), font_size);
+            Self::add_header_text_static(line, 4, styles.heading(4), job, font_id.clone(), styles);
         } else if trimmed.starts_with("###") {
-            Self::add_header_text_static(line, 3, Color32::from_rgb(180, 220, 255), 20.0, job, font_id.clone(), font_size);
+            Self::add_header_text_static(line, 3, styles.heading(3), job, font_id.clone(), styles);
         } else if trimmed.starts_with("##") {
-            Self::add_header_text_static(line, 2, Color32::from_rgb(220, 255, 180), 24.0, job, font_id.clone(), font_size);
+            Self::add_header_text_static(line, 2, styles.heading(2), job, font_id.clone(), styles);
         } else if trimmed.starts_with("#") {
-            Self::add_header_text_static(line, 1, Color32::from_rgb(255, 220, 100), 28.0, job, font_id.clone(), font_size);
+            Self::add_header_text_static(line, 1, styles.heading(1), job, font_id.clone(), styles);
         } else if trimmed.starts_with("```") {
             job.append(line, 0.0, egui::TextFormat {
                 font_id: egui::FontId::monospace(font_size),
-                color: Color32::from_rgb(150, 120, 200),
-                background: Color32::from_rgb(40, 40, 50),
+                color: rgb(styles.code),
+                background: rgb(styles.code_background),
                 ..Default::default()
             });
         } else if trimmed.starts_with(">") {
             job.append(line, 0.0, egui::TextFormat {
                 font_id: font_id.clone(),
-                color: Color32::from_rgb(160, 160, 160),
+                color: rgb(styles.quote),
                 italics: true,
                 ..Default::default()
             });
@@ -361,19 +1062,19 @@ impl Editor {
             || (trimmed.chars().next().is_some_and(|c| c.is_ascii_digit()) && trimmed.contains(". ")) {
             job.append(line, 0.0, egui::TextFormat {
                 font_id,
-                color: Color32::from_rgb(60, 120, 200),
+                color: rgb(styles.list),
                 ..Default::default()
             });
         } else {
             job.append(line, 0.0, egui::TextFormat {
                 font_id,
-                color: Color32::from_rgb(200, 200, 200),
+                color: rgb(styles.text),
                 ..Default::default()
             });
         }
     }
 
-    fn add_header_text_static(line: &str, level: usize, color: Color32, _size: f32, job: &mut egui::text::LayoutJob, font_id: egui::FontId, _font_size: f32) {
+    fn add_header_text_static(line: &str, level: usize, color: Color32, job: &mut egui::text::LayoutJob, font_id: egui::FontId, styles: &EditorStyles) {
         let prefix = "#".repeat(level);
         let prefix_with_space = format!("{} ", prefix);
 
@@ -381,20 +1082,20 @@ impl Editor {
             if content_start > 0 {
                 job.append(&line[..content_start], 0.0, egui::TextFormat {
                     font_id: font_id.clone(),
-                    color: Color32::from_rgb(200, 200, 200),
+                    color: rgb(styles.text),
                     ..Default::default()
                 });
             }
 
             job.append(&prefix, 0.0, egui::TextFormat {
                 font_id: font_id.clone(),
-                color: Color32::from_rgb(100, 100, 100),
+                color: rgb(styles.heading_marker),
                 ..Default::default()
             });
 
             job.append(" ", 0.0, egui::TextFormat {
                 font_id: font_id.clone(),
-                color: Color32::from_rgb(200, 200, 200),
+                color: rgb(styles.text),
                 ..Default::default()
             });
 
@@ -407,7 +1108,7 @@ impl Editor {
         } else {
             job.append(line, 0.0, egui::TextFormat {
                 font_id,
-                color: Color32::from_rgb(200, 200, 200),
+                color: rgb(styles.text),
                 ..Default::default()
             });
         }
@@ -416,81 +1117,177 @@ impl Editor {
     fn apply_match_highlighting(
         job: &mut egui::text::LayoutJob,
         match_ranges: &[(usize, usize)],
-        current_match: Option<usize>
+        current_match: Option<usize>,
+        palette: &ThemePalette,
+    ) {
+        Self::restyle_ranges(job, match_ranges, |format, index| {
+            format.background = if current_match == Some(index) {
+                palette.current_match_background
+            } else {
+                palette.match_background
+            };
+        });
+    }
+
+    fn apply_tag_highlighting(job: &mut egui::text::LayoutJob, text: &str, palette: &ThemePalette) {
+        Self::restyle_ranges(job, &tags::note_tag_ranges(text), |format, _| {
+            format.color = palette.tag;
+        });
+    }
+
+    fn apply_highlight_rules(job: &mut egui::text::LayoutJob, text: &str, rules: &[CompiledHighlightRule]) {
+        for rule in rules {
+            let ranges: Vec<(usize, usize)> = rule
+                .regex
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect();
+            Self::restyle_ranges(job, &ranges, |format, _| {
+                if let Some(color) = rule.color {
+                    format.color = color;
+                }
+                if let Some(background) = rule.background {
+                    format.background = background;
+                }
+            });
+        }
+    }
+
+    /// Colors task lines whose due date has passed.
+    fn apply_overdue_highlighting(job: &mut egui::text::LayoutJob, text: &str, palette: &ThemePalette) {
+        let today = chrono::Local::now().date_naive();
+        let overdue_lines: Vec<usize> = tasks::parse_tasks(text)
+            .into_iter()
+            .filter(|task| task.is_overdue(today))
+            .map(|task| task.line)
+            .collect();
+        if overdue_lines.is_empty() {
+            return;
+        }
+
+        let mut ranges = Vec::new();
+        let mut line_start = 0;
+        for (index, line) in text.split_inclusive('\n').enumerate() {
+            if overdue_lines.contains(&index) {
+                let content = line.trim_end_matches(['\n', '\r']);
+                ranges.push((line_start, line_start + content.len()));
+            }
+            line_start += line.len();
+        }
+        Self::restyle_ranges(job, &ranges, |format, _| {
+            format.color = palette.overdue_task;
+        });
+    }
+
+    /// Colors the priority and due date markers on task lines.
+    fn apply_task_metadata_highlighting(job: &mut egui::text::LayoutJob, text: &str, palette: &ThemePalette) {
+        let task_lines: Vec<usize> = tasks::parse_tasks(text).into_iter().map(|task| task.line).collect();
+        if task_lines.is_empty() {
+            return;
+        }
+
+        let mut ranges = Vec::new();
+        let mut colors = Vec::new();
+        let mut line_start = 0;
+        for (index, line) in text.split_inclusive('\n').enumerate() {
+            if task_lines.contains(&index) {
+                for (start, end, metadata) in tasks::metadata_ranges(line) {
+                    ranges.push((line_start + start, line_start + end));
+                    colors.push(metadata.color(palette));
+                }
+            }
+            line_start += line.len();
+        }
+        Self::restyle_ranges(job, &ranges, |format, index| {
+            format.color = colors[index];
+        });
+    }
+
+    /// Dims everything outside the paragraph or sentence containing the cursor (a char index).
+    fn apply_focus_dimming(job: &mut egui::text::LayoutJob, text: &str, cursor: usize, scope: FocusScope) {
+        let pos = text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i);
+        let (start, end) = match scope {
+            FocusScope::Paragraph => paragraph_range(text, pos),
+            FocusScope::Sentence => sentence_range(text, pos),
+        };
+        let ranges: Vec<(usize, usize)> = [(0, start), (end, text.len())]
+            .into_iter()
+            .filter(|(range_start, range_end)| range_end > range_start)
+            .collect();
+        Self::restyle_ranges(job, &ranges, |format, _| {
+            format.color = format.color.gamma_multiply(FOCUS_DIM_FACTOR);
+        });
+    }
+
+    /// Splits the job's sections at the given sorted, non-overlapping byte ranges and lets
+    /// `restyle` adjust the pieces that fall inside them; it gets the index of the range.
+    fn restyle_ranges(
+        job: &mut egui::text::LayoutJob,
+        ranges: &[(usize, usize)],
+        mut restyle: impl FnMut(&mut egui::TextFormat, usize),
     ) {
-        if match_ranges.is_empty() {
+        if ranges.is_empty() {
             return;
         }
 
-        let mut new_sections = Vec::with_capacity(job.sections.len() + match_ranges.len() * 2);
-        let mut byte_pos: usize = 0;
-        let mut match_idx = 0;
+        let mut new_sections = Vec::with_capacity(job.sections.len() + ranges.len() * 2);
+        let mut range_idx = 0;
 
         for section in job.sections.drain(..) {
-            let section_start = byte_pos;
-            let section_end = byte_pos + section.byte_range.len();
-            let text_offset = section.byte_range.start;
+            let section_start = section.byte_range.start;
+            let section_end = section.byte_range.end;
 
-            while match_idx < match_ranges.len() && match_ranges[match_idx].1 <= section_start {
-                match_idx += 1;
+            while range_idx < ranges.len() && ranges[range_idx].1 <= section_start {
+                range_idx += 1;
             }
 
+            let mut pieces: Vec<(usize, usize, Option<usize>)> = Vec::new();
             let mut local_pos = section_start;
-            let mut local_match_idx = match_idx;
-            let mut first_piece = true;
+            let mut local_range_idx = range_idx;
 
-            while local_pos < section_end && local_match_idx < match_ranges.len() {
-                let (match_start, match_end) = match_ranges[local_match_idx];
-                if match_start >= section_end {
+            while local_pos < section_end && local_range_idx < ranges.len() {
+                let (range_start, range_end) = ranges[local_range_idx];
+                if range_start >= section_end {
                     break;
                 }
 
-                let overlap_start = match_start.max(local_pos);
-                let overlap_end = match_end.min(section_end);
-
+                let overlap_start = range_start.max(local_pos);
+                let overlap_end = range_end.min(section_end);
                 if overlap_start > local_pos {
-                    new_sections.push(egui::text::LayoutSection {
-                        leading_space: if first_piece { section.leading_space } else { 0.0 },
-                        byte_range: (text_offset + (local_pos - section_start))..(text_offset + (overlap_start - section_start)),
-                        format: section.format.clone(),
-                    });
-                    first_piece = false;
+                    pieces.push((local_pos, overlap_start, None));
                 }
+                if overlap_end > overlap_start {
+                    pieces.push((overlap_start, overlap_end, Some(local_range_idx)));
+                }
+                local_pos = local_pos.max(overlap_end);
 
-                let is_current = current_match == Some(local_match_idx);
-                let bg_color = if is_current {
-                    Color32::from_rgb(255, 165, 0)
-                } else {
-                    Color32::from_rgb(100, 100, 50)
-                };
-                let mut highlighted_format = section.format.clone();
-                highlighted_format.background = bg_color;
-                new_sections.push(egui::text::LayoutSection {
-                    leading_space: if first_piece { section.leading_space } else { 0.0 },
-                    byte_range: (text_offset + (overlap_start - section_start))..(text_offset + (overlap_end - section_start)),
-                    format: highlighted_format,
-                });
-                first_piece = false;
-
-                local_pos = overlap_end;
-                if match_end <= section_end {
-                    local_match_idx += 1;
+                if range_end <= section_end {
+                    local_range_idx += 1;
                 } else {
                     break;
                 }
             }
 
+            if pieces.is_empty() {
+                new_sections.push(section);
+                continue;
+            }
             if local_pos < section_end {
+                pieces.push((local_pos, section_end, None));
+            }
+
+            for (piece_index, (start, end, range)) in pieces.into_iter().enumerate() {
+                let mut format = section.format.clone();
+                if let Some(range) = range {
+                    restyle(&mut format, range);
+                }
                 new_sections.push(egui::text::LayoutSection {
-                    leading_space: if first_piece { section.leading_space } else { 0.0 },
-                    byte_range: (text_offset + (local_pos - section_start))..section.byte_range.end,
-                    format: section.format,
+                    leading_space: if piece_index == 0 { section.leading_space } else { 0.0 },
+                    byte_range: start..end,
+                    format,
                 });
-            } else if local_pos == section_start {
-                new_sections.push(section);
             }
-
-            byte_pos = section_end;
         }
 
         job.sections = new_sections;
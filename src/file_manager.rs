@@ -1,41 +1,101 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use eframe::egui;
 use crate::config::Config;
+use crate::ignore_rules::IgnoreRules;
+use crate::note_template::TEMPLATES_FOLDER;
 
+pub const ATTACHMENTS_DIR: &str = "attachments";
+/// Hidden like the other files the app keeps in the notes folder, so the scratchpad never
+/// shows up in the notes list whatever `note_extensions` says.
+pub const SCRATCHPAD_FILE: &str = ".scratchpad.txt";
+/// Soft-deleted notes; hidden, and not scanned for notes.
+pub const TRASH_DIR: &str = ".trash";
+/// Earlier versions of each note, in a folder per note named like the note.
+pub const HISTORY_DIR: &str = ".history";
+/// File names of the snapshots in `HISTORY_DIR`, which sort oldest first.
+const SNAPSHOT_FORMAT: &str = "%Y-%m-%d %H%M%S";
+/// Cached word index for the sidebar search; rebuilt from the notes when missing.
+pub const SEARCH_INDEX_FILE: &str = ".search-index";
+/// Cursor and scroll positions of each note, when `remember_note_views` is on.
+pub const NOTE_VIEWS_FILE: &str = ".note-views.toml";
+/// Words written per day, for the statistics' heatmap and streaks.
+pub const WRITING_ACTIVITY_FILE: &str = ".writing-activity.toml";
+/// How often `NoteWatcher` checks the notes for changes made by other programs.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
 pub struct FileManager {
     notes_dir: PathBuf,
+    /// Extensions of the files listed as notes, without the dot; new notes get the first.
+    extensions: Vec<String>,
+    ignore: IgnoreRules,
 }
 
 impl FileManager {
+    /// The notes folder is created the first time notes are loaded or created, not here.
     pub fn new(config: &Config) -> Self {
-        let notes_dir = config.notes_folder.clone();
-        fs::create_dir_all(&notes_dir).ok();
+        let mut extensions = Vec::new();
+        for extension in std::iter::once(&config.new_note_extension).chain(&config.note_extensions) {
+            let extension = extension.trim().trim_start_matches('.').to_string();
+            if !extension.is_empty() && !extensions.contains(&extension) {
+                extensions.push(extension);
+            }
+        }
+        if extensions.is_empty() {
+            extensions.push("md".to_string());
+        }
+        Self {
+            notes_dir: config.notes_folder.clone(),
+            extensions,
+            ignore: IgnoreRules::new(&config.ignore_patterns),
+        }
+    }
 
-        Self { notes_dir }
+    fn is_note_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| self.extensions.iter().any(|e| e == extension))
     }
 
-    pub fn load_note_names(&self) -> Vec<String> {
-        let mut files = Vec::new();
+    /// The file named `name` in `dir` with whichever note extension it has, or with the one new
+    /// notes get when there's none. When a name has files with several extensions, the one
+    /// listed first wins.
+    fn file_in(&self, dir: &Path, name: &str) -> PathBuf {
+        self.extensions
+            .iter()
+            .map(|extension| dir.join(format!("{}.{}", name, extension)))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| dir.join(format!("{}.{}", name, self.extensions[0])))
+    }
 
-        if let Ok(entries) = fs::read_dir(&self.notes_dir) {
-            files = entries
-                .filter_map(|entry| {
-                    let entry = entry.ok()?;
-                    let path = entry.path();
-                    if path.extension()? == "md" {
-                        let file_name = path.file_stem()?.to_str()?.to_string();
-                        Some(file_name)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            files.sort();
-        }
+    fn note_path(&self, note_name: &str) -> PathBuf {
+        self.file_in(&self.notes_dir, note_name)
+    }
+
+    /// The extension of a note file, which moves with the note when it's renamed or trashed.
+    fn extension_of<'a>(&'a self, path: &'a Path) -> &'a str {
+        path.extension().and_then(|extension| extension.to_str()).unwrap_or(&self.extensions[0])
+    }
+
+    /// A note's file relative to the notes folder, e.g. `Projects/Plan.md`.
+    pub fn note_file(&self, note_name: &str) -> String {
+        format!("{}.{}", note_name, self.extension_of(&self.note_path(note_name)))
+    }
+
+    pub fn load_note_names(&self) -> Vec<String> {
+        fs::create_dir_all(&self.notes_dir).ok();
+        let mut files = self.list_note_names();
 
         if files.is_empty() {
             let default_name = "Welcome".to_string();
-            let default_path = self.notes_dir.join(format!("{}.md", default_name));
+            let default_path = self.note_path(&default_name);
             fs::write(&default_path, "").ok();
             files.push(default_name);
         }
@@ -43,34 +103,447 @@ impl FileManager {
         files
     }
 
+    /// Names of the notes in the folder and its subfolders, without creating the welcome note
+    /// when there are none. A note in a subfolder is named by its path, e.g. `Projects/Plan`.
+    pub fn list_note_names(&self) -> Vec<String> {
+        let mut files = Vec::new();
+        self.collect_notes(&self.notes_dir, "", &mut files, &mut Vec::new());
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    /// Paths of the subfolders that can hold notes, empty ones included, e.g. `Projects/2025`.
+    pub fn list_folders(&self) -> Vec<String> {
+        let mut folders = Vec::new();
+        self.collect_notes(&self.notes_dir, "", &mut Vec::new(), &mut folders);
+        folders.sort();
+        folders
+    }
+
+    /// Walks `dir`, skipping hidden and ignored files and folders and, at the top, the
+    /// attachments and templates.
+    fn collect_notes(&self, dir: &Path, prefix: &str, notes: &mut Vec<String>, folders: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let is_dir = path.is_dir();
+            if name.starts_with('.') || self.ignore.is_ignored(&format!("{}{}", prefix, name), is_dir) {
+                continue;
+            }
+            if is_dir {
+                let reserved = prefix.is_empty() && (name == ATTACHMENTS_DIR || name == TEMPLATES_FOLDER);
+                if !reserved {
+                    let folder = format!("{}{}", prefix, name);
+                    self.collect_notes(&path, &format!("{}/", folder), notes, folders);
+                    folders.push(folder);
+                }
+            } else if self.is_note_file(&path)
+                && let Some(stem) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                notes.push(format!("{}{}", prefix, stem));
+            }
+        }
+    }
+
     pub fn read_note_content(&self, note_name: &str) -> String {
-        let file_path = self.notes_dir.join(format!("{}.md", note_name));
-        fs::read_to_string(&file_path).unwrap_or_default()
+        self.read_note(note_name).unwrap_or_default()
+    }
+
+    pub fn read_note(&self, note_name: &str) -> std::io::Result<String> {
+        fs::read_to_string(self.note_path(note_name))
     }
 
-    pub fn write_note_content(&self, note_name: &str, content: &str) -> bool {
-        let file_path = self.notes_dir.join(format!("{}.md", note_name));
+    pub fn write_note(&self, note_name: &str, content: &str) -> std::io::Result<()> {
+        fs::write(self.note_path(note_name), content)
+    }
+
+    /// Also creates the subfolder a note named like `Projects/Plan` goes in.
+    pub fn create_note(&self, note_name: &str, content: &str) -> bool {
+        let file_path = self.note_path(note_name);
+        create_parent_dir(&file_path);
         fs::write(&file_path, content).is_ok()
     }
 
-    pub fn create_note(&self, note_name: &str) -> bool {
-        let file_path = self.notes_dir.join(format!("{}.md", note_name));
-        fs::write(&file_path, "").is_ok()
+    pub fn create_folder(&self, folder: &str) -> bool {
+        fs::create_dir_all(self.notes_dir.join(folder)).is_ok()
+    }
+
+    /// Renames or moves a subfolder, with the notes in it.
+    pub fn rename_folder(&self, old_folder: &str, new_folder: &str) -> bool {
+        let new_path = self.notes_dir.join(new_folder);
+        if new_path.exists() {
+            return false;
+        }
+        create_parent_dir(&new_path);
+        if fs::rename(self.notes_dir.join(old_folder), new_path).is_err() {
+            return false;
+        }
+        self.move_history(old_folder, new_folder, true);
+        true
+    }
+
+    /// Copies a markdown file from elsewhere into the notes folder as `note_name`, replacing any
+    /// note with that name.
+    pub fn import_note(&self, source: &Path, note_name: &str) -> Result<(), String> {
+        let file_path = self.note_path(note_name);
+        create_parent_dir(&file_path);
+        fs::copy(source, &file_path)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to import '{}': {}", source.display(), e))
     }
 
     pub fn delete_note(&self, note_name: &str) -> bool {
-        let file_path = self.notes_dir.join(format!("{}.md", note_name));
+        let file_path = self.note_path(note_name);
         fs::remove_file(&file_path).is_ok()
     }
 
+    /// Moves a note into the trash folder. If the trash already holds a note with that name,
+    /// the new one gets the deletion time appended.
+    pub fn trash_note(&self, note_name: &str) -> bool {
+        let trash_dir = self.notes_dir.join(TRASH_DIR);
+        if fs::create_dir_all(&trash_dir).is_err() {
+            return false;
+        }
+        let file_path = self.note_path(note_name);
+        let extension = self.extension_of(&file_path);
+        let mut target = trash_dir.join(format!("{}.{}", note_name, extension));
+        if self.file_in(&trash_dir, note_name).exists() {
+            let stamp = chrono::Local::now().format("%Y-%m-%d %H%M%S");
+            target = trash_dir.join(format!("{} {}.{}", note_name, stamp, extension));
+        }
+        create_parent_dir(&target);
+        if fs::rename(&file_path, &target).is_err() {
+            return false;
+        }
+        // Renaming keeps the old modification time; the trash lists notes by when they went in.
+        if let Ok(file) = fs::File::options().append(true).open(&target) {
+            file.set_modified(SystemTime::now()).ok();
+        }
+        true
+    }
+
+    /// Saves a copy of a note's content into its history folder, stamped with the current time.
+    pub fn write_snapshot(&self, note_name: &str, content: &str) -> std::io::Result<()> {
+        let dir = self.notes_dir.join(HISTORY_DIR).join(note_name);
+        fs::create_dir_all(&dir)?;
+        let stamp = chrono::Local::now().format(SNAPSHOT_FORMAT);
+        fs::write(dir.join(format!("{}.md", stamp)), content)
+    }
+
+    /// When each snapshot of a note was taken, newest first.
+    pub fn list_snapshots(&self, note_name: &str) -> Vec<chrono::NaiveDateTime> {
+        let Ok(entries) = fs::read_dir(self.notes_dir.join(HISTORY_DIR).join(note_name)) else {
+            return Vec::new();
+        };
+        let mut snapshots: Vec<chrono::NaiveDateTime> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let stem = path.file_stem()?.to_str()?;
+                chrono::NaiveDateTime::parse_from_str(stem, SNAPSHOT_FORMAT).ok()
+            })
+            .collect();
+        snapshots.sort_by_key(|&taken| std::cmp::Reverse(taken));
+        snapshots
+    }
+
+    pub fn read_snapshot(&self, note_name: &str, taken: chrono::NaiveDateTime) -> std::io::Result<String> {
+        let file = format!("{}.md", taken.format(SNAPSHOT_FORMAT));
+        fs::read_to_string(self.notes_dir.join(HISTORY_DIR).join(note_name).join(file))
+    }
+
+    /// Deletes all but the newest `keep` snapshots of a note.
+    pub fn prune_snapshots(&self, note_name: &str, keep: usize) {
+        let dir = self.notes_dir.join(HISTORY_DIR).join(note_name);
+        for taken in self.list_snapshots(note_name).into_iter().skip(keep) {
+            fs::remove_file(dir.join(format!("{}.md", taken.format(SNAPSHOT_FORMAT)))).ok();
+        }
+    }
+
+    /// Moves history along with a renamed note or folder. A note's snapshots are the files in
+    /// its history folder (`folders` unset); the notes in a folder have their history in the
+    /// subfolders of the folder's (`folders` set). Keeping them apart matters when a note and a
+    /// folder share a name.
+    fn move_history(&self, old_name: &str, new_name: &str, folders: bool) {
+        let old_dir = self.notes_dir.join(HISTORY_DIR).join(old_name);
+        let new_dir = self.notes_dir.join(HISTORY_DIR).join(new_name);
+        let Ok(entries) = fs::read_dir(&old_dir) else {
+            return;
+        };
+        if fs::create_dir_all(&new_dir).is_err() {
+            return;
+        }
+        for entry in entries.flatten() {
+            if entry.path().is_dir() == folders {
+                fs::rename(entry.path(), new_dir.join(entry.file_name())).ok();
+            }
+        }
+        fs::remove_dir(&old_dir).ok();
+    }
+
+    /// The notes in the trash folder, most recently deleted first.
+    pub fn list_trash(&self) -> Vec<TrashedNote> {
+        let mut files = Vec::new();
+        self.collect_notes(&self.notes_dir.join(TRASH_DIR), "", &mut files, &mut Vec::new());
+        files.sort();
+        files.dedup();
+        let mut trashed: Vec<TrashedNote> = files
+            .into_iter()
+            .map(|file| {
+                let path = self.file_in(&self.notes_dir.join(TRASH_DIR), &file);
+                TrashedNote {
+                    name: strip_trash_stamp(&file).to_string(),
+                    deleted: fs::metadata(path).and_then(|m| m.modified()).ok(),
+                    file,
+                }
+            })
+            .collect();
+        trashed.sort_by_key(|trashed| std::cmp::Reverse(trashed.deleted));
+        trashed
+    }
+
+    /// Moves a note out of the trash folder under `note_name`, unless a note has that name.
+    pub fn restore_trashed_note(&self, file: &str, note_name: &str) -> bool {
+        if self.note_path(note_name).exists() {
+            return false;
+        }
+        let source = self.file_in(&self.notes_dir.join(TRASH_DIR), file);
+        let target = self.notes_dir.join(format!("{}.{}", note_name, self.extension_of(&source)));
+        create_parent_dir(&target);
+        fs::rename(&source, &target).is_ok()
+    }
+
+    /// Also moves the note between subfolders when the folder part of its name changes.
     pub fn rename_note(&self, old_name: &str, new_name: &str) -> bool {
-        let old_path = self.notes_dir.join(format!("{}.md", old_name));
-        let new_path = self.notes_dir.join(format!("{}.md", new_name));
-        fs::rename(&old_path, &new_path).is_ok()
+        let old_path = self.note_path(old_name);
+        let new_path = self.notes_dir.join(format!("{}.{}", new_name, self.extension_of(&old_path)));
+        create_parent_dir(&new_path);
+        if fs::rename(&old_path, &new_path).is_err() {
+            return false;
+        }
+        self.move_history(old_name, new_name, false);
+        true
     }
 
     pub fn get_note_modified_time(&self, note_name: &str) -> Option<std::time::SystemTime> {
-        let file_path = self.notes_dir.join(format!("{}.md", note_name));
-        fs::metadata(file_path).and_then(|m| m.modified()).ok()
+        fs::metadata(self.note_path(note_name)).and_then(|m| m.modified()).ok()
     }
-}
\ No newline at end of file
+
+    /// The modification time of every note, for noticing notes changed by other programs.
+    pub fn note_modified_times(&self) -> HashMap<String, SystemTime> {
+        self.list_note_names()
+            .into_iter()
+            .filter_map(|name| {
+                let modified = self.get_note_modified_time(&name)?;
+                Some((name, modified))
+            })
+            .collect()
+    }
+
+    /// When the note file was created; `None` on filesystems that don't record it.
+    pub fn get_note_created_time(&self, note_name: &str) -> Option<std::time::SystemTime> {
+        fs::metadata(self.note_path(note_name)).and_then(|m| m.created()).ok()
+    }
+
+    pub fn read_scratchpad(&self) -> String {
+        fs::read_to_string(self.notes_dir.join(SCRATCHPAD_FILE)).unwrap_or_default()
+    }
+
+    pub fn write_scratchpad(&self, content: &str) -> bool {
+        fs::create_dir_all(&self.notes_dir).ok();
+        fs::write(self.notes_dir.join(SCRATCHPAD_FILE), content).is_ok()
+    }
+
+    pub fn read_search_index(&self) -> String {
+        fs::read_to_string(self.notes_dir.join(SEARCH_INDEX_FILE)).unwrap_or_default()
+    }
+
+    pub fn write_search_index(&self, data: &str) -> bool {
+        fs::write(self.notes_dir.join(SEARCH_INDEX_FILE), data).is_ok()
+    }
+
+    pub fn read_writing_activity(&self) -> String {
+        fs::read_to_string(self.notes_dir.join(WRITING_ACTIVITY_FILE)).unwrap_or_default()
+    }
+
+    pub fn write_writing_activity(&self, data: &str) -> bool {
+        fs::write(self.notes_dir.join(WRITING_ACTIVITY_FILE), data).is_ok()
+    }
+
+    pub fn notes_dir(&self) -> &Path {
+        &self.notes_dir
+    }
+
+    pub fn attachments_dir(&self) -> PathBuf {
+        self.notes_dir.join(ATTACHMENTS_DIR)
+    }
+
+    pub fn list_attachments(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        Self::collect_files(&self.attachments_dir(), &mut files);
+        files.sort();
+        files
+    }
+
+    pub fn delete_attachment(&self, path: &Path) -> bool {
+        path.starts_with(self.attachments_dir()) && fs::remove_file(path).is_ok()
+    }
+
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    Self::collect_files(&path, files);
+                } else {
+                    files.push(path);
+                }
+            }
+        }
+    }
+}
+
+/// A note in the trash folder.
+pub struct TrashedNote {
+    /// The file's name in the trash, which has the deletion time appended when a note with the
+    /// same name was already there.
+    pub file: String,
+    /// The name the note had before it was deleted.
+    pub name: String,
+    pub deleted: Option<SystemTime>,
+}
+
+/// Moves everything in the notes folder `from` into `to`, the app's hidden files and folders
+/// included, merging into subfolders that are already there. Files whose name is taken in `to`
+/// are left where they are and returned.
+pub fn move_notes_folder(from: &Path, to: &Path) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create '{}': {}", to.display(), e))?;
+    let (Ok(source), Ok(target)) = (from.canonicalize(), to.canonicalize()) else {
+        return Err(format!("Failed to find the notes folder '{}'", from.display()));
+    };
+    if target.starts_with(&source) {
+        return Err(format!("Can't move the notes into '{}', which is inside the notes folder", to.display()));
+    }
+    let mut left_behind = Vec::new();
+    move_entries(from, to, &mut left_behind)
+        .map_err(|e| format!("Failed to move the notes to '{}': {}", to.display(), e))?;
+    Ok(left_behind)
+}
+
+fn move_entries(from: &Path, to: &Path, left_behind: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(from)? {
+        let source = entry?.path();
+        let Some(name) = source.file_name() else { continue };
+        let target = to.join(name);
+        if !target.exists() {
+            // Renaming fails across drives, where the entry has to be copied instead.
+            if fs::rename(&source, &target).is_err() {
+                copy_entry(&source, &target)?;
+                if source.is_dir() {
+                    fs::remove_dir_all(&source)?;
+                } else {
+                    fs::remove_file(&source)?;
+                }
+            }
+        } else if source.is_dir() && target.is_dir() {
+            move_entries(&source, &target, left_behind)?;
+            fs::remove_dir(&source).ok();
+        } else {
+            left_behind.push(source);
+        }
+    }
+    Ok(())
+}
+
+fn copy_entry(source: &Path, target: &Path) -> std::io::Result<()> {
+    if !source.is_dir() {
+        return fs::copy(source, target).map(|_| ());
+    }
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let path = entry?.path();
+        if let Some(name) = path.file_name() {
+            copy_entry(&path, &target.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// The note name `trash_note` was given, from the name of its file in the trash.
+fn strip_trash_stamp(file: &str) -> &str {
+    const STAMP_LEN: usize = " 2000-01-01 000000".len();
+    let Some(split) = file.len().checked_sub(STAMP_LEN).filter(|&split| file.is_char_boundary(split)) else {
+        return file;
+    };
+    let (name, stamp) = file.split_at(split);
+    match chrono::NaiveDateTime::parse_from_str(&stamp[1..], "%Y-%m-%d %H%M%S") {
+        Ok(_) if stamp.starts_with(' ') && !name.is_empty() => name,
+        _ => file,
+    }
+}
+
+/// Makes sure the folder a file is about to be written to exists.
+fn create_parent_dir(path: &Path) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+}
+
+/// Polls the notes' modification times on a background thread, like `ConfigWatcher` does for
+/// the config file, and reports notes another program changed with their new time. Notes added
+/// or removed meanwhile aren't reported. Stops when dropped.
+pub struct NoteWatcher {
+    receiver: mpsc::Receiver<(String, SystemTime)>,
+    stop: Arc<AtomicBool>,
+}
+
+impl NoteWatcher {
+    pub fn spawn(file_manager: FileManager, ctx: egui::Context) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+
+        thread::spawn(move || {
+            let mut known = file_manager.note_modified_times();
+            while !stopped.load(Ordering::Relaxed) {
+                thread::sleep(WATCH_INTERVAL);
+                let current = file_manager.note_modified_times();
+                let changed: Vec<(String, SystemTime)> = current
+                    .iter()
+                    .filter(|(name, modified)| known.get(*name).is_some_and(|before| before != *modified))
+                    .map(|(name, modified)| (name.clone(), *modified))
+                    .collect();
+                known = current;
+                if changed.is_empty() {
+                    continue;
+                }
+                for change in changed {
+                    if sender.send(change).is_err() {
+                        return;
+                    }
+                }
+                ctx.request_repaint();
+            }
+        });
+
+        Self { receiver, stop }
+    }
+
+    /// Notes changed since the last call, with their new modification time.
+    pub fn poll(&self) -> Vec<(String, SystemTime)> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for NoteWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
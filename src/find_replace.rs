@@ -1,12 +1,22 @@
 use eframe::egui;
 use regex::Regex;
 
+use crate::i18n::{tr, tr_args};
+use crate::keymap::{Action, Keymap};
+
 #[derive(Debug, Clone)]
 pub struct Match {
     pub start: usize,
     pub end: usize,
 }
 
+/// Whether find & replace works on the note in the editor or on every note.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FindScope {
+    CurrentNote,
+    AllNotes,
+}
+
 pub struct FindReplace {
     pub show_dialog: bool,
     pub find_text: String,
@@ -15,6 +25,12 @@ pub struct FindReplace {
     pub use_regex: bool,
     pub matches: Vec<Match>,
     pub current_match_index: Option<usize>,
+    pub scope: FindScope,
+    /// How many matches each note has, when searching all notes. Notes without any are left out.
+    pub note_match_counts: Vec<(String, usize)>,
+    /// The notes changed by the last replace across all notes, with their content from before,
+    /// so each can be put back.
+    pub replaced_notes: Vec<(String, String)>,
     find_text_changed: bool,
     should_focus: bool,
 }
@@ -29,6 +45,9 @@ impl FindReplace {
             use_regex: false,
             matches: Vec::new(),
             current_match_index: None,
+            scope: FindScope::CurrentNote,
+            note_match_counts: Vec::new(),
+            replaced_notes: Vec::new(),
             find_text_changed: false,
             should_focus: false,
         }
@@ -46,21 +65,44 @@ impl FindReplace {
         self.show_dialog = false;
         self.matches.clear();
         self.current_match_index = None;
+        self.note_match_counts.clear();
+        self.replaced_notes.clear();
     }
 
     pub fn update_matches(&mut self, text: &str) {
-        if self.find_text.is_empty() {
-            self.matches.clear();
+        self.matches = self.find_matches(text);
+
+        if !self.matches.is_empty() && self.current_match_index.is_none() {
+            self.current_match_index = Some(0);
+        } else if self.current_match_index.is_some() && self.matches.is_empty() {
             self.current_match_index = None;
-            return;
+        } else if let Some(idx) = self.current_match_index
+            && idx >= self.matches.len()
+        {
+            self.current_match_index = Some(self.matches.len().saturating_sub(1));
         }
+    }
 
-        self.matches.clear();
+    /// Counts the matches in each of `(note name, content)`, for the preview before replacing
+    /// across all notes.
+    pub fn update_note_match_counts<'a>(&mut self, notes: impl Iterator<Item = (&'a str, &'a str)>) {
+        self.note_match_counts = notes
+            .map(|(name, content)| (name.to_string(), self.find_matches(content).len()))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        self.note_match_counts.sort_by_key(|(name, _)| name.to_lowercase());
+    }
+
+    fn find_matches(&self, text: &str) -> Vec<Match> {
+        let mut matches = Vec::new();
+        if self.find_text.is_empty() {
+            return matches;
+        }
 
         if self.use_regex {
             if let Ok(regex) = self.build_regex() {
                 for mat in regex.find_iter(text) {
-                    self.matches.push(Match {
+                    matches.push(Match {
                         start: mat.start(),
                         end: mat.end(),
                     });
@@ -82,23 +124,14 @@ impl FindReplace {
             let mut start = 0;
             while let Some(pos) = haystack[start..].find(&search_text) {
                 let absolute_pos = start + pos;
-                self.matches.push(Match {
+                matches.push(Match {
                     start: absolute_pos,
                     end: absolute_pos + self.find_text.len(),
                 });
                 start = absolute_pos + 1;
             }
         }
-
-        if !self.matches.is_empty() && self.current_match_index.is_none() {
-            self.current_match_index = Some(0);
-        } else if self.current_match_index.is_some() && self.matches.is_empty() {
-            self.current_match_index = None;
-        } else if let Some(idx) = self.current_match_index
-            && idx >= self.matches.len()
-        {
-            self.current_match_index = Some(self.matches.len().saturating_sub(1));
-        }
+        matches
     }
 
     fn build_regex(&self) -> Result<Regex, regex::Error> {
@@ -169,23 +202,36 @@ impl FindReplace {
             return 0;
         }
 
+        self.replace_matches(text, &self.matches);
+        self.find_text_changed = true;
+        count
+    }
+
+    /// Replaces every match in a note other than the one whose matches are tracked, e.g. while
+    /// replacing across all notes. Returns how many were replaced.
+    pub fn replace_all_in(&self, text: &mut String) -> usize {
+        let matches = self.find_matches(text);
+        if !matches.is_empty() {
+            self.replace_matches(text, &matches);
+        }
+        matches.len()
+    }
+
+    fn replace_matches(&self, text: &mut String, matches: &[Match]) {
         if self.use_regex {
             if let Ok(regex) = self.build_regex() {
                 *text = regex.replace_all(text, self.replace_text.as_str()).to_string();
             }
         } else {
-            for mat in self.matches.iter().rev() {
+            for mat in matches.iter().rev() {
                 if mat.start <= text.len() && mat.end <= text.len() && mat.start <= mat.end {
                     text.replace_range(mat.start..mat.end, &self.replace_text);
                 }
             }
         }
-
-        self.find_text_changed = true;
-        count
     }
 
-    pub fn render(&mut self, ctx: &egui::Context) -> FindReplaceAction {
+    pub fn render(&mut self, ctx: &egui::Context, keymap: &Keymap) -> FindReplaceAction {
         let mut action = FindReplaceAction::None;
 
         if !self.show_dialog {
@@ -194,7 +240,7 @@ impl FindReplace {
 
         let mut close = false;
 
-        egui::Window::new("Find & Replace")
+        egui::Window::new(tr("find-replace-title"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::RIGHT_TOP, egui::Vec2::new(-10.0, 10.0))
@@ -202,11 +248,11 @@ impl FindReplace {
             .show(ctx, |ui| {
                 ui.vertical(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label("Find:");
+                        ui.label(tr("find-label"));
                         let find_response = ui.add_sized(
                             egui::Vec2::new(ui.available_width(), 20.0),
                             egui::TextEdit::singleline(&mut self.find_text)
-                                .hint_text("Enter search text...")
+                                .hint_text(tr("find-hint"))
                         );
 
                         if self.should_focus {
@@ -228,11 +274,11 @@ impl FindReplace {
                     });
 
                     ui.horizontal(|ui| {
-                        ui.label("Replace:");
+                        ui.label(tr("replace-label"));
                         let replace_response = ui.add_sized(
                             egui::Vec2::new(ui.available_width(), 20.0),
                             egui::TextEdit::singleline(&mut self.replace_text)
-                                .hint_text("Enter replacement text...")
+                                .hint_text(tr("replace-hint"))
                         );
 
                         if replace_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
@@ -241,76 +287,61 @@ impl FindReplace {
                     });
 
                     ui.horizontal(|ui| {
-                        if ui.checkbox(&mut self.case_sensitive, "Match case").changed() {
+                        if ui.checkbox(&mut self.case_sensitive, tr("find-match-case")).changed() {
                             self.find_text_changed = true;
                             action = FindReplaceAction::UpdateMatches;
                         }
-                        if ui.checkbox(&mut self.use_regex, "Regex").changed() {
+                        if ui.checkbox(&mut self.use_regex, tr("find-regex")).changed() {
                             self.find_text_changed = true;
                             action = FindReplaceAction::UpdateMatches;
                         }
+                        ui.separator();
+                        for (scope, label) in [(FindScope::CurrentNote, tr("find-scope-current")), (FindScope::AllNotes, tr("find-scope-all"))] {
+                            if ui.selectable_value(&mut self.scope, scope, label).changed() {
+                                self.find_text_changed = true;
+                                action = FindReplaceAction::UpdateMatches;
+                            }
+                        }
                     });
 
                     ui.separator();
 
+                    if self.scope == FindScope::AllNotes {
+                        self.render_all_notes(ui, keymap, &mut action);
+                        return;
+                    }
+
                     ui.horizontal(|ui| {
                         let match_text = if self.matches.is_empty() {
-                            "No matches".to_string()
+                            tr("find-no-matches").to_string()
                         } else if let Some(idx) = self.current_match_index {
-                            format!("{} of {}", idx + 1, self.matches.len())
+                            tr_args("find-match-position", &[("current", &(idx + 1)), ("total", &self.matches.len())])
                         } else {
-                            format!("{} matches", self.matches.len())
+                            tr_args("find-match-count", &[("total", &self.matches.len())])
                         };
 
                         ui.label(match_text);
 
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let mut replace_all_text = egui::text::LayoutJob::default();
-                            replace_all_text.append("Replace ", 0.0, egui::TextFormat::default());
-                            replace_all_text.append("A", 0.0, egui::TextFormat {
-                                underline: egui::Stroke::new(1.0, ui.style().visuals.text_color()),
-                                ..Default::default()
-                            });
-                            replace_all_text.append("ll", 0.0, egui::TextFormat::default());
-
-                            if ui.button(replace_all_text).clicked() {
+                            if ui.button(button_label(tr("find-replace-all"), keymap, Action::ReplaceAll)).clicked() {
                                 action = FindReplaceAction::ReplaceAll;
                             }
-
-                            let mut replace_text = egui::text::LayoutJob::default();
-                            replace_text.append("R", 0.0, egui::TextFormat {
-                                underline: egui::Stroke::new(1.0, ui.style().visuals.text_color()),
-                                ..Default::default()
-                            });
-                            replace_text.append("eplace", 0.0, egui::TextFormat::default());
-
-                            if ui.button(replace_text).clicked() {
+                            if ui.button(button_label(tr("find-replace"), keymap, Action::ReplaceCurrent)).clicked() {
                                 action = FindReplaceAction::ReplaceCurrent;
                             }
-
-                            if ui.button("Previous (Shift+F3)").clicked() {
+                            if ui.button(button_label(tr("find-previous"), keymap, Action::PreviousMatch)).clicked() {
                                 action = FindReplaceAction::PreviousMatch;
                             }
-                            if ui.button("Next (F3)").clicked() {
+                            if ui.button(button_label(tr("find-next"), keymap, Action::NextMatch)).clicked() {
                                 action = FindReplaceAction::NextMatch;
                             }
                         });
                     });
                 });
 
-                ui.input_mut(|i| {
-                    if i.key_pressed(egui::Key::Escape) {
-                        close = true;
-                    }
-
-                    if i.consume_key(egui::Modifiers::ALT, egui::Key::R) {
-                        action = FindReplaceAction::ReplaceCurrent;
-                    }
-
-                    if i.consume_key(egui::Modifiers::ALT, egui::Key::A) {
-                        action = FindReplaceAction::ReplaceAll;
-                    }
-                });
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
             });
 
         if close {
@@ -324,11 +355,63 @@ impl FindReplace {
         action
     }
 
+    /// The matches per note, with the button that replaces them all, and the notes changed by
+    /// the last replace with a button to undo each.
+    fn render_all_notes(&self, ui: &mut egui::Ui, keymap: &Keymap, action: &mut FindReplaceAction) {
+        let total: usize = self.note_match_counts.iter().map(|(_, count)| count).sum();
+        ui.horizontal(|ui| {
+            if total == 0 {
+                ui.label(tr("find-no-matches"));
+            } else {
+                ui.label(tr_args("find-all-notes-count", &[("total", &total), ("notes", &self.note_match_counts.len())]));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let button = egui::Button::new(button_label(tr("find-replace-all-notes"), keymap, Action::ReplaceAll));
+                if ui.add_enabled(total > 0, button).clicked() {
+                    *action = FindReplaceAction::ReplaceAll;
+                }
+            });
+        });
+
+        if !self.note_match_counts.is_empty() {
+            egui::ScrollArea::vertical().id_salt("find_note_counts").max_height(150.0).show(ui, |ui| {
+                for (note, count) in &self.note_match_counts {
+                    ui.horizontal(|ui| {
+                        ui.label(note);
+                        ui.weak(tr_args("find-note-replacements", &[("count", count)]));
+                    });
+                }
+            });
+        }
+
+        if !self.replaced_notes.is_empty() {
+            ui.separator();
+            ui.label(tr_args("find-replaced-notes", &[("count", &self.replaced_notes.len())]));
+            egui::ScrollArea::vertical().id_salt("find_replaced_notes").max_height(150.0).show(ui, |ui| {
+                for (note, _) in &self.replaced_notes {
+                    ui.horizontal(|ui| {
+                        if ui.small_button(tr("find-undo-note")).clicked() {
+                            *action = FindReplaceAction::UndoNote(note.clone());
+                        }
+                        ui.label(note);
+                    });
+                }
+            });
+        }
+    }
+
     pub fn get_match_ranges(&self) -> Vec<(usize, usize)> {
         self.matches.iter().map(|m| (m.start, m.end)).collect()
     }
 }
 
+fn button_label(label: &str, keymap: &Keymap, action: Action) -> String {
+    match keymap.chord_for(action) {
+        Some(chord) => format!("{} ({})", label, chord.display()),
+        None => label.to_string(),
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum FindReplaceAction {
     None,
@@ -337,6 +420,8 @@ pub enum FindReplaceAction {
     PreviousMatch,
     ReplaceCurrent,
     ReplaceAll,
+    /// Put back a note changed by the last replace across all notes.
+    UndoNote(String),
 }
 
 impl Default for FindReplace {
@@ -0,0 +1,145 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::i18n::tr;
+
+/// Lets a folder be chosen by browsing for it, since the app has no native file dialog.
+pub struct FolderPicker {
+    pub show_dialog: bool,
+    /// Keeps the window apart from other pickers' windows.
+    id: &'static str,
+    dir: PathBuf,
+    subfolders: Vec<String>,
+    new_folder: String,
+    error: Option<String>,
+}
+
+impl FolderPicker {
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            show_dialog: false,
+            id,
+            dir: PathBuf::new(),
+            subfolders: Vec::new(),
+            new_folder: String::new(),
+            error: None,
+        }
+    }
+
+    /// Opens the picker at `start`, or at the nearest folder above it that exists.
+    pub fn open(&mut self, start: &Path) {
+        let home = std::env::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        let start = if start.as_os_str().is_empty() { home.as_path() } else { start };
+        let dir = start.ancestors().find(|dir| dir.is_dir()).unwrap_or(&home).to_path_buf();
+        self.new_folder.clear();
+        self.browse(dir);
+        self.show_dialog = true;
+    }
+
+    fn browse(&mut self, dir: PathBuf) {
+        self.error = None;
+        self.subfolders = match fs::read_dir(&dir) {
+            Ok(entries) => {
+                let mut subfolders: Vec<String> = entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                    .filter(|name| !name.starts_with('.'))
+                    .collect();
+                subfolders.sort_by_key(|name| name.to_lowercase());
+                subfolders
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to read '{}': {}", dir.display(), e));
+                Vec::new()
+            }
+        };
+        self.dir = dir;
+    }
+
+    fn create_folder(&mut self) {
+        let name = self.new_folder.trim();
+        let folder = self.dir.join(name);
+        match fs::create_dir(&folder) {
+            Ok(()) => {
+                self.new_folder.clear();
+                self.browse(folder);
+            }
+            Err(e) => self.error = Some(format!("Failed to create '{}': {}", folder.display(), e)),
+        }
+    }
+
+    /// Returns the folder once one is chosen.
+    pub fn render(&mut self, ctx: &egui::Context) -> Option<PathBuf> {
+        if !self.show_dialog {
+            return None;
+        }
+
+        let mut chosen = None;
+        let mut browse_to = None;
+        let mut create = false;
+        let mut open = true;
+        egui::Window::new(tr("folder-picker-title"))
+            .id(egui::Id::new(self.id))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let parent = self.dir.parent().map(Path::to_path_buf);
+                    if ui.add_enabled(parent.is_some(), egui::Button::new(tr("folder-picker-up"))).clicked() {
+                        browse_to = parent;
+                    }
+                    ui.strong(self.dir.display().to_string());
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical().id_salt((self.id, "folders")).max_height(260.0).show(ui, |ui| {
+                    if self.subfolders.is_empty() {
+                        ui.weak(tr("folder-picker-empty"));
+                    }
+                    for name in &self.subfolders {
+                        if ui.selectable_label(false, format!("{}{}", name, std::path::MAIN_SEPARATOR)).clicked() {
+                            browse_to = Some(self.dir.join(name));
+                        }
+                    }
+                });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_folder)
+                            .hint_text(tr("folder-picker-new-hint"))
+                            .desired_width(ui.available_width() - 110.0),
+                    );
+                    let named = !self.new_folder.trim().is_empty();
+                    create = ui.add_enabled(named, egui::Button::new(tr("folder-picker-create"))).clicked();
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(tr("folder-picker-choose")).clicked() {
+                        chosen = Some(self.dir.clone());
+                    }
+                    if ui.button(tr("button-cancel")).clicked() {
+                        self.show_dialog = false;
+                    }
+                });
+            });
+
+        if create {
+            self.create_folder();
+        } else if let Some(dir) = browse_to {
+            self.browse(dir);
+        }
+        if !open || chosen.is_some() {
+            self.show_dialog = false;
+        }
+
+        chosen
+    }
+}
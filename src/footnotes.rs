@@ -0,0 +1,63 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// `[^label]`, either a reference or, followed by `:` at the start of a line, a definition.
+fn footnote_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\[\^([^\]\s]+)\]").expect("footnote regex is valid"))
+}
+
+struct Footnote<'a> {
+    label: &'a str,
+    /// Byte range of the `[^label]` marker.
+    start: usize,
+    end: usize,
+    is_definition: bool,
+}
+
+fn footnotes(text: &str) -> impl Iterator<Item = Footnote<'_>> {
+    footnote_regex().captures_iter(text).filter_map(|capture| {
+        let marker = capture.get(0)?;
+        let line_start = text[..marker.start()].rfind('\n').map_or(0, |i| i + 1);
+        Some(Footnote {
+            label: capture.get(1)?.as_str(),
+            start: marker.start(),
+            end: marker.end(),
+            is_definition: text[line_start..marker.start()].trim().is_empty() && text[marker.end()..].starts_with(':'),
+        })
+    })
+}
+
+/// Inserts the next numbered `[^n]` reference at character position `cursor` and an empty
+/// `[^n]:` definition at the end of the text. Returns the new text and the character position
+/// at the end of the definition, for typing the footnote.
+pub fn insert_footnote(text: &str, cursor: usize) -> (String, usize) {
+    let number = footnotes(text).filter_map(|footnote| footnote.label.parse::<usize>().ok()).max().unwrap_or(0) + 1;
+    let byte = text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i);
+
+    let mut result = String::with_capacity(text.len() + 16);
+    result.push_str(&text[..byte]);
+    result.push_str(&format!("[^{}]", number));
+    result.push_str(&text[byte..]);
+
+    let trimmed_len = result.trim_end_matches('\n').len();
+    result.truncate(trimmed_len);
+    let last_line = result.rsplit('\n').next().unwrap_or_default();
+    let follows_definition = footnotes(last_line).any(|footnote| footnote.is_definition);
+    result.push_str(if follows_definition { "\n" } else { "\n\n" });
+    result.push_str(&format!("[^{}]: ", number));
+
+    let cursor = result.chars().count();
+    result.push('\n');
+    (result, cursor)
+}
+
+/// For a character position inside a footnote reference, the position of its definition, and
+/// for one inside a definition's marker, the position of its first reference.
+pub fn partner(text: &str, cursor: usize) -> Option<usize> {
+    let byte = text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i);
+    let clicked = footnotes(text).find(|footnote| footnote.start <= byte && byte <= footnote.end)?;
+    let target = footnotes(text).find(|footnote| footnote.label == clicked.label && footnote.is_definition != clicked.is_definition)?;
+    Some(text[..target.start].chars().count())
+}
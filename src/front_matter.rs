@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+/// Byte ranges of the front matter's lines and of the whole block, `---` fences included, when
+/// the note starts with one.
+pub fn block(content: &str) -> Option<(Range<usize>, usize)> {
+    let body_start = content.strip_prefix("---\n").map(|_| 4).or_else(|| content.strip_prefix("---\r\n").map(|_| 5))?;
+    let mut offset = body_start;
+    for line in content[body_start..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((body_start..offset, offset + line.len()));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// The value of a `key: value` line in the front matter.
+pub fn value<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    let (lines, _) = block(content)?;
+    content[lines].lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim())
+    })
+}
+
+/// The items of a front matter list, written as `key: [a, b]`, as `- a` lines under `key:`, or
+/// as `key: a, b`.
+pub fn list(content: &str, key: &str) -> Vec<String> {
+    let Some((lines, _)) = block(content) else {
+        return Vec::new();
+    };
+    let mut lines = content[lines].lines();
+    let Some(value) = lines.by_ref().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim())
+    }) else {
+        return Vec::new();
+    };
+
+    let items: Vec<&str> = if value.is_empty() {
+        lines.map_while(|line| line.trim_start().strip_prefix('-')).collect()
+    } else {
+        let value = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')).unwrap_or(value);
+        value.split(',').collect()
+    };
+    items.into_iter().map(unquote).filter(|item| !item.is_empty()).map(str::to_string).collect()
+}
+
+fn unquote(item: &str) -> &str {
+    let item = item.trim();
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| item.strip_prefix(*quote).and_then(|item| item.strip_suffix(*quote)))
+        .unwrap_or(item)
+}
+
+/// Other names a note goes by, from its `aliases` front matter, for search and `[[wikilinks]]`.
+pub fn aliases(content: &str) -> Vec<String> {
+    list(content, "aliases")
+}
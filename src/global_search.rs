@@ -0,0 +1,202 @@
+use std::ops::Range;
+
+use eframe::egui;
+use regex::{Regex, RegexBuilder};
+
+use crate::i18n::{tr, tr_args};
+use crate::theme::ThemePalette;
+
+/// Stops collecting matches past this many, so a one-letter query can't bury the panel.
+const MAX_HITS: usize = 1000;
+/// How much of a long line is shown on each side of the match, in characters.
+const CONTEXT_CHARS: usize = 40;
+
+/// A line of a note containing the query.
+pub struct SearchHit {
+    /// Zero-based line in the note.
+    pub line: usize,
+    pub text: String,
+    /// Byte range of the first match within `text`.
+    pub range: Range<usize>,
+}
+
+pub enum GlobalSearchAction {
+    None,
+    /// The query changed, so the notes need searching again.
+    Search,
+    OpenNote(String),
+    Open { note: String, line: usize, range: Range<usize> },
+}
+
+/// Searches the text of every note, listing each matching line under its note.
+pub struct GlobalSearch {
+    pub show_dialog: bool,
+    query: String,
+    case_sensitive: bool,
+    results: Vec<(String, Vec<SearchHit>)>,
+    /// Whether the results were cut off at `MAX_HITS`.
+    truncated: bool,
+    should_focus: bool,
+}
+
+impl GlobalSearch {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            query: String::new(),
+            case_sensitive: false,
+            results: Vec::new(),
+            truncated: false,
+            should_focus: false,
+        }
+    }
+
+    pub fn toggle_dialog(&mut self) {
+        self.show_dialog = !self.show_dialog;
+        self.should_focus = self.show_dialog;
+    }
+
+    /// Searches `(note name, content)` pairs for the query, notes in alphabetical order.
+    pub fn search<'a>(&mut self, notes: impl Iterator<Item = (&'a str, &'a str)>) {
+        self.results.clear();
+        self.truncated = false;
+        let Some(regex) = self.regex() else {
+            return;
+        };
+
+        let mut total = 0;
+        for (name, content) in notes {
+            let hits: Vec<SearchHit> = content
+                .lines()
+                .enumerate()
+                .filter_map(|(line, text)| {
+                    let found = regex.find(text)?;
+                    Some(SearchHit { line, text: text.to_string(), range: found.range() })
+                })
+                .take(MAX_HITS - total)
+                .collect();
+            if hits.is_empty() {
+                continue;
+            }
+            total += hits.len();
+            self.results.push((name.to_string(), hits));
+            if total >= MAX_HITS {
+                self.truncated = true;
+                break;
+            }
+        }
+        self.results.sort_by_key(|(name, _)| name.to_lowercase());
+    }
+
+    fn regex(&self) -> Option<Regex> {
+        if self.query.trim().is_empty() {
+            return None;
+        }
+        RegexBuilder::new(&regex::escape(&self.query))
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .ok()
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, palette: &ThemePalette) -> GlobalSearchAction {
+        let mut action = GlobalSearchAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        egui::Window::new(tr("global-search-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 440.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.query)
+                            .hint_text(tr("global-search-hint"))
+                            .desired_width(ui.available_width() - 120.0),
+                    );
+                    if self.should_focus {
+                        response.request_focus();
+                        self.should_focus = false;
+                    }
+                    let case_changed = ui.checkbox(&mut self.case_sensitive, tr("global-search-match-case")).changed();
+                    if response.changed() || case_changed {
+                        action = GlobalSearchAction::Search;
+                    }
+                });
+
+                let hits: usize = self.results.iter().map(|(_, hits)| hits.len()).sum();
+                if !self.query.trim().is_empty() {
+                    let count = tr_args("global-search-count", &[("matches", &hits), ("notes", &self.results.len())]);
+                    if self.truncated {
+                        ui.weak(format!("{} {}", count, tr("global-search-truncated")));
+                    } else {
+                        ui.weak(count);
+                    }
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical().auto_shrink([false, false]).id_salt("global_search_scroll").show(ui, |ui| {
+                    for (note, note_hits) in &self.results {
+                        if ui.link(egui::RichText::new(note).strong()).clicked() {
+                            action = GlobalSearchAction::OpenNote(note.clone());
+                        }
+                        for hit in note_hits {
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.weak(format!("{:>4}", hit.line + 1));
+                                let job = context_job(ui, hit, palette.match_background);
+                                let row = ui.add(egui::Label::new(job).sense(egui::Sense::click()).truncate());
+                                if row.on_hover_cursor(egui::CursorIcon::PointingHand).clicked() {
+                                    action = GlobalSearchAction::Open { note: note.clone(), line: hit.line, range: hit.range.clone() };
+                                }
+                            });
+                        }
+                        ui.add_space(6.0);
+                    }
+                    if hits == 0 && !self.query.trim().is_empty() {
+                        ui.weak(tr("global-search-none"));
+                    }
+                });
+            });
+
+        if !open {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+/// The matching line with the match highlighted, cut down to `CONTEXT_CHARS` on each side.
+fn context_job(ui: &egui::Ui, hit: &SearchHit, highlight: egui::Color32) -> egui::text::LayoutJob {
+    let before = &hit.text[..hit.range.start];
+    let after = &hit.text[hit.range.end..];
+    let before = match before.char_indices().rev().nth(CONTEXT_CHARS) {
+        Some((cut, _)) => format!("…{}", before[cut..].trim_start()),
+        None => before.trim_start().to_string(),
+    };
+    let after = match after.char_indices().nth(CONTEXT_CHARS) {
+        Some((cut, _)) => format!("{}…", &after[..cut]),
+        None => after.to_string(),
+    };
+
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let color = ui.visuals().text_color();
+    let plain = egui::TextFormat { font_id: font_id.clone(), color, ..Default::default() };
+    let matched = egui::TextFormat { font_id, color, background: highlight, ..Default::default() };
+    let mut job = egui::text::LayoutJob::default();
+    job.append(&before, 0.0, plain.clone());
+    job.append(&hit.text[hit.range.clone()], 0.0, matched);
+    job.append(&after, 0.0, plain);
+    job
+}
+
+impl Default for GlobalSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
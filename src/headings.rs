@@ -0,0 +1,67 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// A heading in a note. Headings are found with the same parser as the preview, so the n-th
+/// heading here is the n-th heading the preview draws.
+#[derive(Debug, Clone)]
+pub struct Heading {
+    /// 1 for `#`, up to 6.
+    pub level: usize,
+    pub text: String,
+    /// Zero-based line in the note.
+    pub line: usize,
+}
+
+pub fn parse_headings(markdown: &str) -> Vec<Heading> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+
+    let mut headings = Vec::new();
+    let mut current: Option<Heading> = None;
+    for (event, range) in Parser::new_ext(markdown, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some(Heading {
+                    level: heading_level(level),
+                    text: String::new(),
+                    line: markdown[..range.start].matches('\n').count(),
+                });
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = current.as_mut() {
+                    heading.text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => headings.extend(current.take()),
+            _ => {}
+        }
+    }
+    headings
+}
+
+/// Ordinals of the headings `line` falls under, outermost first. Each heading is nested under the
+/// nearest heading before it with a lower level.
+pub fn heading_path(headings: &[Heading], line: usize) -> Vec<usize> {
+    let mut path: Vec<usize> = Vec::new();
+    for (ordinal, heading) in headings.iter().enumerate().take_while(|(_, heading)| heading.line <= line) {
+        while path.last().is_some_and(|&last| headings[last].level >= heading.level) {
+            path.pop();
+        }
+        path.push(ordinal);
+    }
+    path
+}
+
+fn heading_level(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
@@ -0,0 +1,37 @@
+use pulldown_cmark::{Options, Parser};
+
+/// Stylesheet for standalone pages: readable line length, and borders on tables and code.
+pub const DOCUMENT_STYLE: &str = "body { font-family: sans-serif; line-height: 1.5; max-width: 48em; margin: 0 auto; padding: 1em; }
+pre, code { background: #f0f0f0; border-radius: 3px; }
+pre { padding: 0.5em; overflow-x: auto; }
+img { max-width: 100%; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #ccc; padding: 0.25em 0.5em; }";
+
+/// Renders markdown to HTML with the same extensions as the preview. Used wherever a note leaves
+/// the app as HTML: Copy as HTML, File → Serve Note, and HTML export.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, Parser::new_ext(markdown, options));
+    html
+}
+
+/// A complete HTML page for the note, as written by File → Export Note.
+pub fn html_document(title: &str, markdown: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        DOCUMENT_STYLE,
+        markdown_to_html(markdown)
+    )
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,576 @@
+/// An HTML tag as written, without any checking that it's balanced. Also used to read OPML.
+pub struct Tag {
+    pub name: String,
+    pub closing: bool,
+    /// Written as `<name ... />`, so no closing tag follows.
+    pub self_closing: bool,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Tag {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// The value of one CSS property in the `style` attribute, lowercased without spaces.
+    fn style(&self, property: &str) -> Option<String> {
+        self.attribute("style")?.split(';').find_map(|declaration| {
+            let (key, value) = declaration.split_once(':')?;
+            (key.trim().eq_ignore_ascii_case(property)).then(|| value.trim().to_lowercase().replace(' ', ""))
+        })
+    }
+}
+
+pub enum Token {
+    Tag(Tag),
+    Text(String),
+}
+
+/// Elements that never have a closing tag.
+const VOID_ELEMENTS: [&str; 7] = ["br", "hr", "img", "input", "wbr", "meta", "link"];
+/// Elements whose content isn't visible text.
+const HIDDEN_ELEMENTS: [&str; 6] = ["script", "style", "head", "title", "template", "noscript"];
+
+/// An element that's open while converting, with what to write when it closes.
+struct Element {
+    name: String,
+    /// Opening and closing markdown for inline formatting, like `**` for bold.
+    inline: Option<(String, String)>,
+    /// Whitespace inside is kept as written: `<pre>` and `white-space: pre`.
+    preserve_whitespace: bool,
+}
+
+struct List {
+    ordered: bool,
+    next_number: usize,
+    /// Indentation of the item containing this list.
+    parent_indent: String,
+    /// Indentation that lines up with the text of the current item.
+    item_indent: String,
+}
+
+struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+/// Converts HTML, as browsers and word processors put on the clipboard, into markdown. Headings,
+/// paragraphs, lists, links, images, emphasis, code, quotes, and tables are kept; other markup is
+/// dropped and only its text remains.
+pub fn html_to_markdown(html: &str) -> String {
+    // Windows clipboard HTML wraps the copied part in markers inside a larger document.
+    let html = match (html.find("<!--StartFragment-->"), html.find("<!--EndFragment-->")) {
+        (Some(start), Some(end)) if start < end => &html[start + "<!--StartFragment-->".len()..end],
+        _ => html,
+    };
+
+    let mut converter = Converter::default();
+    for token in tokenize(html) {
+        match token {
+            Token::Tag(tag) if tag.closing => converter.close_tag(&tag.name),
+            Token::Tag(tag) => converter.open_tag(&tag),
+            Token::Text(text) => converter.text(&decode_entities(&text)),
+        }
+    }
+    while let Some(element) = converter.elements.pop() {
+        converter.close_element(element);
+    }
+    converter.out.trim_end().to_string()
+}
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    elements: Vec<Element>,
+    lists: Vec<List>,
+    table: Option<Table>,
+    /// Text of the table cell being read; everything goes here instead of `out` while it's set.
+    cell: Option<String>,
+    quote_depth: usize,
+    hidden_depth: usize,
+    /// Line breaks owed before the next text: 1 starts a new line, 2 leaves a blank line.
+    pending_newlines: usize,
+    /// List marker to start the next line with, like `- ` or `2. `.
+    pending_item: Option<String>,
+    /// Opening inline markers not written yet, so they land right before the text instead of
+    /// before a space or around nothing.
+    pending_inline: String,
+    at_line_start: bool,
+}
+
+impl Converter {
+    fn open_tag(&mut self, tag: &Tag) {
+        match tag.name.as_str() {
+            "br" => {
+                if let Some(cell) = &mut self.cell {
+                    cell.push(' ');
+                } else {
+                    self.pending_newlines = (self.pending_newlines + 1).min(2);
+                }
+                return;
+            }
+            "hr" => {
+                self.block(2);
+                self.begin_write();
+                self.out.push_str("---");
+                self.block(2);
+                return;
+            }
+            "img" => {
+                let src = tag.attribute("src").unwrap_or_default();
+                if !src.is_empty() && self.hidden_depth == 0 {
+                    self.begin_write();
+                    let image = format!("![{}]({})", tag.attribute("alt").unwrap_or_default(), src);
+                    self.target().push_str(&image);
+                }
+                return;
+            }
+            "input" => {
+                if tag.attribute("type").is_some_and(|kind| kind.eq_ignore_ascii_case("checkbox")) {
+                    self.begin_write();
+                    let checkbox = if tag.attribute("checked").is_some() { "[x] " } else { "[ ] " };
+                    self.target().push_str(checkbox);
+                }
+                return;
+            }
+            name if VOID_ELEMENTS.contains(&name) => return,
+            _ => {}
+        }
+
+        let preserve_whitespace =
+            tag.name == "pre" || tag.style("white-space").is_some_and(|value| value.starts_with("pre"));
+        let mut inline = None;
+        match tag.name.as_str() {
+            name if HIDDEN_ELEMENTS.contains(&name) => self.hidden_depth += 1,
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = tag.name[1..].parse().unwrap_or(1);
+                self.block(2);
+                self.begin_write();
+                self.out.push_str(&format!("{} ", "#".repeat(level)));
+            }
+            "p" => self.block(2),
+            "div" | "section" | "article" | "header" | "footer" | "main" | "nav" | "aside" | "figure" | "dt" | "dd" => {
+                self.block(1)
+            }
+            "blockquote" => {
+                self.block(2);
+                self.quote_depth += 1;
+            }
+            "pre" => {
+                self.block(2);
+                self.begin_write();
+                self.out.push_str("```");
+                self.pending_newlines = 1;
+            }
+            "ul" | "ol" => {
+                self.block(if self.lists.is_empty() { 2 } else { 1 });
+                let parent_indent = self.lists.last().map(|list| list.item_indent.clone()).unwrap_or_default();
+                self.lists.push(List {
+                    ordered: tag.name == "ol",
+                    next_number: tag.attribute("start").and_then(|start| start.parse().ok()).unwrap_or(1),
+                    item_indent: parent_indent.clone(),
+                    parent_indent,
+                });
+            }
+            "li" => {
+                self.block(1);
+                if let Some(list) = self.lists.last_mut() {
+                    let marker = if list.ordered {
+                        list.next_number += 1;
+                        format!("{}. ", list.next_number - 1)
+                    } else {
+                        "- ".to_string()
+                    };
+                    list.item_indent = format!("{}{}", list.parent_indent, " ".repeat(marker.len()));
+                    self.pending_item = Some(format!("{}{}", list.parent_indent, marker));
+                }
+            }
+            "table" if self.table.is_none() => {
+                self.block(2);
+                self.table = Some(Table { rows: Vec::new() });
+            }
+            "tr" => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                }
+            }
+            "td" | "th" if self.table.is_some() && self.cell.is_none() => self.cell = Some(String::new()),
+            "a" => {
+                if let Some(href) = tag.attribute("href").filter(|href| !href.is_empty()) {
+                    inline = Some(("[".to_string(), format!("]({})", href)));
+                }
+            }
+            // Google Docs wraps whole documents in `<b style="font-weight:normal">`.
+            "strong" | "b" if !tag.style("font-weight").is_some_and(|weight| weight == "normal" || weight == "400") => {
+                inline = Some(("**".to_string(), "**".to_string()))
+            }
+            "em" | "i" => inline = Some(("*".to_string(), "*".to_string())),
+            "del" | "s" | "strike" => inline = Some(("~~".to_string(), "~~".to_string())),
+            "code" if !self.preserving_whitespace() => inline = Some(("`".to_string(), "`".to_string())),
+            "span" => {
+                let bold = tag.style("font-weight").is_some_and(|weight| weight == "bold" || weight.parse::<u32>().is_ok_and(|w| w >= 600));
+                let italic = tag.style("font-style").is_some_and(|style| style == "italic");
+                let marker = match (bold, italic) {
+                    (true, true) => Some("***"),
+                    (true, false) => Some("**"),
+                    (false, true) => Some("*"),
+                    (false, false) => None,
+                };
+                inline = marker.map(|marker| (marker.to_string(), marker.to_string()));
+            }
+            _ => {}
+        }
+
+        if let Some((open, _)) = &inline {
+            self.pending_inline.push_str(open);
+        }
+        self.elements.push(Element {
+            name: tag.name.clone(),
+            inline,
+            preserve_whitespace,
+        });
+    }
+
+    /// Closes the most recent element named `name` along with anything left open inside it.
+    /// Closing tags that don't match an open element are ignored.
+    fn close_tag(&mut self, name: &str) {
+        let Some(position) = self.elements.iter().rposition(|element| element.name == name) else {
+            return;
+        };
+        while self.elements.len() > position {
+            if let Some(element) = self.elements.pop() {
+                self.close_element(element);
+            }
+        }
+    }
+
+    fn close_element(&mut self, element: Element) {
+        if let Some((open, close)) = element.inline {
+            if self.pending_inline.ends_with(&open) {
+                self.pending_inline.truncate(self.pending_inline.len() - open.len());
+            } else {
+                let target = self.target();
+                let trailing_space = target.ends_with(' ');
+                if trailing_space {
+                    target.pop();
+                }
+                target.push_str(&close);
+                if trailing_space {
+                    target.push(' ');
+                }
+            }
+        }
+
+        match element.name.as_str() {
+            name if HIDDEN_ELEMENTS.contains(&name) => self.hidden_depth = self.hidden_depth.saturating_sub(1),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" => self.block(2),
+            "div" | "section" | "article" | "header" | "footer" | "main" | "nav" | "aside" | "figure" | "dt" | "dd" => {
+                self.block(1)
+            }
+            "blockquote" => {
+                self.block(2);
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            "pre" => {
+                // Drop trailing blank lines, including ones that are only quote markers.
+                loop {
+                    let trimmed = self.out.trim_end_matches([' ', '\n']).len();
+                    self.out.truncate(trimmed);
+                    if self.quote_depth == 0 || !self.out.ends_with("\n>") {
+                        break;
+                    }
+                    self.out.truncate(self.out.len() - 2);
+                }
+                self.pending_newlines = 1;
+                self.begin_write();
+                self.out.push_str("```");
+                self.block(2);
+            }
+            "ul" | "ol" => {
+                self.lists.pop();
+                self.block(if self.lists.is_empty() { 2 } else { 1 });
+            }
+            "li" => {
+                self.pending_item = None;
+                self.block(1);
+            }
+            "td" | "th" => {
+                if let (Some(cell), Some(table)) = (self.cell.take(), &mut self.table) {
+                    let cell = cell.trim().replace('|', "\\|");
+                    match table.rows.last_mut() {
+                        Some(row) => row.push(cell),
+                        None => table.rows.push(vec![cell]),
+                    }
+                }
+            }
+            "table" => {
+                if let Some(table) = self.table.take() {
+                    self.write_table(table);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.hidden_depth > 0 {
+            return;
+        }
+
+        if self.preserving_whitespace() && self.cell.is_none() {
+            if text.is_empty() {
+                return;
+            }
+            // Like browsers, drop the line break right after `<pre>`.
+            let text = if self.out.ends_with("```") && self.pending_newlines == 1 {
+                text.strip_prefix('\n').unwrap_or(text)
+            } else {
+                text
+            };
+            self.begin_write();
+            let inline = std::mem::take(&mut self.pending_inline);
+            self.out.push_str(&inline);
+            let prefix = self.line_prefix();
+            self.out.push_str(&text.replace('\n', &format!("\n{}", prefix)));
+            return;
+        }
+
+        let collapsed = text.split_ascii_whitespace().collect::<Vec<_>>().join(" ");
+        let leading_space = text.starts_with(|c: char| c.is_ascii_whitespace());
+        let trailing_space = text.ends_with(|c: char| c.is_ascii_whitespace());
+        if (leading_space || collapsed.is_empty()) && self.mid_line() && !self.target().ends_with(' ') {
+            self.target().push(' ');
+        }
+        if collapsed.is_empty() {
+            return;
+        }
+
+        self.begin_write();
+        let inline = std::mem::take(&mut self.pending_inline);
+        let target = self.target();
+        target.push_str(&inline);
+        target.push_str(&collapsed);
+        if trailing_space {
+            target.push(' ');
+        }
+    }
+
+    fn write_table(&mut self, table: Table) {
+        let rows: Vec<Vec<String>> = table.rows.into_iter().filter(|row| !row.is_empty()).collect();
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let row_line = |row: &[String]| {
+            let cells: Vec<&str> = (0..columns).map(|i| row.get(i).map_or("", String::as_str)).collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![row_line(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+        lines.extend(rows[1..].iter().map(|row| row_line(row)));
+
+        self.block(2);
+        self.begin_write();
+        let separator = format!("\n{}", self.line_prefix());
+        self.out.push_str(&lines.join(&separator));
+        self.block(2);
+    }
+
+    /// Asks for at least `newlines` line breaks before the next text.
+    fn block(&mut self, newlines: usize) {
+        if self.cell.is_none() {
+            self.pending_newlines = self.pending_newlines.max(newlines);
+        }
+    }
+
+    /// Writes any owed line breaks and the start of the line (quote markers, list marker, or
+    /// indentation) before text is added.
+    fn begin_write(&mut self) {
+        if self.cell.is_some() {
+            return;
+        }
+        if self.pending_newlines > 0 && !self.out.is_empty() {
+            let trimmed = self.out.trim_end_matches(' ').len();
+            self.out.truncate(trimmed);
+            let quote = "> ".repeat(self.quote_depth);
+            for _ in 1..self.pending_newlines {
+                self.out.push('\n');
+                self.out.push_str(quote.trim_end());
+            }
+            self.out.push('\n');
+            self.at_line_start = true;
+        }
+        self.pending_newlines = 0;
+        if self.at_line_start || self.out.is_empty() {
+            self.out.push_str(&"> ".repeat(self.quote_depth));
+            match self.pending_item.take() {
+                Some(marker) => self.out.push_str(&marker),
+                None => self.out.push_str(&self.item_indent()),
+            }
+            self.at_line_start = false;
+        }
+    }
+
+    /// What continuation lines start with: quote markers and the current list item's indentation.
+    fn line_prefix(&self) -> String {
+        format!("{}{}", "> ".repeat(self.quote_depth), self.item_indent())
+    }
+
+    fn item_indent(&self) -> String {
+        self.lists.last().map(|list| list.item_indent.clone()).unwrap_or_default()
+    }
+
+    fn preserving_whitespace(&self) -> bool {
+        self.elements.iter().any(|element| element.preserve_whitespace)
+    }
+
+    /// Whether there's text on the current line, so a space between words is needed.
+    fn mid_line(&self) -> bool {
+        match &self.cell {
+            Some(cell) => !cell.is_empty(),
+            None => !self.out.is_empty() && !self.at_line_start && self.pending_newlines == 0,
+        }
+    }
+
+    fn target(&mut self) -> &mut String {
+        match &mut self.cell {
+            Some(cell) => cell,
+            None => &mut self.out,
+        }
+    }
+}
+
+/// Splits HTML into tags and text. Comments, doctypes, and processing instructions are skipped; a
+/// `<` that doesn't start a tag is kept as text.
+pub fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+            continue;
+        }
+        let starts_tag = rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || "/!?".contains(c));
+        if starts_tag {
+            let end = tag_end(rest);
+            let inner = rest[1..end].strip_suffix('>').unwrap_or(&rest[1..end]);
+            rest = &rest[end..];
+            if inner.starts_with(['!', '?']) {
+                continue;
+            }
+            match parse_tag(inner) {
+                Some(tag) => tokens.push(Token::Tag(tag)),
+                None => tokens.push(Token::Text(format!("<{}>", inner))),
+            }
+            continue;
+        }
+        let end = rest[1..].find('<').map_or(rest.len(), |end| end + 1);
+        tokens.push(Token::Text(rest[..end].to_string()));
+        rest = &rest[end..];
+    }
+    tokens
+}
+
+/// Byte offset just past the `>` closing the tag at the start of `html`, skipping any `>` inside
+/// quoted attribute values.
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in html.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return i + 1,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+fn parse_tag(inner: &str) -> Option<Tag> {
+    let self_closing = inner.trim_end().ends_with('/');
+    let inner = inner.trim_end_matches('/').trim();
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, inner),
+    };
+    let name_end = inner.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(inner.len());
+    let name = inner[..name_end].to_ascii_lowercase();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut attributes = Vec::new();
+    let mut rest = &inner[name_end..];
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            break;
+        }
+        let key_end = rest.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let end = after[1..].find(quote).map_or(after.len(), |end| end + 1);
+                    rest = after.get(end + 1..).unwrap_or("");
+                    &after[1..end]
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    rest = &after[end..];
+                    &after[..end]
+                }
+            }
+        } else {
+            ""
+        };
+        attributes.push((key, decode_entities(value)));
+    }
+
+    Some(Tag {
+        name,
+        closing,
+        self_closing,
+        attributes,
+    })
+}
+
+/// Replaces character references like `&amp;` and `&#8217;`. Unknown named ones are left as is.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest[1..].find(';').filter(|&end| end <= 10).map(|end| &rest[1..end + 1]);
+        let character = reference.and_then(|reference| match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let number = reference.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (reference, character) {
+            (Some(reference), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// UI language. Strings live in `locales/<code>.ftl`, one `key = value` per line, with
+/// `{ $name }` placeholders; a key missing from a locale falls back to English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The language's name in that language, so it can be found from any locale.
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Language::English => 0,
+            Language::German => 1,
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+        }
+    }
+
+    fn messages(&self) -> &'static HashMap<&'static str, &'static str> {
+        static TABLES: [OnceLock<HashMap<&'static str, &'static str>>; Language::ALL.len()] =
+            [OnceLock::new(), OnceLock::new()];
+        TABLES[self.index()].get_or_init(|| parse_messages(self.source()))
+    }
+}
+
+static CURRENT_LANGUAGE: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT_LANGUAGE.store(language.index(), Ordering::Relaxed);
+}
+
+pub fn current_language() -> Language {
+    let index = CURRENT_LANGUAGE.load(Ordering::Relaxed);
+    Language::ALL.get(index).copied().unwrap_or_default()
+}
+
+/// Looks up a UI string in the current language, showing the key itself if no locale defines it.
+pub fn tr(key: &'static str) -> &'static str {
+    current_language()
+        .messages()
+        .get(key)
+        .or_else(|| Language::English.messages().get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// Looks up a UI string and fills in its `{ $name }` placeholders.
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${} }}", name), &value.to_string());
+    }
+    text
+}
+
+fn parse_messages(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
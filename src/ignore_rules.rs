@@ -0,0 +1,91 @@
+use regex::Regex;
+
+/// One `ignore_patterns` entry, compiled.
+#[derive(Clone)]
+struct IgnoreRule {
+    regex: Regex,
+    /// A pattern ending in `/` only matches folders.
+    folders_only: bool,
+}
+
+/// Gitignore-style patterns for files and folders in the notes folder to leave out of the
+/// notes list. A pattern without a `/` matches names at any depth, e.g. `*.tmp` or
+/// `.obsidian/`; one with a `/` matches paths from the top of the notes folder, e.g.
+/// `archive/2019/` or `/drafts/*.md`. `*` and `?` don't match `/`, `**` matches across folders,
+/// and `[abc]` matches one of the characters. Empty lines and lines starting with `#` are skipped.
+#[derive(Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Patterns that don't compile are left out; `validate` says what's wrong with them.
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            rules: patterns.iter().filter_map(|pattern| compile(pattern).ok().flatten()).collect(),
+        }
+    }
+
+    pub fn validate(pattern: &str) -> Result<(), String> {
+        compile(pattern).map(|_| ())
+    }
+
+    /// Whether a file or folder, by its path relative to the notes folder with `/` between
+    /// folders, is ignored.
+    pub fn is_ignored(&self, path: &str, is_folder: bool) -> bool {
+        self.rules.iter().any(|rule| (is_folder || !rule.folders_only) && rule.regex.is_match(path))
+    }
+}
+
+fn compile(pattern: &str) -> Result<Option<IgnoreRule>, String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return Ok(None);
+    }
+    let folders_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+
+    let mut regex = String::from(if anchored { "^" } else { "(?:^|/)" });
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let mut class = String::from("[");
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    class.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' {
+                        class.push('\\');
+                    }
+                    class.push(c);
+                }
+                class.push(']');
+                regex.push_str(&class);
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex)
+        .map(|regex| Some(IgnoreRule { regex, folders_only }))
+        .map_err(|e| format!("Invalid ignore pattern '{}': {}", pattern, e))
+}
@@ -0,0 +1,476 @@
+use std::collections::BTreeMap;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    NewNote,
+    CopyNote,
+    DeleteNote,
+    OpenSettings,
+    Undo,
+    Redo,
+    InsertListItem,
+    InsertCheckbox,
+    InsertDate,
+    InsertTime,
+    InsertFootnote,
+    InsertTableOfContents,
+    PastePlainText,
+    CopyAsHtml,
+    FindReplace,
+    NextMatch,
+    PreviousMatch,
+    ReplaceCurrent,
+    ReplaceAll,
+    GlobalSearch,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    ToggleSidebar,
+    TogglePreview,
+    ZenMode,
+    FocusMode,
+    OpenInNewWindow,
+    ShowShortcuts,
+    Present,
+    TodaysNote,
+    DailyNotesCalendar,
+    ShowTasks,
+    NextNote,
+    PreviousNote,
+    RenameNote,
+    FocusNoteSearch,
+    FocusEditor,
+    ReopenClosedNote,
+    NoteHistory,
+    NextRecentNote,
+    PreviousRecentNote,
+    AlwaysOnTop,
+    ToggleScratchpad,
+    HideNotes,
+}
+
+impl Action {
+    pub const ALL: [Action; 45] = [
+        Action::NewNote,
+        Action::CopyNote,
+        Action::DeleteNote,
+        Action::OpenSettings,
+        Action::Undo,
+        Action::Redo,
+        Action::InsertListItem,
+        Action::InsertCheckbox,
+        Action::InsertDate,
+        Action::InsertTime,
+        Action::InsertFootnote,
+        Action::InsertTableOfContents,
+        Action::PastePlainText,
+        Action::CopyAsHtml,
+        Action::FindReplace,
+        Action::NextMatch,
+        Action::PreviousMatch,
+        Action::ReplaceCurrent,
+        Action::ReplaceAll,
+        Action::GlobalSearch,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ResetZoom,
+        Action::ToggleSidebar,
+        Action::TogglePreview,
+        Action::ZenMode,
+        Action::FocusMode,
+        Action::OpenInNewWindow,
+        Action::ShowShortcuts,
+        Action::Present,
+        Action::TodaysNote,
+        Action::DailyNotesCalendar,
+        Action::ShowTasks,
+        Action::NextNote,
+        Action::PreviousNote,
+        Action::RenameNote,
+        Action::FocusNoteSearch,
+        Action::FocusEditor,
+        Action::ReopenClosedNote,
+        Action::NoteHistory,
+        Action::NextRecentNote,
+        Action::PreviousRecentNote,
+        Action::AlwaysOnTop,
+        Action::ToggleScratchpad,
+        Action::HideNotes,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::NewNote => tr("action-new-note"),
+            Action::CopyNote => tr("action-copy-note"),
+            Action::DeleteNote => tr("action-delete-note"),
+            Action::OpenSettings => tr("action-open-settings"),
+            Action::Undo => tr("action-undo"),
+            Action::Redo => tr("action-redo"),
+            Action::InsertListItem => tr("action-insert-list-item"),
+            Action::InsertCheckbox => tr("action-insert-checkbox"),
+            Action::InsertDate => tr("action-insert-date"),
+            Action::InsertTime => tr("action-insert-time"),
+            Action::InsertFootnote => tr("action-insert-footnote"),
+            Action::InsertTableOfContents => tr("action-insert-table-of-contents"),
+            Action::PastePlainText => tr("action-paste-plain-text"),
+            Action::CopyAsHtml => tr("action-copy-as-html"),
+            Action::FindReplace => tr("action-find-replace"),
+            Action::NextMatch => tr("action-next-match"),
+            Action::PreviousMatch => tr("action-previous-match"),
+            Action::ReplaceCurrent => tr("action-replace-current"),
+            Action::ReplaceAll => tr("action-replace-all"),
+            Action::GlobalSearch => tr("action-global-search"),
+            Action::ZoomIn => tr("action-zoom-in"),
+            Action::ZoomOut => tr("action-zoom-out"),
+            Action::ResetZoom => tr("action-reset-zoom"),
+            Action::ToggleSidebar => tr("action-toggle-sidebar"),
+            Action::TogglePreview => tr("action-toggle-preview"),
+            Action::ZenMode => tr("action-zen-mode"),
+            Action::FocusMode => tr("action-focus-mode"),
+            Action::OpenInNewWindow => tr("action-open-in-new-window"),
+            Action::ShowShortcuts => tr("action-show-shortcuts"),
+            Action::Present => tr("action-present"),
+            Action::TodaysNote => tr("action-todays-note"),
+            Action::DailyNotesCalendar => tr("action-daily-notes-calendar"),
+            Action::ShowTasks => tr("action-show-tasks"),
+            Action::NextNote => tr("action-next-note"),
+            Action::PreviousNote => tr("action-previous-note"),
+            Action::RenameNote => tr("action-rename-note"),
+            Action::FocusNoteSearch => tr("action-focus-note-search"),
+            Action::FocusEditor => tr("action-focus-editor"),
+            Action::ReopenClosedNote => tr("action-reopen-closed-note"),
+            Action::NoteHistory => tr("action-note-history"),
+            Action::NextRecentNote => tr("action-next-recent-note"),
+            Action::PreviousRecentNote => tr("action-previous-recent-note"),
+            Action::AlwaysOnTop => tr("action-always-on-top"),
+            Action::ToggleScratchpad => tr("action-toggle-scratchpad"),
+            Action::HideNotes => tr("action-hide-notes"),
+        }
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self {
+            Action::NewNote
+            | Action::CopyNote
+            | Action::DeleteNote
+            | Action::OpenSettings
+            | Action::OpenInNewWindow
+            | Action::TodaysNote
+            | Action::DailyNotesCalendar
+            | Action::RenameNote
+            | Action::ReopenClosedNote
+            | Action::NoteHistory => tr("category-file"),
+            Action::NextNote
+            | Action::PreviousNote
+            | Action::NextRecentNote
+            | Action::PreviousRecentNote
+            | Action::FocusNoteSearch
+            | Action::FocusEditor => tr("category-navigation"),
+            Action::Undo
+            | Action::Redo
+            | Action::InsertListItem
+            | Action::InsertCheckbox
+            | Action::InsertDate
+            | Action::InsertTime
+            | Action::InsertFootnote
+            | Action::InsertTableOfContents
+            | Action::PastePlainText
+            | Action::CopyAsHtml => tr("category-editing"),
+            Action::FindReplace
+            | Action::NextMatch
+            | Action::PreviousMatch
+            | Action::ReplaceCurrent
+            | Action::ReplaceAll
+            | Action::GlobalSearch => tr("category-find-replace"),
+            Action::ZoomIn
+            | Action::ZoomOut
+            | Action::ResetZoom
+            | Action::ToggleSidebar
+            | Action::TogglePreview
+            | Action::ZenMode
+            | Action::FocusMode
+            | Action::ShowShortcuts
+            | Action::Present
+            | Action::ShowTasks
+            | Action::AlwaysOnTop
+            | Action::ToggleScratchpad
+            | Action::HideNotes => tr("category-view"),
+        }
+    }
+
+    pub fn default_chord(&self) -> &'static str {
+        match self {
+            Action::NewNote => "Ctrl+N",
+            Action::CopyNote => "Ctrl+C",
+            Action::DeleteNote => "Ctrl+D",
+            Action::OpenSettings => "Ctrl+,",
+            Action::Undo => "Ctrl+Z",
+            Action::Redo => "Ctrl+Y",
+            Action::InsertListItem => "Ctrl+L",
+            Action::InsertCheckbox => "Ctrl+.",
+            Action::InsertDate => "Ctrl+Shift+D",
+            Action::InsertTime => "Alt+T",
+            Action::InsertFootnote => "Ctrl+Alt+N",
+            Action::InsertTableOfContents => "Ctrl+Alt+O",
+            Action::PastePlainText => "Ctrl+Alt+V",
+            Action::CopyAsHtml => "Ctrl+Shift+H",
+            Action::FindReplace => "Ctrl+F",
+            Action::NextMatch => "F3",
+            Action::PreviousMatch => "Shift+F3",
+            Action::ReplaceCurrent => "Alt+R",
+            Action::ReplaceAll => "Alt+A",
+            Action::GlobalSearch => "Ctrl+Shift+F",
+            Action::ZoomIn => "Ctrl+=",
+            Action::ZoomOut => "Ctrl+-",
+            Action::ResetZoom => "Ctrl+0",
+            Action::ToggleSidebar => "Ctrl+\\",
+            Action::TogglePreview => "Ctrl+Shift+V",
+            Action::ZenMode => "F11",
+            Action::FocusMode => "F8",
+            Action::OpenInNewWindow => "Ctrl+Shift+N",
+            Action::ShowShortcuts => "F1",
+            Action::Present => "F5",
+            Action::TodaysNote => "Ctrl+T",
+            Action::DailyNotesCalendar => "Ctrl+Shift+C",
+            Action::ShowTasks => "Ctrl+Shift+K",
+            Action::NextNote => "Ctrl+PageDown",
+            Action::PreviousNote => "Ctrl+PageUp",
+            Action::RenameNote => "F2",
+            Action::FocusNoteSearch => "Ctrl+K",
+            Action::FocusEditor => "Ctrl+Shift+E",
+            Action::ReopenClosedNote => "Ctrl+Shift+T",
+            Action::NoteHistory => "Ctrl+Alt+H",
+            Action::NextRecentNote => "Ctrl+Tab",
+            Action::PreviousRecentNote => "Ctrl+Shift+Tab",
+            Action::AlwaysOnTop => "Ctrl+Shift+P",
+            Action::ToggleScratchpad => "Ctrl+J",
+            Action::HideNotes => "Ctrl+Shift+L",
+        }
+    }
+}
+
+/// Ready-made sets of bindings that fill in the whole keymap at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeymapPreset {
+    Default,
+    VsCode,
+    VimLite,
+    MacOs,
+}
+
+impl KeymapPreset {
+    pub const ALL: [KeymapPreset; 4] = [KeymapPreset::Default, KeymapPreset::VsCode, KeymapPreset::VimLite, KeymapPreset::MacOs];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeymapPreset::Default => tr("keymap-preset-default"),
+            KeymapPreset::VsCode => tr("keymap-preset-vscode"),
+            KeymapPreset::VimLite => tr("keymap-preset-vim-lite"),
+            KeymapPreset::MacOs => tr("keymap-preset-macos"),
+        }
+    }
+
+    /// The chord this preset uses for an action; anything a preset doesn't mention keeps its default.
+    /// Chords are consumed before the editor sees them, so presets stay off text-editing keys
+    /// like Ctrl+Delete and Ctrl+Backspace, which would otherwise delete the note mid-word.
+    pub fn chord(&self, action: Action) -> &'static str {
+        let chord = match self {
+            KeymapPreset::Default => None,
+            KeymapPreset::VsCode => match action {
+                Action::Redo => Some("Ctrl+Shift+Z"),
+                Action::FindReplace => Some("Ctrl+H"),
+                Action::ReplaceAll => Some("Ctrl+Alt+Enter"),
+                Action::ToggleSidebar => Some("Ctrl+B"),
+                _ => None,
+            },
+            KeymapPreset::VimLite => match action {
+                Action::NewNote => Some("Alt+O"),
+                Action::CopyNote => Some("Alt+Y"),
+                Action::DeleteNote => Some("Alt+D"),
+                Action::Undo => Some("Alt+U"),
+                Action::Redo => Some("Ctrl+R"),
+                Action::InsertListItem => Some("Alt+L"),
+                Action::InsertCheckbox => Some("Alt+X"),
+                Action::FindReplace => Some("Ctrl+/"),
+                Action::NextMatch => Some("Alt+N"),
+                Action::PreviousMatch => Some("Alt+Shift+N"),
+                Action::ToggleSidebar => Some("Alt+E"),
+                _ => None,
+            },
+            KeymapPreset::MacOs => match action {
+                Action::Redo => Some("Ctrl+Shift+Z"),
+                Action::FindReplace => Some("Ctrl+Alt+F"),
+                Action::NextMatch => Some("Ctrl+G"),
+                Action::PreviousMatch => Some("Ctrl+Shift+G"),
+                Action::ReplaceCurrent => Some("Ctrl+Alt+R"),
+                Action::ReplaceAll => Some("Ctrl+Alt+A"),
+                Action::ToggleSidebar => Some("Ctrl+Alt+S"),
+                Action::ZenMode => Some("Ctrl+Shift+Enter"),
+                _ => None,
+            },
+        };
+        chord.unwrap_or_else(|| action.default_chord())
+    }
+}
+
+/// A key plus modifiers. `Ctrl` matches both Ctrl and Cmd so bindings work the same on macOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub key: egui::Key,
+}
+
+impl KeyChord {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (modifier_text, key_text) = if let Some(stripped) = text.strip_suffix("++") {
+            (stripped, "+")
+        } else {
+            match text.rfind('+') {
+                Some(pos) => (&text[..pos], &text[pos + 1..]),
+                None => ("", text),
+            }
+        };
+
+        let mut chord = KeyChord {
+            ctrl: false,
+            shift: false,
+            alt: false,
+            key: egui::Key::from_name(key_text.trim())
+                .ok_or_else(|| format!("unknown key '{}'", key_text.trim()))?,
+        };
+
+        for modifier in modifier_text.split('+').map(|m| m.trim()).filter(|m| !m.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" | "command" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" | "option" => chord.alt = true,
+                other => return Err(format!("unknown modifier '{}'", other)),
+            }
+        }
+
+        Ok(chord)
+    }
+
+    fn modifier_count(&self) -> usize {
+        self.ctrl as usize + self.shift as usize + self.alt as usize
+    }
+
+    fn modifiers(&self) -> egui::Modifiers {
+        egui::Modifiers {
+            alt: self.alt,
+            shift: self.shift,
+            ..Default::default()
+        }
+    }
+
+    pub fn consume(&self, input: &mut egui::InputState) -> bool {
+        // egui-winit turns Ctrl+V with any other modifiers into a paste event without a key press.
+        if self.ctrl && self.key == egui::Key::V {
+            if input.modifiers.shift != self.shift || input.modifiers.alt != self.alt {
+                return false;
+            }
+            let paste = input.events.iter().position(|event| matches!(event, egui::Event::Paste(_)));
+            return paste.map(|index| input.events.remove(index)).is_some();
+        }
+        if self.ctrl {
+            input.consume_key(self.modifiers() | egui::Modifiers::CTRL, self.key)
+                || input.consume_key(self.modifiers() | egui::Modifiers::MAC_CMD, self.key)
+        } else {
+            input.consume_key(self.modifiers(), self.key)
+        }
+    }
+
+    pub fn display(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("Ctrl");
+        }
+        if self.shift {
+            parts.push("Shift");
+        }
+        if self.alt {
+            parts.push("Alt");
+        }
+        parts.push(self.key.symbol_or_name());
+        parts.join("+")
+    }
+}
+
+/// Resolved bindings: defaults overlaid with the user's `[keymap]` overrides from config.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, KeyChord)>,
+}
+
+impl Keymap {
+    /// Builds the keymap, returning any invalid or conflicting bindings as error messages.
+    pub fn from_overrides(overrides: &BTreeMap<Action, String>) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut bindings: Vec<(Action, KeyChord)> = Vec::new();
+
+        for action in Action::ALL {
+            let default_chord = KeyChord::parse(action.default_chord()).expect("default keybinding must parse");
+            let chord = match overrides.get(&action) {
+                Some(text) if text.trim().is_empty() => continue,
+                Some(text) => match KeyChord::parse(text) {
+                    Ok(chord) => chord,
+                    Err(e) => {
+                        errors.push(format!("Keybinding for '{}' is invalid ({}), using {}", action.label(), e, action.default_chord()));
+                        default_chord
+                    }
+                },
+                None => default_chord,
+            };
+
+            if let Some((other, _)) = bindings.iter().find(|(_, c)| *c == chord) {
+                errors.push(format!(
+                    "Keybinding {} is assigned to both '{}' and '{}'; '{}' is disabled",
+                    chord.display(),
+                    other.label(),
+                    action.label(),
+                    action.label()
+                ));
+                continue;
+            }
+
+            bindings.push((action, chord));
+        }
+
+        // egui ignores extra Shift/Alt when matching, so check the most specific chords first.
+        bindings.sort_by_key(|(_, chord)| std::cmp::Reverse(chord.modifier_count()));
+
+        (Self { bindings }, errors)
+    }
+
+    pub fn chord_for(&self, action: Action) -> Option<KeyChord> {
+        self.bindings.iter().find(|(a, _)| *a == action).map(|(_, chord)| *chord)
+    }
+
+    pub fn chord_text(&self, action: Action) -> String {
+        self.chord_for(action).map(|c| c.display()).unwrap_or_default()
+    }
+
+    /// Consumes every bound chord pressed this frame and returns the triggered actions.
+    pub fn triggered_actions(&self, input: &mut egui::InputState) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter(|(_, chord)| chord.consume(input))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_overrides(&BTreeMap::new()).0
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use eframe::egui;
+
+use crate::config::Config;
+use crate::uri_scheme::DeepLink;
+
+/// How long the listener thread sleeps when no link is waiting.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+/// How long forwarding waits for the running app before starting a new one instead.
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where the running app writes the port it takes links on and the token they need, next to
+/// the config file.
+fn instance_file() -> PathBuf {
+    Config::get_config_path().with_file_name("instance")
+}
+
+/// Hands a `notesquirrel://` link to the NoteSquirrel already running, if there is one, so
+/// links open in its window instead of starting another. Returns whether it took the link.
+pub fn forward(link: &str) -> bool {
+    let Ok(instance) = std::fs::read_to_string(instance_file()) else {
+        return false;
+    };
+    let Some((port, token)) = instance.trim().split_once(' ') else {
+        return false;
+    };
+    let Ok(port) = port.parse::<u16>() else {
+        return false;
+    };
+    let send = || -> std::io::Result<String> {
+        let mut stream = TcpStream::connect_timeout(&SocketAddr::from(([127, 0, 0, 1], port)), FORWARD_TIMEOUT)?;
+        stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+        stream.write_all(format!("{}\n{}\n", token, link).as_bytes())?;
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply)?;
+        Ok(reply)
+    };
+    send().is_ok_and(|reply| reply.trim() == "ok")
+}
+
+/// Takes the links other launches of the app forward to it. Stops and removes its instance
+/// file when dropped.
+pub struct LinkListener {
+    links: Receiver<Result<DeepLink, String>>,
+    stop: Arc<AtomicBool>,
+    /// What this listener wrote to the instance file, so a later instance's isn't removed.
+    instance: String,
+}
+
+impl LinkListener {
+    pub fn spawn(ctx: &egui::Context) -> Result<Self, String> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+            .map_err(|e| format!("Failed to listen for notesquirrel:// links: {}", e))?;
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        let token = new_token();
+        let instance = format!("{} {}", port, token);
+        let path = instance_file();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        write_private(&path, &instance).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+
+        let (sender, links) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(e) = receive(stream, &token, &sender, &ctx) {
+                            eprintln!("Failed to take a forwarded link: {}", e);
+                        }
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+                    Err(e) => {
+                        eprintln!("Stopped listening for notesquirrel:// links: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self { links, stop, instance })
+    }
+
+    pub fn poll(&self) -> Vec<Result<DeepLink, String>> {
+        self.links.try_iter().collect()
+    }
+}
+
+impl Drop for LinkListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let path = instance_file();
+        if std::fs::read_to_string(&path).is_ok_and(|instance| instance == self.instance) {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
+
+fn receive(
+    stream: TcpStream,
+    token: &str,
+    sender: &Sender<Result<DeepLink, String>>,
+    ctx: &egui::Context,
+) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let (mut sent_token, mut link) = (String::new(), String::new());
+    reader.read_line(&mut sent_token)?;
+    if sent_token.trim_end() != token {
+        return Err(std::io::Error::new(ErrorKind::PermissionDenied, "wrong token"));
+    }
+    reader.read_line(&mut link)?;
+    sender.send(DeepLink::parse(link.trim())).ok();
+    ctx.request_repaint();
+    (&stream).write_all(b"ok\n")
+}
+
+/// Writes the instance file readable only by the current user, as it holds the token.
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// A token other users' programs can't guess, from the standard library's randomly seeded
+/// hasher.
+fn new_token() -> String {
+    let seed = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let halves = [RandomState::new().hash_one(seed), RandomState::new().hash_one(std::process::id())];
+    format!("{:016x}{:016x}", halves[0], halves[1])
+}
@@ -1,9 +1,10 @@
-use eframe::egui;
-
 use crate::app_frame::AppFrame;
 use crate::icon::load_app_icon;
+use crate::cli::Command;
+use crate::uri_scheme::DeepLink;
 
 mod file_manager;
+mod ignore_rules;
 mod icon;
 mod app_frame;
 mod notes_list;
@@ -11,8 +12,82 @@ mod editor;
 mod rendered_view;
 mod config;
 mod find_replace;
+mod global_search;
+mod attachments;
+mod attachment_preview;
+mod settings;
+mod theme;
+mod keymap;
+mod settings_transfer;
+mod i18n;
+mod note_template;
+mod note_window;
+mod note_views;
+mod note_import;
+mod shortcuts_sheet;
+mod uri_scheme;
+mod link_forwarding;
+mod cli;
+mod setup_wizard;
+mod presentation;
+mod vault_stats;
+mod vault_index;
+mod daily_notes;
+mod tasks;
+mod reminders;
+mod note_switcher;
+mod scratchpad;
+mod privacy_screen;
+mod tags;
+mod headings;
+mod note_server;
+mod note_io;
+mod search_index;
+mod writing_activity;
+mod profiler;
+mod html_to_markdown;
+mod pasted_lists;
+mod delimited_table;
+mod footnotes;
+mod toc;
+mod autocorrect;
+mod front_matter;
+mod word_goal;
+mod wikilinks;
+mod docx_export;
+mod html_export;
+mod pandoc;
+mod note_export;
+mod note_share;
+mod opml;
+mod automation_api;
+mod system_hotkey;
+mod merge_tool;
+mod note_conflict;
+mod note_history;
+mod version_control;
+mod vault_switcher;
+mod folder_picker;
+mod task_calendar;
+mod template_manager;
 
 fn main() -> Result<(), eframe::Error> {
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, Command::usage());
+            std::process::exit(2);
+        }
+    };
+    if let Some(exit_code) = command.run_without_window() {
+        std::process::exit(exit_code);
+    }
+    if let Command::Open { vault: None, link: Some(Ok(DeepLink::Note(name))) } = &command
+        && link_forwarding::forward(&uri_scheme::note_link(name))
+    {
+        return Ok(());
+    }
+
     #[cfg(target_os = "linux")]
     if std::env::var_os("LIBGL_ALWAYS_SOFTWARE").is_none() {
         use std::os::unix::process::CommandExt;
@@ -25,9 +100,16 @@ fn main() -> Result<(), eframe::Error> {
         panic!("failed to re-exec with software rendering: {err}");
     }
 
-    let mut viewport = egui::ViewportBuilder::default()
-        .with_inner_size([1200.0, 800.0])
-        .with_title("Note Squirrel");
+    let mut app = AppFrame::default();
+    if let Command::Open { vault, link } = command {
+        if let Some(vault) = vault {
+            app.open_vault(vault);
+        }
+        if let Some(link) = link {
+            app.handle_launch_link(link);
+        }
+    }
+    let mut viewport = app.initial_viewport().with_title("Note Squirrel");
 
     if let Some(icon) = load_app_icon() {
         viewport = viewport.with_icon(icon);
@@ -39,9 +121,13 @@ fn main() -> Result<(), eframe::Error> {
             viewport,
             ..Default::default()
         },
-        Box::new(|cc| {
-            let mut app = AppFrame::default();
+        Box::new(move |cc| {
+            app.apply_theme(&cc.egui_ctx);
+            app.apply_ui_scale(&cc.egui_ctx);
             app.setup_fonts_and_collect_errors(&cc.egui_ctx);
+            app.start_config_watcher(&cc.egui_ctx);
+            app.start_note_watcher(&cc.egui_ctx);
+            app.start_link_listener(&cc.egui_ctx);
             Ok(Box::new(app))
         }),
     )
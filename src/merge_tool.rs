@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+
+/// Two versions of a note for the user to merge in their external tool.
+pub struct MergeJob {
+    pub note: String,
+    pub local: String,
+    pub incoming: String,
+}
+
+pub enum MergeEvent {
+    Merged { note: String, content: String },
+    Failed(String),
+}
+
+/// A merge tool the user is working in, and the temporary files it was given.
+struct RunningMerge {
+    note: String,
+    child: Child,
+    dir: PathBuf,
+    /// The file holding the result: `{merged}` when the command has it, otherwise `{local}`,
+    /// which the tool edits in place.
+    result: PathBuf,
+}
+
+impl Drop for RunningMerge {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
+/// Runs the configured `merge_tool` on one pair of versions at a time, so several conflicts
+/// don't open a pile of windows at once.
+pub struct MergeQueue {
+    waiting: VecDeque<MergeJob>,
+    running: Option<RunningMerge>,
+    next_id: usize,
+}
+
+impl MergeQueue {
+    pub fn new() -> Self {
+        Self {
+            waiting: VecDeque::new(),
+            running: None,
+            next_id: 0,
+        }
+    }
+
+    pub fn push(&mut self, job: MergeJob) {
+        self.waiting.push_back(job);
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.running.is_some() || !self.waiting.is_empty()
+    }
+
+    /// Collects the result of a finished merge and starts the next one with `command`.
+    pub fn poll(&mut self, command: &str) -> Vec<MergeEvent> {
+        let mut events = Vec::new();
+        if let Some(running) = &mut self.running {
+            match running.child.try_wait() {
+                Ok(None) => return events,
+                Ok(Some(status)) if status.success() => {
+                    let event = match fs::read_to_string(&running.result) {
+                        Ok(content) => MergeEvent::Merged { note: running.note.clone(), content },
+                        Err(e) => MergeEvent::Failed(format!("Failed to read the merged version of '{}': {}", running.note, e)),
+                    };
+                    events.push(event);
+                }
+                Ok(Some(status)) => events.push(MergeEvent::Failed(format!(
+                    "The merge tool exited with {} for '{}'; the note was left unchanged",
+                    status, running.note
+                ))),
+                Err(e) => events.push(MergeEvent::Failed(format!("Lost track of the merge tool for '{}': {}", running.note, e))),
+            }
+            self.running = None;
+        }
+
+        while self.running.is_none()
+            && let Some(job) = self.waiting.pop_front()
+        {
+            let id = self.next_id;
+            self.next_id += 1;
+            match start(command, job, id) {
+                Ok(running) => self.running = Some(running),
+                Err(e) => events.push(MergeEvent::Failed(e)),
+            }
+        }
+        events
+    }
+}
+
+impl Default for MergeQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes both versions to a temporary folder and launches the tool on them. `{local}`,
+/// `{incoming}`, and `{merged}` in the command are replaced by the file paths; the merged file
+/// starts out as the local version.
+fn start(command: &str, job: MergeJob, id: usize) -> Result<RunningMerge, String> {
+    let words = split_command(command);
+    let Some((program, args)) = words.split_first() else {
+        return Err("No merge tool is configured; set `merge_tool` in config.toml".to_string());
+    };
+
+    let dir = std::env::temp_dir().join(format!("notesquirrel-merge-{}-{}", std::process::id(), id));
+    // A note in a subfolder is named by its path; the files only need its last part.
+    let stem = job.note.rsplit('/').next().unwrap_or_default();
+    let local = dir.join(format!("{}.local.md", stem));
+    let incoming = dir.join(format!("{}.incoming.md", stem));
+    let merged = dir.join(format!("{}.merged.md", stem));
+    let write = || -> std::io::Result<()> {
+        fs::create_dir_all(&dir)?;
+        fs::write(&local, &job.local)?;
+        fs::write(&incoming, &job.incoming)?;
+        fs::write(&merged, &job.local)
+    };
+    if let Err(e) = write() {
+        fs::remove_dir_all(&dir).ok();
+        return Err(format!("Failed to prepare files for merging '{}': {}", job.note, e));
+    }
+
+    let args: Vec<String> = args
+        .iter()
+        .map(|arg| {
+            arg.replace("{local}", &local.display().to_string())
+                .replace("{incoming}", &incoming.display().to_string())
+                .replace("{merged}", &merged.display().to_string())
+        })
+        .collect();
+    let result = if command.contains("{merged}") { merged } else { local };
+
+    match Command::new(program).args(&args).spawn() {
+        Ok(child) => Ok(RunningMerge { note: job.note, child, dir, result }),
+        Err(e) => {
+            fs::remove_dir_all(&dir).ok();
+            Err(format!("Failed to run merge tool '{}': {}", program, e))
+        }
+    }
+}
+
+/// Splits a command line on spaces, keeping double-quoted parts together.
+fn split_command(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+    for c in command.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
@@ -0,0 +1,343 @@
+use std::collections::VecDeque;
+
+use eframe::egui;
+
+use crate::i18n::{tr, tr_args};
+use crate::theme::ThemePalette;
+
+/// Past this many line pairs the middle of a diff is shown as one changed block rather than
+/// working out which lines match, to keep the table small.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Edits to a note that couldn't be saved because another program changed it first.
+pub struct NoteConflict {
+    pub note: String,
+    /// The app's version, which wasn't saved.
+    pub mine: String,
+    /// The version on disk, which the note now has.
+    pub theirs: String,
+}
+
+/// One row of a side-by-side diff. A side is `None` where it has no line to line up.
+pub struct DiffRow {
+    mine: Option<String>,
+    theirs: Option<String>,
+}
+
+impl DiffRow {
+    pub fn is_changed(&self) -> bool {
+        self.mine != self.theirs
+    }
+}
+
+/// A run of lines that are the same in both versions, or differ.
+enum Hunk<'a> {
+    Same(Vec<&'a str>),
+    Changed { mine: Vec<&'a str>, theirs: Vec<&'a str> },
+}
+
+/// Splits two texts into runs of matching and differing lines, using the longest common
+/// subsequence of lines.
+fn diff_hunks<'a>(mine: &'a str, theirs: &'a str) -> Vec<Hunk<'a>> {
+    let a: Vec<&str> = mine.lines().collect();
+    let b: Vec<&str> = theirs.lines().collect();
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+    let middle_a = &a[prefix..a.len() - suffix];
+    let middle_b = &b[prefix..b.len() - suffix];
+
+    let mut hunks = Vec::new();
+    let push_same = |hunks: &mut Vec<Hunk<'a>>, line: &'a str| match hunks.last_mut() {
+        Some(Hunk::Same(lines)) => lines.push(line),
+        _ => hunks.push(Hunk::Same(vec![line])),
+    };
+    let push_changed = |hunks: &mut Vec<Hunk<'a>>, line: &'a str, is_mine: bool| {
+        if !matches!(hunks.last(), Some(Hunk::Changed { .. })) {
+            hunks.push(Hunk::Changed { mine: Vec::new(), theirs: Vec::new() });
+        }
+        if let Some(Hunk::Changed { mine, theirs }) = hunks.last_mut() {
+            if is_mine { mine.push(line) } else { theirs.push(line) }
+        }
+    };
+
+    for &line in &a[..prefix] {
+        push_same(&mut hunks, line);
+    }
+    if middle_a.len().saturating_mul(middle_b.len()) > MAX_DIFF_CELLS {
+        hunks.push(Hunk::Changed { mine: middle_a.to_vec(), theirs: middle_b.to_vec() });
+    } else {
+        // lengths[i][j] is the longest common subsequence of middle_a[i..] and middle_b[j..].
+        let width = middle_b.len() + 1;
+        let mut lengths = vec![0u32; (middle_a.len() + 1) * width];
+        for i in (0..middle_a.len()).rev() {
+            for j in (0..middle_b.len()).rev() {
+                lengths[i * width + j] = if middle_a[i] == middle_b[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < middle_a.len() || j < middle_b.len() {
+            if i < middle_a.len() && j < middle_b.len() && middle_a[i] == middle_b[j] {
+                push_same(&mut hunks, middle_a[i]);
+                i += 1;
+                j += 1;
+            } else if j == middle_b.len() || (i < middle_a.len() && lengths[(i + 1) * width + j] >= lengths[i * width + j + 1]) {
+                push_changed(&mut hunks, middle_a[i], true);
+                i += 1;
+            } else {
+                push_changed(&mut hunks, middle_b[j], false);
+                j += 1;
+            }
+        }
+    }
+    for &line in &a[a.len() - suffix..] {
+        push_same(&mut hunks, line);
+    }
+    hunks
+}
+
+/// The rows of a side-by-side diff, with changed lines from each version paired up.
+pub fn diff_rows(mine: &str, theirs: &str) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    for hunk in diff_hunks(mine, theirs) {
+        match hunk {
+            Hunk::Same(lines) => rows.extend(lines.into_iter().map(|line| DiffRow {
+                mine: Some(line.to_string()),
+                theirs: Some(line.to_string()),
+            })),
+            Hunk::Changed { mine, theirs } => {
+                for k in 0..mine.len().max(theirs.len()) {
+                    rows.push(DiffRow {
+                        mine: mine.get(k).map(|line| line.to_string()),
+                        theirs: theirs.get(k).map(|line| line.to_string()),
+                    });
+                }
+            }
+        }
+    }
+    rows
+}
+
+/// Both versions in one text, with git-style conflict markers around each place they differ,
+/// as a starting point for merging by hand.
+fn merged_with_markers(mine: &str, theirs: &str) -> String {
+    let mut merged = String::new();
+    for hunk in diff_hunks(mine, theirs) {
+        match hunk {
+            Hunk::Same(lines) => {
+                for line in lines {
+                    merged.push_str(line);
+                    merged.push('\n');
+                }
+            }
+            Hunk::Changed { mine, theirs } => {
+                merged.push_str("<<<<<<< mine\n");
+                for line in mine {
+                    merged.push_str(line);
+                    merged.push('\n');
+                }
+                merged.push_str("=======\n");
+                for line in theirs {
+                    merged.push_str(line);
+                    merged.push('\n');
+                }
+                merged.push_str(">>>>>>> theirs\n");
+            }
+        }
+    }
+    merged
+}
+
+pub enum ConflictAction {
+    None,
+    KeepMine(NoteConflict),
+    /// The note already has the version on disk, so nothing needs saving.
+    TakeTheirs,
+    Merged { note: String, content: String },
+    MergeTool(NoteConflict),
+}
+
+/// Shows notes whose edits clashed with another program's changes, one at a time, with both
+/// versions side by side so the user can pick one or merge them.
+pub struct ConflictDialog {
+    queue: VecDeque<NoteConflict>,
+    /// The diff of the conflict at the front of the queue.
+    rows: Vec<DiffRow>,
+    /// The text being merged by hand, once the user has chosen to.
+    merging: Option<String>,
+}
+
+impl ConflictDialog {
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            rows: Vec::new(),
+            merging: None,
+        }
+    }
+
+    /// Queues a conflict, replacing any earlier one for the same note since the note has
+    /// moved on from it.
+    pub fn push(&mut self, conflict: NoteConflict) {
+        match self.queue.iter().position(|queued| queued.note == conflict.note) {
+            Some(0) => {
+                self.queue[0] = conflict;
+                self.merging = None;
+                self.compute_rows();
+            }
+            Some(position) => self.queue[position] = conflict,
+            None => {
+                self.queue.push_back(conflict);
+                if self.queue.len() == 1 {
+                    self.compute_rows();
+                }
+            }
+        }
+    }
+
+    fn compute_rows(&mut self) {
+        self.rows = self.queue.front().map(|conflict| diff_rows(&conflict.mine, &conflict.theirs)).unwrap_or_default();
+    }
+
+    /// Takes the conflict being shown and moves on to the next.
+    fn resolve(&mut self) -> NoteConflict {
+        let conflict = self.queue.pop_front().expect("a conflict is being shown");
+        self.merging = None;
+        self.compute_rows();
+        conflict
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, palette: &ThemePalette, has_merge_tool: bool) -> ConflictAction {
+        let Some(conflict) = self.queue.front() else {
+            return ConflictAction::None;
+        };
+        let note = conflict.note.clone();
+        let mut choice = None;
+
+        egui::Window::new(tr("note-conflict-title"))
+            .id(egui::Id::new("note_conflict_dialog"))
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(760.0, 480.0))
+            .show(ctx, |ui| {
+                ui.label(tr_args("note-conflict-intro", &[("note", &note)]));
+                if self.queue.len() > 1 {
+                    ui.weak(tr_args("note-conflict-more", &[("count", &(self.queue.len() - 1))]));
+                }
+                ui.separator();
+
+                if let Some(merged) = &mut self.merging {
+                    ui.weak(tr("note-conflict-merge-hint"));
+                    egui::ScrollArea::vertical().max_height(ui.available_height() - 40.0).show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(merged)
+                                .code_editor()
+                                .desired_width(f32::INFINITY)
+                                .desired_rows(16),
+                        );
+                    });
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("note-conflict-save-merged")).clicked() {
+                            choice = Some(Choice::SaveMerged);
+                        }
+                        if ui.button(tr("note-conflict-back")).clicked() {
+                            choice = Some(Choice::Back);
+                        }
+                    });
+                    return;
+                }
+
+                let headers = (tr("note-conflict-mine"), tr("note-conflict-theirs"));
+                diff_table(ui, "note_conflict_diff", &self.rows, headers, palette);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("note-conflict-keep-mine")).clicked() {
+                        choice = Some(Choice::KeepMine);
+                    }
+                    if ui.button(tr("note-conflict-take-theirs")).clicked() {
+                        choice = Some(Choice::TakeTheirs);
+                    }
+                    if ui.button(tr("note-conflict-merge")).clicked() {
+                        choice = Some(Choice::Merge);
+                    }
+                    if has_merge_tool && ui.button(tr("note-conflict-merge-tool")).clicked() {
+                        choice = Some(Choice::MergeTool);
+                    }
+                });
+            });
+
+        match choice {
+            None => ConflictAction::None,
+            Some(Choice::KeepMine) => ConflictAction::KeepMine(self.resolve()),
+            Some(Choice::TakeTheirs) => {
+                self.resolve();
+                ConflictAction::TakeTheirs
+            }
+            Some(Choice::Merge) => {
+                if let Some(conflict) = self.queue.front() {
+                    self.merging = Some(merged_with_markers(&conflict.mine, &conflict.theirs));
+                }
+                ConflictAction::None
+            }
+            Some(Choice::MergeTool) => ConflictAction::MergeTool(self.resolve()),
+            Some(Choice::SaveMerged) => {
+                let content = self.merging.take().unwrap_or_default();
+                let note = self.resolve().note;
+                ConflictAction::Merged { note, content }
+            }
+            Some(Choice::Back) => {
+                self.merging = None;
+                ConflictAction::None
+            }
+        }
+    }
+}
+
+enum Choice {
+    KeepMine,
+    TakeTheirs,
+    Merge,
+    MergeTool,
+    SaveMerged,
+    Back,
+}
+
+/// Two versions side by side under their headers, with a count of the lines that differ. The
+/// left side's changed lines are shaded with `diff_mine`, the right's with `diff_theirs`.
+pub fn diff_table(ui: &mut egui::Ui, id: &str, rows: &[DiffRow], headers: (&str, &str), palette: &ThemePalette) {
+    let changed = rows.iter().filter(|row| row.is_changed()).count();
+    ui.weak(tr_args("note-conflict-changed", &[("count", &changed)]));
+    let column_width = (ui.available_width() - 60.0) / 2.0;
+    egui::ScrollArea::both().id_salt(id).max_height(ui.available_height() - 40.0).auto_shrink([false, true]).show(ui, |ui| {
+        egui::Grid::new(id).num_columns(2).spacing([12.0, 0.0]).show(ui, |ui| {
+            ui.strong(headers.0);
+            ui.strong(headers.1);
+            ui.end_row();
+            for row in rows {
+                let changed = row.is_changed();
+                diff_cell(ui, row.mine.as_deref(), changed.then_some(palette.diff_mine), column_width);
+                diff_cell(ui, row.theirs.as_deref(), changed.then_some(palette.diff_theirs), column_width);
+                ui.end_row();
+            }
+        });
+    });
+}
+
+/// A line of one version in the diff, shaded when it differs from the other version, or an
+/// empty cell where that version has no line.
+fn diff_cell(ui: &mut egui::Ui, line: Option<&str>, fill: Option<egui::Color32>, width: f32) {
+    egui::Frame::new().fill(fill.unwrap_or(egui::Color32::TRANSPARENT)).show(ui, |ui| {
+        ui.set_width(width);
+        ui.add(egui::Label::new(egui::RichText::new(line.unwrap_or_default()).monospace()).truncate());
+    });
+}
+
+impl Default for ConflictDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
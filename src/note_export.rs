@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::i18n::{tr, tr_args};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Html,
+    Docx,
+    Odt,
+    Epub,
+    Rst,
+    Org,
+    Opml,
+}
+
+impl ExportFormat {
+    const ALL: [ExportFormat; 7] = [
+        ExportFormat::Html,
+        ExportFormat::Docx,
+        ExportFormat::Odt,
+        ExportFormat::Epub,
+        ExportFormat::Rst,
+        ExportFormat::Org,
+        ExportFormat::Opml,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Html => "HTML",
+            ExportFormat::Docx => "Word (DOCX)",
+            ExportFormat::Odt => "OpenDocument (ODT)",
+            ExportFormat::Epub => "EPUB",
+            ExportFormat::Rst => "reStructuredText",
+            ExportFormat::Org => "Org mode",
+            ExportFormat::Opml => "OPML outline",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Html => "html",
+            ExportFormat::Docx => "docx",
+            ExportFormat::Odt => "odt",
+            ExportFormat::Epub => "epub",
+            ExportFormat::Rst => "rst",
+            ExportFormat::Org => "org",
+            ExportFormat::Opml => "opml",
+        }
+    }
+
+    /// Pandoc's name for the format; `None` for formats written without pandoc.
+    pub fn pandoc_format(&self) -> Option<&'static str> {
+        match self {
+            ExportFormat::Html | ExportFormat::Docx | ExportFormat::Opml => None,
+            other => Some(other.extension()),
+        }
+    }
+}
+
+/// File → Export Note: writes the current note as HTML, DOCX, or an OPML outline, or through
+/// pandoc as another document format. File → Export Selection does the same for the selected
+/// text, which can also be copied as HTML.
+pub struct NoteExport {
+    pub show_dialog: bool,
+    note: String,
+    /// The text being exported when exporting a selection rather than the whole note.
+    selection: Option<String>,
+    format: ExportFormat,
+    path_text: String,
+    /// `pandoc --version` when pandoc was found; formats other than HTML need it.
+    pandoc_version: Option<String>,
+}
+
+pub enum NoteExportAction {
+    None,
+    Export { format: ExportFormat, path: PathBuf },
+    CopyHtml,
+}
+
+impl NoteExport {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            note: String::new(),
+            selection: None,
+            format: ExportFormat::Html,
+            path_text: String::new(),
+            pandoc_version: None,
+        }
+    }
+
+    /// Opens the dialog for `note`, or for just `selection` from it when given.
+    pub fn open(&mut self, note: &str, selection: Option<String>, pandoc_version: Option<String>) {
+        if self.note != note || self.path_text.is_empty() {
+            self.path_text = default_export_dir().join(note).display().to_string();
+        }
+        self.note = note.to_string();
+        self.selection = selection;
+        self.pandoc_version = pandoc_version;
+        if self.pandoc_version.is_none() {
+            self.format = ExportFormat::Html;
+        }
+        self.set_extension();
+        self.show_dialog = true;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> NoteExportAction {
+        let mut action = NoteExportAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        let mut close = false;
+
+        let title = if self.selection.is_some() { tr("export-selection-title") } else { tr("export-title") };
+        egui::Window::new(title)
+            .id(egui::Id::new("note_export"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                let heading = if self.selection.is_some() { "export-selection" } else { "export-note" };
+                ui.label(tr_args(heading, &[("note", &self.note)]));
+                ui.add_space(4.0);
+
+                let previous_format = self.format;
+                ui.horizontal(|ui| {
+                    ui.label(tr("export-format"));
+                    egui::ComboBox::from_id_salt("export_format")
+                        .selected_text(self.format.label())
+                        .show_ui(ui, |ui| {
+                            for format in ExportFormat::ALL {
+                                let available = format.pandoc_format().is_none() || self.pandoc_version.is_some();
+                                ui.add_enabled_ui(available, |ui| {
+                                    ui.selectable_value(&mut self.format, format, format.label());
+                                });
+                            }
+                        });
+                });
+                if self.format != previous_format {
+                    self.set_extension();
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("export-file"));
+                    ui.add(egui::TextEdit::singleline(&mut self.path_text).desired_width(f32::INFINITY));
+                });
+
+                match &self.pandoc_version {
+                    Some(version) => ui.weak(version),
+                    None => ui.weak(tr("export-no-pandoc")),
+                };
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let path_text = self.path_text.trim();
+                    if ui.add_enabled(!path_text.is_empty(), egui::Button::new(tr("export-button"))).clicked() {
+                        action = NoteExportAction::Export {
+                            format: self.format,
+                            path: PathBuf::from(path_text),
+                        };
+                    }
+                    if self.selection.is_some() && ui.button(tr("export-copy-html")).clicked() {
+                        action = NoteExportAction::CopyHtml;
+                        close = true;
+                    }
+                    if ui.button(tr("button-close")).clicked() {
+                        close = true;
+                    }
+                });
+
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
+            });
+
+        if !open || close {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+
+    /// The selected text when exporting a selection.
+    pub fn selection(&self) -> Option<&str> {
+        self.selection.as_deref()
+    }
+
+    /// Gives the path the selected format's extension.
+    fn set_extension(&mut self) {
+        let path = PathBuf::from(self.path_text.trim()).with_extension(self.format.extension());
+        self.path_text = path.display().to_string();
+    }
+}
+
+impl Default for NoteExport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_export_dir() -> PathBuf {
+    std::env::home_dir().unwrap_or_else(|| PathBuf::from("."))
+}
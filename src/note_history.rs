@@ -0,0 +1,214 @@
+use chrono::NaiveDateTime;
+use eframe::egui;
+
+use crate::file_manager::FileManager;
+use crate::i18n::{tr, tr_args};
+use crate::note_conflict::{self, DiffRow};
+use crate::theme::ThemePalette;
+use crate::version_control::{self, NoteCommit};
+
+/// Where the earlier versions of a note come from.
+#[derive(Clone, Copy, PartialEq)]
+pub enum HistorySource {
+    /// The snapshots in the notes folder's `.history` folder.
+    Snapshots,
+    /// The commits in the notes folder's git repository.
+    Commits,
+}
+
+enum Version {
+    Snapshot(NaiveDateTime),
+    Commit(NoteCommit),
+}
+
+impl Version {
+    fn label(&self) -> String {
+        match self {
+            Version::Snapshot(taken) => taken.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Version::Commit(commit) => format!("{}  {}", commit.date, commit.message),
+        }
+    }
+
+    /// What the diff's left column is headed with.
+    fn heading(&self) -> String {
+        match self {
+            Version::Snapshot(taken) => tr_args("note-history-snapshot", &[("time", &taken.format("%Y-%m-%d %H:%M"))]),
+            Version::Commit(commit) => tr_args("note-history-commit", &[("commit", &commit.hash), ("time", &commit.date)]),
+        }
+    }
+
+    fn read(&self, file_manager: &FileManager, note: &str) -> Result<String, String> {
+        match self {
+            Version::Snapshot(taken) => file_manager.read_snapshot(note, *taken).map_err(|e| e.to_string()),
+            Version::Commit(commit) => version_control::show(file_manager.notes_dir(), commit),
+        }
+    }
+}
+
+pub enum HistoryAction {
+    None,
+    /// Replace the note's content with an earlier version.
+    Restore { note: String, content: String },
+}
+
+/// Lists the earlier versions of the current note, from its snapshots or its git log, and
+/// shows how a chosen one differs from the note as it is now.
+pub struct HistoryPanel {
+    pub show_dialog: bool,
+    source: HistorySource,
+    /// The note the versions are of; the list is read again when the current note changes.
+    note: String,
+    versions: Vec<Version>,
+    /// The chosen version, by position in `versions`, and its content.
+    selected: Option<(usize, String)>,
+    /// The note's content the diff was worked out against.
+    compared_with: String,
+    rows: Vec<DiffRow>,
+    error: Option<String>,
+}
+
+impl HistoryPanel {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            source: HistorySource::Snapshots,
+            note: String::new(),
+            versions: Vec::new(),
+            selected: None,
+            compared_with: String::new(),
+            rows: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Shows the versions from `source`, or closes the panel when it already shows them.
+    pub fn toggle_dialog(&mut self, source: HistorySource, note: &str, file_manager: &FileManager) {
+        self.show_dialog = !(self.show_dialog && self.source == source);
+        self.source = source;
+        if self.show_dialog {
+            self.load(note, file_manager);
+        }
+    }
+
+    /// Reads the note's list of versions again, as after one was added.
+    pub fn load(&mut self, note: &str, file_manager: &FileManager) {
+        self.note = note.to_string();
+        self.selected = None;
+        self.rows.clear();
+        self.error = None;
+        self.versions = match self.source {
+            HistorySource::Snapshots => file_manager.list_snapshots(note).into_iter().map(Version::Snapshot).collect(),
+            HistorySource::Commits => match version_control::note_log(file_manager.notes_dir(), &file_manager.note_file(note)) {
+                Ok(commits) => commits.into_iter().map(Version::Commit).collect(),
+                Err(e) => {
+                    self.error = Some(e);
+                    Vec::new()
+                }
+            },
+        };
+    }
+
+    fn select(&mut self, position: usize, file_manager: &FileManager, current: &str) {
+        match self.versions[position].read(file_manager, &self.note) {
+            Ok(content) => {
+                self.rows = note_conflict::diff_rows(&content, current);
+                self.compared_with = current.to_string();
+                self.selected = Some((position, content));
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("{}: {}", tr("note-history-read-failed"), e)),
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        palette: &ThemePalette,
+        file_manager: &FileManager,
+        note: &str,
+        current: &str,
+    ) -> HistoryAction {
+        let mut action = HistoryAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+        if note != self.note {
+            self.load(note, file_manager);
+        }
+        if let Some((_, content)) = &self.selected
+            && self.compared_with != current
+        {
+            self.rows = note_conflict::diff_rows(content, current);
+            self.compared_with = current.to_string();
+        }
+
+        let mut open = true;
+        let mut clicked = None;
+        let title = match self.source {
+            HistorySource::Snapshots => tr_args("note-history-title", &[("note", &self.note)]),
+            HistorySource::Commits => tr_args("note-history-git-title", &[("note", &self.note)]),
+        };
+        egui::Window::new(title)
+            .id(egui::Id::new("note_history_panel"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(820.0, 480.0))
+            .show(ctx, |ui| {
+                if self.versions.is_empty() {
+                    match (&self.error, self.source) {
+                        (Some(error), _) => ui.colored_label(ui.visuals().error_fg_color, error),
+                        (None, HistorySource::Snapshots) => ui.label(tr("note-history-none")),
+                        (None, HistorySource::Commits) => ui.label(tr("note-history-no-commits")),
+                    };
+                    return;
+                }
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(220.0);
+                        egui::ScrollArea::vertical().id_salt("note_history_versions").show(ui, |ui| {
+                            for (position, version) in self.versions.iter().enumerate() {
+                                let selected = self.selected.as_ref().is_some_and(|(chosen, _)| *chosen == position);
+                                if ui.selectable_label(selected, version.label()).clicked() {
+                                    clicked = Some(position);
+                                }
+                            }
+                        });
+                    });
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        if let Some(error) = &self.error {
+                            ui.colored_label(ui.visuals().error_fg_color, error);
+                        }
+                        let Some((position, content)) = &self.selected else {
+                            ui.weak(tr("note-history-select"));
+                            return;
+                        };
+                        let heading = self.versions[*position].heading();
+                        note_conflict::diff_table(ui, "note_history_diff", &self.rows, (&heading, tr("note-history-current")), palette);
+                        ui.separator();
+                        let unchanged = self.rows.iter().all(|row| !row.is_changed());
+                        if ui.add_enabled(!unchanged, egui::Button::new(tr("note-history-restore"))).clicked() {
+                            action = HistoryAction::Restore { note: self.note.clone(), content: content.clone() };
+                        }
+                    });
+                });
+            });
+
+        if let Some(position) = clicked {
+            self.select(position, file_manager, current);
+        }
+        if !open {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+impl Default for HistoryPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,185 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::i18n::tr;
+use crate::opml;
+use crate::pandoc;
+
+/// What to do with a dropped file whose name matches an existing note.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictChoice {
+    KeepBoth,
+    Replace,
+    /// Open both versions in the configured `merge_tool` and keep what it saves.
+    Merge,
+    Skip,
+}
+
+impl ConflictChoice {
+    const ALL: [ConflictChoice; 4] = [ConflictChoice::KeepBoth, ConflictChoice::Replace, ConflictChoice::Merge, ConflictChoice::Skip];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ConflictChoice::KeepBoth => tr("import-keep-both"),
+            ConflictChoice::Replace => tr("import-replace"),
+            ConflictChoice::Merge => tr("import-merge"),
+            ConflictChoice::Skip => tr("import-skip"),
+        }
+    }
+}
+
+pub struct PendingImport {
+    pub source: PathBuf,
+    pub name: String,
+    pub choice: ConflictChoice,
+}
+
+pub struct ImportDialog {
+    pub show_dialog: bool,
+    conflicts: Vec<PendingImport>,
+    can_merge: bool,
+}
+
+pub enum ImportAction {
+    None,
+    Resolve(Vec<PendingImport>),
+}
+
+impl ImportDialog {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            conflicts: Vec::new(),
+            can_merge: false,
+        }
+    }
+
+    /// `can_merge` offers the Merge choice, for when a merge tool is configured.
+    pub fn open(&mut self, conflicts: Vec<PendingImport>, can_merge: bool) {
+        self.conflicts = conflicts;
+        self.can_merge = can_merge;
+        self.show_dialog = true;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> ImportAction {
+        let mut action = ImportAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(tr("import-conflicts-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(480.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr("import-conflicts-intro"));
+                ui.separator();
+
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("import_conflicts_grid").num_columns(2).show(ui, |ui| {
+                        for (index, pending) in self.conflicts.iter_mut().enumerate() {
+                            ui.label(&pending.name).on_hover_text(pending.source.display().to_string());
+                            egui::ComboBox::from_id_salt(("import_conflict_choice", index))
+                                .selected_text(pending.choice.label())
+                                .show_ui(ui, |ui| {
+                                    for choice in ConflictChoice::ALL {
+                                        if choice == ConflictChoice::Merge && !self.can_merge {
+                                            continue;
+                                        }
+                                        ui.selectable_value(&mut pending.choice, choice, choice.label());
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("import-apply-to-all")).clicked()
+                        && let Some(first) = self.conflicts.first().map(|p| p.choice)
+                    {
+                        for pending in &mut self.conflicts {
+                            pending.choice = first;
+                        }
+                    }
+                    if ui.button(tr("import-import")).clicked() {
+                        action = ImportAction::Resolve(std::mem::take(&mut self.conflicts));
+                        close = true;
+                    }
+                    if ui.button(tr("button-cancel")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if !open || close {
+            self.show_dialog = false;
+            self.conflicts.clear();
+        }
+
+        action
+    }
+}
+
+impl Default for ImportDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Expands dropped paths into the markdown files they contain, searching folders recursively.
+/// With `with_pandoc`, documents pandoc can convert are included too.
+pub fn collect_importable_files(paths: &[PathBuf], with_pandoc: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        collect_from(path, with_pandoc, &mut files);
+    }
+    files
+}
+
+fn collect_from(path: &Path, with_pandoc: bool, files: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        let mut children: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        children.sort();
+        for child in children {
+            collect_from(&child, with_pandoc, files);
+        }
+    } else if is_markdown(path) || opml::is_opml(path) || (with_pandoc && pandoc::import_format(path).is_some()) {
+        files.push(path.to_path_buf());
+    }
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+/// The note name a dropped file would get: its file name without the extension.
+pub fn note_name_for(path: &Path) -> Option<String> {
+    path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string)
+}
+
+/// `name` if it's free, otherwise `name 2`, `name 3`, ...
+pub fn unique_name(name: &str, existing: &[String]) -> String {
+    let is_taken = |candidate: &str| existing.iter().any(|n| n.eq_ignore_ascii_case(candidate));
+    if !is_taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|suffix| format!("{} {}", name, suffix))
+        .find(|candidate| !is_taken(candidate))
+        .expect("an unused suffix always exists")
+}
@@ -0,0 +1,306 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::file_manager::FileManager;
+
+/// Most threads used to read notes at once.
+const MAX_READERS: usize = 8;
+/// How many queued notes the I/O thread reads before checking for writes again.
+const LOAD_BATCH: usize = 64;
+
+/// A note read from disk by `read_notes`.
+pub struct ReadNote {
+    pub name: String,
+    pub content: std::io::Result<String>,
+    pub modified: Option<SystemTime>,
+}
+
+/// Reads `names` on several threads at once, returning them in the same order. Used for loading
+/// many notes, where a sequential read is dominated by waiting on the disk.
+pub fn read_notes(file_manager: &FileManager, names: Vec<String>) -> Vec<ReadNote> {
+    let readers = thread::available_parallelism().map_or(1, |n| n.get()).min(MAX_READERS);
+    let chunk_size = names.len().div_ceil(readers).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = names
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|name| ReadNote {
+                            name: name.clone(),
+                            modified: file_manager.get_note_modified_time(name),
+                            content: file_manager.read_note(name),
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap_or_default()).collect()
+    })
+}
+
+/// Totals for the I/O thread since it started, for the profiler overlay.
+#[derive(Clone, Copy, Default)]
+pub struct IoStats {
+    pub reads: u64,
+    pub writes: u64,
+    /// Time spent reading and writing.
+    pub busy: Duration,
+}
+
+#[derive(Default)]
+struct IoCounters {
+    reads: AtomicU64,
+    writes: AtomicU64,
+    busy_micros: AtomicU64,
+}
+
+impl IoCounters {
+    fn record(&self, reads: u64, writes: u64, started: Instant) {
+        self.reads.fetch_add(reads, Ordering::Relaxed);
+        self.writes.fetch_add(writes, Ordering::Relaxed);
+        self.busy_micros.fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+/// What the I/O thread reports back to the UI thread.
+pub enum IoEvent {
+    Loaded { name: String, content: String, modified: Option<SystemTime> },
+    LoadFailed { name: String, error: String },
+    Written { name: String, modified: Option<SystemTime> },
+    WriteFailed { name: String, error: String },
+    /// Not written, since another program changed the file after the app last read or wrote it.
+    /// `content` is what would have been saved.
+    WriteConflict { name: String, content: String },
+    SnapshotFailed { name: String, error: String },
+}
+
+enum IoRequest {
+    Load(Vec<String>),
+    /// `expected` is the file's modification time as last seen by the app, if known; the write is
+    /// held back when the file has changed since.
+    Write { name: String, content: String, expected: Option<SystemTime> },
+    /// Saves `content` into the note's history, keeping the newest `keep` snapshots.
+    Snapshot { name: String, content: String, keep: usize },
+    /// Answered once every write queued before it is on disk.
+    Flush(Sender<()>),
+}
+
+/// Reads and writes note files on a background thread so slow disks and network shares don't
+/// stall the UI. Writes always go ahead of queued loads, and repeated writes to one note are
+/// collapsed into the latest. Dropping it finishes the pending writes.
+pub struct NoteIo {
+    requests: Option<Sender<IoRequest>>,
+    events: Receiver<IoEvent>,
+    worker: Option<JoinHandle<()>>,
+    counters: Arc<IoCounters>,
+    /// Writes asked for but not yet handled by the I/O thread, per note.
+    pending_writes: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl NoteIo {
+    pub fn new(file_manager: FileManager) -> Self {
+        let (request_sender, request_receiver) = mpsc::channel();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let counters = Arc::new(IoCounters::default());
+        let worker_counters = Arc::clone(&counters);
+        let pending_writes = Arc::new(Mutex::new(HashMap::new()));
+        let worker_pending = Arc::clone(&pending_writes);
+        let worker = thread::spawn(move || run_worker(file_manager, request_receiver, event_sender, &worker_counters, &worker_pending));
+        Self {
+            requests: Some(request_sender),
+            events: event_receiver,
+            worker: Some(worker),
+            counters,
+            pending_writes,
+        }
+    }
+
+    /// Queues the notes to be read; each one comes back as an `IoEvent::Loaded` or `LoadFailed`.
+    pub fn load(&self, names: Vec<String>) {
+        self.send(IoRequest::Load(names));
+    }
+
+    pub fn write(&self, name: &str, content: &str, expected: Option<SystemTime>) {
+        if let Ok(mut pending) = self.pending_writes.lock() {
+            *pending.entry(name.to_string()).or_default() += 1;
+        }
+        self.send(IoRequest::Write {
+            name: name.to_string(),
+            content: content.to_string(),
+            expected,
+        });
+    }
+
+    pub fn snapshot(&self, name: &str, content: &str, keep: usize) {
+        self.send(IoRequest::Snapshot {
+            name: name.to_string(),
+            content: content.to_string(),
+            keep,
+        });
+    }
+
+    /// Blocks until every write queued so far has finished. Call before deleting or renaming a
+    /// note so a late write can't recreate the old file.
+    pub fn flush(&self) {
+        let (sender, receiver) = mpsc::channel();
+        self.send(IoRequest::Flush(sender));
+        receiver.recv().ok();
+    }
+
+    /// Whether a save of the note is still queued, so its file doesn't have the latest content yet.
+    pub fn has_pending_write(&self, name: &str) -> bool {
+        self.pending_writes.lock().is_ok_and(|pending| pending.get(name).is_some_and(|&count| count > 0))
+    }
+
+    pub fn stats(&self) -> IoStats {
+        IoStats {
+            reads: self.counters.reads.load(Ordering::Relaxed),
+            writes: self.counters.writes.load(Ordering::Relaxed),
+            busy: Duration::from_micros(self.counters.busy_micros.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Events that have arrived since the last call.
+    pub fn poll(&self) -> Vec<IoEvent> {
+        self.events.try_iter().collect()
+    }
+
+    fn send(&self, request: IoRequest) {
+        if let Some(requests) = &self.requests
+            && requests.send(request).is_err()
+        {
+            eprintln!("Note I/O thread has stopped");
+        }
+    }
+}
+
+impl Drop for NoteIo {
+    fn drop(&mut self) {
+        self.requests = None;
+        if let Some(worker) = self.worker.take() {
+            worker.join().ok();
+        }
+    }
+}
+
+fn run_worker(
+    file_manager: FileManager,
+    requests: Receiver<IoRequest>,
+    events: Sender<IoEvent>,
+    counters: &IoCounters,
+    pending_writes: &Mutex<HashMap<String, usize>>,
+) {
+    let mut load_queue: VecDeque<String> = VecDeque::new();
+    // The modification time each note's file got from this thread's last write to it.
+    let mut own_writes: HashMap<String, Option<SystemTime>> = HashMap::new();
+    loop {
+        // Wait for work when there's nothing left to load, otherwise just pick up what's queued.
+        let mut batch = Vec::new();
+        if load_queue.is_empty() {
+            match requests.recv() {
+                Ok(request) => batch.push(request),
+                Err(_) => return,
+            }
+        }
+        let mut disconnected = false;
+        loop {
+            match requests.try_recv() {
+                Ok(request) => batch.push(request),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        for (position, request) in batch.iter().enumerate() {
+            match request {
+                IoRequest::Load(names) => load_queue.extend(names.iter().cloned()),
+                IoRequest::Write { name, content, expected } => {
+                    let superseded = batch[position + 1..]
+                        .iter()
+                        .take_while(|later| !matches!(later, IoRequest::Flush(_)))
+                        .any(|later| matches!(later, IoRequest::Write { name: later_name, .. } if later_name == name));
+                    if !superseded {
+                        let event = write_unless_changed(&file_manager, name, content, *expected, &mut own_writes, counters);
+                        events.send(event).ok();
+                    }
+                    // Only counted as done once the file is written, so a change to it seen
+                    // meanwhile isn't mistaken for another program's.
+                    if let Ok(mut pending) = pending_writes.lock()
+                        && let Some(count) = pending.get_mut(name)
+                    {
+                        *count = count.saturating_sub(1);
+                    }
+                }
+                IoRequest::Snapshot { name, content, keep } => {
+                    let started = Instant::now();
+                    let written = file_manager.write_snapshot(name, content);
+                    file_manager.prune_snapshots(name, *keep);
+                    counters.record(0, 1, started);
+                    if let Err(e) = written {
+                        events.send(IoEvent::SnapshotFailed { name: name.clone(), error: e.to_string() }).ok();
+                    }
+                }
+                IoRequest::Flush(done) => {
+                    done.send(()).ok();
+                }
+            }
+        }
+        // Pending writes are done; loads don't matter once the UI is gone.
+        if disconnected {
+            return;
+        }
+
+        let names: Vec<String> = load_queue.drain(..load_queue.len().min(LOAD_BATCH)).collect();
+        let started = Instant::now();
+        let notes = read_notes(&file_manager, names);
+        counters.record(notes.len() as u64, 0, started);
+        for note in notes {
+            let event = match note.content {
+                Ok(content) => IoEvent::Loaded { name: note.name, content, modified: note.modified },
+                Err(e) => IoEvent::LoadFailed { name: note.name, error: e.to_string() },
+            };
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Writes a note, unless its file was changed by another program since `expected`, the time
+/// the app last saw. Changes from this thread's own earlier writes don't count.
+fn write_unless_changed(
+    file_manager: &FileManager,
+    name: &str,
+    content: &str,
+    expected: Option<SystemTime>,
+    own_writes: &mut HashMap<String, Option<SystemTime>>,
+    counters: &IoCounters,
+) -> IoEvent {
+    let on_disk = file_manager.get_note_modified_time(name);
+    let changed_elsewhere =
+        expected.is_some() && on_disk.is_some() && on_disk != expected && own_writes.get(name).is_none_or(|own| *own != on_disk);
+    if changed_elsewhere {
+        return IoEvent::WriteConflict { name: name.to_string(), content: content.to_string() };
+    }
+
+    let started = Instant::now();
+    let written = file_manager.write_note(name, content);
+    counters.record(0, 1, started);
+    match written {
+        Ok(()) => {
+            let modified = file_manager.get_note_modified_time(name);
+            own_writes.insert(name.to_string(), modified);
+            IoEvent::Written { name: name.to_string(), modified }
+        }
+        Err(e) => IoEvent::WriteFailed { name: name.to_string(), error: e.to_string() },
+    }
+}
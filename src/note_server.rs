@@ -0,0 +1,257 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::html_export::{escape_html, markdown_to_html, DOCUMENT_STYLE};
+use crate::i18n::{tr, tr_args};
+
+/// How long the server thread sleeps when no connection is waiting.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+/// How often the served page asks whether the note changed.
+const RELOAD_POLL_MS: u32 = 1000;
+
+/// The note as the server hands it out. `version` goes up on every change so open pages know
+/// to reload.
+struct Page {
+    title: String,
+    content: String,
+    html: String,
+    version: u64,
+}
+
+/// Serves one note as HTML on the local network from a background thread. Stops when dropped.
+struct RunningServer {
+    note: String,
+    url: String,
+    page: Arc<Mutex<Page>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl RunningServer {
+    fn spawn(note: &str, content: &str, port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        let url = format!("http://{}:{}/", lan_address(), listener.local_addr()?.port());
+
+        let page = Arc::new(Mutex::new(Page {
+            title: note.to_string(),
+            content: content.to_string(),
+            html: markdown_to_html(content),
+            version: 0,
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_page = Arc::clone(&page);
+        let thread_stop = Arc::clone(&stop);
+
+        thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(e) = respond(stream, &thread_page) {
+                            eprintln!("Note server request failed: {}", e);
+                        }
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+                    Err(e) => {
+                        eprintln!("Note server stopped accepting connections: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            note: note.to_string(),
+            url,
+            page,
+            stop,
+        })
+    }
+}
+
+impl Drop for RunningServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// File → Serve Note: shares the current note over HTTP so it can be read from a phone or
+/// another machine on the LAN. The page reloads itself whenever the note is saved.
+pub struct NoteServer {
+    pub show_dialog: bool,
+    running: Option<RunningServer>,
+}
+
+pub enum NoteServerAction {
+    None,
+    Stop,
+}
+
+impl NoteServer {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            running: None,
+        }
+    }
+
+    /// Starts serving `note`, replacing whatever was served before.
+    pub fn start(&mut self, note: &str, content: &str, port: u16) -> Result<(), String> {
+        self.running = None;
+        let server = RunningServer::spawn(note, content, port)
+            .map_err(|e| format!("Failed to start the note server on port {}: {}", port, e))?;
+        self.running = Some(server);
+        self.show_dialog = true;
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.running = None;
+        self.show_dialog = false;
+    }
+
+    pub fn served_note(&self) -> Option<&str> {
+        self.running.as_ref().map(|server| server.note.as_str())
+    }
+
+    /// Publishes the served note's latest saved content.
+    pub fn update(&self, content: &str) {
+        let Some(server) = &self.running else {
+            return;
+        };
+        let Ok(mut page) = server.page.lock() else {
+            return;
+        };
+        if page.content != content {
+            page.content = content.to_string();
+            page.html = markdown_to_html(content);
+            page.version += 1;
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> NoteServerAction {
+        let mut action = NoteServerAction::None;
+
+        let Some(server) = &self.running else {
+            return action;
+        };
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        egui::Window::new(tr("serve-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr_args("serve-note", &[("note", &server.note)]));
+                ui.horizontal(|ui| {
+                    ui.hyperlink(&server.url);
+                    if ui.small_button(tr("serve-copy-url")).clicked() {
+                        ctx.copy_text(server.url.clone());
+                    }
+                });
+                ui.weak(tr("serve-hint"));
+                ui.separator();
+                if ui.button(tr("serve-stop")).clicked() {
+                    action = NoteServerAction::Stop;
+                }
+            });
+
+        if !open {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+impl Default for NoteServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Answers one request: `/version` for the reload poll, anything else gets the page.
+fn respond(mut stream: TcpStream, page: &Mutex<Page>) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let (content_type, body) = {
+        let page = page.lock().map_err(|_| std::io::Error::other("note server page lock poisoned"))?;
+        if path == "/version" {
+            ("text/plain; charset=utf-8", page.version.to_string())
+        } else {
+            ("text/html; charset=utf-8", page_document(&page))
+        }
+    };
+
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())
+}
+
+fn page_document(page: &Page) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+{style}
+</style>
+</head>
+<body>
+{html}
+<script>
+const version = {version};
+setInterval(() => {{
+  fetch("/version").then(r => r.text()).then(v => {{ if (Number(v) !== version) location.reload(); }}).catch(() => {{}});
+}}, {poll});
+</script>
+</body>
+</html>
+"#,
+        title = escape_html(&page.title),
+        style = DOCUMENT_STYLE,
+        html = page.html,
+        version = page.version,
+        poll = RELOAD_POLL_MS,
+    )
+}
+
+/// The address other machines on the network can reach us at. Connecting a UDP socket sends
+/// nothing; it only picks the outgoing interface.
+fn lan_address() -> String {
+    UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("192.0.2.1:80")?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|_| "127.0.0.1".to_string())
+}
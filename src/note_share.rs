@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use eframe::egui;
+
+use crate::html_export;
+use crate::i18n::{tr, tr_args};
+
+/// Whether the platform's mail client can be handed an attachment; a `mailto:` link can only
+/// carry text.
+pub const CAN_ATTACH: bool = cfg!(any(target_os = "linux", target_os = "macos"));
+
+/// File → Share: starts an email draft with the current note, optionally attaching it as HTML.
+pub struct NoteShare {
+    pub show_dialog: bool,
+    note: String,
+    attach_html: bool,
+}
+
+pub enum NoteShareAction {
+    None,
+    Email { attach_html: bool },
+}
+
+impl NoteShare {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            note: String::new(),
+            attach_html: false,
+        }
+    }
+
+    pub fn open(&mut self, note: &str) {
+        self.note = note.to_string();
+        self.show_dialog = true;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> NoteShareAction {
+        let mut action = NoteShareAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(tr("share-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(380.0)
+            .show(ctx, |ui| {
+                ui.label(tr_args("share-note", &[("note", &self.note)]));
+                ui.add_space(4.0);
+                ui.add_enabled_ui(CAN_ATTACH, |ui| {
+                    ui.checkbox(&mut self.attach_html, tr("share-attach-html"))
+                        .on_disabled_hover_text(tr("share-attach-unsupported"));
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("share-email")).clicked() {
+                        action = NoteShareAction::Email {
+                            attach_html: CAN_ATTACH && self.attach_html,
+                        };
+                        close = true;
+                    }
+                    if ui.button(tr("button-close")).clicked() {
+                        close = true;
+                    }
+                });
+
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
+            });
+
+        if !open || close {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+impl Default for NoteShare {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Opens a draft in the default mail client with the note as its body and the note name as its
+/// subject. With `attach_html`, the note is also written to a temporary HTML file and attached.
+pub fn email(note: &str, markdown: &str, attach_html: bool) -> Result<(), String> {
+    if !attach_html {
+        let url = format!("mailto:?subject={}&body={}", percent_encode(note), percent_encode(markdown));
+        return webbrowser::open(&url).map_err(|e| format!("Failed to open the mail client: {}", e));
+    }
+
+    let dir = std::env::temp_dir().join(format!("notesquirrel-share-{}", std::process::id()));
+    let attachment = dir.join(format!("{}.html", note.rsplit('/').next().unwrap_or_default()));
+    std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&attachment, html_export::html_document(note, markdown)))
+        .map_err(|e| format!("Failed to write '{}': {}", attachment.display(), e))?;
+    email_with_attachment(note, markdown, &attachment)
+}
+
+#[cfg(target_os = "linux")]
+fn email_with_attachment(note: &str, markdown: &str, attachment: &Path) -> Result<(), String> {
+    run(std::process::Command::new("xdg-email")
+        .args(["--subject", note, "--body", markdown, "--attach"])
+        .arg(attachment))
+}
+
+/// Mail.app is the only client scriptable this way; others can still be reached without the
+/// attachment through `mailto:`.
+#[cfg(target_os = "macos")]
+fn email_with_attachment(note: &str, markdown: &str, attachment: &Path) -> Result<(), String> {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    let script = format!(
+        "tell application \"Mail\"\n\
+         set draft to make new outgoing message with properties {{subject:{}, content:{}, visible:true}}\n\
+         tell content of draft to make new attachment with properties {{file name:(POSIX file {})}} at after the last paragraph\n\
+         activate\n\
+         end tell",
+        quote(note),
+        quote(markdown),
+        quote(&attachment.display().to_string())
+    );
+    run(std::process::Command::new("osascript").args(["-e", &script]))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn email_with_attachment(_note: &str, _markdown: &str, _attachment: &Path) -> Result<(), String> {
+    Err("Attaching notes to emails isn't supported on this platform".to_string())
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run(command: &mut std::process::Command) -> Result<(), String> {
+    let program = Path::new(command.get_program()).to_path_buf();
+    let status = command
+        .status()
+        .map_err(|e| format!("Failed to start an email with {}: {}", program.display(), e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program.display(), status))
+    }
+}
+
+/// Encodes everything but unreserved characters, so line breaks and `&` survive in the body.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'\n' => encoded.push_str("%0D%0A"),
+            b'\r' => {}
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
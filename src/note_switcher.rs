@@ -0,0 +1,123 @@
+use eframe::egui;
+
+use crate::i18n::tr;
+use crate::keymap::KeyChord;
+
+/// How many recently used notes the switcher remembers.
+const RECENT_LIMIT: usize = 15;
+
+/// Ctrl+Tab switcher: cycles through notes in most-recently-used order while the modifier is
+/// held, showing the candidates in an overlay, and opens the selected one on release.
+pub struct NoteSwitcher {
+    /// Most recently used first.
+    recent: Vec<String>,
+    candidates: Vec<String>,
+    /// Index into `candidates` while the overlay is showing.
+    selected: Option<usize>,
+    chord: Option<KeyChord>,
+}
+
+pub enum NoteSwitcherAction {
+    None,
+    Open(String),
+}
+
+impl NoteSwitcher {
+    pub fn new() -> Self {
+        Self {
+            recent: Vec::new(),
+            candidates: Vec::new(),
+            selected: None,
+            chord: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.recent.clear();
+        self.selected = None;
+    }
+
+    /// Moves `name` to the front of the most-recently-used order.
+    pub fn note_opened(&mut self, name: &str) {
+        if name.is_empty() {
+            return;
+        }
+        self.recent.retain(|n| n != name);
+        self.recent.insert(0, name.to_string());
+        self.recent.truncate(RECENT_LIMIT);
+    }
+
+    /// Opens the overlay, or moves the selection if it's already showing. `chord` is the shortcut
+    /// that triggered it; the switch happens once its modifiers are released.
+    pub fn cycle(&mut self, step: isize, chord: Option<KeyChord>, current: &str, note_names: &[String]) {
+        if self.selected.is_none() {
+            self.recent.retain(|n| note_names.contains(n));
+            self.candidates = std::iter::once(current.to_string())
+                .chain(self.recent.iter().filter(|n| *n != current).cloned())
+                .collect();
+            if self.candidates.len() < 2 {
+                return;
+            }
+            self.chord = chord;
+            self.selected = Some(0);
+        }
+
+        let count = self.candidates.len() as isize;
+        if let Some(selected) = self.selected.as_mut() {
+            *selected = (*selected as isize + step).rem_euclid(count) as usize;
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> NoteSwitcherAction {
+        let Some(selected) = self.selected else {
+            return NoteSwitcherAction::None;
+        };
+
+        let (held, cancelled) = ctx.input_mut(|i| {
+            let held = self.chord.is_some_and(|chord| {
+                (chord.ctrl && (i.modifiers.ctrl || i.modifiers.mac_cmd)) || (chord.alt && i.modifiers.alt)
+            });
+            (held, i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+        });
+        if cancelled {
+            self.selected = None;
+            return NoteSwitcherAction::None;
+        }
+        if !held {
+            self.selected = None;
+            return NoteSwitcherAction::Open(self.candidates[selected].clone());
+        }
+
+        let mut clicked = None;
+        egui::Area::new(egui::Id::new("note_switcher"))
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 80.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_min_width(280.0);
+                    for (index, name) in self.candidates.iter().enumerate() {
+                        let response = ui.add(egui::Button::selectable(index == selected, name).min_size(egui::vec2(280.0, 0.0)));
+                        if response.clicked() {
+                            clicked = Some(index);
+                        }
+                    }
+                    ui.separator();
+                    ui.weak(tr("switcher-hint"));
+                });
+            });
+
+        match clicked {
+            Some(index) => {
+                self.selected = None;
+                NoteSwitcherAction::Open(self.candidates[index].clone())
+            }
+            None => NoteSwitcherAction::None,
+        }
+    }
+}
+
+impl Default for NoteSwitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate};
+
+use crate::config::{Config, DateFormats};
+
+pub const DEFAULT_DAILY_NOTE_FORMAT: &str = "%Y-%m-%d";
+
+/// Characters that can't appear in a note's file name on at least one supported platform.
+const INVALID_FILE_NAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Folder in the notes folder where the Templates settings page keeps templates.
+pub const TEMPLATES_FOLDER: &str = "templates";
+
+/// Where the cursor goes in a note created from a template, as `{{cursor}}` or `{cursor}`.
+const CURSOR_PLACEHOLDERS: [&str; 2] = ["{{cursor}}", "{cursor}"];
+
+/// Fills in `{date}`, `{time}`, `{datetime}`, and `{week}` from `now` using the configured formats,
+/// plus any extra `(name, value)` pairs. Each can also be written in double braces, like
+/// `{{date}}`.
+pub fn expand_placeholders(text: &str, now: &DateTime<Local>, formats: &DateFormats, extra: &[(&str, &str)]) -> String {
+    let mut values = vec![
+        ("datetime", formats.format_datetime(now)),
+        ("date", formats.format_date(now)),
+        ("time", formats.format_time(now)),
+        ("week", formats.format_week(now)),
+    ];
+    values.extend(extra.iter().map(|(name, value)| (*name, value.to_string())));
+
+    let mut expanded = text.to_string();
+    for (name, value) in values {
+        expanded = expanded.replace(&format!("{{{{{}}}}}", name), &value).replace(&format!("{{{}}}", name), &value);
+    }
+    expanded
+}
+
+/// `content` without its first cursor placeholder, and the character position it marked. Any
+/// other cursor placeholders are dropped.
+pub fn take_cursor(content: &str) -> (String, Option<usize>) {
+    let first = CURSOR_PLACEHOLDERS
+        .iter()
+        .filter_map(|placeholder| content.find(placeholder))
+        .min()
+        .map(|byte| content[..byte].chars().count());
+    let mut stripped = content.to_string();
+    for placeholder in CURSOR_PLACEHOLDERS {
+        stripped = stripped.replace(placeholder, "");
+    }
+    (stripped, first)
+}
+
+/// Builds a name for a new note from the configured pattern that doesn't clash with `existing`.
+/// `{n}` counts up from one past the number of notes; patterns without it get " 2", " 3", ... appended.
+pub fn new_note_name(pattern: &str, existing: &[String], now: &DateTime<Local>, formats: &DateFormats) -> String {
+    let pattern = if pattern.trim().is_empty() { "Note {n}" } else { pattern };
+    let is_taken = |name: &str| existing.iter().any(|n| n.eq_ignore_ascii_case(name));
+
+    if pattern.contains("{n}") {
+        let mut n = existing.len() + 1;
+        loop {
+            let name = sanitize_file_name(&expand_placeholders(pattern, now, formats, &[("n", &n.to_string())]));
+            if !is_taken(&name) {
+                return name;
+            }
+            n += 1;
+        }
+    }
+
+    let base = sanitize_file_name(&expand_placeholders(pattern, now, formats, &[]));
+    if !is_taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|suffix| format!("{} {}", base, suffix))
+        .find(|name| !is_taken(name))
+        .expect("an unused suffix always exists")
+}
+
+/// Reads the configured template for new notes and fills in its placeholders, including `{title}`.
+/// Relative template paths are resolved against the notes folder.
+pub fn new_note_content(config: &Config, title: &str, now: &DateTime<Local>) -> Result<String, String> {
+    template_content(config, config.new_note_template.as_deref(), title, now)
+}
+
+/// Name of the daily note for `day`, from the configured daily note format.
+pub fn daily_note_name(config: &Config, day: NaiveDate) -> String {
+    sanitize_file_name(&day.format(daily_note_format(config)).to_string())
+}
+
+/// The day a note is the daily note for, if its name matches the daily note format.
+pub fn daily_note_day(config: &Config, name: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(name, daily_note_format(config))
+        .ok()
+        .filter(|day| daily_note_name(config, *day) == name)
+}
+
+fn daily_note_format(config: &Config) -> &str {
+    let format = &config.daily_note_format;
+    if format.trim().is_empty() || DateFormats::validate(format).is_err() {
+        DEFAULT_DAILY_NOTE_FORMAT
+    } else {
+        format
+    }
+}
+
+/// Content for a new daily note: the daily template, or the new-note template when none is set.
+/// Placeholders are filled in for the note's day rather than today.
+pub fn daily_note_content(config: &Config, title: &str, day: NaiveDate) -> Result<String, String> {
+    let when = day
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .unwrap_or_else(Local::now);
+    let template = config.daily_note_template.as_deref().or(config.new_note_template.as_deref());
+    template_content(config, template, title, &when)
+}
+
+fn template_content(config: &Config, template: Option<&Path>, title: &str, now: &DateTime<Local>) -> Result<String, String> {
+    let Some(template) = template.filter(|p| !p.as_os_str().is_empty()) else {
+        return Ok(String::new());
+    };
+
+    let path = if template.is_absolute() {
+        template.to_path_buf()
+    } else {
+        config.notes_folder.join(template)
+    };
+    let text = read_template(&path)?;
+    let clipboard = if text.contains("{clipboard}") { clipboard_text() } else { String::new() };
+    Ok(expand_placeholders(&text, now, &config.formats, &[("title", title), ("clipboard", &clipboard)]))
+}
+
+/// The clipboard's text for `{clipboard}`, or nothing when it holds none.
+fn clipboard_text() -> String {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map(|text| text.replace("\r\n", "\n"))
+        .unwrap_or_default()
+}
+
+/// Names of the templates in the notes folder's templates folder, sorted.
+pub fn list_templates(notes_folder: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(notes_folder.join(TEMPLATES_FOLDER)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "md").then(|| path.file_stem()?.to_str().map(str::to_string))?
+        })
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Path of a template in the templates folder, relative to the notes folder as the template
+/// settings expect.
+pub fn template_file(name: &str) -> PathBuf {
+    Path::new(TEMPLATES_FOLDER).join(format!("{}.md", name))
+}
+
+fn read_template(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read note template '{}': {}", path.display(), e))
+}
+
+/// Replaces characters that aren't allowed in file names so `name` can't escape the notes folder.
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if INVALID_FILE_NAME_CHARS.contains(&c) { '-' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.').to_string();
+    if cleaned.is_empty() { "Untitled".to_string() } else { cleaned }
+}
+
+/// Like `sanitize_file_name`, but keeps the `/` between the folders of a note in a subfolder.
+/// `.` and `..` are dropped, so the note still can't escape the notes folder.
+pub fn sanitize_note_path(name: &str) -> String {
+    let segments: Vec<String> = name
+        .split(['/', '\\'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+        .map(sanitize_file_name)
+        .collect();
+    if segments.is_empty() { "Untitled".to_string() } else { segments.join("/") }
+}
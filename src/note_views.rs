@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_manager::NOTE_VIEWS_FILE;
+
+/// Where a note was being viewed when it was last left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoteView {
+    /// Cursor position as a character index into the note.
+    #[serde(default)]
+    pub cursor: Option<usize>,
+    #[serde(default)]
+    pub editor_scroll: f32,
+    #[serde(default)]
+    pub preview_scroll: f32,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct NoteViewsFile {
+    #[serde(default)]
+    notes: BTreeMap<String, NoteView>,
+}
+
+/// The view of each note opened this session, so switching back to a note puts the cursor and
+/// scroll positions back. With `remember_note_views` they're also kept in the notes folder
+/// across restarts.
+pub struct NoteViews {
+    views: BTreeMap<String, NoteView>,
+    /// Where the views are saved, when they're kept across restarts.
+    file: Option<PathBuf>,
+}
+
+impl NoteViews {
+    pub fn new() -> Self {
+        Self {
+            views: BTreeMap::new(),
+            file: None,
+        }
+    }
+
+    /// Views for the notes in `notes_dir`, read from its views file when `persist` is set.
+    pub fn load(notes_dir: &Path, persist: bool) -> Self {
+        if !persist {
+            return Self::new();
+        }
+        let file = notes_dir.join(NOTE_VIEWS_FILE);
+        let views = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|text| toml::from_str::<NoteViewsFile>(&text).ok())
+            .unwrap_or_default()
+            .notes;
+        Self { views, file: Some(file) }
+    }
+
+    /// Starts or stops keeping the views in `notes_dir` across restarts.
+    pub fn set_persist(&mut self, notes_dir: &Path, persist: bool) {
+        self.file = persist.then(|| notes_dir.join(NOTE_VIEWS_FILE));
+    }
+
+    pub fn get(&self, note: &str) -> Option<NoteView> {
+        self.views.get(note).copied()
+    }
+
+    pub fn remember(&mut self, note: &str, view: NoteView) {
+        self.views.insert(note.to_string(), view);
+    }
+
+    /// Drops the views of notes that no longer exist.
+    pub fn retain(&mut self, exists: impl Fn(&str) -> bool) {
+        self.views.retain(|note, _| exists(note));
+    }
+
+    /// Writes the views to the notes folder, if they're kept across restarts.
+    pub fn save(&self) -> Result<(), String> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let text = toml::to_string(&NoteViewsFile { notes: self.views.clone() })
+            .map_err(|e| format!("Failed to save note positions: {}", e))?;
+        std::fs::write(file, text).map_err(|e| format!("Failed to write '{}': {}", file.display(), e))
+    }
+}
+
+impl Default for NoteViews {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,99 @@
+use eframe::egui;
+
+use crate::config::Config;
+use crate::editor::Editor;
+use crate::i18n::{tr, tr_args};
+use crate::privacy_screen::PrivacyScreen;
+
+/// A note opened in its own OS window. The `NotesList` stays the single source of truth:
+/// edits made here are written back through `AppFrame`, and changes made elsewhere are
+/// pulled in with `sync_content`.
+pub struct NoteWindow {
+    pub note: String,
+    viewport_id: egui::ViewportId,
+    editor: Editor,
+    open: bool,
+}
+
+pub enum NoteWindowAction {
+    None,
+    Changed(String),
+}
+
+impl NoteWindow {
+    pub fn new(note: &str, content: &str, config: &Config, serial: u64) -> Self {
+        let mut editor = Editor::new(config);
+        editor.set_text(content);
+        Self {
+            note: note.to_string(),
+            viewport_id: egui::ViewportId::from_hash_of(("note_window", serial)),
+            editor,
+            open: true,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn set_config(&mut self, config: &Config) {
+        self.editor.set_config(config);
+    }
+
+    /// Replaces the window's text if the note was changed in another window.
+    pub fn sync_content(&mut self, content: &str) {
+        if self.editor.get_text() != content {
+            self.editor.set_text(content);
+        }
+    }
+
+    /// Shows the window, or only the privacy screen while it's up.
+    pub fn render(&mut self, ctx: &egui::Context, privacy_screen: &mut PrivacyScreen) -> NoteWindowAction {
+        let mut action = NoteWindowAction::None;
+        if !self.open {
+            return action;
+        }
+
+        let title = if privacy_screen.is_active() {
+            tr("app-title-hidden").to_string()
+        } else {
+            tr_args("app-title", &[("note", &self.note)])
+        };
+        let builder = egui::ViewportBuilder::default()
+            .with_title(title.clone())
+            .with_inner_size([700.0, 600.0]);
+
+        ctx.show_viewport_immediate(self.viewport_id, builder, |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                if privacy_screen.is_active() {
+                    return;
+                }
+                // Platforms without multiple native windows get an in-app window instead.
+                egui::Window::new(title.as_str())
+                    .id(egui::Id::new(self.viewport_id))
+                    .open(&mut self.open)
+                    .default_size([600.0, 500.0])
+                    .show(ctx, |ui| {
+                        if self.editor.render(ui) {
+                            action = NoteWindowAction::Changed(self.editor.get_text().to_string());
+                        }
+                    });
+                return;
+            }
+
+            if ctx.input(|i| i.viewport().close_requested()) {
+                self.open = false;
+            }
+            privacy_screen.note_activity(ctx);
+            egui::CentralPanel::default().show(ctx, |ui| {
+                if privacy_screen.is_active() {
+                    privacy_screen.render(ui);
+                } else if self.editor.render(ui) {
+                    action = NoteWindowAction::Changed(self.editor.get_text().to_string());
+                }
+            });
+        });
+
+        action
+    }
+}
@@ -1,7 +1,35 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
 use eframe::egui;
 
-use crate::file_manager::FileManager;
+use crate::file_manager::{FileManager, NoteWatcher, TrashedNote};
 use crate::config::Config;
+use crate::headings::{self, Heading};
+use crate::front_matter;
+use crate::i18n::{tr, tr_args};
+use crate::note_conflict::NoteConflict;
+use crate::note_import;
+use crate::note_io::{self, IoEvent, IoStats, NoteIo};
+use crate::note_template;
+use crate::search_index::SearchIndex;
+use crate::tags;
+use crate::writing_activity::WritingActivity;
+
+/// How far each level of the folder tree is indented in the sidebar.
+const FOLDER_INDENT: f32 = 14.0;
+
+/// How many of the most recently modified notes are read in the background after loading the
+/// vault; the rest are read when first opened or when a feature needs every note.
+const PREFETCH_RECENT_NOTES: usize = 20;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ContentState {
+    Unloaded,
+    /// Waiting on the I/O thread.
+    Queued,
+    Loaded,
+}
 
 #[derive(PartialEq, Clone)]
 pub enum SortOrder {
@@ -9,48 +37,505 @@ pub enum SortOrder {
     LastModified,
 }
 
+/// A heading picked from the search results.
+#[derive(Clone, Copy)]
+pub struct HeadingJump {
+    pub index: usize,
+    /// Zero-based line of the heading in the note.
+    pub line: usize,
+    /// Position of the heading among the note's headings.
+    pub ordinal: usize,
+}
+
+/// The name of a note being dragged from the list, e.g. into the editor to link it.
+pub struct NoteDrag(pub String);
+
+pub enum NotesListAction {
+    None,
+    SwitchTo(usize),
+    JumpToHeading(HeadingJump),
+    /// Create a note in this folder, from its context menu in the sidebar.
+    NewNoteIn(String),
+}
+
+/// A line of the sidebar: a folder of the tree, or a note indented under its folder.
+enum SidebarRow {
+    Folder { path: String, depth: usize },
+    Note { index: usize, depth: usize },
+}
+
+/// The folder a note or folder is in, `""` at the top level.
+fn parent_folder(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+/// The last part of a note or folder path, as shown in the tree.
+fn leaf_name(path: &str) -> &str {
+    path.rsplit_once('/').map_or(path, |(_, leaf)| leaf)
+}
+
+/// A note's tags without repeats, for filtering and counting notes by tag.
+fn distinct_tags(content: &str) -> Vec<String> {
+    let mut note_tags = tags::note_tags(content);
+    note_tags.sort();
+    note_tags.dedup();
+    note_tags
+}
+
+fn join_path(folder: &str, name: &str) -> String {
+    if folder.is_empty() { name.to_string() } else { format!("{}/{}", folder, name) }
+}
+
+/// The search box text, split into a note name part and an optional heading part.
+/// `name > heading` searches the headings of matching notes; `@heading` searches the current note.
+struct SearchQuery {
+    name: String,
+    heading: Option<String>,
+    current_only: bool,
+}
+
+impl SearchQuery {
+    fn parse(text: &str) -> Self {
+        let text = text.to_lowercase();
+        if let Some(heading) = text.strip_prefix('@') {
+            Self { name: String::new(), heading: Some(heading.trim().to_string()), current_only: true }
+        } else if let Some((name, heading)) = text.split_once('>') {
+            Self { name: name.trim().to_string(), heading: Some(heading.trim().to_string()), current_only: false }
+        } else {
+            Self { name: text, heading: None, current_only: false }
+        }
+    }
+}
+
 pub struct NotesList {
     file_manager: FileManager,
+    io: NoteIo,
     config: Config,
     notes_list: Vec<String>,
+    /// Subfolders of the notes folder, empty ones included, e.g. `Projects/2025`.
+    folders: Vec<String>,
+    collapsed_folders: HashSet<String>,
+    editing_folder: Option<String>,
+    temp_folder_name: String,
     current_note_index: usize,
     search_text: String,
     editing_note_name: Option<usize>,
     temp_note_name: String,
     current_content: Vec<String>,
+    /// The headings of each note, parallel to `current_content`.
+    note_headings: Vec<Vec<Heading>>,
+    /// The `aliases` in each note's front matter, parallel to `current_content`.
+    note_aliases: Vec<Vec<String>>,
+    /// The lowercased tags of each note, from its front matter and inline `#tags`, parallel to
+    /// `current_content`.
+    note_tags: Vec<Vec<String>>,
+    /// Whether each note's content has been read yet, parallel to `current_content`.
+    content_state: Vec<ContentState>,
+    /// Each note file's modification time when the app last read or wrote it, parallel to
+    /// `current_content`, to tell another program's changes from the app's own.
+    disk_modified: Vec<Option<std::time::SystemTime>>,
+    /// Reports notes changed by other programs, once `watch_for_changes` has been called.
+    watcher: Option<NoteWatcher>,
+    watch_ctx: Option<egui::Context>,
+    /// Set when the current note was reloaded after another program changed it, until the
+    /// editor picks up the new content.
+    current_reloaded: bool,
+    /// Unsaved edits to notes another program changed, until the app picks them up.
+    conflicts: Vec<NoteConflict>,
+    /// The notes in the trash folder, most recently deleted first.
+    trashed: Vec<TrashedNote>,
+    /// When each note last had a snapshot saved into its history, this session.
+    last_snapshot: HashMap<String, Instant>,
+    /// When a note was last saved with new content, added, removed, or renamed, this session.
+    last_change: Option<Instant>,
+    /// Notes read in the background so far, out of how many, while loading.
+    loading: Option<(usize, usize)>,
+    /// Words in each note, saved in the notes folder, so searching covers notes not read yet.
+    search_index: SearchIndex,
+    /// Words written per day, saved in the notes folder alongside the search index.
+    writing_activity: WritingActivity,
     sort_order: SortOrder,
     display_order: Vec<usize>,
+    search_focus_requested: bool,
+    scroll_to_current: bool,
+    /// Only notes with this `#tag` are listed, when set.
+    tag_filter: Option<String>,
+    /// Where the template put the cursor in the note created last, until the editor takes it.
+    template_cursor: Option<usize>,
 }
 
 impl NotesList {
     pub fn new(config: &Config) -> Self {
         Self {
             file_manager: FileManager::new(config),
+            io: NoteIo::new(FileManager::new(config)),
             config: config.clone(),
             notes_list: Vec::new(),
+            folders: Vec::new(),
+            collapsed_folders: HashSet::new(),
+            editing_folder: None,
+            temp_folder_name: String::new(),
             current_note_index: 0,
             search_text: String::new(),
             editing_note_name: None,
             temp_note_name: String::new(),
             current_content: Vec::new(),
+            note_headings: Vec::new(),
+            note_aliases: Vec::new(),
+            note_tags: Vec::new(),
+            content_state: Vec::new(),
+            disk_modified: Vec::new(),
+            watcher: None,
+            watch_ctx: None,
+            current_reloaded: false,
+            conflicts: Vec::new(),
+            trashed: Vec::new(),
+            last_snapshot: HashMap::new(),
+            last_change: None,
+            loading: None,
+            search_index: SearchIndex::default(),
+            writing_activity: WritingActivity::default(),
             sort_order: SortOrder::Alphabetical,
             display_order: Vec::new(),
+            search_focus_requested: false,
+            scroll_to_current: false,
+            tag_filter: None,
+            template_cursor: None,
+        }
+    }
+
+    /// Returns true when the notes folder, the note extensions, or the ignore patterns changed
+    /// and the notes need to be reloaded.
+    pub fn set_config(&mut self, config: &Config) -> bool {
+        let folder_changed = self.config.notes_folder != config.notes_folder;
+        let scan_changed = self.config.note_extensions != config.note_extensions
+            || self.config.new_note_extension != config.new_note_extension
+            || self.config.ignore_patterns != config.ignore_patterns;
+        self.config = config.clone();
+        if scan_changed && !folder_changed {
+            self.flush_writes();
+            self.file_manager = FileManager::new(config);
+            self.io = NoteIo::new(FileManager::new(config));
+        }
+        if folder_changed {
+            self.save_search_index();
+            self.save_writing_activity();
+            self.file_manager = FileManager::new(config);
+            self.io = NoteIo::new(FileManager::new(config));
+            self.current_note_index = 0;
+            self.editing_note_name = None;
+            self.editing_folder = None;
+            self.collapsed_folders.clear();
+            self.tag_filter = None;
         }
+        folder_changed || scan_changed
     }
 
     pub fn load_notes(&mut self) {
         self.notes_list = self.file_manager.load_note_names();
+        self.folders = self.file_manager.list_folders();
+        self.folders.sort_by_key(|folder| folder.to_lowercase());
+        self.trashed = self.file_manager.list_trash();
+        self.search_index = SearchIndex::parse(&self.file_manager.read_search_index());
+        self.search_index.retain(&self.notes_list);
+        self.writing_activity = WritingActivity::parse(&self.file_manager.read_writing_activity());
         self.initialize_content_vectors();
-        self.load_all_content();
+        self.ensure_loaded(self.current_note_index);
         self.compute_display_order();
+        self.queue_background_loads();
+        self.watcher = self.watch_ctx.clone().map(|ctx| NoteWatcher::spawn(self.file_manager.clone(), ctx));
+    }
+
+    /// Starts noticing notes changed by other programs, now and whenever the notes are reloaded.
+    pub fn watch_for_changes(&mut self, ctx: &egui::Context) {
+        self.watch_ctx = Some(ctx.clone());
+        self.watcher = Some(NoteWatcher::spawn(self.file_manager.clone(), ctx.clone()));
+    }
+
+    /// Whether the current note was reloaded from disk since the last call, so the editor needs
+    /// its new content.
+    pub fn take_current_reloaded(&mut self) -> bool {
+        std::mem::take(&mut self.current_reloaded)
+    }
+
+    /// Applies what the I/O thread has finished since the last frame, and reloads notes other
+    /// programs changed. Returns the errors to report.
+    pub fn poll_io(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for event in self.io.poll() {
+            match event {
+                IoEvent::Loaded { name, content, modified } => {
+                    // A note saved since the load was queued already has newer content.
+                    if let Some(index) = self.find_note_index(&name)
+                        && self.content_state[index] != ContentState::Loaded
+                    {
+                        self.set_loaded_content(index, content, modified);
+                    }
+                    self.advance_loading();
+                }
+                IoEvent::Written { name, modified } => {
+                    if let Some(index) = self.find_note_index(&name) {
+                        self.disk_modified[index] = modified;
+                    }
+                    self.search_index.set_modified(&name, modified);
+                }
+                IoEvent::WriteConflict { name, content } => errors.extend(self.keep_conflicting_edits(&name, content)),
+                IoEvent::LoadFailed { name, error } => {
+                    errors.push(format!("Failed to read note '{}': {}", name, error));
+                    self.advance_loading();
+                }
+                IoEvent::WriteFailed { name, error } => {
+                    errors.push(format!("Failed to save note '{}': {}", name, error));
+                }
+                IoEvent::SnapshotFailed { name, error } => {
+                    errors.push(format!("Failed to save the history of note '{}': {}", name, error));
+                }
+            }
+        }
+        self.reload_changed_notes();
+        errors
+    }
+
+    /// Reloads notes another program changed. A note with a save still queued is left alone:
+    /// the save is held back by the I/O thread and reported as a conflict instead.
+    fn reload_changed_notes(&mut self) {
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        for (name, modified) in watcher.poll() {
+            let Some(index) = self.find_note_index(&name) else {
+                continue;
+            };
+            // Notes not read yet get the new version whenever they are.
+            if self.disk_modified[index] == Some(modified)
+                || self.content_state[index] != ContentState::Loaded
+                || self.io.has_pending_write(&name)
+            {
+                continue;
+            }
+            let Ok(content) = self.file_manager.read_note(&name) else {
+                continue;
+            };
+            if content == self.current_content[index] {
+                self.disk_modified[index] = Some(modified);
+            } else {
+                self.set_loaded_content(index, content, Some(modified));
+                self.current_reloaded |= index == self.current_note_index;
+            }
+        }
+    }
+
+    /// Handles a save held back because another program changed the note: the note takes the
+    /// other program's version, and the unsaved edits are kept as a conflict for the user to
+    /// resolve.
+    fn keep_conflicting_edits(&mut self, name: &str, content: String) -> Option<String> {
+        let index = self.find_note_index(name)?;
+        let modified = self.file_manager.get_note_modified_time(name);
+        let theirs = match self.file_manager.read_note(name) {
+            Ok(theirs) => theirs,
+            Err(e) => return Some(format!("Failed to read note '{}' after another program changed it: {}", name, e)),
+        };
+        self.set_loaded_content(index, theirs.clone(), modified);
+        self.current_reloaded |= index == self.current_note_index;
+        self.conflicts.push(NoteConflict { note: name.to_string(), mine: content, theirs });
+        None
+    }
+
+    /// Notes whose edits clashed with another program's changes since the last call.
+    pub fn take_conflicts(&mut self) -> Vec<NoteConflict> {
+        std::mem::take(&mut self.conflicts)
+    }
+
+    /// How many notes have been read so far, out of how many, while the vault is still loading.
+    pub fn loading_progress(&self) -> Option<(usize, usize)> {
+        self.loading
+    }
+
+    pub fn io_stats(&self) -> IoStats {
+        self.io.stats()
+    }
+
+    /// Waits for queued saves to reach the disk, e.g. before the app exits.
+    pub fn flush_writes(&mut self) {
+        self.io.flush();
+        self.save_search_index();
+        self.save_writing_activity();
+    }
+
+    fn save_search_index(&mut self) {
+        if self.search_index.is_dirty() && self.file_manager.write_search_index(&self.search_index.serialize()) {
+            self.search_index.mark_saved();
+        }
+    }
+
+    /// Writes the words written per day to the notes folder, e.g. before the statistics read it.
+    pub fn save_writing_activity(&mut self) {
+        if self.writing_activity.is_dirty() && self.file_manager.write_writing_activity(&self.writing_activity.serialize()) {
+            self.writing_activity.mark_saved();
+        }
+    }
+
+    fn advance_loading(&mut self) {
+        if let Some((done, total)) = self.loading.as_mut() {
+            *done += 1;
+            if done >= total {
+                self.loading = None;
+                self.save_search_index();
+            }
+        }
     }
 
     pub fn get_search_text_mut(&mut self) -> &mut String {
         &mut self.search_text
     }
 
+    pub fn focus_search(&mut self) {
+        self.search_focus_requested = true;
+    }
+
+    /// Whether the search box should take keyboard focus this frame.
+    pub fn take_search_focus_request(&mut self) -> bool {
+        std::mem::take(&mut self.search_focus_requested)
+    }
+
+    /// The note `step` places before or after the current one in the visible list, if any.
+    pub fn adjacent_note_index(&self, step: isize) -> Option<usize> {
+        let visible: Vec<usize> = self
+            .sidebar_rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                SidebarRow::Note { index, .. } => Some(index),
+                SidebarRow::Folder { .. } => None,
+            })
+            .collect();
+        let position = visible.iter().position(|&index| index == self.current_note_index)?;
+        let target = position.checked_add_signed(step)?;
+        visible.get(target).copied()
+    }
+
+    /// Scrolls the list so the current note is visible, e.g. after switching with the keyboard.
+    pub fn reveal_current(&mut self) {
+        self.scroll_to_current = true;
+        if let Some(name) = self.notes_list.get(self.current_note_index) {
+            let mut folder = parent_folder(name);
+            while !folder.is_empty() {
+                self.collapsed_folders.remove(folder);
+                folder = parent_folder(folder);
+            }
+        }
+    }
+
+    pub fn rename_current_note(&mut self) {
+        if let Some(name) = self.notes_list.get(self.current_note_index) {
+            self.temp_note_name = leaf_name(name).to_string();
+            self.editing_note_name = Some(self.current_note_index);
+        }
+    }
+
+    pub fn tag_filter(&self) -> Option<&str> {
+        self.tag_filter.as_deref()
+    }
+
+    pub fn set_tag_filter(&mut self, tag: Option<String>) {
+        if tag.is_some() {
+            self.load_all_content_in_background();
+        }
+        self.tag_filter = tag;
+    }
+
+    /// Every tag used in the notes read so far, alphabetically, with how many notes have it.
+    pub fn all_tags(&self) -> Vec<(&str, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in self.note_tags.iter().flatten() {
+            *counts.entry(tag).or_default() += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    fn matches_search(&self, index: usize) -> bool {
+        let query = SearchQuery::parse(&self.search_text);
+        let name_matches = if query.current_only {
+            index == self.current_note_index
+        } else {
+            let name = &self.notes_list[index];
+            query.name.is_empty()
+                || name.to_lowercase().contains(&query.name)
+                || self.matching_alias(index, &query).is_some()
+                || self.search_index.matches(name, &query.name)
+        };
+        let tag_matches = self.tag_filter.as_ref().is_none_or(|tag| {
+            self.note_tags.get(index).is_some_and(|note_tags| note_tags.contains(&tag.to_lowercase()))
+        });
+        let heading_matches = query.heading.as_ref().is_none_or(|heading| {
+            heading.is_empty() || !self.matching_headings(index, &query).is_empty()
+        });
+        name_matches && tag_matches && heading_matches
+    }
+
+    /// The first alias of note `index` matching the name part of the search, when its name doesn't.
+    fn matching_alias(&self, index: usize, query: &SearchQuery) -> Option<&str> {
+        if query.name.is_empty() || self.notes_list[index].to_lowercase().contains(&query.name) {
+            return None;
+        }
+        self.note_aliases
+            .get(index)?
+            .iter()
+            .find(|alias| alias.to_lowercase().contains(&query.name))
+            .map(String::as_str)
+    }
+
+    /// The note called `name`, or with `name` among its aliases, ignoring case. A note in a
+    /// subfolder can also be found by its name without the folder.
+    pub fn resolve_note(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.find_note_index(name)
+            .or_else(|| self.notes_list.iter().position(|note| note.eq_ignore_ascii_case(name)))
+            .or_else(|| self.notes_list.iter().position(|note| leaf_name(note).eq_ignore_ascii_case(name)))
+            .or_else(|| {
+                self.note_aliases
+                    .iter()
+                    .position(|aliases| aliases.iter().any(|alias| alias.to_lowercase() == name.to_lowercase()))
+            })
+    }
+
+    /// The headings of note `index` matching the heading part of the search, with their ordinals.
+    /// Empty when the search has no heading part.
+    fn matching_headings(&self, index: usize, query: &SearchQuery) -> Vec<(usize, &Heading)> {
+        let Some(heading_query) = &query.heading else {
+            return Vec::new();
+        };
+        self.note_headings
+            .get(index)
+            .map(|note_headings| {
+                note_headings
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, heading)| heading.text.to_lowercase().contains(heading_query.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The first heading listed for the current search, in display order.
+    pub fn first_heading_match(&self) -> Option<HeadingJump> {
+        let query = SearchQuery::parse(&self.search_text);
+        self.display_order
+            .iter()
+            .copied()
+            .filter(|&index| self.matches_search(index))
+            .find_map(|index| {
+                self.matching_headings(index, &query)
+                    .first()
+                    .map(|&(ordinal, heading)| HeadingJump { index, line: heading.line, ordinal })
+            })
+    }
+
+    pub fn get_current_note_index(&self) -> usize {
+        self.current_note_index
+    }
+
     pub fn get_current_note_name(&self) -> &str {
-        self.notes_list.get(self.current_note_index).map(|s| s.as_str()).unwrap_or("No Note")
+        self.notes_list.get(self.current_note_index).map(|s| s.as_str()).unwrap_or(tr("no-note"))
     }
 
     pub fn get_current_content(&self) -> &str {
@@ -61,18 +546,117 @@ impl NotesList {
         }
     }
 
-    pub fn create_new_note(&mut self) -> Option<String> {
-        let new_note_name = format!("Note {}", self.notes_list.len() + 1);
-        if self.file_manager.create_note(&new_note_name) {
-            self.notes_list.push(new_note_name.clone());
-            self.current_content.push(String::new());
+    pub fn get_current_headings(&self) -> &[Heading] {
+        self.note_headings.get(self.current_note_index).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn get_note_names(&self) -> &[String] {
+        &self.notes_list
+    }
+
+    pub fn get_all_content(&self) -> &[String] {
+        &self.current_content
+    }
+
+    /// Every note's name paired with its content.
+    pub fn notes_with_content(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.notes_list.iter().map(String::as_str).zip(self.current_content.iter().map(String::as_str))
+    }
+
+    pub fn get_file_manager(&self) -> &FileManager {
+        &self.file_manager
+    }
+
+    /// Creates a note in `folder` (`""` for the top level) named by the configured pattern and
+    /// filled from the template, if any. A template that can't be read is reported and the note
+    /// is created empty.
+    pub fn create_new_note(&mut self, folder: &str) -> (Option<String>, Option<String>) {
+        let now = chrono::Local::now();
+        let siblings: Vec<String> = self
+            .notes_list
+            .iter()
+            .filter(|name| parent_folder(name) == folder)
+            .map(|name| leaf_name(name).to_string())
+            .collect();
+        let title = note_template::new_note_name(&self.config.new_note_pattern, &siblings, &now, &self.config.formats);
+        let content = note_template::new_note_content(&self.config, &title, &now);
+        let new_note_name = join_path(folder, &title);
+        let (created, template_error) = self.create_named_note(&new_note_name, content);
+        (created.then_some(new_note_name), template_error)
+    }
+
+    /// Switches to the note called `name`, creating it from the template if it doesn't exist.
+    /// Returns whether the note is now current, plus any template error.
+    pub fn open_or_create_note(&mut self, name: &str) -> (bool, Option<String>) {
+        if let Some(index) = self.find_note_index(name) {
+            return (self.switch_to_note(index), None);
+        }
+        let content = note_template::new_note_content(&self.config, name, &chrono::Local::now());
+        self.create_named_note(name, content)
+    }
+
+    /// Switches to the daily note for `day`, creating it from the daily template if needed.
+    pub fn open_or_create_daily_note(&mut self, day: chrono::NaiveDate) -> (bool, Option<String>) {
+        let name = note_template::daily_note_name(&self.config, day);
+        if let Some(index) = self.find_note_index(&name) {
+            return (self.switch_to_note(index), None);
+        }
+        let content = note_template::daily_note_content(&self.config, &name, day);
+        self.create_named_note(&name, content)
+    }
 
+    /// Days that already have a daily note.
+    pub fn daily_note_days(&self) -> Vec<chrono::NaiveDate> {
+        self.notes_list
+            .iter()
+            .filter_map(|name| note_template::daily_note_day(&self.config, name))
+            .collect()
+    }
+
+    fn create_named_note(&mut self, name: &str, content: Result<String, String>) -> (bool, Option<String>) {
+        let (content, template_error) = match content {
+            Ok(content) => (content, None),
+            Err(e) => (String::new(), Some(e)),
+        };
+        let (content, cursor) = note_template::take_cursor(&content);
+
+        if self.add_note(name, &content) {
             self.current_note_index = self.notes_list.len() - 1;
-            self.compute_display_order();
-            Some(new_note_name)
+            self.template_cursor = cursor;
+            (true, template_error)
         } else {
-            None
+            (false, template_error)
+        }
+    }
+
+    /// Where `{{cursor}}` was in the template of the note just created, if it had one.
+    pub fn take_template_cursor(&mut self) -> Option<usize> {
+        self.template_cursor.take()
+    }
+
+    /// Creates a note without switching to it, as when a script writes to a new note.
+    pub fn add_note(&mut self, name: &str, content: &str) -> bool {
+        if !self.file_manager.create_note(name, content) {
+            return false;
         }
+        self.push_note(name, content);
+        true
+    }
+
+    /// Adds a note whose file already exists to the list.
+    fn push_note(&mut self, name: &str, content: &str) {
+        self.remember_folder(parent_folder(name));
+        self.notes_list.push(name.to_string());
+        self.note_headings.push(headings::parse_headings(content));
+        self.note_aliases.push(front_matter::aliases(content));
+        self.note_tags.push(distinct_tags(content));
+        self.current_content.push(content.to_string());
+        self.content_state.push(ContentState::Loaded);
+        let modified = self.file_manager.get_note_modified_time(name);
+        self.disk_modified.push(modified);
+        self.index_note(self.notes_list.len() - 1, modified);
+        self.compute_display_order();
+        self.last_change = Some(Instant::now());
     }
 
     pub fn delete_current_note(&mut self) -> bool {
@@ -80,8 +664,15 @@ impl NotesList {
             return false;
         }
 
+        self.io.flush();
         let note_name = &self.notes_list[self.current_note_index];
-        if self.file_manager.delete_note(note_name) {
+        let deleted = if self.config.delete_to_trash {
+            self.file_manager.trash_note(note_name)
+        } else {
+            self.file_manager.delete_note(note_name)
+        };
+        if deleted {
+            self.trashed = self.file_manager.list_trash();
             self.remove_note_from_vectors(self.current_note_index);
             self.adjust_current_index_after_deletion();
             self.compute_display_order();
@@ -91,12 +682,55 @@ impl NotesList {
         }
     }
 
+    /// Recreates a deleted note with its old content and makes it current, taking it back out
+    /// of the trash when it's there.
+    pub fn restore_note(&mut self, name: &str, content: &str) -> bool {
+        if self.find_note_index(name).is_some() {
+            return false;
+        }
+        let trashed = self.trashed.iter().find(|trashed| trashed.name == name).map(|trashed| trashed.file.clone());
+        match trashed {
+            Some(file) if self.restore_trashed_note(&file).is_some() => {
+                if self.current_content[self.current_note_index] != content {
+                    self.save_content_at(self.current_note_index, content);
+                }
+                true
+            }
+            _ if self.add_note(name, content) => {
+                self.current_note_index = self.notes_list.len() - 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The notes in the trash folder, most recently deleted first.
+    pub fn trashed_notes(&self) -> &[TrashedNote] {
+        &self.trashed
+    }
+
+    /// Moves a note out of the trash and makes it current. It gets a number appended when a
+    /// note has taken its name since. Returns the name it was restored under.
+    pub fn restore_trashed_note(&mut self, file: &str) -> Option<String> {
+        let trashed = self.trashed.iter().position(|trashed| trashed.file == file)?;
+        let name = note_import::unique_name(&self.trashed[trashed].name, &self.notes_list);
+        if !self.file_manager.restore_trashed_note(file, &name) {
+            return None;
+        }
+        self.trashed.remove(trashed);
+        let content = self.file_manager.read_note_content(&name);
+        self.push_note(&name, &content);
+        self.current_note_index = self.notes_list.len() - 1;
+        Some(name)
+    }
+
     pub fn find_note_index(&self, name: &str) -> Option<usize> {
         self.notes_list.iter().position(|n| n == name)
     }
 
     pub fn switch_to_note(&mut self, index: usize) -> bool {
         if index < self.notes_list.len() {
+            self.ensure_loaded(index);
             self.current_note_index = index;
             true
         } else {
@@ -105,11 +739,86 @@ impl NotesList {
     }
 
     pub fn save_current_content(&mut self, content: &str) {
-        if self.current_note_index < self.current_content.len() {
-            self.current_content[self.current_note_index] = content.to_string();
-            let note_name = self.notes_list[self.current_note_index].clone();
-            self.file_manager.write_note_content(&note_name, content);
+        self.save_content_at(self.current_note_index, content);
+    }
+
+    /// Saves a note that may not be the current one, e.g. one open in a separate window.
+    pub fn save_note_content(&mut self, name: &str, content: &str) {
+        if let Some(index) = self.find_note_index(name) {
+            self.save_content_at(index, content);
+        }
+    }
+
+    /// A note's content, read from disk right away if it hasn't been loaded yet.
+    pub fn load_note_now(&mut self, name: &str) -> Option<&str> {
+        let index = self.find_note_index(name)?;
+        if self.content_state[index] != ContentState::Loaded {
+            let note = note_io::read_notes(&self.file_manager, vec![name.to_string()]).pop()?;
+            self.set_loaded_content(index, note.content.ok()?, note.modified);
+        }
+        Some(&self.current_content[index])
+    }
+
+    /// Notes whose name contains `query` or whose content has all its words, in display order.
+    pub fn search_notes(&self, query: &str) -> Vec<&str> {
+        let query = query.to_lowercase();
+        self.display_order
+            .iter()
+            .map(|&index| self.notes_list[index].as_str())
+            .filter(|name| name.to_lowercase().contains(&query) || self.search_index.matches(name, &query))
+            .collect()
+    }
+
+    pub fn get_note_content(&self, name: &str) -> Option<&str> {
+        self.find_note_index(name)
+            .and_then(|index| self.current_content.get(index))
+            .map(|content| content.as_str())
+    }
+
+    fn save_content_at(&mut self, index: usize, content: &str) {
+        if index < self.current_content.len() {
+            if self.content_state[index] == ContentState::Loaded {
+                let today = chrono::Local::now().date_naive();
+                self.writing_activity.record_edit(today, &self.current_content[index], content);
+                self.snapshot_if_due(index, content);
+            }
+            if self.current_content[index] != content || self.content_state[index] != ContentState::Loaded {
+                self.last_change = Some(Instant::now());
+            }
+            self.set_loaded_content(index, content.to_string(), None);
+            self.io.write(&self.notes_list[index], content, self.disk_modified[index]);
+        }
+    }
+
+    pub fn last_change(&self) -> Option<Instant> {
+        self.last_change
+    }
+
+    /// Saves the version a save is about to replace into the note's history, unless history is
+    /// off or the note's last snapshot is newer than `note_history_minutes`.
+    fn snapshot_if_due(&mut self, index: usize, content: &str) {
+        let minutes = self.config.note_history_minutes;
+        if minutes == 0 || self.current_content[index] == content {
+            return;
+        }
+        let interval = Duration::from_secs(u64::from(minutes) * 60);
+        if self.last_snapshot.get(&self.notes_list[index]).is_some_and(|taken| taken.elapsed() < interval) {
+            return;
         }
+        self.snapshot_note(&self.notes_list[index].clone());
+    }
+
+    /// Saves a note's current content into its history right away, as before restoring an
+    /// earlier version so the restore can be undone the same way.
+    pub fn snapshot_note(&mut self, name: &str) {
+        let Some(index) = self.find_note_index(name) else {
+            return;
+        };
+        if self.content_state[index] != ContentState::Loaded {
+            return;
+        }
+        self.io.snapshot(name, &self.current_content[index], self.config.note_history_limit.max(1));
+        self.last_snapshot.insert(name.to_string(), Instant::now());
     }
 
     pub fn set_sort_order(&mut self, order: SortOrder) {
@@ -121,27 +830,126 @@ impl NotesList {
         &self.sort_order
     }
 
-    pub fn render(&mut self, ui: &mut egui::Ui) -> Option<usize> {
-        let mut switch_to_note_index = None;
+    /// The rows of the sidebar. Notes are shown as a tree under their folders, or as a flat list
+    /// of full names while searching or filtering by tag, so matches in closed folders still show.
+    fn sidebar_rows(&self) -> Vec<SidebarRow> {
+        if !self.shows_tree() {
+            return self
+                .display_order
+                .iter()
+                .copied()
+                .filter(|&index| self.matches_search(index))
+                .map(|index| SidebarRow::Note { index, depth: 0 })
+                .collect();
+        }
+        let mut rows = Vec::new();
+        self.push_folder_rows("", 0, &mut rows);
+        rows
+    }
+
+    fn shows_tree(&self) -> bool {
+        !self.folders.is_empty() && self.search_text.trim().is_empty() && self.tag_filter.is_none()
+    }
+
+    /// Adds the subfolders of `folder`, each followed by its contents unless it's collapsed, then
+    /// the notes directly in it.
+    fn push_folder_rows(&self, folder: &str, depth: usize, rows: &mut Vec<SidebarRow>) {
+        for path in self.folders.iter().filter(|path| parent_folder(path) == folder) {
+            rows.push(SidebarRow::Folder { path: path.clone(), depth });
+            if !self.collapsed_folders.contains(path) {
+                self.push_folder_rows(path, depth + 1, rows);
+            }
+        }
+        rows.extend(
+            self.display_order
+                .iter()
+                .copied()
+                .filter(|&index| parent_folder(&self.notes_list[index]) == folder)
+                .map(|index| SidebarRow::Note { index, depth }),
+        );
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui) -> NotesListAction {
+        let mut action = NotesListAction::None;
+        let query = SearchQuery::parse(&self.search_text);
+        if query.heading.is_some() {
+            self.load_all_content_in_background();
+        }
+        let tree = self.shows_tree();
         let mut start_editing_index = None;
         let mut finish_editing = false;
         let mut rename_action = None;
+        let mut move_action = None;
+        let mut start_editing_folder = None;
+        let mut finish_folder_editing = false;
+        let mut folder_rename = None;
+        let mut new_folder_in = None;
 
-        for display_pos in 0..self.display_order.len() {
-            let index = self.display_order[display_pos];
-            let note_name = self.notes_list[index].clone();
-
-            if !self.search_text.is_empty()
-                && !note_name
-                    .to_lowercase()
-                    .contains(&self.search_text.to_lowercase())
-            {
-                continue;
-            }
+        for row in self.sidebar_rows() {
+            let (index, depth) = match row {
+                SidebarRow::Note { index, depth } => (index, depth),
+                SidebarRow::Folder { path, depth } => {
+                    ui.horizontal(|ui| {
+                        ui.add_space(FOLDER_INDENT * depth as f32);
+                        if self.editing_folder.as_ref() == Some(&path) {
+                            let response = ui.add_sized(
+                                [ui.available_width(), 25.0],
+                                egui::TextEdit::singleline(&mut self.temp_folder_name)
+                                    .id(egui::Id::new(("edit_folder", &path)))
+                            );
+                            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                finish_folder_editing = true;
+                            } else if response.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                                let new_leaf = note_template::sanitize_file_name(&self.temp_folder_name);
+                                if new_leaf != leaf_name(&path) {
+                                    folder_rename = Some((path.clone(), join_path(parent_folder(&path), &new_leaf)));
+                                }
+                                finish_folder_editing = true;
+                            }
+                            response.request_focus();
+                            return;
+                        }
 
+                        let palette = self.config.theme.palette();
+                        let collapsed = self.collapsed_folders.contains(&path);
+                        let arrow = if collapsed { "▶" } else { "▼" };
+                        let label = egui::RichText::new(format!("{} 📁 {}", arrow, leaf_name(&path)))
+                            .color(palette.list_text)
+                            .font(self.config.get_list_font_id(self.config.list_font_size));
+                        let button = ui.add_sized([ui.available_width(), 25.0], egui::Button::new(label).frame(false));
+                        if button.dnd_hover_payload::<NoteDrag>().is_some() {
+                            let stroke = egui::Stroke::new(2.0, ui.visuals().selection.stroke.color);
+                            ui.painter().rect_stroke(button.rect, 3.0, stroke, egui::StrokeKind::Inside);
+                        }
+                        if let Some(note) = button.dnd_release_payload::<NoteDrag>() {
+                            move_action = Some((note.0.clone(), path.clone()));
+                        }
+                        if button.clicked() && !self.collapsed_folders.remove(&path) {
+                            self.collapsed_folders.insert(path.clone());
+                        }
+                        button.context_menu(|ui| {
+                            if ui.button(tr("notes-new-note-here")).clicked() {
+                                action = NotesListAction::NewNoteIn(path.clone());
+                                ui.close();
+                            }
+                            if ui.button(tr("notes-new-folder-here")).clicked() {
+                                new_folder_in = Some(path.clone());
+                                ui.close();
+                            }
+                            if ui.button(tr("notes-rename-folder")).clicked() {
+                                start_editing_folder = Some(path.clone());
+                                ui.close();
+                            }
+                        });
+                    });
+                    continue;
+                }
+            };
+            let note_name = self.notes_list[index].clone();
             let is_selected = index == self.current_note_index;
 
             ui.horizontal(|ui| {
+                ui.add_space(FOLDER_INDENT * depth as f32);
                 if self.editing_note_name == Some(index) {
                     let response = ui.add_sized(
                         [ui.available_width(), 25.0],
@@ -149,9 +957,16 @@ impl NotesList {
                             .id(egui::Id::new(format!("edit_note_{}", index)))
                     );
 
-                    if response.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        finish_editing = true;
+                    } else if response.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Enter)) {
                         let old_name = note_name.clone();
-                        let new_name = self.temp_note_name.clone();
+                        // Typing `Folder/Name` moves the note into that subfolder.
+                        let new_name = if self.temp_note_name.trim().is_empty() {
+                            String::new()
+                        } else {
+                            note_template::sanitize_note_path(&join_path(parent_folder(&old_name), &self.temp_note_name))
+                        };
 
                         if !new_name.is_empty() && new_name != old_name {
                             rename_action = Some((old_name, new_name));
@@ -161,33 +976,82 @@ impl NotesList {
 
                     response.request_focus();
                 } else {
-                    let button_label = egui::RichText::new(note_name.as_str())
-                        .color(egui::Color32::WHITE)
+                    let palette = self.config.theme.palette();
+                    let shown_name = if tree { leaf_name(&note_name) } else { note_name.as_str() };
+                    let button_label = egui::RichText::new(shown_name)
+                        .color(palette.list_text)
                         .font(self.config.get_list_font_id(self.config.list_font_size))
                         .strong();
 
                     let button = if is_selected {
                         let button = egui::Button::new(button_label)
-                            .fill(egui::Color32::from_rgb(60, 120, 200));
+                            .fill(palette.list_selected_fill);
                         ui.add_sized([ui.available_width(), 25.0], button)
                     } else {
                         ui.add_sized([ui.available_width(), 25.0], egui::Button::new(button_label))
                     };
+                    let button = button.interact(egui::Sense::drag());
+                    button.dnd_set_drag_payload(NoteDrag(note_name.clone()));
+
+                    button.widget_info(|| {
+                        egui::WidgetInfo::selected(egui::WidgetType::SelectableLabel, true, is_selected, note_name.as_str())
+                    });
+                    if button.has_focus() {
+                        let stroke = egui::Stroke::new(2.0, ui.visuals().selection.stroke.color);
+                        ui.painter().rect_stroke(button.rect.expand(1.0), 3.0, stroke, egui::StrokeKind::Outside);
+                    }
+                    if is_selected && self.scroll_to_current {
+                        button.scroll_to_me(None);
+                        self.scroll_to_current = false;
+                    }
 
                     if button.clicked() && index != self.current_note_index {
-                        switch_to_note_index = Some(index);
+                        action = NotesListAction::SwitchTo(index);
                     }
 
                     if button.double_clicked() {
                         start_editing_index = Some(index);
                     }
+
+                    if !self.folders.is_empty() {
+                        button.context_menu(|ui| {
+                            ui.menu_button(tr("notes-move-to"), |ui| {
+                                let current_folder = parent_folder(&note_name);
+                                let top_level = std::iter::once((String::new(), tr("notes-top-level").to_string()));
+                                let folders = self.folders.iter().map(|folder| (folder.clone(), folder.clone()));
+                                for (folder, label) in top_level.chain(folders) {
+                                    if ui.add_enabled(folder != current_folder, egui::Button::new(label)).clicked() {
+                                        move_action = Some((note_name.clone(), folder));
+                                        ui.close();
+                                    }
+                                }
+                            });
+                        });
+                    }
                 }
             });
+
+            if let Some(alias) = self.matching_alias(index, &query) {
+                ui.horizontal(|ui| {
+                    ui.add_space(12.0);
+                    ui.weak(tr_args("notes-alias-match", &[("alias", &alias)]));
+                });
+            }
+
+            for (ordinal, heading) in self.matching_headings(index, &query) {
+                ui.horizontal(|ui| {
+                    ui.add_space(12.0 * heading.level as f32);
+                    let label = egui::RichText::new(&heading.text).color(self.config.theme.palette().list_text);
+                    if ui.add(egui::Button::new(label).frame(false)).clicked() {
+                        action = NotesListAction::JumpToHeading(HeadingJump { index, line: heading.line, ordinal });
+                    }
+                });
+            }
         }
 
         if let Some(idx) = start_editing_index {
             self.editing_note_name = Some(idx);
-            self.temp_note_name = self.notes_list[idx].clone();
+            self.temp_note_name = leaf_name(&self.notes_list[idx]).to_string();
         }
         if finish_editing {
             self.editing_note_name = None;
@@ -195,28 +1059,199 @@ impl NotesList {
         if let Some((old, new)) = rename_action {
             self.rename_note(&old, &new);
         }
+        if let Some((note, folder)) = move_action {
+            self.move_note(&note, &folder);
+        }
+        if let Some(folder) = start_editing_folder {
+            self.temp_folder_name = leaf_name(&folder).to_string();
+            self.editing_folder = Some(folder);
+        }
+        if finish_folder_editing {
+            self.editing_folder = None;
+        }
+        if let Some((old, new)) = folder_rename {
+            self.rename_folder(&old, &new);
+        }
+        if let Some(parent) = new_folder_in {
+            self.create_folder(&parent);
+        }
+
+        action
+    }
+
+    /// Creates a folder named "New Folder" in `parent` (`""` for the top level) and starts
+    /// renaming it in the sidebar.
+    pub fn create_folder(&mut self, parent: &str) {
+        let base = tr("notes-new-folder");
+        let is_taken = |path: &str| self.folders.iter().any(|folder| folder.eq_ignore_ascii_case(path));
+        let Some(folder) = std::iter::once(join_path(parent, base))
+            .chain((2..).map(|suffix| join_path(parent, &format!("{} {}", base, suffix))))
+            .find(|path| !is_taken(path))
+        else {
+            return;
+        };
+        if self.file_manager.create_folder(&folder) {
+            self.remember_folder(&folder);
+            self.collapsed_folders.remove(parent);
+            self.temp_folder_name = leaf_name(&folder).to_string();
+            self.editing_folder = Some(folder);
+        }
+    }
+
+    /// Adds `folder` and the folders above it to the tree, if they aren't in it yet.
+    fn remember_folder(&mut self, folder: &str) {
+        let mut path = folder;
+        while !path.is_empty() {
+            if !self.folders.iter().any(|known| known == path) {
+                self.folders.push(path.to_string());
+            }
+            path = parent_folder(path);
+        }
+        self.folders.sort_by_key(|folder| folder.to_lowercase());
+    }
+
+    /// Moves a note into `folder` (`""` for the top level), keeping its name.
+    fn move_note(&mut self, note: &str, folder: &str) {
+        let target = join_path(folder, leaf_name(note));
+        if target != note {
+            self.rename_note(note, &target);
+        }
+    }
 
-        switch_to_note_index
+    /// Renames or moves a folder along with the notes and folders in it.
+    fn rename_folder(&mut self, old_folder: &str, new_folder: &str) {
+        let old_prefix = format!("{}/", old_folder);
+        if new_folder == old_folder || new_folder.starts_with(&old_prefix) {
+            return;
+        }
+        self.io.flush();
+        if !self.file_manager.rename_folder(old_folder, new_folder) {
+            return;
+        }
+        let moved = |path: &str| {
+            if path == old_folder {
+                Some(new_folder.to_string())
+            } else {
+                path.strip_prefix(&old_prefix).map(|rest| join_path(new_folder, rest))
+            }
+        };
+        for index in 0..self.notes_list.len() {
+            if let Some(new_name) = moved(&self.notes_list[index]) {
+                self.search_index.rename(&self.notes_list[index], &new_name);
+                self.notes_list[index] = new_name;
+            }
+        }
+        for folder in &mut self.folders {
+            if let Some(new_path) = moved(folder) {
+                *folder = new_path;
+            }
+        }
+        self.collapsed_folders = self.collapsed_folders.iter().map(|folder| moved(folder).unwrap_or_else(|| folder.clone())).collect();
+        self.remember_folder(parent_folder(new_folder));
+        self.compute_display_order();
+        self.last_change = Some(Instant::now());
     }
 
     fn initialize_content_vectors(&mut self) {
         self.current_content.clear();
+        self.note_headings.clear();
+        self.note_aliases.clear();
+        self.note_tags.clear();
+        self.content_state.clear();
+        self.disk_modified.clear();
 
         for _ in &self.notes_list {
             self.current_content.push(String::new());
+            self.note_headings.push(Vec::new());
+            self.note_aliases.push(Vec::new());
+            self.note_tags.push(Vec::new());
+            self.content_state.push(ContentState::Unloaded);
+            self.disk_modified.push(None);
+        }
+    }
+
+    /// Reads a note right away if the background load hasn't reached it yet.
+    fn ensure_loaded(&mut self, index: usize) {
+        if self.content_state.get(index).is_some_and(|&state| state != ContentState::Loaded) {
+            let name = &self.notes_list[index];
+            let modified = self.file_manager.get_note_modified_time(name);
+            let content = self.file_manager.read_note_content(name);
+            self.set_loaded_content(index, content, modified);
+        }
+    }
+
+    /// Reads every note not read yet, right away. For features whose results would be wrong with
+    /// notes missing, like the unused attachments report.
+    pub fn load_all_content(&mut self) {
+        let indices: Vec<usize> =
+            (0..self.notes_list.len()).filter(|&index| self.content_state[index] != ContentState::Loaded).collect();
+        let names = indices.iter().map(|&index| self.notes_list[index].clone()).collect();
+        for (index, note) in indices.into_iter().zip(note_io::read_notes(&self.file_manager, names)) {
+            self.set_loaded_content(index, note.content.unwrap_or_default(), note.modified);
+        }
+    }
+
+    /// Queues every note not read yet for the I/O thread, for features that can fill in as notes
+    /// arrive, like filtering by tag.
+    pub fn load_all_content_in_background(&mut self) {
+        self.queue_loads((0..self.notes_list.len()).collect());
+    }
+
+    /// Queues the most recently modified notes, plus any note the search index is out of date for.
+    fn queue_background_loads(&mut self) {
+        let modified: Vec<_> = self.notes_list.iter().map(|name| self.file_manager.get_note_modified_time(name)).collect();
+        let mut recent: Vec<usize> = (0..self.notes_list.len()).collect();
+        recent.sort_by_key(|&index| std::cmp::Reverse(modified[index]));
+        recent.truncate(PREFETCH_RECENT_NOTES);
+        let stale = (0..self.notes_list.len()).filter(|&index| self.search_index.is_stale(&self.notes_list[index], modified[index]));
+        let indices = recent.into_iter().chain(stale).collect();
+        self.queue_loads(indices);
+    }
+
+    fn queue_loads(&mut self, indices: Vec<usize>) {
+        let mut names = Vec::new();
+        for index in indices {
+            if self.content_state[index] == ContentState::Unloaded {
+                self.content_state[index] = ContentState::Queued;
+                names.push(self.notes_list[index].clone());
+            }
         }
+        if names.is_empty() {
+            return;
+        }
+        let (done, total) = self.loading.unwrap_or((0, 0));
+        self.loading = Some((done, total + names.len()));
+        self.io.load(names);
     }
 
-    fn load_all_content(&mut self) {
-        for (i, note_name) in self.notes_list.iter().enumerate() {
-            let content = self.file_manager.read_note_content(note_name);
-            self.current_content[i] = content;
+    /// `modified` is the file's modification time when `content` was read from it, or `None`
+    /// when it's a save that hasn't reached the disk yet.
+    fn set_loaded_content(&mut self, index: usize, content: String, modified: Option<std::time::SystemTime>) {
+        self.note_headings[index] = headings::parse_headings(&content);
+        self.note_aliases[index] = front_matter::aliases(&content);
+        self.note_tags[index] = distinct_tags(&content);
+        self.current_content[index] = content;
+        self.content_state[index] = ContentState::Loaded;
+        if modified.is_some() {
+            self.disk_modified[index] = modified;
         }
+        self.index_note(index, modified);
+    }
+
+    fn index_note(&mut self, index: usize, modified: Option<std::time::SystemTime>) {
+        self.search_index.update(&self.notes_list[index], &self.current_content[index], modified);
     }
 
     fn remove_note_from_vectors(&mut self, index: usize) {
+        self.search_index.remove(&self.notes_list[index]);
         self.notes_list.remove(index);
         self.current_content.remove(index);
+        self.note_headings.remove(index);
+        self.note_aliases.remove(index);
+        self.note_tags.remove(index);
+        self.content_state.remove(index);
+        self.disk_modified.remove(index);
+        self.last_change = Some(Instant::now());
     }
 
     fn adjust_current_index_after_deletion(&mut self) {
@@ -226,9 +1261,17 @@ impl NotesList {
     }
 
     fn rename_note(&mut self, old_name: &str, new_name: &str) {
+        if self.find_note_index(new_name).is_some() {
+            return;
+        }
+        self.io.flush();
         if self.file_manager.rename_note(old_name, new_name)
             && let Some(index) = self.notes_list.iter().position(|name| name == old_name) {
                 self.notes_list[index] = new_name.to_string();
+                self.search_index.rename(old_name, new_name);
+                self.remember_folder(parent_folder(new_name));
+                self.compute_display_order();
+                self.last_change = Some(Instant::now());
             }
     }
 
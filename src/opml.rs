@@ -0,0 +1,154 @@
+use std::path::Path;
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::html_export::escape_html;
+use crate::html_to_markdown::{Token, decode_entities, tokenize};
+
+pub fn is_opml(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| ext.eq_ignore_ascii_case("opml"))
+}
+
+/// Turns an OPML outline into a nested markdown list, under a heading with the outline's title.
+/// Notes attached to an item (`_note`) follow it as indented lines, and link outlines become
+/// markdown links.
+pub fn to_markdown(opml: &str) -> Result<String, String> {
+    let mut title = String::new();
+    let mut list = String::new();
+    let mut in_title = false;
+    let mut in_body = false;
+    let mut depth = 0usize;
+
+    for token in tokenize(opml) {
+        match token {
+            Token::Tag(tag) if tag.name == "title" => in_title = !tag.closing && !tag.self_closing,
+            Token::Tag(tag) if tag.name == "body" => in_body = !tag.closing && !tag.self_closing,
+            Token::Tag(tag) if tag.name == "outline" && in_body => {
+                if tag.closing {
+                    depth = depth.saturating_sub(1);
+                    continue;
+                }
+                let indent = "  ".repeat(depth);
+                let text = tag.attribute("text").or(tag.attribute("title")).unwrap_or("");
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                let url = tag.attribute("url").or(tag.attribute("htmlurl")).or(tag.attribute("xmlurl"));
+                match url {
+                    Some(url) if !url.is_empty() => list.push_str(&format!("{}- [{}]({})\n", indent, text, url)),
+                    _ => list.push_str(&format!("{}- {}\n", indent, text)),
+                }
+                if let Some(note) = tag.attribute("_note") {
+                    for line in note.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                        list.push_str(&format!("{}  {}\n", indent, line));
+                    }
+                }
+                if !tag.self_closing {
+                    depth += 1;
+                }
+            }
+            Token::Text(text) if in_title => title.push_str(&decode_entities(&text)),
+            _ => {}
+        }
+    }
+
+    if list.is_empty() {
+        return Err("The file has no outline items to import".to_string());
+    }
+    let title = title.trim();
+    Ok(if title.is_empty() { list } else { format!("# {}\n\n{}", title, list) })
+}
+
+/// An outline item from a heading or list item, and what's nested under it.
+struct Outline {
+    text: String,
+    /// Headings rank by level; list items rank below every heading, deeper the more nested.
+    rank: usize,
+    children: Vec<Outline>,
+}
+
+/// Writes the note's headings and list items as an OPML outline: list items nest under the
+/// heading above them, and nested lists under their item. Other text is left out.
+pub fn from_markdown(title: &str, markdown: &str) -> String {
+    let mut roots: Vec<Outline> = Vec::new();
+    // Child indices from the roots down to the outline being filled in.
+    let mut path: Vec<usize> = Vec::new();
+    let mut list_depth = 0usize;
+    let mut collecting = false;
+
+    // Task markers are dropped rather than written as `[ ]` text.
+    for event in Parser::new_ext(markdown, Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                open_outline(&mut roots, &mut path, heading_rank(level));
+                collecting = true;
+            }
+            Event::Start(Tag::Item) => {
+                open_outline(&mut roots, &mut path, 6 + list_depth);
+                collecting = true;
+            }
+            Event::Start(Tag::List(_)) => {
+                list_depth += 1;
+                collecting = false;
+            }
+            Event::End(TagEnd::List(_)) => list_depth -= 1,
+            Event::End(TagEnd::Heading(_) | TagEnd::Paragraph | TagEnd::Item) => collecting = false,
+            Event::Text(text) | Event::Code(text) if collecting => outline_at(&mut roots, &path).text.push_str(&text),
+            Event::SoftBreak | Event::HardBreak if collecting => outline_at(&mut roots, &path).text.push(' '),
+            _ => {}
+        }
+    }
+
+    let mut opml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n  <head>\n    <title>{}</title>\n  </head>\n  <body>\n",
+        escape_html(title)
+    );
+    for outline in &roots {
+        write_outline(&mut opml, outline, 2);
+    }
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+fn heading_rank(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 0,
+        HeadingLevel::H2 => 1,
+        HeadingLevel::H3 => 2,
+        HeadingLevel::H4 => 3,
+        HeadingLevel::H5 => 4,
+        HeadingLevel::H6 => 5,
+    }
+}
+
+/// Adds an outline of `rank` under the nearest open one that ranks above it.
+fn open_outline(roots: &mut Vec<Outline>, path: &mut Vec<usize>, rank: usize) {
+    while !path.is_empty() && outline_at(roots, path).rank >= rank {
+        path.pop();
+    }
+    let outline = Outline {
+        text: String::new(),
+        rank,
+        children: Vec::new(),
+    };
+    let siblings = if path.is_empty() { roots } else { &mut outline_at(roots, path).children };
+    siblings.push(outline);
+    path.push(siblings.len() - 1);
+}
+
+fn outline_at<'a>(roots: &'a mut [Outline], path: &[usize]) -> &'a mut Outline {
+    let (first, rest) = path.split_first().expect("outline path is never empty here");
+    rest.iter().fold(&mut roots[*first], |outline, &index| &mut outline.children[index])
+}
+
+fn write_outline(opml: &mut String, outline: &Outline, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let text = escape_html(outline.text.trim());
+    if outline.children.is_empty() {
+        opml.push_str(&format!("{}<outline text=\"{}\"/>\n", indent, text));
+        return;
+    }
+    opml.push_str(&format!("{}<outline text=\"{}\">\n", indent, text));
+    for child in &outline.children {
+        write_outline(opml, child, depth + 1);
+    }
+    opml.push_str(&format!("{}</outline>\n", indent));
+}
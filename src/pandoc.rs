@@ -0,0 +1,89 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::file_manager::ATTACHMENTS_DIR;
+
+/// Pandoc's name for the format of a file it can turn into a note, going by the extension.
+pub fn import_format(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "docx" => Some("docx"),
+        "odt" => Some("odt"),
+        "epub" => Some("epub"),
+        "rst" => Some("rst"),
+        "org" => Some("org"),
+        _ => None,
+    }
+}
+
+/// The first line of `pandoc --version`, or `None` if pandoc can't be run.
+pub fn version(pandoc_path: &str) -> Option<String> {
+    let output = Command::new(pandoc_path).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).lines().next().map(str::to_string)
+}
+
+/// Converts `source` to markdown. Images embedded in the document are extracted into a folder
+/// named after the note inside `attachments/`, which the markdown links to.
+pub fn to_markdown(pandoc_path: &str, source: &Path, format: &str, notes_dir: &Path, note_name: &str) -> Result<String, String> {
+    let media_folder: String = note_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let output = run(
+        Command::new(pandoc_path)
+            .current_dir(notes_dir)
+            .args(["--from", format, "--to", "gfm", "--wrap=none"])
+            .arg(format!("--extract-media={}/{}", ATTACHMENTS_DIR, media_folder))
+            .arg(source),
+        pandoc_path,
+        None,
+    )
+    .map_err(|e| format!("Failed to import '{}': {}", source.display(), e))?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Writes `markdown` to `destination` in pandoc's `format`. Runs from the notes folder so
+/// relative image links are found.
+pub fn from_markdown(pandoc_path: &str, markdown: &str, format: &str, title: &str, notes_dir: &Path, destination: &Path) -> Result<(), String> {
+    run(
+        Command::new(pandoc_path)
+            .current_dir(notes_dir)
+            .args(["--from", "gfm", "--to", format, "--standalone"])
+            .arg(format!("--metadata=title:{}", title))
+            .arg("--output")
+            .arg(destination),
+        pandoc_path,
+        Some(markdown),
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to export to '{}': {}", destination.display(), e))
+}
+
+/// Runs pandoc with `input` on stdin and returns its stdout, or what went wrong.
+fn run(command: &mut Command, pandoc_path: &str, input: Option<&str>) -> Result<Vec<u8>, String> {
+    let mut child = command
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                format!("pandoc was not found at '{}'; install it or set `pandoc_path` in config.toml", pandoc_path)
+            }
+            _ => format!("could not run pandoc: {}", e),
+        })?;
+
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input.as_bytes()).map_err(|e| format!("could not send the note to pandoc: {}", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("pandoc did not finish: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("pandoc failed: {}", stderr.trim()));
+    }
+    Ok(output.stdout)
+}
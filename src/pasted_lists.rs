@@ -0,0 +1,96 @@
+/// Bullets word processors and chat apps put in copied text, with the nesting level each
+/// usually marks.
+const BULLETS: [(char, usize); 13] = [
+    ('•', 0),
+    ('●', 0),
+    ('·', 0),
+    ('‣', 0),
+    ('⁃', 0),
+    ('–', 0),
+    ('—', 0),
+    ('◦', 1),
+    ('○', 1),
+    ('▪', 2),
+    ('■', 2),
+    ('▫', 2),
+    ('□', 2),
+];
+/// Columns a tab counts for when comparing indentation.
+const TAB_WIDTH: usize = 4;
+/// Spaces per nesting level in the markdown, enough for items under both `-` and `1.`.
+const LEVEL_INDENT: usize = 4;
+
+struct PastedItem<'a> {
+    /// Width of the leading whitespace.
+    indent: usize,
+    /// Nesting suggested by the bullet itself, for text that lost its indentation.
+    bullet_level: usize,
+    /// The number of a `1)` item; `None` for bullets.
+    number: Option<&'a str>,
+    text: &'a str,
+}
+
+fn parse_item(line: &str) -> Option<PastedItem<'_>> {
+    let trimmed = line.trim_start_matches([' ', '\t']);
+    let indent = line[..line.len() - trimmed.len()].chars().map(|c| if c == '\t' { TAB_WIDTH } else { 1 }).sum();
+    let first = trimmed.chars().next()?;
+
+    let (bullet_level, number, rest) = if let Some(&(bullet, level)) = BULLETS.iter().find(|(bullet, _)| *bullet == first) {
+        (level, None, &trimmed[bullet.len_utf8()..])
+    } else if let Some(rest) = trimmed.strip_prefix('o').filter(|rest| rest.starts_with('\t')) {
+        // Word's second-level bullet, copied as a letter and a tab.
+        (1, None, rest)
+    } else {
+        let digits = trimmed.len() - trimmed.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let rest = trimmed[digits..].strip_prefix(')').filter(|_| digits > 0)?;
+        (0, Some(&trimmed[..digits]), rest)
+    };
+    if !rest.starts_with([' ', '\t', '\u{a0}']) {
+        return None;
+    }
+    Some(PastedItem {
+        indent,
+        bullet_level,
+        number,
+        text: rest.trim_start_matches([' ', '\t', '\u{a0}']),
+    })
+}
+
+/// Turns lines bulleted with `•`, `–`, `◦` and the like, or numbered `1)`, into markdown list
+/// items with `marker` or `1.`, nested by their indentation, or by their bullets when the
+/// indentation was lost. `None` when there were no such lines.
+pub fn normalize(text: &str, marker: char) -> Option<String> {
+    let items: Vec<Option<PastedItem>> = text.lines().map(parse_item).collect();
+    if items.iter().all(Option::is_none) {
+        return None;
+    }
+
+    let mut indents: Vec<usize> = items.iter().flatten().map(|item| item.indent).collect();
+    indents.sort_unstable();
+    indents.dedup();
+    let min_bullet_level = items.iter().flatten().map(|item| item.bullet_level).min().unwrap_or(0);
+    let level = |item: &PastedItem| {
+        if indents.len() > 1 {
+            indents.iter().position(|&indent| indent == item.indent).unwrap_or(0)
+        } else {
+            item.bullet_level - min_bullet_level
+        }
+    };
+
+    let lines: Vec<String> = text
+        .lines()
+        .zip(&items)
+        .map(|(line, item)| match item {
+            Some(item) => {
+                let marker = item.number.map_or_else(|| marker.to_string(), |number| format!("{}.", number));
+                format!("{}{} {}", " ".repeat(LEVEL_INDENT * level(item)), marker, item.text)
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    let mut normalized = lines.join("\n");
+    if text.ends_with('\n') {
+        normalized.push('\n');
+    }
+    Some(normalized)
+}
@@ -0,0 +1,164 @@
+use eframe::egui;
+
+use crate::config::{Config, MarkdownStyle, MarkdownStyles};
+use crate::i18n::tr_args;
+use crate::keymap::KeyChord;
+use crate::rendered_view::RenderedView;
+
+/// How much larger slide text is than the preview's.
+const SLIDE_SCALE: f32 = 1.8;
+/// Slide content is kept to this fraction of the screen width.
+const SLIDE_WIDTH_FRACTION: f32 = 0.8;
+
+/// Shows the current note fullscreen as slides, one per H1/H2 section or `---` block.
+pub struct Presentation {
+    pub active: bool,
+    slides: Vec<String>,
+    current: usize,
+    rendered_view: RenderedView,
+}
+
+pub enum PresentationAction {
+    None,
+    Exit,
+}
+
+impl Presentation {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            active: false,
+            slides: Vec::new(),
+            current: 0,
+            rendered_view: RenderedView::new(config),
+        }
+    }
+
+    pub fn start(&mut self, markdown_text: &str, config: &Config) {
+        let mut slide_config = config.clone();
+        slide_config.markdown_styles = scaled_styles(&config.markdown_styles, SLIDE_SCALE);
+        self.rendered_view.set_config(&slide_config);
+        self.slides = split_slides(markdown_text);
+        self.current = 0;
+        self.active = true;
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, exit_chord: Option<KeyChord>) -> PresentationAction {
+        if !self.active {
+            return PresentationAction::None;
+        }
+
+        let exit = ui.input_mut(|i| {
+            exit_chord.is_some_and(|chord| chord.consume(i)) || i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)
+        });
+        if exit {
+            self.active = false;
+            return PresentationAction::Exit;
+        }
+        self.handle_navigation(ui);
+
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            let full_rect = ui.available_rect_before_wrap();
+            let footer_height = 24.0;
+            let width = full_rect.width() * SLIDE_WIDTH_FRACTION;
+            let slide_rect = egui::Rect::from_min_size(
+                egui::pos2(full_rect.center().x - width / 2.0, full_rect.top() + full_rect.height() * 0.08),
+                egui::vec2(width, full_rect.height() * 0.84 - footer_height),
+            );
+            let slide = self.slides.get(self.current).cloned().unwrap_or_default();
+            ui.scope_builder(egui::UiBuilder::new().max_rect(slide_rect).id_salt(("slide", self.current)), |ui| {
+                self.rendered_view.render(ui, &slide);
+            });
+
+            let counter = tr_args(
+                "presentation-counter",
+                &[("current", &(self.current + 1)), ("total", &self.slides.len().max(1))],
+            );
+            ui.painter().text(
+                full_rect.right_bottom() - egui::vec2(16.0, 12.0),
+                egui::Align2::RIGHT_BOTTOM,
+                counter,
+                egui::FontId::proportional(14.0),
+                ui.visuals().weak_text_color(),
+            );
+            ui.advance_cursor_after_rect(full_rect);
+        });
+
+        PresentationAction::None
+    }
+
+    fn handle_navigation(&mut self, ui: &egui::Ui) {
+        let last = self.slides.len().saturating_sub(1);
+        ui.input(|i| {
+            let next = [egui::Key::ArrowRight, egui::Key::ArrowDown, egui::Key::PageDown, egui::Key::Space]
+                .iter()
+                .any(|key| i.key_pressed(*key));
+            let previous = [egui::Key::ArrowLeft, egui::Key::ArrowUp, egui::Key::PageUp, egui::Key::Backspace]
+                .iter()
+                .any(|key| i.key_pressed(*key));
+            if next {
+                self.current = (self.current + 1).min(last);
+            } else if previous {
+                self.current = self.current.saturating_sub(1);
+            } else if i.key_pressed(egui::Key::Home) {
+                self.current = 0;
+            } else if i.key_pressed(egui::Key::End) {
+                self.current = last;
+            }
+        });
+    }
+}
+
+/// Splits a note into slides at `---` lines and before every H1 or H2 heading, ignoring code blocks.
+fn split_slides(markdown_text: &str) -> Vec<String> {
+    let mut slides = Vec::new();
+    let mut current = String::new();
+    let mut in_code_block = false;
+
+    for line in markdown_text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+        }
+        if !in_code_block {
+            if trimmed == "---" {
+                slides.push(std::mem::take(&mut current));
+                continue;
+            }
+            let starts_section = line.starts_with("# ") || line.starts_with("## ");
+            if starts_section && !current.trim().is_empty() {
+                slides.push(std::mem::take(&mut current));
+            }
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    slides.push(current);
+
+    slides.retain(|slide| !slide.trim().is_empty());
+    slides
+}
+
+fn scaled_styles(styles: &MarkdownStyles, factor: f32) -> MarkdownStyles {
+    let scale = |style: &MarkdownStyle| MarkdownStyle {
+        font_size: style.font_size * factor,
+        ..style.clone()
+    };
+    MarkdownStyles {
+        h1: scale(&styles.h1),
+        h2: scale(&styles.h2),
+        h3: scale(&styles.h3),
+        h4: scale(&styles.h4),
+        h5: scale(&styles.h5),
+        h6: scale(&styles.h6),
+        paragraph: scale(&styles.paragraph),
+        strong: scale(&styles.strong),
+        emphasis: scale(&styles.emphasis),
+        strikethrough: scale(&styles.strikethrough),
+        code_inline: scale(&styles.code_inline),
+        code_block: scale(&styles.code_block),
+        code_block_background: styles.code_block_background,
+        list_bullet: scale(&styles.list_bullet),
+        editor_line_spacing: styles.editor_line_spacing,
+        block_spacing: styles.block_spacing * factor,
+    }
+}
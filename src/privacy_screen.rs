@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::i18n::tr;
+
+/// Covers the notes after a while without input, when the window loses focus, or on request, so
+/// they aren't left readable on a shared or unattended screen.
+pub struct PrivacyScreen {
+    active: bool,
+    last_activity: Instant,
+    /// Whether the main window had focus last frame, to notice it losing it.
+    was_focused: bool,
+}
+
+impl PrivacyScreen {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            last_activity: Instant::now(),
+            was_focused: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn hide(&mut self) {
+        self.active = true;
+    }
+
+    /// Counts input in any window as activity.
+    pub fn note_activity(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| !i.events.is_empty() || i.pointer.is_moving()) {
+            self.last_activity = Instant::now();
+        }
+    }
+
+    /// Raises the screen once the main window has had no input for `idle_minutes` (`0` for
+    /// never), or when it loses focus if `on_focus_loss` is set.
+    pub fn update(&mut self, ctx: &egui::Context, idle_minutes: u32, on_focus_loss: bool) {
+        self.note_activity(ctx);
+        let focused = ctx.input(|i| i.focused);
+        if on_focus_loss && self.was_focused && !focused {
+            self.active = true;
+        }
+        self.was_focused = focused;
+
+        if idle_minutes == 0 || self.active {
+            return;
+        }
+        let timeout = Duration::from_secs(u64::from(idle_minutes) * 60);
+        match timeout.checked_sub(self.last_activity.elapsed()) {
+            Some(remaining) if !remaining.is_zero() => ctx.request_repaint_after(remaining),
+            _ => self.active = true,
+        }
+    }
+
+    /// Fills the window in place of the notes. A click or key press brings them back.
+    pub fn render(&mut self, ui: &mut egui::Ui) {
+        let reveal = ui.input(|i| {
+            i.pointer.any_click()
+                || i.events.iter().any(|event| matches!(event, egui::Event::Key { pressed: true, repeat: false, .. }))
+        });
+        let fill = ui.visuals().extreme_bg_color;
+        egui::CentralPanel::default().frame(egui::Frame::new().fill(fill)).show_inside(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space((ui.available_height() / 2.0 - 30.0).max(0.0));
+                ui.heading(format!("🔒 {}", tr("privacy-screen-title")));
+                ui.weak(tr("privacy-screen-hint"));
+            });
+        });
+        if reveal {
+            self.active = false;
+            self.last_activity = Instant::now();
+        }
+    }
+}
+
+impl Default for PrivacyScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
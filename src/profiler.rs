@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::i18n::{tr, tr_args};
+use crate::note_io::IoStats;
+
+/// How many recent frames the average and worst frame time cover.
+const FRAME_HISTORY: usize = 120;
+
+/// Numbers gathered from around the app for one frame of the overlay.
+pub struct ProfileSample {
+    pub parse: Duration,
+    pub layout: Duration,
+    pub io: IoStats,
+    pub undo_bytes: usize,
+}
+
+/// Debug overlay with frame and subsystem timings, shown when `show_profiler` is set, so a
+/// slowdown can be reported with numbers attached.
+pub struct Profiler {
+    frame_times: VecDeque<Duration>,
+    frame_start: Option<Instant>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            frame_times: VecDeque::with_capacity(FRAME_HISTORY),
+            frame_start: None,
+        }
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Some(Instant::now());
+    }
+
+    pub fn end_frame(&mut self) {
+        if let Some(start) = self.frame_start.take() {
+            if self.frame_times.len() == FRAME_HISTORY {
+                self.frame_times.pop_front();
+            }
+            self.frame_times.push_back(start.elapsed());
+        }
+    }
+
+    pub fn render(&self, ctx: &egui::Context, sample: &ProfileSample) {
+        let average = self.frame_times.iter().sum::<Duration>() / self.frame_times.len().max(1) as u32;
+        let worst = self.frame_times.iter().max().copied().unwrap_or_default();
+
+        egui::Area::new(egui::Id::new("profiler_overlay"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Monospace);
+                    ui.strong(tr("profiler-title"));
+                    ui.label(tr_args("profiler-frame", &[("average", &millis(average)), ("worst", &millis(worst))]));
+                    ui.label(tr_args("profiler-parse", &[("time", &millis(sample.parse))]));
+                    ui.label(tr_args("profiler-layout", &[("time", &millis(sample.layout))]));
+                    ui.label(tr_args(
+                        "profiler-io",
+                        &[("reads", &sample.io.reads), ("writes", &sample.io.writes), ("time", &millis(sample.io.busy))],
+                    ));
+                    ui.label(tr_args("profiler-undo", &[("size", &format!("{:.1}", sample.undo_bytes as f64 / 1024.0))]));
+                });
+            });
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.2}", duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate};
+
+use crate::config::Config;
+use crate::file_manager::FileManager;
+use crate::i18n::{tr, tr_args};
+use crate::tasks;
+
+/// How often the scheduler rescans the notes folder for tasks due today.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Background thread that sends a desktop notification once for each open task due today.
+/// Stops when dropped.
+pub struct ReminderScheduler {
+    notes_folder: PathBuf,
+    stop: Arc<AtomicBool>,
+}
+
+impl ReminderScheduler {
+    pub fn spawn(config: &Config) -> Self {
+        let file_manager = FileManager::new(config);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        thread::spawn(move || {
+            let mut notified: HashSet<(String, String)> = HashSet::new();
+            let mut notified_day: Option<NaiveDate> = None;
+            while !thread_stop.load(Ordering::Relaxed) {
+                let today = Local::now().date_naive();
+                if notified_day != Some(today) {
+                    notified.clear();
+                    notified_day = Some(today);
+                }
+
+                for note in file_manager.list_note_names() {
+                    let content = file_manager.read_note_content(&note);
+                    for task in tasks::parse_tasks(&content) {
+                        if task.done || task.due != Some(today) {
+                            continue;
+                        }
+                        let key = (note.clone(), task.text.clone());
+                        if notified.contains(&key) {
+                            continue;
+                        }
+                        let body = tr_args("reminder-body", &[("task", &task.text), ("note", &note)]);
+                        if let Err(e) = notify(tr("reminder-title"), &body) {
+                            eprintln!("{}", e);
+                        }
+                        notified.insert(key);
+                    }
+                }
+
+                thread::sleep(CHECK_INTERVAL);
+            }
+        });
+
+        Self {
+            notes_folder: config.notes_folder.clone(),
+            stop,
+        }
+    }
+
+    pub fn watches(&self, notes_folder: &Path) -> bool {
+        self.notes_folder == notes_folder
+    }
+}
+
+impl Drop for ReminderScheduler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn notify(title: &str, body: &str) -> Result<(), String> {
+    run("notify-send", &["--app-name=Note Squirrel", title, body], &[])
+}
+
+#[cfg(target_os = "macos")]
+fn notify(title: &str, body: &str) -> Result<(), String> {
+    let script = format!(
+        "display notification {} with title {}",
+        apple_script_string(body),
+        apple_script_string(title)
+    );
+    run("osascript", &["-e", &script], &[])
+}
+
+#[cfg(target_os = "macos")]
+fn apple_script_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(target_os = "windows")]
+fn notify(title: &str, body: &str) -> Result<(), String> {
+    // The text goes in through the environment rather than the script, so nothing in a task can
+    // end a PowerShell string early.
+    let script = "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+         $xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+         $text = $xml.GetElementsByTagName('text'); \
+         $text.Item(0).AppendChild($xml.CreateTextNode($env:NS_TITLE)) > $null; \
+         $text.Item(1).AppendChild($xml.CreateTextNode($env:NS_BODY)) > $null; \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('Note Squirrel').Show([Windows.UI.Notifications.ToastNotification]::new($xml))";
+    run(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", script],
+        &[("NS_TITLE", title), ("NS_BODY", body)],
+    )
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn notify(_title: &str, _body: &str) -> Result<(), String> {
+    Err("Desktop notifications aren't supported on this platform".to_string())
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn run(program: &str, args: &[&str], envs: &[(&str, &str)]) -> Result<(), String> {
+    let status = std::process::Command::new(program)
+        .args(args)
+        .envs(envs.iter().copied())
+        .status()
+        .map_err(|e| format!("Failed to send a reminder with {}: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
@@ -2,7 +2,16 @@ use eframe::egui;
 use egui::{Color32, RichText};
 use pulldown_cmark::{Parser, Event, Tag, TagEnd, HeadingLevel, Options};
 
+use crate::attachment_preview;
 use crate::config::Config;
+use crate::i18n::{tr, tr_args};
+use crate::tags;
+use crate::tasks;
+use crate::uri_scheme::DeepLink;
+use crate::wikilinks;
+
+/// How long the text has to stay unchanged before a burst of edits is parsed again.
+const REPARSE_DEBOUNCE: f64 = 0.15;
 
 #[derive(Debug, Clone)]
 struct MarkdownContext {
@@ -11,6 +20,8 @@ struct MarkdownContext {
     list_depth: usize,
     list_item_number: usize,
     is_ordered_list: bool,
+    /// Headings rendered so far.
+    heading_count: usize,
 }
 
 impl MarkdownContext {
@@ -21,68 +32,184 @@ impl MarkdownContext {
             list_depth: 0,
             list_item_number: 0,
             is_ordered_list: false,
+            heading_count: 0,
         }
     }
 }
 
 pub struct RenderedView {
-    current_markdown_text: String,
     config: Config,
     cached_events: Vec<Event<'static>>,
+    /// The text `cached_events` were parsed from. Can lag behind the text being rendered while
+    /// edits are being debounced.
     cached_events_text: String,
+    /// When the rendered text last differed from the frame before.
+    last_text_change: Option<f64>,
+    /// Hash of the text rendered last frame, to notice changes without keeping a copy.
+    last_text_hash: u64,
+    /// How long the latest parse took, for the profiler overlay.
+    last_parse_duration: std::time::Duration,
+    /// A `#tag` clicked during the last render, without the `#`.
+    clicked_tag: std::cell::Cell<Option<String>>,
+    /// A note whose `notesquirrel://note/` link was clicked during the last render.
+    clicked_note: std::cell::Cell<Option<String>>,
+    /// A link to a local image or PDF clicked during the last render, as written in the note.
+    clicked_attachment: std::cell::Cell<Option<String>>,
+    /// The `#anchor` of a heading whose link was clicked during the last render, without the `#`.
+    clicked_anchor: std::cell::Cell<Option<String>>,
+    /// A web link clicked during the last render, waiting to be confirmed before it's opened.
+    clicked_external_link: std::cell::Cell<Option<String>>,
+    /// Ordinal of a heading to scroll to on the next render.
+    scroll_to_heading: std::cell::Cell<Option<usize>>,
+    scroll_offset: f32,
+    /// Scroll offset to jump to on the next render, as when returning to a note.
+    scroll_override: Option<f32>,
+    /// Render notes over `large_note_threshold_kb` anyway, after the user asked to.
+    render_large_notes: bool,
 }
 
 impl RenderedView {
     pub fn new(config: &Config) -> Self {
         Self {
-            current_markdown_text: String::new(),
             config: config.clone(),
             cached_events: Vec::new(),
             cached_events_text: String::new(),
+            last_text_change: None,
+            last_text_hash: 0,
+            last_parse_duration: std::time::Duration::ZERO,
+            clicked_tag: std::cell::Cell::new(None),
+            clicked_note: std::cell::Cell::new(None),
+            clicked_attachment: std::cell::Cell::new(None),
+            clicked_anchor: std::cell::Cell::new(None),
+            clicked_external_link: std::cell::Cell::new(None),
+            scroll_to_heading: std::cell::Cell::new(None),
+            scroll_offset: 0.0,
+            scroll_override: None,
+            render_large_notes: false,
         }
     }
 
+    /// Scrolls the `ordinal`-th heading to the top of the preview on the next render.
+    pub fn scroll_to_heading(&mut self, ordinal: usize) {
+        self.scroll_to_heading.set(Some(ordinal));
+    }
+
+    pub fn get_scroll_offset(&self) -> f32 {
+        self.scroll_offset
+    }
+
+    /// Scrolls the preview to `offset` on the next render.
+    pub fn restore_scroll(&mut self, offset: f32) {
+        self.scroll_offset = offset.max(0.0);
+        self.scroll_override = Some(self.scroll_offset);
+    }
+
+    pub fn last_parse_duration(&self) -> std::time::Duration {
+        self.last_parse_duration
+    }
+
+    pub fn take_clicked_tag(&mut self) -> Option<String> {
+        self.clicked_tag.take()
+    }
+
+    pub fn take_clicked_note(&mut self) -> Option<String> {
+        self.clicked_note.take()
+    }
+
+    pub fn take_clicked_attachment(&mut self) -> Option<String> {
+        self.clicked_attachment.take()
+    }
+
+    pub fn take_clicked_anchor(&mut self) -> Option<String> {
+        self.clicked_anchor.take()
+    }
+
+    pub fn take_clicked_external_link(&mut self) -> Option<String> {
+        self.clicked_external_link.take()
+    }
+
+    pub fn set_config(&mut self, config: &Config) {
+        self.config = config.clone();
+    }
+
     pub fn render(&mut self, ui: &mut egui::Ui, markdown_text: &str) -> Option<Vec<usize>> {
-        self.current_markdown_text = markdown_text.to_string();
         let inner = ui.available_size();
         let mut result = None;
         ui.allocate_ui_with_layout(inner, egui::Layout::top_down(egui::Align::LEFT), |ui| {
-            egui::ScrollArea::vertical()
+            let mut scroll_area = egui::ScrollArea::vertical()
                 .auto_shrink([false, false])
-                .id_salt("rendered_scroll")
-                .show(ui, |ui| {
-                    if markdown_text.trim().is_empty() {
-                        ui.label(
-                            egui::RichText::new("Start typing to see your rendered notes (markdown)...")
-                                .color(egui::Color32::from_rgb(150, 150, 150))
-                                .font(self.config.get_rendered_font_id(14.0)),
-                        );
-                        result = Some(Vec::new());
-                    } else {
-                        let checkbox_toggles = self.render_markdown(ui, markdown_text);
-                        result = Some(checkbox_toggles);
+                .id_salt("rendered_scroll");
+            if let Some(offset) = self.scroll_override.take() {
+                scroll_area = scroll_area.vertical_scroll_offset(offset);
+            }
+            let output = scroll_area.show(ui, |ui| {
+                if markdown_text.trim().is_empty() {
+                    ui.label(
+                        egui::RichText::new(tr("preview-placeholder"))
+                            .color(self.config.theme.palette().placeholder_text)
+                            .font(self.config.get_rendered_font_id(14.0)),
+                    );
+                    result = Some(Vec::new());
+                } else if !self.render_large_notes
+                    && markdown_text.len() > self.config.large_note_threshold_kb * 1024
+                {
+                    let size = format!("{:.1}", markdown_text.len() as f64 / (1024.0 * 1024.0));
+                    ui.label(tr_args("preview-large-note", &[("size", &size)]));
+                    if ui.button(tr("preview-render-anyway")).clicked() {
+                        self.render_large_notes = true;
                     }
-                });
+                    result = Some(Vec::new());
+                } else {
+                    let checkbox_toggles = self.render_markdown(ui, markdown_text);
+                    result = Some(checkbox_toggles);
+                }
+            });
+            self.scroll_offset = output.state.offset.y;
         });
         result
     }
 
-    fn ensure_cached_events(&mut self, markdown_text: &str) {
-        if self.cached_events_text != markdown_text {
+    /// Parses `markdown_text` unless it's in the middle of a burst of edits, in which case the
+    /// previous parse is shown until the text settles. The first change after a quiet spell, like
+    /// switching notes, is parsed right away.
+    fn ensure_cached_events(&mut self, ctx: &egui::Context, markdown_text: &str) {
+        let now = ctx.input(|i| i.time);
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        std::hash::Hash::hash(markdown_text, &mut hasher);
+        let hash = std::hash::Hasher::finish(&hasher);
+        let mut settled = true;
+        if hash != self.last_text_hash {
+            self.last_text_hash = hash;
+            settled = self.last_text_change.is_none_or(|changed| now - changed >= REPARSE_DEBOUNCE);
+            self.last_text_change = Some(now);
+        } else if let Some(changed) = self.last_text_change {
+            let quiet_for = now - changed;
+            settled = quiet_for >= REPARSE_DEBOUNCE;
+            if !settled {
+                ctx.request_repaint_after(std::time::Duration::from_secs_f64(REPARSE_DEBOUNCE - quiet_for));
+            }
+        }
+
+        if settled && self.cached_events_text != markdown_text {
+            let started = std::time::Instant::now();
             let mut options = Options::empty();
             options.insert(Options::ENABLE_STRIKETHROUGH);
             options.insert(Options::ENABLE_TABLES);
             options.insert(Options::ENABLE_FOOTNOTES);
             options.insert(Options::ENABLE_TASKLISTS);
+            // Front matter, like a note's aliases or word goal, isn't shown.
+            options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
 
-            let parser = Parser::new_ext(markdown_text, options);
+            let source = wikilinks::to_note_links(markdown_text);
+            let parser = Parser::new_ext(&source, options);
             self.cached_events = parser.map(|e| e.into_static()).collect();
             self.cached_events_text = markdown_text.to_string();
+            self.last_parse_duration = started.elapsed();
         }
     }
 
     fn render_markdown(&mut self, ui: &mut egui::Ui, markdown_text: &str) -> Vec<usize> {
-        self.ensure_cached_events(markdown_text);
+        self.ensure_cached_events(ui.ctx(), markdown_text);
 
         let events = &self.cached_events;
         let mut context = MarkdownContext::new();
@@ -93,6 +220,10 @@ impl RenderedView {
             i = self.render_markdown_events(ui, events, i, &mut context, &mut checkbox_toggles);
         }
 
+        // Toggle lines refer to the parsed text, which may be behind the editor while debouncing.
+        if self.cached_events_text != markdown_text {
+            checkbox_toggles.clear();
+        }
         checkbox_toggles
     }
 
@@ -104,19 +235,21 @@ impl RenderedView {
         match &events[start] {
             Event::Start(Tag::Heading { level, .. }) => {
                 context.current_heading = Some(*level);
-                self.render_heading_inline(ui, events, start + 1, context)
+                let next = self.render_heading_inline(ui, events, start + 1, context);
+                context.heading_count += 1;
+                next
             }
             Event::Start(Tag::Paragraph) => {
                 self.render_paragraph_with_spacing(ui, events, start, context)
             }
             Event::Start(Tag::List(first_item_number)) => {
                 self.handle_list_start(context, *first_item_number);
-                ui.add_space(4.0);
+                ui.add_space(self.block_spacing());
                 start + 1
             }
             Event::End(TagEnd::List(_)) => {
                 self.handle_list_end(context);
-                ui.add_space(4.0);
+                ui.add_space(self.block_spacing());
                 start + 1
             }
             Event::Start(Tag::Item) => {
@@ -132,6 +265,10 @@ impl RenderedView {
         }
     }
 
+    fn block_spacing(&self) -> f32 {
+        self.config.markdown_styles.block_spacing.max(0.0)
+    }
+
     fn handle_list_start(&self, context: &mut MarkdownContext, first_item_number: Option<u64>) {
         context.in_list = true;
         context.list_depth += 1;
@@ -148,7 +285,7 @@ impl RenderedView {
 
     fn render_paragraph_with_spacing(&self, ui: &mut egui::Ui, events: &[Event], start: usize, context: &MarkdownContext) -> usize {
         if !context.in_list {
-            ui.add_space(4.0);
+            ui.add_space(self.block_spacing());
         }
         self.render_paragraph_inline(ui, events, start + 1, context)
     }
@@ -173,15 +310,19 @@ impl RenderedView {
             Some(HeadingLevel::H4) => (self.config.markdown_styles.h4.font_size, self.config.markdown_styles.h4.to_color32()),
             Some(HeadingLevel::H5) => (self.config.markdown_styles.h5.font_size, self.config.markdown_styles.h5.to_color32()),
             Some(HeadingLevel::H6) => (self.config.markdown_styles.h6.font_size, self.config.markdown_styles.h6.to_color32()),
-            None => (self.config.markdown_styles.paragraph.font_size, Color32::WHITE),
+            None => (self.config.markdown_styles.paragraph.font_size, self.config.markdown_styles.paragraph.to_color32()),
         };
 
-        ui.add_space(8.0);
-        ui.label(RichText::new(&heading_text)
+        ui.add_space(self.block_spacing() * 2.0);
+        let response = ui.label(RichText::new(&heading_text)
             .font(self.config.get_rendered_font_id(font_size))
             .strong()
             .color(color));
-        ui.add_space(4.0);
+        if self.scroll_to_heading.get() == Some(context.heading_count) {
+            response.scroll_to_me(Some(egui::Align::TOP));
+            self.scroll_to_heading.set(None);
+        }
+        ui.add_space(self.block_spacing());
 
         i + 1
     }
@@ -218,10 +359,7 @@ impl RenderedView {
                             temp_i += 1;
                         }
 
-                        if ui.add(egui::Hyperlink::from_label_and_url(&link_text, dest_url.as_ref())).clicked()
-                            && let Err(e) = webbrowser::open(dest_url.as_ref()) {
-                                eprintln!("Failed to open link: {}", e);
-                            }
+                        self.render_link(ui, &link_text, dest_url.as_ref());
 
                         current_i = temp_i + 1;
                     }
@@ -229,36 +367,39 @@ impl RenderedView {
                         current_i += 1;
                     }
                     Event::Text(text) => {
-                        let mut rich_text = RichText::new(text.as_ref())
-                            .font(self.config.get_rendered_font_id(self.config.rendered_font_size));
-
-                        if in_strikethrough {
-                            rich_text = rich_text.strikethrough().color(self.config.markdown_styles.strikethrough.to_color32());
-                        } else if in_strong {
-                            rich_text = rich_text.strong().color(self.config.markdown_styles.strong.to_color32());
-                        } else if in_emphasis {
-                            rich_text = rich_text.italics().color(self.config.markdown_styles.emphasis.to_color32());
-                        } else {
-                            rich_text = rich_text.color(self.config.markdown_styles.paragraph.to_color32());
-                        }
+                        let style = |piece: &str| {
+                            let mut rich_text = RichText::new(piece)
+                                .font(self.config.get_rendered_font_id(self.config.rendered_font_size));
 
-                        if in_strong && !in_strikethrough {
-                            rich_text = rich_text.strong();
-                        }
-                        if in_emphasis && !in_strikethrough {
-                            rich_text = rich_text.italics();
-                        }
-                        if in_strikethrough {
-                            rich_text = rich_text.strikethrough();
-                        }
+                            if in_strikethrough {
+                                rich_text = rich_text.strikethrough().color(self.config.markdown_styles.strikethrough.to_color32());
+                            } else if in_strong {
+                                rich_text = rich_text.strong().color(self.config.markdown_styles.strong.to_color32());
+                            } else if in_emphasis {
+                                rich_text = rich_text.italics().color(self.config.markdown_styles.emphasis.to_color32());
+                            } else {
+                                rich_text = rich_text.color(self.config.markdown_styles.paragraph.to_color32());
+                            }
 
-                        ui.label(rich_text);
+                            if in_strong && !in_strikethrough {
+                                rich_text = rich_text.strong();
+                            }
+                            if in_emphasis && !in_strikethrough {
+                                rich_text = rich_text.italics();
+                            }
+                            if in_strikethrough {
+                                rich_text = rich_text.strikethrough();
+                            }
+                            rich_text
+                        };
+
+                        self.label_with_tags(ui, text.as_ref(), style);
                         current_i += 1;
                     }
                     Event::Code(code) => {
                         ui.label(RichText::new(code.as_ref())
                             .monospace()
-                            .background_color(Color32::from_rgb(255, 245, 235))
+                            .background_color(self.config.theme.palette().inline_code_background)
                             .color(self.config.markdown_styles.code_inline.to_color32()));
                         current_i += 1;
                     }
@@ -296,12 +437,28 @@ impl RenderedView {
             }
         }
 
+        let item_text: String = events[start..]
+            .iter()
+            .take_while(|event| !matches!(event, Event::End(TagEnd::Item)))
+            .filter_map(|event| match event {
+                Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                _ => None,
+            })
+            .collect();
+        let is_overdue = is_task_item
+            && !is_checked
+            && tasks::due_date(&item_text).is_some_and(|due| due < chrono::Local::now().date_naive());
+
         ui.horizontal_wrapped(|ui| {
             ui.add_space(indent);
 
             if is_task_item {
                 let mut checkbox_checked = is_checked;
-                if ui.checkbox(&mut checkbox_checked, "").clicked() && checkbox_checked != is_checked {
+                let checkbox = ui.checkbox(&mut checkbox_checked, "");
+                checkbox.widget_info(|| {
+                    egui::WidgetInfo::selected(egui::WidgetType::Checkbox, true, checkbox_checked, item_text.as_str())
+                });
+                if checkbox.clicked() && checkbox_checked != is_checked {
                     let line_number = self.find_task_line_number(events, start);
                     checkbox_toggles.push(line_number);
                 }
@@ -346,10 +503,7 @@ impl RenderedView {
                             temp_i += 1;
                         }
 
-                        if ui.add(egui::Hyperlink::from_label_and_url(&link_text, dest_url.as_ref())).clicked()
-                            && let Err(e) = webbrowser::open(dest_url.as_ref()) {
-                                eprintln!("Failed to open link: {}", e);
-                            }
+                        self.render_link(ui, &link_text, dest_url.as_ref());
 
                         current_i = temp_i + 1;
                     }
@@ -359,38 +513,48 @@ impl RenderedView {
                     Event::Start(Tag::Strikethrough) => { in_strikethrough = true; current_i += 1; }
                     Event::End(TagEnd::Strikethrough) => { in_strikethrough = false; current_i += 1; }
                     Event::Text(text) => {
-                        let mut rich_text = RichText::new(text.as_ref())
-                            .font(self.config.get_rendered_font_id(self.config.rendered_font_size));
-
-                        if (is_task_item && is_checked) || in_strikethrough {
-                            rich_text = rich_text.strikethrough().color(self.config.markdown_styles.strikethrough.to_color32());
-                        } else if in_strong {
-                            rich_text = rich_text.strong().color(self.config.markdown_styles.strong.to_color32());
-                        } else if in_emphasis {
-                            rich_text = rich_text.italics().color(self.config.markdown_styles.emphasis.to_color32());
-                        } else {
-                            rich_text = rich_text.color(self.config.markdown_styles.paragraph.to_color32());
-                        }
-
-                        if !is_checked || !is_task_item {
-                            if in_strong && !in_strikethrough {
-                                rich_text = rich_text.strong();
+                        let style = |piece: &str| {
+                            let mut rich_text = RichText::new(piece)
+                                .font(self.config.get_rendered_font_id(self.config.rendered_font_size));
+
+                            if (is_task_item && is_checked) || in_strikethrough {
+                                rich_text = rich_text.strikethrough().color(self.config.markdown_styles.strikethrough.to_color32());
+                            } else if in_strong {
+                                rich_text = rich_text.strong().color(self.config.markdown_styles.strong.to_color32());
+                            } else if in_emphasis {
+                                rich_text = rich_text.italics().color(self.config.markdown_styles.emphasis.to_color32());
+                            } else {
+                                rich_text = rich_text.color(self.config.markdown_styles.paragraph.to_color32());
                             }
-                            if in_emphasis && !in_strikethrough {
-                                rich_text = rich_text.italics();
+                            if is_overdue {
+                                rich_text = rich_text.color(self.config.theme.palette().overdue_task);
                             }
-                            if in_strikethrough {
-                                rich_text = rich_text.strikethrough();
+
+                            if !is_checked || !is_task_item {
+                                if in_strong && !in_strikethrough {
+                                    rich_text = rich_text.strong();
+                                }
+                                if in_emphasis && !in_strikethrough {
+                                    rich_text = rich_text.italics();
+                                }
+                                if in_strikethrough {
+                                    rich_text = rich_text.strikethrough();
+                                }
                             }
-                        }
+                            rich_text
+                        };
 
-                        ui.label(rich_text);
+                        if is_task_item {
+                            self.label_with_task_metadata(ui, text.as_ref(), style, is_overdue);
+                        } else {
+                            self.label_with_tags(ui, text.as_ref(), style);
+                        }
                         current_i += 1;
                     }
                     Event::Code(code) => {
                         ui.label(RichText::new(code.as_ref())
                             .monospace()
-                            .background_color(Color32::from_rgb(255, 245, 235))
+                            .background_color(self.config.theme.palette().inline_code_background)
                             .color(self.config.markdown_styles.code_inline.to_color32()));
                         current_i += 1;
                     }
@@ -413,6 +577,89 @@ impl RenderedView {
         i + 1
     }
 
+    /// Shows `text` styled by `style`, with each `#tag` in it as a clickable label in the tag color.
+    fn label_with_tags(&self, ui: &mut egui::Ui, text: &str, style: impl Fn(&str) -> RichText) {
+        let mut last = 0;
+        for (start, end) in tags::tag_ranges(text) {
+            if start > last {
+                ui.label(style(&text[last..start]));
+            }
+            let tag = &text[start..end];
+            let label = egui::Label::new(style(tag).color(self.config.theme.palette().tag)).sense(egui::Sense::click());
+            if ui.add(label).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_text(tr("preview-tag-hint")).clicked() {
+                self.clicked_tag.set(Some(tag[1..].to_string()));
+            }
+            last = end;
+        }
+        if last == 0 || last < text.len() {
+            ui.label(style(&text[last..]));
+        }
+    }
+
+    /// Shows a task's text with its priority and due date markers as colored badges.
+    fn label_with_task_metadata(&self, ui: &mut egui::Ui, text: &str, style: impl Fn(&str) -> RichText, overdue: bool) {
+        let palette = self.config.theme.palette();
+        let mut last = 0;
+        for (start, end, metadata) in tasks::metadata_ranges(text) {
+            if start > last {
+                self.label_with_tags(ui, &text[last..start], &style);
+            }
+            let (badge, color) = match metadata {
+                tasks::TaskMetadata::Priority(priority) => (priority.label().to_string(), metadata.color(&palette)),
+                tasks::TaskMetadata::Due(date) if overdue => (format!("📅 {}", date), palette.overdue_task),
+                tasks::TaskMetadata::Due(date) => (format!("📅 {}", date), metadata.color(&palette)),
+            };
+            ui.label(
+                RichText::new(badge)
+                    .font(self.config.get_rendered_font_id(self.config.rendered_font_size * 0.85))
+                    .color(color)
+                    .background_color(color.gamma_multiply(0.2)),
+            );
+            last = end;
+        }
+        if last == 0 || last < text.len() {
+            self.label_with_tags(ui, &text[last..], style);
+        }
+    }
+
+    /// Links to other notes open them in the app; everything else goes to the browser, after
+    /// asking when `confirm_external_links` is on. Hovering a link shows where it goes.
+    fn render_link(&self, ui: &mut egui::Ui, text: &str, url: &str) {
+        if let Ok(DeepLink::Note(note)) = DeepLink::parse(url) {
+            if ui.link(text).on_hover_text(&note).clicked() {
+                self.clicked_note.set(Some(note));
+            }
+            return;
+        }
+        if let Some(anchor) = url.strip_prefix('#') {
+            if ui.link(text).on_hover_text(url).clicked() {
+                self.clicked_anchor.set(Some(anchor.to_string()));
+            }
+            return;
+        }
+        if attachment_preview::is_previewable(url) {
+            if ui.link(text).on_hover_text(url).clicked() {
+                self.clicked_attachment.set(Some(url.to_string()));
+            }
+            return;
+        }
+        let link = ui.link(text).on_hover_text(url);
+        link.context_menu(|ui| {
+            if ui.button(tr("preview-copy-link")).clicked() {
+                ui.ctx().copy_text(url.to_string());
+                ui.close();
+            }
+        });
+        if !link.clicked() {
+            return;
+        }
+        if self.config.confirm_external_links {
+            self.clicked_external_link.set(Some(url.to_string()));
+        } else if let Err(e) = webbrowser::open(url) {
+            eprintln!("Failed to open link: {}", e);
+        }
+    }
+
     fn render_code_block(&self, ui: &mut egui::Ui, events: &[Event], start: usize) -> usize {
         let mut i = start;
         let mut code_text = String::new();
@@ -426,7 +673,7 @@ impl RenderedView {
             i += 1;
         }
 
-        ui.add_space(8.0);
+        ui.add_space(self.block_spacing() * 2.0);
         ui.vertical(|ui| {
             ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
             ui.label(RichText::new(&code_text)
@@ -439,7 +686,7 @@ impl RenderedView {
                 ))
                 .color(self.config.markdown_styles.code_block.to_color32()));
         });
-        ui.add_space(8.0);
+        ui.add_space(self.block_spacing() * 2.0);
 
         i + 1
     }
@@ -447,9 +694,9 @@ impl RenderedView {
     fn render_blockquote(&self, ui: &mut egui::Ui, events: &[Event], start: usize, context: &mut MarkdownContext, checkbox_toggles: &mut Vec<usize>) -> usize {
         let mut i = start;
 
-        ui.add_space(4.0);
+        ui.add_space(self.block_spacing());
         ui.horizontal(|ui| {
-            ui.label(RichText::new("▎").color(Color32::from_rgb(120, 120, 120)).font(self.config.get_rendered_font_id(20.0)));
+            ui.label(RichText::new("▎").color(self.config.theme.palette().blockquote_bar).font(self.config.get_rendered_font_id(20.0)));
             ui.vertical(|ui| {
                 while i < events.len() {
                     match &events[i] {
@@ -461,7 +708,7 @@ impl RenderedView {
                 }
             });
         });
-        ui.add_space(4.0);
+        ui.add_space(self.block_spacing());
 
         i + 1
     }
@@ -500,7 +747,7 @@ impl RenderedView {
         }
 
         let mut count = 0usize;
-        for (lineno, line) in self.current_markdown_text.lines().enumerate() {
+        for (lineno, line) in self.cached_events_text.lines().enumerate() {
             if line.contains("- [ ]") || line.contains("- [x]") {
                 count += 1;
                 if count == task_ordinal {
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::config::Config;
+use crate::editor::Editor;
+use crate::file_manager::FileManager;
+use crate::i18n::tr;
+
+const PANEL_WIDTH: f32 = 380.0;
+const PANEL_MARGIN: f32 = 8.0;
+
+/// Slide-over panel with one persistent buffer for throwaway text. It's saved in the notes
+/// folder on every edit but kept out of the notes list.
+pub struct Scratchpad {
+    pub show_panel: bool,
+    editor: Editor,
+    file_manager: FileManager,
+    notes_folder: PathBuf,
+}
+
+pub enum ScratchpadAction {
+    None,
+    Close,
+    SaveFailed,
+}
+
+impl Scratchpad {
+    pub fn new(config: &Config) -> Self {
+        let file_manager = FileManager::new(config);
+        let mut editor = Editor::new(config);
+        editor.set_text(&file_manager.read_scratchpad());
+        Self {
+            show_panel: false,
+            editor,
+            file_manager,
+            notes_folder: config.notes_folder.clone(),
+        }
+    }
+
+    /// Picks up style changes, and reloads the text when the notes folder has changed.
+    pub fn set_config(&mut self, config: &Config) -> Vec<String> {
+        if self.notes_folder != config.notes_folder {
+            self.file_manager = FileManager::new(config);
+            self.notes_folder = config.notes_folder.clone();
+            self.editor.set_text(&self.file_manager.read_scratchpad());
+        }
+        self.editor.set_config(config)
+    }
+
+    pub fn toggle_panel(&mut self) {
+        self.show_panel = !self.show_panel;
+        if self.show_panel {
+            self.editor.request_focus();
+        }
+    }
+
+    pub fn has_focus(&self, ctx: &egui::Context) -> bool {
+        self.show_panel && self.editor.has_focus(ctx)
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.editor.undo() && self.save()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.editor.redo() && self.save()
+    }
+
+    fn save(&self) -> bool {
+        self.file_manager.write_scratchpad(self.editor.get_text())
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> ScratchpadAction {
+        let mut action = ScratchpadAction::None;
+        let openness = ctx.animate_bool(egui::Id::new("scratchpad_slide"), self.show_panel);
+        if openness == 0.0 {
+            return action;
+        }
+
+        let screen = ctx.content_rect();
+        let width = PANEL_WIDTH.min(screen.width());
+        egui::Area::new(egui::Id::new("scratchpad_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(egui::pos2(screen.right() - width * openness, screen.top()))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .corner_radius(0.0)
+                    .inner_margin(PANEL_MARGIN)
+                    .show(ui, |ui| {
+                        ui.set_width(width - 2.0 * PANEL_MARGIN);
+                        ui.set_height(screen.height() - 2.0 * PANEL_MARGIN);
+                        ui.horizontal(|ui| {
+                            ui.strong(tr("scratchpad-title"));
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.small_button("✕").on_hover_text(tr("scratchpad-close")).clicked() {
+                                    action = ScratchpadAction::Close;
+                                }
+                            });
+                        });
+                        ui.weak(tr("scratchpad-hint"));
+                        ui.separator();
+                        if self.editor.render(ui) && !self.save() {
+                            action = ScratchpadAction::SaveFailed;
+                        }
+                    });
+            });
+
+        if let ScratchpadAction::Close = action {
+            self.show_panel = false;
+        }
+
+        action
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::{BTreeSet, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// First line of the index file; bump the number when the format changes so old files are
+/// rebuilt instead of misread.
+const INDEX_HEADER: &str = "notesquirrel-search-index 1";
+
+struct IndexedNote {
+    /// The file's modification time when it was indexed, in milliseconds since the epoch.
+    /// `None` until the save that produced the indexed content has reached the disk.
+    modified: Option<u64>,
+    terms: BTreeSet<String>,
+}
+
+/// The words in every note, kept in the notes folder between runs so a large
+/// vault is searchable at startup without reading every file. Only notes whose modification time
+/// changed since they were indexed need to be read again.
+#[derive(Default)]
+pub struct SearchIndex {
+    notes: HashMap<String, IndexedNote>,
+    /// Changed since it was loaded or last saved.
+    dirty: bool,
+}
+
+impl SearchIndex {
+    /// Reads an index saved by `serialize`. Anything unreadable just gives an empty index.
+    pub fn parse(data: &str) -> Self {
+        let mut index = Self::default();
+        let mut lines = data.lines();
+        if lines.next() != Some(INDEX_HEADER) {
+            return index;
+        }
+        for line in lines {
+            let mut fields = line.splitn(3, '\t');
+            let (Some(name), Some(modified), Some(terms)) = (fields.next(), fields.next(), fields.next()) else {
+                continue;
+            };
+            let terms: BTreeSet<String> = terms.split(' ').filter(|t| !t.is_empty()).map(str::to_string).collect();
+            index.notes.insert(name.to_string(), IndexedNote { modified: modified.parse().ok(), terms });
+        }
+        index
+    }
+
+    pub fn serialize(&self) -> String {
+        let mut names: Vec<&String> = self.notes.keys().collect();
+        names.sort();
+        let mut data = format!("{}\n", INDEX_HEADER);
+        for name in names {
+            let note = &self.notes[name];
+            let modified = note.modified.map(|m| m.to_string()).unwrap_or_default();
+            let terms: Vec<&str> = note.terms.iter().map(String::as_str).collect();
+            data.push_str(&format!("{}\t{}\t{}\n", name, modified, terms.join(" ")));
+        }
+        data
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Whether the note needs to be read again: it's missing, or the file changed since it was
+    /// indexed.
+    pub fn is_stale(&self, name: &str, modified: Option<SystemTime>) -> bool {
+        self.notes
+            .get(name)
+            .is_none_or(|note| note.modified.is_none() || note.modified != modified.and_then(millis))
+    }
+
+    /// Indexes `content` as note `name`. `modified` is the file's modification time, when known.
+    pub fn update(&mut self, name: &str, content: &str, modified: Option<SystemTime>) {
+        let terms: BTreeSet<String> = tokenize(content).collect();
+        let modified = modified.and_then(millis);
+        if self.notes.get(name).is_some_and(|note| note.terms == terms && note.modified == modified) {
+            return;
+        }
+        self.notes.insert(name.to_string(), IndexedNote { modified, terms });
+        self.dirty = true;
+    }
+
+    /// Records the modification time of a save that has reached the disk.
+    pub fn set_modified(&mut self, name: &str, modified: Option<SystemTime>) {
+        if let Some(note) = self.notes.get_mut(name) {
+            note.modified = modified.and_then(millis);
+            self.dirty = true;
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        if self.notes.remove(name).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    pub fn rename(&mut self, old_name: &str, new_name: &str) {
+        if let Some(note) = self.notes.remove(old_name) {
+            self.notes.insert(new_name.to_string(), note);
+            self.dirty = true;
+        }
+    }
+
+    /// Drops notes that aren't in `names`, e.g. ones deleted while the app was closed.
+    pub fn retain(&mut self, names: &[String]) {
+        let gone: Vec<String> = self.notes.keys().filter(|name| !names.contains(name)).cloned().collect();
+        for name in gone {
+            self.remove(&name);
+        }
+    }
+
+    /// Whether note `name` contains every word of `query`, each as a word or the start of one.
+    /// The terms are sorted, so each word is a single range lookup.
+    pub fn matches(&self, name: &str, query: &str) -> bool {
+        let Some(note) = self.notes.get(name) else {
+            return false;
+        };
+        let mut words = tokenize(query).peekable();
+        words.peek().is_some()
+            && words.all(|word| note.terms.range(word.clone()..).next().is_some_and(|term| term.starts_with(&word)))
+    }
+}
+
+/// The lowercased words in `text`.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn millis(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_millis() as u64)
+}
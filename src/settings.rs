@@ -0,0 +1,617 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::config::{Config, DateFormats, FocusScope, HighlightRule, ListMarker, MarkdownStyle, MAX_UI_SCALE, MIN_UI_SCALE, UI_SCALE_STEP};
+use crate::editor::compile_highlight_rules;
+use crate::folder_picker::FolderPicker;
+use crate::ignore_rules::IgnoreRules;
+use crate::i18n::{tr, tr_args, Language};
+use crate::keymap::{Action, Keymap, KeymapPreset};
+use crate::template_manager::{TemplateManager, TemplateManagerAction};
+use crate::theme::ThemePreset;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsTab {
+    General,
+    Editor,
+    PreviewStyles,
+    Keybindings,
+    Vault,
+    Templates,
+}
+
+pub struct SettingsDialog {
+    pub show_dialog: bool,
+    draft: Config,
+    notes_folder_text: String,
+    folder_picker: FolderPicker,
+    /// Whether applying a new notes folder moves the notes from the old one into it.
+    move_notes: bool,
+    note_extensions_text: String,
+    ignore_patterns_text: String,
+    template_text: String,
+    daily_template_text: String,
+    keymap_text: BTreeMap<Action, String>,
+    installed_fonts: Option<Vec<String>>,
+    keymap_preset: KeymapPreset,
+    templates: TemplateManager,
+    tab: SettingsTab,
+}
+
+pub enum SettingsAction {
+    None,
+    Apply {
+        config: Box<Config>,
+        /// Move the notes from the current notes folder into the new one.
+        move_notes: bool,
+    },
+}
+
+impl SettingsDialog {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            show_dialog: false,
+            draft: config.clone(),
+            notes_folder_text: config.notes_folder.display().to_string(),
+            folder_picker: FolderPicker::new("settings_folder_picker"),
+            move_notes: false,
+            note_extensions_text: config.note_extensions.join(", "),
+            ignore_patterns_text: config.ignore_patterns.join("\n"),
+            template_text: Self::path_text(config.new_note_template.as_ref()),
+            daily_template_text: Self::path_text(config.daily_note_template.as_ref()),
+            keymap_text: Self::keymap_text_from(config),
+            installed_fonts: None,
+            keymap_preset: KeymapPreset::Default,
+            templates: TemplateManager::new(),
+            tab: SettingsTab::General,
+        }
+    }
+
+    pub fn open(&mut self, config: &Config) {
+        self.draft = config.clone();
+        self.notes_folder_text = config.notes_folder.display().to_string();
+        self.move_notes = false;
+        self.note_extensions_text = self.draft.note_extensions.join(", ");
+        self.ignore_patterns_text = self.draft.ignore_patterns.join("\n");
+        self.template_text = Self::path_text(config.new_note_template.as_ref());
+        self.daily_template_text = Self::path_text(config.daily_note_template.as_ref());
+        self.keymap_text = Self::keymap_text_from(config);
+        self.templates.load(&config.notes_folder);
+        self.show_dialog = true;
+    }
+
+    pub fn toggle_dialog(&mut self, config: &Config) {
+        if self.show_dialog {
+            self.show_dialog = false;
+        } else {
+            self.open(config);
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> SettingsAction {
+        let mut action = SettingsAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(tr("settings-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.tab, SettingsTab::General, tr("settings-tab-general"));
+                    ui.selectable_value(&mut self.tab, SettingsTab::Editor, tr("settings-tab-editor"));
+                    ui.selectable_value(&mut self.tab, SettingsTab::PreviewStyles, tr("settings-tab-preview"));
+                    ui.selectable_value(&mut self.tab, SettingsTab::Keybindings, tr("settings-tab-keybindings"));
+                    ui.selectable_value(&mut self.tab, SettingsTab::Vault, tr("settings-tab-vault"));
+                    ui.selectable_value(&mut self.tab, SettingsTab::Templates, tr("settings-tab-templates"));
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .id_salt("settings_scroll")
+                    .show(ui, |ui| {
+                        match self.tab {
+                            SettingsTab::General => self.render_general_tab(ui),
+                            SettingsTab::Editor => self.render_editor_tab(ui),
+                            SettingsTab::PreviewStyles => self.render_preview_styles_tab(ui),
+                            SettingsTab::Keybindings => self.render_keybindings_tab(ui),
+                            SettingsTab::Vault => self.render_vault_tab(ui),
+                            SettingsTab::Templates => self.render_templates_tab(ui),
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("button-ok")).clicked() {
+                        action = self.build_apply_action();
+                        close = true;
+                    }
+                    if ui.button(tr("button-apply")).clicked() {
+                        action = self.build_apply_action();
+                    }
+                    if ui.button(tr("button-cancel")).clicked() {
+                        close = true;
+                    }
+                });
+
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
+            });
+
+        if let Some(folder) = self.folder_picker.render(ctx) {
+            self.notes_folder_text = folder.display().to_string();
+        }
+        if !open || close {
+            self.show_dialog = false;
+            self.folder_picker.show_dialog = false;
+        }
+
+        action
+    }
+
+    fn path_text(path: Option<&PathBuf>) -> String {
+        path.map(|p| p.display().to_string()).unwrap_or_default()
+    }
+
+    fn keymap_text_from(config: &Config) -> BTreeMap<Action, String> {
+        Action::ALL
+            .iter()
+            .map(|action| {
+                let text = config.keymap.get(action).cloned().unwrap_or_else(|| action.default_chord().to_string());
+                (*action, text)
+            })
+            .collect()
+    }
+
+    fn build_apply_action(&mut self) -> SettingsAction {
+        self.templates.save();
+        let folder = self.notes_folder_text.trim();
+        if !folder.is_empty() {
+            self.draft.notes_folder = PathBuf::from(folder);
+        }
+        self.draft.note_extensions = self
+            .note_extensions_text
+            .split(',')
+            .map(|extension| extension.trim().trim_start_matches('.').to_string())
+            .filter(|extension| !extension.is_empty())
+            .collect();
+        self.draft.ignore_patterns = self
+            .ignore_patterns_text
+            .lines()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .collect();
+        let template = self.template_text.trim();
+        self.draft.new_note_template = (!template.is_empty()).then(|| PathBuf::from(template));
+        let daily_template = self.daily_template_text.trim();
+        self.draft.daily_note_template = (!daily_template.is_empty()).then(|| PathBuf::from(daily_template));
+        self.draft.keymap = self
+            .keymap_text
+            .iter()
+            .filter(|(action, text)| text.trim() != action.default_chord())
+            .map(|(action, text)| (*action, text.trim().to_string()))
+            .collect();
+        let move_notes = std::mem::take(&mut self.move_notes);
+        SettingsAction::Apply {
+            config: Box::new(self.draft.clone()),
+            move_notes,
+        }
+    }
+
+    fn render_general_tab(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("settings_general_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr("settings-language"));
+            egui::ComboBox::from_id_salt("settings_language_combo")
+                .selected_text(self.draft.language.native_name())
+                .show_ui(ui, |ui| {
+                    for language in Language::ALL {
+                        ui.selectable_value(&mut self.draft.language, language, language.native_name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr("settings-theme"));
+            let mut selected_theme = self.draft.theme;
+            egui::ComboBox::from_id_salt("settings_theme_combo")
+                .selected_text(selected_theme.label())
+                .show_ui(ui, |ui| {
+                    for preset in ThemePreset::ALL {
+                        ui.selectable_value(&mut selected_theme, preset, preset.label());
+                    }
+                });
+            if selected_theme != self.draft.theme {
+                self.draft.theme = selected_theme;
+                let spacing = (self.draft.markdown_styles.editor_line_spacing, self.draft.markdown_styles.block_spacing);
+                self.draft.markdown_styles = selected_theme.markdown_styles();
+                (self.draft.markdown_styles.editor_line_spacing, self.draft.markdown_styles.block_spacing) = spacing;
+                self.draft.editor_styles = selected_theme.editor_styles();
+            }
+            ui.end_row();
+
+            ui.label(tr("settings-ui-scale"));
+            ui.add(
+                egui::Slider::new(&mut self.draft.ui_scale, MIN_UI_SCALE..=MAX_UI_SCALE)
+                    .step_by(UI_SCALE_STEP as f64)
+                    .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)),
+            );
+            ui.end_row();
+
+            ui.label(tr("settings-list-font"));
+            font_picker(ui, "settings_list_font", &mut self.draft.list_font_family, self.installed_fonts.get_or_insert_with(Config::list_installed_fonts));
+            ui.end_row();
+
+            ui.label(tr("settings-list-font-size"));
+            ui.add(egui::DragValue::new(&mut self.draft.list_font_size).range(6.0..=48.0).speed(0.5));
+            ui.end_row();
+        });
+        ui.add_space(4.0);
+        ui.label(font_family_hint());
+        ui.separator();
+
+        ui.label(tr("settings-date-formats"));
+        let now = chrono::Local::now();
+        let formats = &mut self.draft.formats;
+        egui::Grid::new("settings_date_formats_grid").num_columns(3).show(ui, |ui| {
+            format_row(ui, tr("settings-format-date"), &mut formats.date, tr("format-date"), &now);
+            format_row(ui, tr("settings-format-time"), &mut formats.time, tr("format-time"), &now);
+            format_row(ui, tr("settings-format-datetime"), &mut formats.datetime, tr("format-datetime"), &now);
+            format_row(ui, tr("settings-format-week"), &mut formats.week, tr("format-week"), &now);
+        });
+        ui.label(tr("settings-date-formats-hint"));
+    }
+
+    fn render_editor_tab(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("settings_editor_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr("settings-editor-font"));
+            font_picker(ui, "settings_editor_font", &mut self.draft.editor_font_family, self.installed_fonts.get_or_insert_with(Config::list_installed_fonts));
+            ui.end_row();
+
+            ui.label(tr("settings-editor-font-size"));
+            ui.add(egui::DragValue::new(&mut self.draft.editor_font_size).range(6.0..=48.0).speed(0.5));
+            ui.end_row();
+
+            ui.label(tr("settings-editor-max-line-width"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.draft.editor_max_line_width).range(0..=400));
+                ui.label(tr("settings-editor-max-line-width-hint"));
+            });
+            ui.end_row();
+
+            ui.label(tr("settings-zen-line-width"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.draft.zen_line_width).range(0..=400));
+                ui.checkbox(&mut self.draft.zen_dim_surroundings, tr("settings-zen-dim"));
+            });
+            ui.end_row();
+
+            ui.label(tr("settings-focus-scope"));
+            egui::ComboBox::from_id_salt("settings_focus_scope_combo")
+                .selected_text(self.draft.focus_scope.label())
+                .show_ui(ui, |ui| {
+                    for scope in FocusScope::ALL {
+                        ui.selectable_value(&mut self.draft.focus_scope, scope, scope.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr("settings-autocorrect"));
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.draft.autocorrect_markdown, tr("settings-autocorrect-enabled"));
+                ui.label(tr("settings-list-marker"));
+                egui::ComboBox::from_id_salt("settings_list_marker_combo")
+                    .selected_text(self.draft.list_marker.marker().to_string())
+                    .width(40.0)
+                    .show_ui(ui, |ui| {
+                        for marker in ListMarker::ALL {
+                            ui.selectable_value(&mut self.draft.list_marker, marker, marker.marker().to_string());
+                        }
+                    });
+            });
+            ui.end_row();
+
+            ui.label(tr("settings-editor-line-spacing"));
+            ui.add(egui::DragValue::new(&mut self.draft.markdown_styles.editor_line_spacing).range(0.8..=3.0).speed(0.05).fixed_decimals(2));
+            ui.end_row();
+        });
+        ui.add_space(4.0);
+        ui.label(font_family_hint());
+        ui.separator();
+
+        ui.label(tr("settings-syntax-highlighting"));
+        let styles = &mut self.draft.editor_styles;
+        egui::Grid::new("settings_editor_styles_grid").num_columns(2).show(ui, |ui| {
+            color_row(ui, tr("style-text"), &mut styles.text);
+            color_row(ui, tr("style-heading-marker"), &mut styles.heading_marker);
+            color_row(ui, &heading_label(1), &mut styles.h1);
+            color_row(ui, &heading_label(2), &mut styles.h2);
+            color_row(ui, &heading_label(3), &mut styles.h3);
+            color_row(ui, &heading_label(4), &mut styles.h4);
+            color_row(ui, &heading_label(5), &mut styles.h5);
+            color_row(ui, &heading_label(6), &mut styles.h6);
+            color_row(ui, tr("style-code-fence"), &mut styles.code);
+            color_row(ui, tr("style-code-fence-background"), &mut styles.code_background);
+            color_row(ui, tr("style-block-quote"), &mut styles.quote);
+            color_row(ui, tr("style-list-item"), &mut styles.list);
+        });
+        ui.separator();
+
+        ui.label(tr("settings-highlight-rules"));
+        let mut remove_index = None;
+        egui::Grid::new("settings_highlight_rules_grid").num_columns(4).show(ui, |ui| {
+            for (index, rule) in self.draft.highlight_rules.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut rule.pattern).hint_text("TODO:").desired_width(160.0));
+                optional_color(ui, tr("settings-highlight-text-color"), &mut rule.color, [230, 120, 40]);
+                optional_color(ui, tr("settings-highlight-background"), &mut rule.background, [80, 60, 20]);
+                if ui.button("✖").on_hover_text(tr("settings-highlight-remove")).clicked() {
+                    remove_index = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = remove_index {
+            self.draft.highlight_rules.remove(index);
+        }
+        for error in compile_highlight_rules(&self.draft.highlight_rules).1 {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        if ui.button(tr("settings-highlight-add")).clicked() {
+            self.draft.highlight_rules.push(HighlightRule {
+                pattern: String::new(),
+                color: Some([230, 120, 40]),
+                background: None,
+            });
+        }
+    }
+
+    fn render_preview_styles_tab(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("settings_preview_font_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr("settings-preview-font"));
+            font_picker(ui, "settings_rendered_font", &mut self.draft.rendered_font_family, self.installed_fonts.get_or_insert_with(Config::list_installed_fonts));
+            ui.end_row();
+
+            ui.label(tr("settings-preview-font-size"));
+            ui.add(egui::DragValue::new(&mut self.draft.rendered_font_size).range(6.0..=48.0).speed(0.5));
+            ui.end_row();
+
+            ui.label(tr("settings-block-spacing"));
+            ui.add(egui::DragValue::new(&mut self.draft.markdown_styles.block_spacing).range(0.0..=40.0).speed(0.5));
+            ui.end_row();
+        });
+        ui.separator();
+
+        let styles = &mut self.draft.markdown_styles;
+        egui::Grid::new("settings_preview_styles_grid").num_columns(3).show(ui, |ui| {
+            style_row(ui, &heading_label(1), &mut styles.h1);
+            style_row(ui, &heading_label(2), &mut styles.h2);
+            style_row(ui, &heading_label(3), &mut styles.h3);
+            style_row(ui, &heading_label(4), &mut styles.h4);
+            style_row(ui, &heading_label(5), &mut styles.h5);
+            style_row(ui, &heading_label(6), &mut styles.h6);
+            style_row(ui, tr("style-paragraph"), &mut styles.paragraph);
+            style_row(ui, tr("style-strong"), &mut styles.strong);
+            style_row(ui, tr("style-emphasis"), &mut styles.emphasis);
+            style_row(ui, tr("style-strikethrough"), &mut styles.strikethrough);
+            style_row(ui, tr("style-inline-code"), &mut styles.code_inline);
+            style_row(ui, tr("style-code-block"), &mut styles.code_block);
+            style_row(ui, tr("style-list-bullet"), &mut styles.list_bullet);
+
+            ui.label(tr("style-code-block-background"));
+            ui.color_edit_button_srgb(&mut styles.code_block_background);
+            ui.end_row();
+        });
+    }
+
+    fn render_keybindings_tab(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-keymap-preset"));
+            egui::ComboBox::from_id_salt("settings_keymap_preset_combo")
+                .selected_text(self.keymap_preset.label())
+                .show_ui(ui, |ui| {
+                    for preset in KeymapPreset::ALL {
+                        ui.selectable_value(&mut self.keymap_preset, preset, preset.label());
+                    }
+                });
+            if ui.button(tr("settings-keymap-preset-apply")).clicked() {
+                for action in Action::ALL {
+                    self.keymap_text.insert(action, self.keymap_preset.chord(action).to_string());
+                }
+            }
+        });
+        ui.separator();
+
+        egui::Grid::new("settings_keybindings_grid").num_columns(3).striped(true).show(ui, |ui| {
+            for action in Action::ALL {
+                ui.label(action.category());
+                ui.label(action.label());
+                if let Some(text) = self.keymap_text.get_mut(&action) {
+                    ui.add(egui::TextEdit::singleline(text).desired_width(120.0));
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(4.0);
+        ui.label(tr("settings-keybindings-hint"));
+
+        let (_, errors) = Keymap::from_overrides(&self.keymap_text);
+        for error in errors {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        if ui.button(tr("settings-reset-keybindings")).clicked() {
+            self.keymap_preset = KeymapPreset::Default;
+            for action in Action::ALL {
+                self.keymap_text.insert(action, action.default_chord().to_string());
+            }
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-global-hotkey"));
+            ui.add(egui::TextEdit::singleline(&mut self.draft.global_hotkey).desired_width(120.0));
+        });
+        ui.label(tr("settings-global-hotkey-hint"));
+    }
+
+    fn render_vault_tab(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("settings-notes-folder"));
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.notes_folder_text).desired_width(ui.available_width() - 80.0));
+            if ui.button(tr("settings-browse")).clicked() {
+                self.folder_picker.open(Path::new(self.notes_folder_text.trim()));
+            }
+        });
+        ui.add_space(4.0);
+        ui.label(tr("settings-notes-folder-hint"));
+        let folder = self.notes_folder_text.trim();
+        if !folder.is_empty() && Path::new(folder) != self.draft.notes_folder {
+            ui.checkbox(&mut self.move_notes, tr("settings-move-notes"))
+                .on_hover_text(tr("settings-move-notes-hint"));
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-note-extensions"));
+            ui.add(egui::TextEdit::singleline(&mut self.note_extensions_text).desired_width(160.0));
+            ui.label(tr("settings-new-note-extension"));
+            ui.add(egui::TextEdit::singleline(&mut self.draft.new_note_extension).desired_width(60.0));
+        });
+        ui.label(tr("settings-note-extensions-hint"));
+        ui.label(tr("settings-ignore-patterns"));
+        ui.add(
+            egui::TextEdit::multiline(&mut self.ignore_patterns_text)
+                .hint_text(".obsidian/\ntemplates/\n*.tmp")
+                .desired_rows(3)
+                .desired_width(f32::INFINITY),
+        );
+        for error in self.ignore_patterns_text.lines().filter_map(|pattern| IgnoreRules::validate(pattern).err()) {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ui.label(tr("settings-ignore-patterns-hint"));
+        ui.checkbox(&mut self.draft.confirm_delete, tr("settings-confirm-delete"));
+        ui.checkbox(&mut self.draft.confirm_external_links, tr("settings-confirm-external-links"));
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-privacy-screen-minutes"));
+            ui.add(egui::DragValue::new(&mut self.draft.privacy_screen_minutes).range(0..=240));
+        });
+        ui.checkbox(&mut self.draft.privacy_screen_on_focus_loss, tr("settings-privacy-screen-on-focus-loss"));
+        ui.checkbox(&mut self.draft.delete_to_trash, tr("settings-delete-to-trash"));
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-note-history-minutes"));
+            ui.add(egui::DragValue::new(&mut self.draft.note_history_minutes).range(0..=1440));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-note-history-limit"));
+            ui.add(egui::DragValue::new(&mut self.draft.note_history_limit).range(1..=1000));
+        });
+        ui.checkbox(&mut self.draft.task_reminders, tr("settings-task-reminders"));
+        ui.checkbox(&mut self.draft.remember_note_views, tr("settings-remember-note-views"));
+        ui.separator();
+
+        ui.label(tr("settings-new-note-pattern"));
+        ui.add(egui::TextEdit::singleline(&mut self.draft.new_note_pattern).desired_width(f32::INFINITY));
+        ui.label(tr("settings-new-note-pattern-hint"));
+        ui.add_space(4.0);
+
+        ui.label(tr("settings-new-note-template"));
+        ui.add(egui::TextEdit::singleline(&mut self.template_text).desired_width(f32::INFINITY));
+        ui.label(tr("settings-new-note-template-hint"));
+        ui.separator();
+
+        ui.label(tr("settings-daily-note-format"));
+        ui.add(egui::TextEdit::singleline(&mut self.draft.daily_note_format).desired_width(f32::INFINITY));
+        if let Err(e) = DateFormats::validate(&self.draft.daily_note_format) {
+            ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+        ui.add_space(4.0);
+
+        ui.label(tr("settings-daily-note-template"));
+        ui.add(egui::TextEdit::singleline(&mut self.daily_template_text).desired_width(f32::INFINITY));
+        ui.label(tr("settings-daily-note-template-hint"));
+    }
+
+    fn render_templates_tab(&mut self, ui: &mut egui::Ui) {
+        match self.templates.render(ui, &self.draft.formats) {
+            TemplateManagerAction::None => {}
+            TemplateManagerAction::UseForNewNotes(path) => self.template_text = path.display().to_string(),
+            TemplateManagerAction::UseForDailyNotes(path) => self.daily_template_text = path.display().to_string(),
+            TemplateManagerAction::Renamed { from, to } => {
+                let from = from.display().to_string();
+                for text in [&mut self.template_text, &mut self.daily_template_text] {
+                    if text.trim() == from {
+                        *text = to.display().to_string();
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn style_row(ui: &mut egui::Ui, label: &str, style: &mut MarkdownStyle) {
+    ui.label(label);
+    ui.add(egui::DragValue::new(&mut style.font_size).range(6.0..=72.0).speed(0.5));
+    ui.color_edit_button_srgb(&mut style.color);
+    ui.end_row();
+}
+
+fn format_row(ui: &mut egui::Ui, label: &str, format: &mut String, default_format: &str, now: &chrono::DateTime<chrono::Local>) {
+    ui.label(label);
+    ui.add(egui::TextEdit::singleline(format).hint_text(default_format).desired_width(160.0));
+    let effective = if format.trim().is_empty() { default_format } else { format.as_str() };
+    match DateFormats::validate(effective) {
+        Ok(()) => ui.label(now.format(effective).to_string()),
+        Err(e) => ui.colored_label(ui.visuals().error_fg_color, e),
+    };
+    ui.end_row();
+}
+
+fn optional_color(ui: &mut egui::Ui, label: &str, color: &mut Option<[u8; 3]>, default_color: [u8; 3]) {
+    ui.horizontal(|ui| {
+        let mut enabled = color.is_some();
+        if ui.checkbox(&mut enabled, label).changed() {
+            *color = enabled.then_some(default_color);
+        }
+        if let Some(color) = color {
+            ui.color_edit_button_srgb(color);
+        }
+    });
+}
+
+fn color_row(ui: &mut egui::Ui, label: &str, color: &mut [u8; 3]) {
+    ui.label(label);
+    ui.color_edit_button_srgb(color);
+    ui.end_row();
+}
+
+pub fn font_picker(ui: &mut egui::Ui, id: &str, family: &mut String, installed_fonts: &[String]) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(family.as_str())
+        .width(220.0)
+        .height(300.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(family, "monospace".to_string(), tr("settings-font-monospace"));
+            ui.selectable_value(family, "proportional".to_string(), tr("settings-font-proportional"));
+            ui.separator();
+            for font in installed_fonts {
+                ui.selectable_value(family, font.clone(), font);
+            }
+        });
+}
+
+pub fn font_family_hint() -> &'static str {
+    tr("settings-font-hint")
+}
+
+fn heading_label(level: u8) -> String {
+    tr_args("style-heading", &[("level", &level)])
+}
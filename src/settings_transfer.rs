@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::i18n::tr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferMode {
+    Export,
+    Import,
+}
+
+pub struct SettingsTransfer {
+    pub show_dialog: bool,
+    mode: TransferMode,
+    path_text: String,
+    keep_notes_folder: bool,
+    status: Option<Result<String, String>>,
+}
+
+pub enum SettingsTransferAction {
+    None,
+    Export(PathBuf),
+    Import { path: PathBuf, keep_notes_folder: bool },
+}
+
+impl SettingsTransfer {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            mode: TransferMode::Export,
+            path_text: default_bundle_path().display().to_string(),
+            keep_notes_folder: true,
+            status: None,
+        }
+    }
+
+    pub fn open(&mut self, mode: TransferMode) {
+        self.mode = mode;
+        self.status = None;
+        self.show_dialog = true;
+    }
+
+    /// Shows the outcome of the last export or import below the path field.
+    pub fn set_status(&mut self, status: Result<String, String>) {
+        self.status = Some(status);
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> SettingsTransferAction {
+        let mut action = SettingsTransferAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let title = match self.mode {
+            TransferMode::Export => tr("transfer-export-title"),
+            TransferMode::Import => tr("transfer-import-title"),
+        };
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(title)
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                match self.mode {
+                    TransferMode::Export => {
+                        ui.label(tr("transfer-export-description"));
+                    }
+                    TransferMode::Import => {
+                        ui.label(tr("transfer-import-description"));
+                    }
+                }
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("transfer-file"));
+                    ui.add(egui::TextEdit::singleline(&mut self.path_text).desired_width(f32::INFINITY));
+                });
+
+                if self.mode == TransferMode::Import {
+                    ui.checkbox(&mut self.keep_notes_folder, tr("transfer-keep-notes-folder"));
+                }
+
+                match &self.status {
+                    Some(Ok(message)) => {
+                        ui.label(message);
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    None => {}
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let path_text = self.path_text.trim();
+                    let button_label = match self.mode {
+                        TransferMode::Export => tr("transfer-export"),
+                        TransferMode::Import => tr("transfer-import"),
+                    };
+                    if ui.add_enabled(!path_text.is_empty(), egui::Button::new(button_label)).clicked() {
+                        let path = PathBuf::from(path_text);
+                        action = match self.mode {
+                            TransferMode::Export => SettingsTransferAction::Export(path),
+                            TransferMode::Import => SettingsTransferAction::Import {
+                                path,
+                                keep_notes_folder: self.keep_notes_folder,
+                            },
+                        };
+                    }
+                    if ui.button(tr("button-close")).clicked() {
+                        close = true;
+                    }
+                });
+
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
+            });
+
+        if !open || close {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+fn default_bundle_path() -> PathBuf {
+    std::env::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("NoteSquirrel-settings.toml")
+}
+
+impl Default for SettingsTransfer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
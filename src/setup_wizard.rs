@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::config::Config;
+use crate::folder_picker::FolderPicker;
+use crate::i18n::{tr, tr_args};
+use crate::settings::{font_family_hint, font_picker};
+use crate::theme::ThemePreset;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SetupStep {
+    NotesFolder,
+    Theme,
+    Fonts,
+    Import,
+}
+
+impl SetupStep {
+    const ALL: [SetupStep; 4] = [SetupStep::NotesFolder, SetupStep::Theme, SetupStep::Fonts, SetupStep::Import];
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|step| step == self).unwrap_or(0)
+    }
+}
+
+/// Walks a new user through the basic settings on first launch, before any notes folder is created.
+pub struct SetupWizard {
+    pub show_dialog: bool,
+    step: SetupStep,
+    draft: Config,
+    notes_folder_text: String,
+    import_enabled: bool,
+    import_folder_text: String,
+    notes_folder_picker: FolderPicker,
+    import_folder_picker: FolderPicker,
+    installed_fonts: Option<Vec<String>>,
+}
+
+pub enum SetupWizardAction {
+    None,
+    Finish {
+        config: Box<Config>,
+        import_from: Option<PathBuf>,
+    },
+}
+
+impl SetupWizard {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            step: SetupStep::NotesFolder,
+            draft: Config::default(),
+            notes_folder_text: String::new(),
+            import_enabled: false,
+            import_folder_text: String::new(),
+            notes_folder_picker: FolderPicker::new("setup_notes_folder_picker"),
+            import_folder_picker: FolderPicker::new("setup_import_folder_picker"),
+            installed_fonts: None,
+        }
+    }
+
+    pub fn open(&mut self, config: &Config) {
+        self.draft = config.clone();
+        self.notes_folder_text = config.notes_folder.display().to_string();
+        self.step = SetupStep::NotesFolder;
+        self.show_dialog = true;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> SetupWizardAction {
+        let mut action = SetupWizardAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut finish = false;
+
+        egui::Window::new(tr("setup-title"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(480.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.weak(tr_args(
+                    "setup-step",
+                    &[("current", &(self.step.index() + 1)), ("total", &SetupStep::ALL.len())],
+                ));
+                ui.add_space(4.0);
+
+                match self.step {
+                    SetupStep::NotesFolder => self.render_notes_folder_step(ui),
+                    SetupStep::Theme => self.render_theme_step(ui),
+                    SetupStep::Fonts => self.render_fonts_step(ui),
+                    SetupStep::Import => self.render_import_step(ui),
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let index = self.step.index();
+                    if ui.add_enabled(index > 0, egui::Button::new(tr("setup-back"))).clicked() {
+                        self.step = SetupStep::ALL[index - 1];
+                    }
+                    let folder_chosen = !self.notes_folder_text.trim().is_empty();
+                    if index + 1 < SetupStep::ALL.len() {
+                        if ui.add_enabled(folder_chosen, egui::Button::new(tr("setup-next"))).clicked() {
+                            self.step = SetupStep::ALL[index + 1];
+                        }
+                    } else if ui.add_enabled(folder_chosen, egui::Button::new(tr("setup-finish"))).clicked() {
+                        finish = true;
+                    }
+                    if ui.button(tr("setup-skip")).clicked() {
+                        self.draft = Config::default();
+                        self.notes_folder_text = self.draft.notes_folder.display().to_string();
+                        self.import_enabled = false;
+                        finish = true;
+                    }
+                });
+            });
+
+        if let Some(folder) = self.notes_folder_picker.render(ctx) {
+            self.notes_folder_text = folder.display().to_string();
+        }
+        if let Some(folder) = self.import_folder_picker.render(ctx) {
+            self.import_folder_text = folder.display().to_string();
+        }
+
+        if finish {
+            self.notes_folder_picker.show_dialog = false;
+            self.import_folder_picker.show_dialog = false;
+            self.draft.notes_folder = PathBuf::from(self.notes_folder_text.trim());
+            let import_from = (self.import_enabled && !self.import_folder_text.trim().is_empty())
+                .then(|| PathBuf::from(self.import_folder_text.trim()));
+            action = SetupWizardAction::Finish {
+                config: Box::new(self.draft.clone()),
+                import_from,
+            };
+            self.show_dialog = false;
+        }
+
+        action
+    }
+
+    fn render_notes_folder_step(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("setup-welcome"));
+        ui.label(tr("setup-notes-folder-intro"));
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-notes-folder"));
+            ui.add(egui::TextEdit::singleline(&mut self.notes_folder_text).desired_width(ui.available_width() - 80.0));
+            if ui.button(tr("settings-browse")).clicked() {
+                self.notes_folder_picker.open(Path::new(self.notes_folder_text.trim()));
+            }
+        });
+    }
+
+    fn render_theme_step(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("setup-theme-intro"));
+        ui.add_space(4.0);
+        for preset in ThemePreset::ALL {
+            if ui.radio_value(&mut self.draft.theme, preset, preset.label()).clicked() {
+                self.draft.markdown_styles = preset.markdown_styles();
+                self.draft.editor_styles = preset.editor_styles();
+                preset.apply(ui.ctx());
+            }
+        }
+    }
+
+    fn render_fonts_step(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("setup-fonts-intro"));
+        ui.add_space(4.0);
+        let installed_fonts = self.installed_fonts.get_or_insert_with(Config::list_installed_fonts);
+        egui::Grid::new("setup_fonts_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr("settings-editor-font"));
+            font_picker(ui, "setup_editor_font", &mut self.draft.editor_font_family, installed_fonts);
+            ui.end_row();
+
+            ui.label(tr("settings-list-font"));
+            font_picker(ui, "setup_list_font", &mut self.draft.list_font_family, installed_fonts);
+            ui.end_row();
+
+            ui.label(tr("settings-preview-font"));
+            font_picker(ui, "setup_preview_font", &mut self.draft.rendered_font_family, installed_fonts);
+            ui.end_row();
+        });
+        ui.add_space(4.0);
+        ui.weak(font_family_hint());
+    }
+
+    fn render_import_step(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("setup-import-intro"));
+        ui.add_space(4.0);
+        ui.checkbox(&mut self.import_enabled, tr("setup-import-enabled"));
+        ui.add_enabled_ui(self.import_enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("setup-import-folder"));
+                ui.add(egui::TextEdit::singleline(&mut self.import_folder_text).desired_width(ui.available_width() - 80.0));
+                if ui.button(tr("settings-browse")).clicked() {
+                    self.import_folder_picker.open(Path::new(self.import_folder_text.trim()));
+                }
+            });
+        });
+    }
+}
+
+impl Default for SetupWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,83 @@
+use eframe::egui;
+
+use crate::i18n::tr;
+use crate::keymap::{Action, Keymap};
+
+/// Overlay listing every bound shortcut by category, read from the live keymap.
+pub struct ShortcutsSheet {
+    pub show_dialog: bool,
+}
+
+impl ShortcutsSheet {
+    pub fn new() -> Self {
+        Self { show_dialog: false }
+    }
+
+    pub fn toggle_dialog(&mut self) {
+        self.show_dialog = !self.show_dialog;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, keymap: &Keymap) {
+        if !self.show_dialog {
+            return;
+        }
+
+        let mut open = true;
+        egui::Window::new(tr("shortcuts-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for (index, (category, bindings)) in grouped_bindings(keymap).into_iter().enumerate() {
+                        if index > 0 {
+                            ui.add_space(6.0);
+                        }
+                        ui.label(egui::RichText::new(category).strong());
+                        ui.separator();
+                        for (action, chord) in bindings {
+                            ui.horizontal(|ui| {
+                                ui.label(action.label());
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    ui.monospace(chord);
+                                });
+                            });
+                        }
+                    }
+                });
+
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    self.show_dialog = false;
+                }
+            });
+
+        if !open {
+            self.show_dialog = false;
+        }
+    }
+}
+
+/// Bound actions with their chord text, grouped by category in the order categories first appear.
+fn grouped_bindings(keymap: &Keymap) -> Vec<(&'static str, Vec<(Action, String)>)> {
+    let mut groups: Vec<(&'static str, Vec<(Action, String)>)> = Vec::new();
+    for action in Action::ALL {
+        let Some(chord) = keymap.chord_for(action) else {
+            continue;
+        };
+        let category = action.category();
+        let binding = (action, chord.display());
+        match groups.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, bindings)) => bindings.push(binding),
+            None => groups.push((category, vec![binding])),
+        }
+    }
+    groups
+}
+
+impl Default for ShortcutsSheet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
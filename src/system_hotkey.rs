@@ -0,0 +1,83 @@
+use eframe::egui;
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+use crate::config::Config;
+
+/// The opt-in system-wide shortcut that brings the main window to the front from any app, or
+/// minimizes it again when it already has focus.
+pub struct SystemHotkey {
+    /// Created when a shortcut is first set, on the main thread as Windows and macOS require.
+    manager: Option<GlobalHotKeyManager>,
+    registered: Option<HotKey>,
+    /// The shortcut last registered, or that failed to register; empty while off.
+    chord: String,
+}
+
+impl SystemHotkey {
+    pub fn new() -> Self {
+        Self {
+            manager: None,
+            registered: None,
+            chord: String::new(),
+        }
+    }
+
+    /// Registers, replaces, or drops the shortcut to match the config. A shortcut that fails to
+    /// register isn't retried until it changes.
+    pub fn sync(&mut self, config: &Config, ctx: &egui::Context) -> Result<(), String> {
+        let wanted = config.global_hotkey.trim();
+        if wanted == self.chord {
+            return Ok(());
+        }
+        self.chord = wanted.to_string();
+        if let (Some(manager), Some(hotkey)) = (&self.manager, self.registered.take()) {
+            let _ = manager.unregister(hotkey);
+        }
+        if wanted.is_empty() {
+            return Ok(());
+        }
+
+        let hotkey: HotKey = wanted
+            .parse()
+            .map_err(|e| format!("Invalid global hotkey \"{}\": {}", wanted, e))?;
+        let manager = match &mut self.manager {
+            Some(manager) => manager,
+            None => {
+                let manager = GlobalHotKeyManager::new().map_err(|e| format!("Global hotkeys aren't available: {}", e))?;
+                // Events arrive on another thread, and keep coming while the window is minimized
+                // and not redrawing, so the window is toggled from there.
+                let ctx = ctx.clone();
+                GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+                    if event.state == HotKeyState::Pressed {
+                        toggle_window(&ctx);
+                    }
+                }));
+                self.manager.insert(manager)
+            }
+        };
+        manager
+            .register(hotkey)
+            .map_err(|e| format!("Failed to register the global hotkey {}: {}", wanted, e))?;
+        self.registered = Some(hotkey);
+        Ok(())
+    }
+}
+
+impl Default for SystemHotkey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Raises and focuses the window, or minimizes it when it's already in front.
+fn toggle_window(ctx: &egui::Context) {
+    let (minimized, focused) = ctx.input(|i| (i.viewport().minimized.unwrap_or(false), i.viewport().focused.unwrap_or(false)));
+    if minimized || !focused {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    } else {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+    }
+    ctx.request_repaint();
+}
@@ -0,0 +1,49 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::front_matter;
+
+/// `#tag` after the start of a line or whitespace. Headings don't match since their `#` is
+/// followed by a space.
+fn tag_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?:^|\s)(#[\p{L}\p{N}_/-]+)").expect("tag regex is valid"))
+}
+
+/// Byte ranges of the tags in `text`, `#` included. All-digit tags like `#1` don't count.
+pub fn tag_ranges(text: &str) -> Vec<(usize, usize)> {
+    tag_regex()
+        .captures_iter(text)
+        .filter_map(|capture| capture.get(1))
+        .filter(|tag| !tag.as_str()[1..].chars().all(|c| c.is_ascii_digit()))
+        .map(|tag| (tag.start(), tag.end()))
+        .collect()
+}
+
+/// Tag ranges across a whole note, skipping fenced code blocks.
+pub fn note_tag_ranges(content: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut in_code_block = false;
+    let mut line_start = 0;
+    for line in content.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            ranges.extend(tag_ranges(line).into_iter().map(|(start, end)| (line_start + start, line_start + end)));
+        }
+        line_start += line.len();
+    }
+    ranges
+}
+
+/// The lowercased tags in a note, without the `#`: those listed under `tags` in its front
+/// matter, e.g. `tags: [work, ideas]`, then the inline ones in order of appearance.
+pub fn note_tags(content: &str) -> Vec<String> {
+    front_matter::list(content, "tags")
+        .into_iter()
+        .map(|tag| tag.trim_start_matches('#').to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .chain(note_tag_ranges(content).into_iter().map(|(start, end)| content[start + 1..end].to_lowercase()))
+        .collect()
+}
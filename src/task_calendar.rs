@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::config::Config;
+use crate::file_manager::FileManager;
+use crate::tasks::{self, TaskMetadata};
+
+/// How often the notes' modification times are checked for changes to the calendar.
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An all-day calendar entry for an open task with a due date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub note: String,
+    pub summary: String,
+    pub due: NaiveDate,
+}
+
+/// An event for each open task with a due date in `(note name, content)` pairs. Done tasks are
+/// left out, so checking a task off removes it from the calendar.
+pub fn calendar_events(notes: impl Iterator<Item = (String, String)>) -> Vec<CalendarEvent> {
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    for (note, content) in notes {
+        for task in tasks::parse_tasks(&content) {
+            let Some(due) = task.due.filter(|_| !task.done) else {
+                continue;
+            };
+            let summary = summary(&task.text);
+            // The same task text twice in a note still needs distinct IDs.
+            let mut uid = format!("{:016x}@notesquirrel", fnv1a(&format!("{}\n{}", note, summary)));
+            let repeats = events.iter().filter(|e| e.uid.ends_with(&uid)).count();
+            if repeats > 0 {
+                uid = format!("{}-{}", repeats, uid);
+            }
+            events.push(CalendarEvent { uid, note: note.clone(), summary, due });
+        }
+    }
+    events.sort_by(|a, b| a.due.cmp(&b.due).then_with(|| a.note.cmp(&b.note)));
+    events
+}
+
+/// `events` as an iCalendar file, stamped with `now`.
+pub fn to_ics(events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//NoteSquirrel//Tasks//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:NoteSquirrel tasks".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", event.due.format("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", event.due.succ_opt().unwrap_or(event.due).format("%Y%m%d")));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&format!("From note {}", event.note))));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in lines {
+        ics.push_str(&fold(&line));
+        ics.push_str("\r\n");
+    }
+    ics
+}
+
+/// The task text without its due date, which the calendar shows on its own.
+fn summary(text: &str) -> String {
+    let mut summary = text.to_string();
+    for (start, end, metadata) in tasks::metadata_ranges(text).into_iter().rev() {
+        if let TaskMetadata::Due(_) = metadata {
+            summary.replace_range(start..end, "");
+        }
+    }
+    summary.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+/// Splits a content line into 75-byte pieces, as RFC 5545 asks, without breaking a character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+/// Stable across runs and Rust versions, unlike `DefaultHasher`, so calendar apps keep
+/// recognising the same task.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn read_events(file_manager: &FileManager) -> Vec<CalendarEvent> {
+    let notes = file_manager.list_note_names().into_iter().map(|note| {
+        let content = file_manager.read_note_content(&note);
+        (note, content)
+    });
+    calendar_events(notes)
+}
+
+/// The notes' contents for the sync thread, read again only when a note's modification time
+/// changes, so an idle vault costs a directory scan rather than reading every note.
+#[derive(Default)]
+struct NoteCache {
+    notes: HashMap<String, (SystemTime, String)>,
+}
+
+impl NoteCache {
+    /// Catches up with the notes folder. Returns whether any note was added, changed, or removed.
+    fn refresh(&mut self, file_manager: &FileManager) -> bool {
+        let times = file_manager.note_modified_times();
+        let before = self.notes.len();
+        self.notes.retain(|note, _| times.contains_key(note));
+        let mut changed = self.notes.len() != before;
+        for (note, modified) in times {
+            if self.notes.get(&note).is_some_and(|(cached, _)| *cached == modified) {
+                continue;
+            }
+            let content = file_manager.read_note_content(&note);
+            self.notes.insert(note, (modified, content));
+            changed = true;
+        }
+        changed
+    }
+
+    fn events(&self) -> Vec<CalendarEvent> {
+        calendar_events(self.notes.iter().map(|(note, (_, content))| (note.clone(), content.clone())))
+    }
+}
+
+/// The calendar for every note in the notes folder, for `--tasks-ics`.
+pub fn notes_folder_ics(config: &Config) -> String {
+    to_ics(&read_events(&FileManager::new(config)), Utc::now())
+}
+
+/// Background thread that keeps the `tasks_calendar` file in step with the notes, rewriting it
+/// only when the tasks change. Stops when dropped.
+pub struct CalendarSync {
+    notes_folder: PathBuf,
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+}
+
+impl CalendarSync {
+    pub fn spawn(config: &Config, path: &Path) -> Self {
+        let file_manager = FileManager::new(config);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let target = config.notes_folder.join(path);
+
+        let thread_target = target.clone();
+        thread::spawn(move || {
+            let mut cache = NoteCache::default();
+            let mut written: Option<Vec<CalendarEvent>> = None;
+            while !thread_stop.load(Ordering::Relaxed) {
+                // A failed write leaves `written` unset, so it's tried again next time.
+                if cache.refresh(&file_manager) || written.is_none() {
+                    let events = cache.events();
+                    if written.as_ref() != Some(&events) {
+                        match std::fs::write(&thread_target, to_ics(&events, Utc::now())) {
+                            Ok(()) => written = Some(events),
+                            Err(e) => eprintln!("Failed to write task calendar {}: {}", thread_target.display(), e),
+                        }
+                    }
+                }
+                thread::sleep(SYNC_INTERVAL);
+            }
+        });
+
+        Self {
+            notes_folder: config.notes_folder.clone(),
+            path: target,
+            stop,
+        }
+    }
+
+    pub fn watches(&self, notes_folder: &Path, path: &Path) -> bool {
+        self.notes_folder == notes_folder && self.path == notes_folder.join(path)
+    }
+}
+
+impl Drop for CalendarSync {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
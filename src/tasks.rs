@@ -0,0 +1,380 @@
+use std::sync::OnceLock;
+
+use chrono::NaiveDate;
+use eframe::egui;
+use regex::Regex;
+
+use crate::i18n::{tr, tr_args};
+use crate::theme::ThemePalette;
+
+/// Inline task metadata: `!high`, `!medium`, or `!low` priority, and a due date written as
+/// `@2025-07-01` or `📅 2025-07-01`.
+fn metadata_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"(?:^|\s)(!(?:high|medium|low)|(?:@|📅\s*)(\d{4}-\d{2}-\d{2}))\b").expect("task metadata regex is valid")
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+impl Priority {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::High => tr("task-priority-high"),
+            Priority::Medium => tr("task-priority-medium"),
+            Priority::Low => tr("task-priority-low"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskMetadata {
+    Priority(Priority),
+    Due(NaiveDate),
+}
+
+impl TaskMetadata {
+    pub fn color(&self, palette: &ThemePalette) -> egui::Color32 {
+        match self {
+            TaskMetadata::Priority(Priority::High) => palette.priority_high,
+            TaskMetadata::Priority(Priority::Medium) => palette.priority_medium,
+            TaskMetadata::Priority(Priority::Low) => palette.priority_low,
+            TaskMetadata::Due(_) => palette.due_date,
+        }
+    }
+}
+
+/// The byte ranges of the priority and due date markers in a task's text, with what they say.
+pub fn metadata_ranges(text: &str) -> Vec<(usize, usize, TaskMetadata)> {
+    metadata_regex()
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let token = captures.get(1)?;
+            let metadata = match (token.as_str(), captures.get(2)) {
+                (_, Some(date)) => TaskMetadata::Due(NaiveDate::parse_from_str(date.as_str(), "%Y-%m-%d").ok()?),
+                ("!high", _) => TaskMetadata::Priority(Priority::High),
+                ("!medium", _) => TaskMetadata::Priority(Priority::Medium),
+                _ => TaskMetadata::Priority(Priority::Low),
+            };
+            Some((token.start(), token.end(), metadata))
+        })
+        .collect()
+}
+
+/// A `- [ ]` or `- [x]` item found in a note.
+#[derive(Debug, Clone)]
+pub struct Task {
+    /// Zero-based line in the note.
+    pub line: usize,
+    pub done: bool,
+    pub text: String,
+    pub due: Option<NaiveDate>,
+    pub priority: Option<Priority>,
+}
+
+impl Task {
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.done && self.due.is_some_and(|due| due < today)
+    }
+}
+
+/// Every task item in a note, skipping fenced code blocks.
+pub fn parse_tasks(content: &str) -> Vec<Task> {
+    let mut tasks = Vec::new();
+    let mut in_code_block = false;
+    for (line_index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let (done, text) = if let Some(text) = trimmed.strip_prefix("- [ ]") {
+            (false, text)
+        } else if let Some(text) = trimmed.strip_prefix("- [x]").or_else(|| trimmed.strip_prefix("- [X]")) {
+            (true, text)
+        } else {
+            continue;
+        };
+        let text = text.trim();
+        tasks.push(Task {
+            line: line_index,
+            done,
+            text: text.to_string(),
+            due: due_date(text),
+            priority: priority(text),
+        });
+    }
+    tasks
+}
+
+/// The first valid `@YYYY-MM-DD` or `📅 YYYY-MM-DD` due date in a task's text.
+pub fn due_date(text: &str) -> Option<NaiveDate> {
+    metadata_ranges(text).into_iter().find_map(|(_, _, metadata)| match metadata {
+        TaskMetadata::Due(date) => Some(date),
+        TaskMetadata::Priority(_) => None,
+    })
+}
+
+/// The first `!high`, `!medium`, or `!low` in a task's text.
+pub fn priority(text: &str) -> Option<Priority> {
+    metadata_ranges(text).into_iter().find_map(|(_, _, metadata)| match metadata {
+        TaskMetadata::Priority(priority) => Some(priority),
+        TaskMetadata::Due(_) => None,
+    })
+}
+
+/// `content` with the task on `line` checked or unchecked, or `None` if that line isn't a task.
+pub fn toggle_task_line(content: &str, line: usize) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let target = lines.get_mut(line)?;
+    let indent = target.len() - target.trim_start().len();
+    let rest = &target[indent..];
+    let toggled = if let Some(text) = rest.strip_prefix("- [ ]") {
+        format!("{}- [x]{}", &target[..indent], text)
+    } else if let Some(text) = rest.strip_prefix("- [x]").or_else(|| rest.strip_prefix("- [X]")) {
+        format!("{}- [ ]{}", &target[..indent], text)
+    } else {
+        return None;
+    };
+    *target = toggled;
+
+    let mut new_content = lines.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    Some(new_content)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskFilter {
+    Open,
+    Done,
+    Overdue,
+    All,
+}
+
+impl TaskFilter {
+    const ALL: [TaskFilter; 4] = [TaskFilter::Open, TaskFilter::Done, TaskFilter::Overdue, TaskFilter::All];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TaskFilter::Open => tr("tasks-filter-open"),
+            TaskFilter::Done => tr("tasks-filter-done"),
+            TaskFilter::Overdue => tr("tasks-filter-overdue"),
+            TaskFilter::All => tr("tasks-filter-all"),
+        }
+    }
+
+    fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        match self {
+            TaskFilter::Open => !task.done,
+            TaskFilter::Done => task.done,
+            TaskFilter::Overdue => task.is_overdue(today),
+            TaskFilter::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskSort {
+    Note,
+    DueDate,
+    Priority,
+}
+
+impl TaskSort {
+    const ALL: [TaskSort; 3] = [TaskSort::Note, TaskSort::DueDate, TaskSort::Priority];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TaskSort::Note => tr("tasks-sort-note"),
+            TaskSort::DueDate => tr("tasks-sort-due"),
+            TaskSort::Priority => tr("tasks-sort-priority"),
+        }
+    }
+
+    /// Orders tasks by this key, soonest or most important first, with tasks missing it last.
+    fn compare(&self, a: &Task, b: &Task) -> std::cmp::Ordering {
+        let due = |task: &Task| (task.due.is_none(), task.due);
+        let priority = |task: &Task| (task.priority.is_none(), task.priority);
+        match self {
+            TaskSort::Note => std::cmp::Ordering::Equal,
+            TaskSort::DueDate => due(a).cmp(&due(b)).then_with(|| priority(a).cmp(&priority(b))),
+            TaskSort::Priority => priority(a).cmp(&priority(b)).then_with(|| due(a).cmp(&due(b))),
+        }
+    }
+}
+
+/// Lists the tasks from every note, grouped by note or sorted by due date or priority, with
+/// checkboxes that edit the source line.
+pub struct TasksPanel {
+    pub show_dialog: bool,
+    filter: TaskFilter,
+    sort: TaskSort,
+    groups: Vec<(String, Vec<Task>)>,
+}
+
+pub enum TasksAction {
+    None,
+    Refresh,
+    Toggle { note: String, line: usize },
+    OpenNote(String),
+}
+
+impl TasksPanel {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            filter: TaskFilter::Open,
+            sort: TaskSort::Note,
+            groups: Vec::new(),
+        }
+    }
+
+    pub fn toggle_dialog(&mut self) {
+        self.show_dialog = !self.show_dialog;
+    }
+
+    /// Rescans `(note name, content)` pairs for tasks.
+    pub fn refresh<'a>(&mut self, notes: impl Iterator<Item = (&'a str, &'a str)>) {
+        self.groups = notes
+            .map(|(name, content)| (name.to_string(), parse_tasks(content)))
+            .filter(|(_, tasks)| !tasks.is_empty())
+            .collect();
+        self.groups.sort_by_key(|(name, _)| name.to_lowercase());
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, today: NaiveDate) -> TasksAction {
+        let mut action = TasksAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let shown: usize = self
+            .groups
+            .iter()
+            .map(|(_, tasks)| tasks.iter().filter(|t| self.filter.matches(t, today)).count())
+            .sum();
+
+        let mut open = true;
+        egui::Window::new(tr("tasks-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(460.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for filter in TaskFilter::ALL {
+                        ui.selectable_value(&mut self.filter, filter, filter.label());
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(tr("tasks-refresh")).clicked() {
+                            action = TasksAction::Refresh;
+                        }
+                        ui.weak(tr_args("tasks-count", &[("count", &shown)]));
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("tasks-sort"));
+                    egui::ComboBox::from_id_salt("tasks_sort_combo")
+                        .selected_text(self.sort.label())
+                        .show_ui(ui, |ui| {
+                            for sort in TaskSort::ALL {
+                                ui.selectable_value(&mut self.sort, sort, sort.label());
+                            }
+                        });
+                });
+                ui.separator();
+
+                let overdue_color = ui.visuals().error_fg_color;
+                egui::ScrollArea::vertical().auto_shrink([false, false]).id_salt("tasks_scroll").show(ui, |ui| {
+                    if self.sort == TaskSort::Note {
+                        for (note, tasks) in &self.groups {
+                            let visible: Vec<&Task> = tasks.iter().filter(|t| self.filter.matches(t, today)).collect();
+                            if visible.is_empty() {
+                                continue;
+                            }
+
+                            if ui.link(egui::RichText::new(note).strong()).clicked() {
+                                action = TasksAction::OpenNote(note.clone());
+                            }
+                            for task in visible {
+                                task_row(ui, note, task, false, today, overdue_color, &mut action);
+                            }
+                            ui.add_space(6.0);
+                        }
+                    } else {
+                        let mut visible: Vec<(&String, &Task)> = self
+                            .groups
+                            .iter()
+                            .flat_map(|(note, tasks)| tasks.iter().map(move |task| (note, task)))
+                            .filter(|(_, task)| self.filter.matches(task, today))
+                            .collect();
+                        visible.sort_by(|(_, a), (_, b)| self.sort.compare(a, b));
+                        for (note, task) in visible {
+                            task_row(ui, note, task, true, today, overdue_color, &mut action);
+                        }
+                    }
+                    if shown == 0 {
+                        ui.weak(tr("tasks-none"));
+                    }
+                });
+            });
+
+        if !open {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+/// A checkbox and the task's text, followed by a link to its note when the list isn't grouped.
+fn task_row(
+    ui: &mut egui::Ui,
+    note: &str,
+    task: &Task,
+    show_note: bool,
+    today: NaiveDate,
+    overdue_color: egui::Color32,
+    action: &mut TasksAction,
+) {
+    ui.horizontal(|ui| {
+        ui.add_space(8.0);
+        let mut done = task.done;
+        let checkbox = ui.checkbox(&mut done, "");
+        checkbox.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Checkbox, true, done, task.text.as_str()));
+        if checkbox.changed() {
+            *action = TasksAction::Toggle {
+                note: note.to_string(),
+                line: task.line,
+            };
+        }
+        let mut text = egui::RichText::new(&task.text);
+        if task.done {
+            text = text.strikethrough().weak();
+        } else if task.is_overdue(today) {
+            text = text.color(overdue_color);
+        }
+        ui.label(text);
+        if show_note && ui.link(egui::RichText::new(note).weak()).clicked() {
+            *action = TasksAction::OpenNote(note.to_string());
+        }
+    });
+}
+
+impl Default for TasksPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
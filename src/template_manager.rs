@@ -0,0 +1,291 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use eframe::egui;
+
+use crate::config::DateFormats;
+use crate::i18n::{tr, tr_args};
+use crate::note_template::{self, TEMPLATES_FOLDER};
+
+/// What a new template starts with.
+const NEW_TEMPLATE_TEXT: &str = "# {{title}}\n\n{{cursor}}\n";
+
+/// Settings → Templates: creates, edits, previews, renames, and deletes the markdown templates
+/// kept in the notes folder's `templates` folder.
+pub struct TemplateManager {
+    notes_folder: PathBuf,
+    templates: Vec<String>,
+    selected: Option<String>,
+    text: String,
+    /// The selected template as last read or saved, to tell whether it has unsaved edits.
+    saved_text: String,
+    name_text: String,
+    show_preview: bool,
+    confirm_delete: bool,
+    error: Option<String>,
+}
+
+pub enum TemplateManagerAction {
+    None,
+    UseForNewNotes(PathBuf),
+    UseForDailyNotes(PathBuf),
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+impl TemplateManager {
+    pub fn new() -> Self {
+        Self {
+            notes_folder: PathBuf::new(),
+            templates: Vec::new(),
+            selected: None,
+            text: String::new(),
+            saved_text: String::new(),
+            name_text: String::new(),
+            show_preview: false,
+            confirm_delete: false,
+            error: None,
+        }
+    }
+
+    /// Lists the templates in `notes_folder`, keeping the selection if it's still there.
+    pub fn load(&mut self, notes_folder: &Path) {
+        if self.notes_folder != notes_folder {
+            self.notes_folder = notes_folder.to_path_buf();
+            self.selected = None;
+        }
+        self.templates = note_template::list_templates(notes_folder);
+        self.error = None;
+        self.confirm_delete = false;
+        let selected = self
+            .selected
+            .take()
+            .filter(|name| self.templates.contains(name))
+            .or_else(|| self.templates.first().cloned());
+        match selected {
+            Some(name) => self.read(&name),
+            None => self.clear_selection(),
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.notes_folder.join(note_template::template_file(name))
+    }
+
+    fn is_modified(&self) -> bool {
+        self.selected.is_some() && self.text != self.saved_text
+    }
+
+    fn clear_selection(&mut self) {
+        self.selected = None;
+        self.text.clear();
+        self.saved_text.clear();
+        self.name_text.clear();
+    }
+
+    fn read(&mut self, name: &str) {
+        let path = self.path(name);
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                self.selected = Some(name.to_string());
+                self.saved_text = text.clone();
+                self.text = text;
+                self.name_text = name.to_string();
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to read template '{}': {}", path.display(), e));
+                self.clear_selection();
+            }
+        }
+    }
+
+    /// Switches to another template, saving edits to the current one first.
+    fn select(&mut self, name: &str) {
+        if self.selected.as_deref() == Some(name) || !self.save() {
+            return;
+        }
+        self.confirm_delete = false;
+        self.read(name);
+    }
+
+    /// Writes unsaved edits to the selected template. `false` if that failed.
+    pub fn save(&mut self) -> bool {
+        let Some(name) = self.selected.clone().filter(|_| self.is_modified()) else {
+            return true;
+        };
+        let path = self.path(&name);
+        match fs::write(&path, &self.text) {
+            Ok(()) => {
+                self.saved_text = self.text.clone();
+                true
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to save template '{}': {}", path.display(), e));
+                false
+            }
+        }
+    }
+
+    fn create(&mut self) {
+        if !self.save() {
+            return;
+        }
+        let base = tr("templates-new-name");
+        let name = std::iter::once(base.to_string())
+            .chain((2..).map(|n| format!("{} {}", base, n)))
+            .find(|name| !self.templates.iter().any(|t| t.eq_ignore_ascii_case(name)))
+            .expect("an unused name always exists");
+        let path = self.path(&name);
+        let written = fs::create_dir_all(self.notes_folder.join(TEMPLATES_FOLDER)).and_then(|()| fs::write(&path, NEW_TEMPLATE_TEXT));
+        if let Err(e) = written {
+            self.error = Some(format!("Failed to create template '{}': {}", path.display(), e));
+            return;
+        }
+        self.templates = note_template::list_templates(&self.notes_folder);
+        self.read(&name);
+    }
+
+    fn rename(&mut self) -> TemplateManagerAction {
+        let Some(old) = self.selected.clone() else {
+            return TemplateManagerAction::None;
+        };
+        let new = note_template::sanitize_file_name(&self.name_text);
+        if new == old {
+            return TemplateManagerAction::None;
+        }
+        if self.templates.iter().any(|t| t.eq_ignore_ascii_case(&new) && !t.eq_ignore_ascii_case(&old)) {
+            self.error = Some(format!("A template named '{}' already exists", new));
+            return TemplateManagerAction::None;
+        }
+        if !self.save() {
+            return TemplateManagerAction::None;
+        }
+        if let Err(e) = fs::rename(self.path(&old), self.path(&new)) {
+            self.error = Some(format!("Failed to rename template '{}': {}", old, e));
+            return TemplateManagerAction::None;
+        }
+        self.templates = note_template::list_templates(&self.notes_folder);
+        self.read(&new);
+        TemplateManagerAction::Renamed {
+            from: note_template::template_file(&old),
+            to: note_template::template_file(&new),
+        }
+    }
+
+    fn delete(&mut self) {
+        let Some(name) = self.selected.clone() else {
+            return;
+        };
+        let path = self.path(&name);
+        if let Err(e) = fs::remove_file(&path) {
+            self.error = Some(format!("Failed to delete template '{}': {}", path.display(), e));
+            return;
+        }
+        self.load(&self.notes_folder.clone());
+    }
+
+    /// Renders the page. `formats` fills in the preview the way new notes will be filled in.
+    pub fn render(&mut self, ui: &mut egui::Ui, formats: &DateFormats) -> TemplateManagerAction {
+        let mut action = TemplateManagerAction::None;
+
+        ui.label(tr_args("templates-hint", &[("folder", &TEMPLATES_FOLDER)]));
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            let selected_text = match &self.selected {
+                Some(name) if self.is_modified() => format!("{} *", name),
+                Some(name) => name.clone(),
+                None => tr("templates-none").to_string(),
+            };
+            let mut clicked = None;
+            egui::ComboBox::from_id_salt("settings_templates_combo")
+                .selected_text(selected_text)
+                .width(220.0)
+                .show_ui(ui, |ui| {
+                    for name in &self.templates {
+                        if ui.selectable_label(self.selected.as_ref() == Some(name), name).clicked() {
+                            clicked = Some(name.clone());
+                        }
+                    }
+                });
+            if let Some(name) = clicked {
+                self.select(&name);
+            }
+            if ui.button(tr("templates-new")).clicked() {
+                self.create();
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        let Some(name) = self.selected.clone() else {
+            return action;
+        };
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr("templates-name"));
+            let response = ui.add(egui::TextEdit::singleline(&mut self.name_text).desired_width(200.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button(tr("templates-rename")).clicked() || submitted {
+                action = self.rename();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.show_preview, false, tr("templates-edit"));
+            ui.selectable_value(&mut self.show_preview, true, tr("templates-preview"));
+        });
+        if self.show_preview {
+            let preview = note_template::expand_placeholders(&self.text, &Local::now(), formats, &[("title", &name)]);
+            ui.add(
+                egui::TextEdit::multiline(&mut preview.as_str())
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(10)
+                    .desired_width(f32::INFINITY),
+            );
+        } else {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.text)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(10)
+                    .desired_width(f32::INFINITY),
+            );
+        }
+        ui.label(tr("templates-variables-hint"));
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(self.is_modified(), egui::Button::new(tr("templates-save"))).clicked() && self.save() {
+                self.error = None;
+            }
+            if ui.button(tr("templates-use-new")).clicked() {
+                action = TemplateManagerAction::UseForNewNotes(note_template::template_file(&name));
+            }
+            if ui.button(tr("templates-use-daily")).clicked() {
+                action = TemplateManagerAction::UseForDailyNotes(note_template::template_file(&name));
+            }
+            if self.confirm_delete {
+                ui.label(tr("templates-delete-confirm"));
+                if ui.button(tr("button-yes")).clicked() {
+                    self.confirm_delete = false;
+                    self.delete();
+                }
+                if ui.button(tr("button-no")).clicked() {
+                    self.confirm_delete = false;
+                }
+            } else if ui.button(tr("templates-delete")).clicked() {
+                self.confirm_delete = true;
+            }
+        });
+
+        action
+    }
+}
+
+impl Default for TemplateManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
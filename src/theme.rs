@@ -0,0 +1,390 @@
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::config::{default_block_spacing, default_editor_line_spacing, EditorStyles, MarkdownStyle, MarkdownStyles};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    /// White on black with bold outlines and yellow focus, for low vision.
+    #[serde(rename = "high_contrast")]
+    HighContrast,
+    /// Blue/orange accents from the Okabe-Ito palette, readable with red-green color blindness.
+    #[serde(rename = "colorblind_dark")]
+    ColorblindDark,
+    #[serde(rename = "colorblind_light")]
+    ColorblindLight,
+}
+
+/// Colors used by the app chrome for a theme preset.
+#[derive(Debug, Clone)]
+pub struct ThemePalette {
+    pub list_text: Color32,
+    pub list_selected_fill: Color32,
+    pub search_icon: Color32,
+    pub placeholder_text: Color32,
+    pub inline_code_background: Color32,
+    pub blockquote_bar: Color32,
+    pub match_background: Color32,
+    pub current_match_background: Color32,
+    pub overdue_task: Color32,
+    pub tag: Color32,
+    pub priority_high: Color32,
+    pub priority_medium: Color32,
+    pub priority_low: Color32,
+    pub due_date: Color32,
+    /// Background of lines only in your version of a conflicting note.
+    pub diff_mine: Color32,
+    /// Background of lines only in the version on disk.
+    pub diff_theirs: Color32,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 5] = [
+        ThemePreset::Dark,
+        ThemePreset::Light,
+        ThemePreset::HighContrast,
+        ThemePreset::ColorblindDark,
+        ThemePreset::ColorblindLight,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemePreset::Dark => tr("theme-dark"),
+            ThemePreset::Light => tr("theme-light"),
+            ThemePreset::HighContrast => tr("theme-high-contrast"),
+            ThemePreset::ColorblindDark => tr("theme-colorblind-dark"),
+            ThemePreset::ColorblindLight => tr("theme-colorblind-light"),
+        }
+    }
+
+    pub fn egui_theme(&self) -> egui::Theme {
+        match self {
+            ThemePreset::Dark | ThemePreset::HighContrast | ThemePreset::ColorblindDark => egui::Theme::Dark,
+            ThemePreset::Light | ThemePreset::ColorblindLight => egui::Theme::Light,
+        }
+    }
+
+    pub fn visuals(&self) -> egui::Visuals {
+        match self {
+            ThemePreset::Dark => egui::Visuals::dark(),
+            ThemePreset::Light => egui::Visuals::light(),
+            ThemePreset::HighContrast => {
+                let mut visuals = egui::Visuals::dark();
+                let outline = egui::Stroke::new(1.0, Color32::WHITE);
+                let focus = egui::Stroke::new(2.0, Color32::YELLOW);
+                visuals.override_text_color = Some(Color32::WHITE);
+                visuals.panel_fill = Color32::BLACK;
+                visuals.window_fill = Color32::BLACK;
+                visuals.extreme_bg_color = Color32::BLACK;
+                visuals.faint_bg_color = Color32::from_gray(20);
+                visuals.window_stroke = outline;
+                visuals.hyperlink_color = Color32::from_rgb(0, 255, 255);
+                visuals.selection.bg_fill = Color32::from_rgb(0, 70, 200);
+                visuals.selection.stroke = focus;
+                visuals.widgets.noninteractive.bg_stroke = outline;
+                visuals.widgets.noninteractive.fg_stroke = outline;
+                visuals.widgets.inactive.bg_fill = Color32::BLACK;
+                visuals.widgets.inactive.weak_bg_fill = Color32::BLACK;
+                visuals.widgets.inactive.bg_stroke = outline;
+                visuals.widgets.inactive.fg_stroke = outline;
+                visuals.widgets.hovered.bg_stroke = focus;
+                visuals.widgets.hovered.fg_stroke = outline;
+                visuals.widgets.active.bg_stroke = focus;
+                visuals.widgets.active.fg_stroke = outline;
+                visuals.widgets.open.bg_stroke = focus;
+                visuals
+            }
+            ThemePreset::ColorblindDark => {
+                let mut visuals = egui::Visuals::dark();
+                visuals.selection.bg_fill = Color32::from_rgb(0, 114, 178);
+                visuals.hyperlink_color = Color32::from_rgb(86, 180, 233);
+                visuals.error_fg_color = Color32::from_rgb(230, 159, 0);
+                visuals.warn_fg_color = Color32::from_rgb(240, 228, 66);
+                visuals
+            }
+            ThemePreset::ColorblindLight => {
+                let mut visuals = egui::Visuals::light();
+                visuals.selection.bg_fill = Color32::from_rgb(160, 205, 235);
+                visuals.hyperlink_color = Color32::from_rgb(0, 114, 178);
+                visuals.error_fg_color = Color32::from_rgb(213, 94, 0);
+                visuals.warn_fg_color = Color32::from_rgb(170, 110, 0);
+                visuals
+            }
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_theme(self.egui_theme());
+        ctx.set_visuals_of(self.egui_theme(), self.visuals());
+    }
+
+    pub fn palette(&self) -> ThemePalette {
+        match self {
+            ThemePreset::Dark => ThemePalette {
+                list_text: Color32::WHITE,
+                list_selected_fill: Color32::from_rgb(60, 120, 200),
+                search_icon: Color32::from_rgb(170, 170, 170),
+                placeholder_text: Color32::from_rgb(150, 150, 150),
+                inline_code_background: Color32::from_rgb(255, 245, 235),
+                blockquote_bar: Color32::from_rgb(120, 120, 120),
+                match_background: Color32::from_rgb(100, 100, 50),
+                current_match_background: Color32::from_rgb(255, 165, 0),
+                overdue_task: Color32::from_rgb(255, 120, 110),
+                tag: Color32::from_rgb(120, 180, 255),
+                priority_high: Color32::from_rgb(240, 110, 90),
+                priority_medium: Color32::from_rgb(230, 180, 80),
+                priority_low: Color32::from_rgb(130, 190, 130),
+                due_date: Color32::from_rgb(170, 150, 230),
+                diff_mine: Color32::from_rgb(70, 40, 40),
+                diff_theirs: Color32::from_rgb(35, 65, 40),
+            },
+            ThemePreset::Light => ThemePalette {
+                list_text: Color32::from_rgb(30, 30, 30),
+                list_selected_fill: Color32::from_rgb(170, 200, 240),
+                search_icon: Color32::from_rgb(110, 110, 110),
+                placeholder_text: Color32::from_rgb(120, 120, 120),
+                inline_code_background: Color32::from_rgb(240, 235, 225),
+                blockquote_bar: Color32::from_rgb(170, 170, 170),
+                match_background: Color32::from_rgb(250, 240, 150),
+                current_match_background: Color32::from_rgb(255, 165, 0),
+                overdue_task: Color32::from_rgb(200, 40, 40),
+                tag: Color32::from_rgb(30, 110, 200),
+                priority_high: Color32::from_rgb(190, 40, 30),
+                priority_medium: Color32::from_rgb(170, 110, 0),
+                priority_low: Color32::from_rgb(40, 130, 60),
+                due_date: Color32::from_rgb(100, 70, 180),
+                diff_mine: Color32::from_rgb(255, 220, 220),
+                diff_theirs: Color32::from_rgb(215, 245, 215),
+            },
+            ThemePreset::HighContrast => ThemePalette {
+                list_text: Color32::WHITE,
+                list_selected_fill: Color32::from_rgb(0, 70, 200),
+                search_icon: Color32::WHITE,
+                placeholder_text: Color32::from_gray(200),
+                inline_code_background: Color32::from_gray(45),
+                blockquote_bar: Color32::WHITE,
+                match_background: Color32::from_rgb(90, 90, 0),
+                current_match_background: Color32::from_rgb(190, 0, 190),
+                overdue_task: Color32::from_rgb(255, 90, 90),
+                tag: Color32::from_rgb(0, 255, 255),
+                priority_high: Color32::from_rgb(255, 80, 80),
+                priority_medium: Color32::from_rgb(255, 220, 0),
+                priority_low: Color32::from_rgb(0, 255, 120),
+                due_date: Color32::from_rgb(200, 160, 255),
+                diff_mine: Color32::from_rgb(110, 0, 0),
+                diff_theirs: Color32::from_rgb(0, 90, 0),
+            },
+            ThemePreset::ColorblindDark => ThemePalette {
+                list_text: Color32::WHITE,
+                list_selected_fill: Color32::from_rgb(0, 114, 178),
+                search_icon: Color32::from_rgb(170, 170, 170),
+                placeholder_text: Color32::from_rgb(150, 150, 150),
+                inline_code_background: Color32::from_rgb(50, 50, 60),
+                blockquote_bar: Color32::from_rgb(120, 120, 120),
+                match_background: Color32::from_rgb(100, 80, 0),
+                current_match_background: Color32::from_rgb(0, 114, 178),
+                overdue_task: Color32::from_rgb(230, 159, 0),
+                tag: Color32::from_rgb(86, 180, 233),
+                priority_high: Color32::from_rgb(213, 94, 0),
+                priority_medium: Color32::from_rgb(240, 228, 66),
+                priority_low: Color32::from_rgb(86, 180, 233),
+                due_date: Color32::from_rgb(204, 121, 167),
+                diff_mine: Color32::from_rgb(100, 60, 0),
+                diff_theirs: Color32::from_rgb(0, 60, 100),
+            },
+            ThemePreset::ColorblindLight => ThemePalette {
+                list_text: Color32::from_rgb(30, 30, 30),
+                list_selected_fill: Color32::from_rgb(160, 205, 235),
+                search_icon: Color32::from_rgb(110, 110, 110),
+                placeholder_text: Color32::from_rgb(120, 120, 120),
+                inline_code_background: Color32::from_rgb(235, 235, 242),
+                blockquote_bar: Color32::from_rgb(170, 170, 170),
+                match_background: Color32::from_rgb(240, 228, 66),
+                current_match_background: Color32::from_rgb(86, 180, 233),
+                overdue_task: Color32::from_rgb(213, 94, 0),
+                tag: Color32::from_rgb(0, 114, 178),
+                priority_high: Color32::from_rgb(213, 94, 0),
+                priority_medium: Color32::from_rgb(160, 120, 0),
+                priority_low: Color32::from_rgb(0, 114, 178),
+                due_date: Color32::from_rgb(160, 70, 130),
+                diff_mine: Color32::from_rgb(250, 215, 170),
+                diff_theirs: Color32::from_rgb(190, 225, 245),
+            },
+        }
+    }
+
+    pub fn markdown_styles(&self) -> MarkdownStyles {
+        match self {
+            ThemePreset::Dark => MarkdownStyles {
+                h1: MarkdownStyle { font_size: 24.0, color: [255, 220, 100] },
+                h2: MarkdownStyle { font_size: 20.0, color: [220, 255, 180] },
+                h3: MarkdownStyle { font_size: 18.0, color: [180, 220, 255] },
+                h4: MarkdownStyle { font_size: 16.0, color: [255, 180, 220] },
+                h5: MarkdownStyle { font_size: 14.0, color: [220, 180, 255] },
+                h6: MarkdownStyle { font_size: 12.0, color: [255, 255, 180] },
+                paragraph: MarkdownStyle { font_size: 14.0, color: [240, 240, 240] },
+                strong: MarkdownStyle { font_size: 14.0, color: [255, 255, 255] },
+                emphasis: MarkdownStyle { font_size: 14.0, color: [220, 180, 255] },
+                strikethrough: MarkdownStyle { font_size: 14.0, color: [150, 150, 150] },
+                code_inline: MarkdownStyle { font_size: 14.0, color: [200, 80, 20] },
+                code_block: MarkdownStyle { font_size: 12.0, color: [150, 120, 200] },
+                code_block_background: [40, 40, 50],
+                list_bullet: MarkdownStyle { font_size: 14.0, color: [60, 120, 200] },
+                editor_line_spacing: default_editor_line_spacing(),
+                block_spacing: default_block_spacing(),
+            },
+            ThemePreset::Light => MarkdownStyles {
+                h1: MarkdownStyle { font_size: 24.0, color: [180, 90, 0] },
+                h2: MarkdownStyle { font_size: 20.0, color: [60, 130, 40] },
+                h3: MarkdownStyle { font_size: 18.0, color: [30, 90, 170] },
+                h4: MarkdownStyle { font_size: 16.0, color: [170, 40, 110] },
+                h5: MarkdownStyle { font_size: 14.0, color: [110, 60, 170] },
+                h6: MarkdownStyle { font_size: 12.0, color: [120, 110, 20] },
+                paragraph: MarkdownStyle { font_size: 14.0, color: [30, 30, 30] },
+                strong: MarkdownStyle { font_size: 14.0, color: [0, 0, 0] },
+                emphasis: MarkdownStyle { font_size: 14.0, color: [110, 60, 170] },
+                strikethrough: MarkdownStyle { font_size: 14.0, color: [140, 140, 140] },
+                code_inline: MarkdownStyle { font_size: 14.0, color: [180, 60, 10] },
+                code_block: MarkdownStyle { font_size: 12.0, color: [90, 60, 150] },
+                code_block_background: [238, 238, 244],
+                list_bullet: MarkdownStyle { font_size: 14.0, color: [30, 90, 170] },
+                editor_line_spacing: default_editor_line_spacing(),
+                block_spacing: default_block_spacing(),
+            },
+            ThemePreset::HighContrast => MarkdownStyles {
+                h1: MarkdownStyle { font_size: 26.0, color: [255, 255, 0] },
+                h2: MarkdownStyle { font_size: 22.0, color: [0, 255, 255] },
+                h3: MarkdownStyle { font_size: 19.0, color: [255, 255, 255] },
+                h4: MarkdownStyle { font_size: 17.0, color: [255, 255, 0] },
+                h5: MarkdownStyle { font_size: 15.0, color: [0, 255, 255] },
+                h6: MarkdownStyle { font_size: 14.0, color: [255, 255, 255] },
+                paragraph: MarkdownStyle { font_size: 15.0, color: [255, 255, 255] },
+                strong: MarkdownStyle { font_size: 15.0, color: [255, 255, 255] },
+                emphasis: MarkdownStyle { font_size: 15.0, color: [0, 255, 255] },
+                strikethrough: MarkdownStyle { font_size: 15.0, color: [190, 190, 190] },
+                code_inline: MarkdownStyle { font_size: 15.0, color: [255, 255, 0] },
+                code_block: MarkdownStyle { font_size: 14.0, color: [255, 255, 255] },
+                code_block_background: [30, 30, 30],
+                list_bullet: MarkdownStyle { font_size: 15.0, color: [255, 255, 0] },
+                editor_line_spacing: default_editor_line_spacing(),
+                block_spacing: default_block_spacing(),
+            },
+            ThemePreset::ColorblindDark => MarkdownStyles {
+                h1: MarkdownStyle { font_size: 24.0, color: [230, 159, 0] },
+                h2: MarkdownStyle { font_size: 20.0, color: [86, 180, 233] },
+                h3: MarkdownStyle { font_size: 18.0, color: [240, 228, 66] },
+                h4: MarkdownStyle { font_size: 16.0, color: [204, 121, 167] },
+                h5: MarkdownStyle { font_size: 14.0, color: [86, 180, 233] },
+                h6: MarkdownStyle { font_size: 12.0, color: [230, 159, 0] },
+                paragraph: MarkdownStyle { font_size: 14.0, color: [240, 240, 240] },
+                strong: MarkdownStyle { font_size: 14.0, color: [255, 255, 255] },
+                emphasis: MarkdownStyle { font_size: 14.0, color: [204, 121, 167] },
+                strikethrough: MarkdownStyle { font_size: 14.0, color: [150, 150, 150] },
+                code_inline: MarkdownStyle { font_size: 14.0, color: [230, 159, 0] },
+                code_block: MarkdownStyle { font_size: 12.0, color: [86, 180, 233] },
+                code_block_background: [40, 40, 50],
+                list_bullet: MarkdownStyle { font_size: 14.0, color: [86, 180, 233] },
+                editor_line_spacing: default_editor_line_spacing(),
+                block_spacing: default_block_spacing(),
+            },
+            ThemePreset::ColorblindLight => MarkdownStyles {
+                h1: MarkdownStyle { font_size: 24.0, color: [213, 94, 0] },
+                h2: MarkdownStyle { font_size: 20.0, color: [0, 114, 178] },
+                h3: MarkdownStyle { font_size: 18.0, color: [150, 100, 0] },
+                h4: MarkdownStyle { font_size: 16.0, color: [170, 70, 130] },
+                h5: MarkdownStyle { font_size: 14.0, color: [0, 114, 178] },
+                h6: MarkdownStyle { font_size: 12.0, color: [213, 94, 0] },
+                paragraph: MarkdownStyle { font_size: 14.0, color: [30, 30, 30] },
+                strong: MarkdownStyle { font_size: 14.0, color: [0, 0, 0] },
+                emphasis: MarkdownStyle { font_size: 14.0, color: [170, 70, 130] },
+                strikethrough: MarkdownStyle { font_size: 14.0, color: [140, 140, 140] },
+                code_inline: MarkdownStyle { font_size: 14.0, color: [213, 94, 0] },
+                code_block: MarkdownStyle { font_size: 12.0, color: [0, 90, 150] },
+                code_block_background: [238, 238, 244],
+                list_bullet: MarkdownStyle { font_size: 14.0, color: [0, 114, 178] },
+                editor_line_spacing: default_editor_line_spacing(),
+                block_spacing: default_block_spacing(),
+            },
+        }
+    }
+
+    pub fn editor_styles(&self) -> EditorStyles {
+        match self {
+            ThemePreset::Dark => EditorStyles {
+                text: [200, 200, 200],
+                heading_marker: [100, 100, 100],
+                h1: [255, 220, 100],
+                h2: [220, 255, 180],
+                h3: [180, 220, 255],
+                h4: [255, 180, 220],
+                h5: [220, 180, 255],
+                h6: [255, 255, 180],
+                code: [150, 120, 200],
+                code_background: [40, 40, 50],
+                quote: [160, 160, 160],
+                list: [60, 120, 200],
+            },
+            ThemePreset::Light => EditorStyles {
+                text: [40, 40, 40],
+                heading_marker: [160, 160, 160],
+                h1: [180, 90, 0],
+                h2: [60, 130, 40],
+                h3: [30, 90, 170],
+                h4: [170, 40, 110],
+                h5: [110, 60, 170],
+                h6: [120, 110, 20],
+                code: [110, 60, 160],
+                code_background: [235, 235, 242],
+                quote: [110, 110, 110],
+                list: [30, 90, 170],
+            },
+            ThemePreset::HighContrast => EditorStyles {
+                text: [255, 255, 255],
+                heading_marker: [190, 190, 190],
+                h1: [255, 255, 0],
+                h2: [0, 255, 255],
+                h3: [255, 255, 255],
+                h4: [255, 255, 0],
+                h5: [0, 255, 255],
+                h6: [255, 255, 255],
+                code: [255, 255, 0],
+                code_background: [30, 30, 30],
+                quote: [210, 210, 210],
+                list: [255, 255, 0],
+            },
+            ThemePreset::ColorblindDark => EditorStyles {
+                text: [210, 210, 210],
+                heading_marker: [110, 110, 110],
+                h1: [230, 159, 0],
+                h2: [86, 180, 233],
+                h3: [240, 228, 66],
+                h4: [204, 121, 167],
+                h5: [86, 180, 233],
+                h6: [230, 159, 0],
+                code: [86, 180, 233],
+                code_background: [40, 40, 50],
+                quote: [160, 160, 160],
+                list: [86, 180, 233],
+            },
+            ThemePreset::ColorblindLight => EditorStyles {
+                text: [40, 40, 40],
+                heading_marker: [160, 160, 160],
+                h1: [213, 94, 0],
+                h2: [0, 114, 178],
+                h3: [150, 100, 0],
+                h4: [170, 70, 130],
+                h5: [0, 114, 178],
+                h6: [213, 94, 0],
+                code: [0, 90, 150],
+                code_background: [235, 235, 242],
+                quote: [110, 110, 110],
+                list: [0, 114, 178],
+            },
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use crate::headings::{self, Heading};
+
+/// Marks the generated table of contents, so it can be found and refreshed in place.
+const TOC_START: &str = "<!-- toc -->";
+const TOC_END: &str = "<!-- /toc -->";
+/// A line that asks for a table of contents to be generated in its place.
+const TOC_PLACEHOLDER: &str = "[TOC]";
+
+/// The `#anchor` a heading links to, as GitHub makes them: lowercase, punctuation dropped, and
+/// spaces turned into dashes.
+pub fn slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Anchors for `headings` in order, with `-1`, `-2`, ... added to repeated ones.
+pub fn anchors(headings: &[Heading]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    headings
+        .iter()
+        .map(|heading| {
+            let slug = slug(&heading.text);
+            let count = seen.entry(slug.clone()).or_default();
+            let anchor = if *count == 0 { slug } else { format!("{}-{}", slug, count) };
+            *count += 1;
+            anchor
+        })
+        .collect()
+}
+
+fn toc_block(headings: &[Heading]) -> String {
+    let top_level = headings.iter().map(|heading| heading.level).min().unwrap_or(1);
+    let mut block = format!("{}\n", TOC_START);
+    for (heading, anchor) in headings.iter().zip(anchors(headings)) {
+        let indent = "  ".repeat(heading.level - top_level);
+        let text = heading.text.replace('[', "\\[").replace(']', "\\]");
+        block.push_str(&format!("{}- [{}](#{})\n", indent, text, anchor));
+    }
+    block.push_str(TOC_END);
+    block
+}
+
+/// Refreshes the note's table of contents, or replaces a `[TOC]` line with one, or else adds one
+/// on the line at character position `cursor`. Returns the new text and the character position
+/// just after the table.
+pub fn insert_or_refresh(text: &str, cursor: usize) -> (String, usize) {
+    let block = toc_block(&headings::parse_headings(text));
+
+    let (start, end) = if let Some(start) = text.find(TOC_START)
+        && let Some(end) = text[start..].find(TOC_END)
+    {
+        (start, start + end + TOC_END.len())
+    } else if let Some(start) = placeholder_line(text) {
+        (start, start + TOC_PLACEHOLDER.len())
+    } else {
+        let byte = text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i);
+        let line_start = text[..byte].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[byte..].find('\n').map_or(text.len(), |i| byte + i);
+        // Keep what's on the cursor's line, with the table on lines of its own after it.
+        let at = if text[line_start..line_end].trim().is_empty() { line_start } else { line_end };
+        let before = &text[..at];
+        let prefix = if before.is_empty() || before.ends_with("\n\n") {
+            ""
+        } else if before.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        let after = &text[at..];
+        let suffix = if after.is_empty() || after.starts_with("\n\n") {
+            ""
+        } else if after.starts_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        let block = format!("{}{}{}", prefix, block, suffix);
+        let mut result = text.to_string();
+        result.insert_str(at, &block);
+        let cursor = result[..at + block.len()].chars().count();
+        return (result, cursor);
+    };
+
+    let mut result = text.to_string();
+    result.replace_range(start..end, &block);
+    let cursor = result[..start + block.len()].chars().count();
+    (result, cursor)
+}
+
+/// Byte offset of a line holding only `[TOC]`.
+fn placeholder_line(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim() == TOC_PLACEHOLDER {
+            return Some(offset + line.find(TOC_PLACEHOLDER).unwrap_or(0));
+        }
+        offset += line.len();
+    }
+    None
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use crate::note_template::sanitize_note_path;
+
+pub const SCHEME: &str = "notesquirrel";
+
+/// A `notesquirrel://` link the app was launched with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    /// `notesquirrel://note/<name>`: open the note, creating it if it doesn't exist.
+    Note(String),
+}
+
+impl DeepLink {
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri
+            .strip_prefix(SCHEME)
+            .and_then(|rest| rest.strip_prefix("://"))
+            .ok_or_else(|| format!("'{}' is not a {}:// link", uri, SCHEME))?;
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+
+        match rest.split_once('/') {
+            Some(("note", name)) => {
+                let name = percent_decode(name.trim_end_matches('/'))?;
+                if name.trim().is_empty() {
+                    return Err(format!("Link '{}' doesn't name a note", uri));
+                }
+                Ok(DeepLink::Note(sanitize_note_path(&name)))
+            }
+            _ => Err(format!("Unsupported link '{}'; expected {}://note/<name>", uri, SCHEME)),
+        }
+    }
+}
+
+/// A `notesquirrel://note/<name>` link to `note`, for linking between notes.
+pub fn note_link(note: &str) -> String {
+    let mut link = format!("{}://note/", SCHEME);
+    for byte in note.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => link.push(byte as char),
+            _ => link.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    link
+}
+
+fn percent_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = text
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("Invalid escape in link: '{}'", text))?;
+                decoded.push(hex);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format!("Link is not valid UTF-8: '{}'", text))
+}
+
+/// Whether `register` can make this platform send `notesquirrel://` links to the app. macOS
+/// delivers them to bundles as Apple Events rather than arguments, which the app doesn't take.
+pub const CAN_REGISTER: bool = cfg!(any(target_os = "linux", target_os = "windows"));
+
+/// Registers this executable as the handler for `notesquirrel://` links for the current user.
+pub fn register() -> Result<String, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to find the NoteSquirrel executable: {}", e))?;
+    register_for(&exe)
+}
+
+#[cfg(target_os = "linux")]
+fn register_for(exe: &Path) -> Result<String, String> {
+    let applications = std::env::home_dir()
+        .ok_or("Failed to find the home directory")?
+        .join(".local/share/applications");
+    std::fs::create_dir_all(&applications).map_err(|e| format!("Failed to create '{}': {}", applications.display(), e))?;
+
+    let desktop_name = "notesquirrel-url-handler.desktop";
+    let desktop_file = format!(
+        "[Desktop Entry]\nType=Application\nName=Note Squirrel\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+        exe.display(),
+        SCHEME
+    );
+    let path = applications.join(desktop_name);
+    std::fs::write(&path, desktop_file).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+
+    run("xdg-mime", &["default", desktop_name, &format!("x-scheme-handler/{}", SCHEME)])?;
+    Ok(format!("Registered {}:// links via {}", SCHEME, path.display()))
+}
+
+#[cfg(target_os = "windows")]
+fn register_for(exe: &Path) -> Result<String, String> {
+    let key = format!("HKCU\\Software\\Classes\\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    run("reg", &["add", &key, "/ve", "/d", "URL:Note Squirrel", "/f"])?;
+    run("reg", &["add", &key, "/v", "URL Protocol", "/d", "", "/f"])?;
+    run("reg", &["add", &format!("{}\\shell\\open\\command", key), "/ve", "/d", &command, "/f"])?;
+    Ok(format!("Registered {}:// links in {}", SCHEME, key))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn register_for(_exe: &Path) -> Result<String, String> {
+    Err(format!("{}:// links can't be registered on this platform", SCHEME))
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let status = std::process::Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+use eframe::egui;
+
+use crate::config::DateFormats;
+use crate::file_manager::FileManager;
+use crate::i18n::tr;
+use crate::note_io;
+use crate::tags;
+use crate::uri_scheme;
+
+/// Name of the generated index note.
+pub const INDEX_NOTE: &str = "Vault Index";
+/// Marks the index note as generated, so refreshing it never overwrites a note the user wrote.
+const GENERATED_MARKER: &str = "<!-- Generated by NoteSquirrel: Tools → Vault Index refreshes this note and replaces any edits. -->";
+
+/// A note as listed in the index and the CSV export.
+pub struct NoteListing {
+    pub name: String,
+    pub words: usize,
+    /// The note's `#tags`, lowercased and without duplicates.
+    pub tags: Vec<String>,
+    pub modified: Option<SystemTime>,
+}
+
+/// Reads every note but the index itself from disk.
+pub fn list_notes(file_manager: &FileManager) -> Vec<NoteListing> {
+    let names = file_manager.list_note_names().into_iter().filter(|name| name != INDEX_NOTE).collect();
+    note_io::read_notes(file_manager, names)
+        .into_iter()
+        .map(|note| {
+            let content = note.content.unwrap_or_default();
+            let mut tags = tags::note_tags(&content);
+            tags.sort();
+            tags.dedup();
+            NoteListing {
+                name: note.name,
+                words: content.split_whitespace().count(),
+                tags,
+                modified: note.modified,
+            }
+        })
+        .collect()
+}
+
+/// Whether `content` is an index note written by `index_note`.
+pub fn is_generated(content: &str) -> bool {
+    content.contains(GENERATED_MARKER)
+}
+
+/// The index note: a link to every note under each of its tags, then the untagged notes.
+pub fn index_note(notes: &[NoteListing], now: &DateTime<Local>, formats: &DateFormats) -> String {
+    let mut by_tag: BTreeMap<&str, Vec<&NoteListing>> = BTreeMap::new();
+    let mut untagged = Vec::new();
+    for note in notes {
+        if note.tags.is_empty() {
+            untagged.push(note);
+        }
+        for tag in &note.tags {
+            by_tag.entry(tag).or_default().push(note);
+        }
+    }
+
+    let mut index = format!(
+        "# {}\n\n{}\n\n{} notes, updated {}.\n",
+        INDEX_NOTE,
+        GENERATED_MARKER,
+        notes.len(),
+        formats.format_datetime(now)
+    );
+    let sections = by_tag
+        .into_iter()
+        .map(|(tag, notes)| (format!("`#{}`", tag), notes))
+        .chain((!untagged.is_empty()).then(|| ("Untagged".to_string(), untagged)));
+    for (heading, notes) in sections {
+        index.push_str(&format!("\n## {}\n\n", heading));
+        for note in notes {
+            let text = note.name.replace('[', "\\[").replace(']', "\\]");
+            index.push_str(&format!("- [{}]({})\n", text, uri_scheme::note_link(&note.name)));
+        }
+    }
+    index
+}
+
+/// The notes as CSV: title, word count, last modified, and tags.
+pub fn to_csv(notes: &[NoteListing]) -> String {
+    let mut csv = String::from("title,words,modified,tags\n");
+    for note in notes {
+        let modified = note
+            .modified
+            .map(|time| DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        let tags: Vec<String> = note.tags.iter().map(|tag| format!("#{}", tag)).collect();
+        csv.push_str(&format!("{},{},{},{}\n", csv_field(&note.name), note.words, modified, csv_field(&tags.join(" "))));
+    }
+    csv
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Tools → Vault Index: writes the index note, or the note list as CSV.
+pub struct VaultIndex {
+    pub show_dialog: bool,
+    path_text: String,
+    status: Option<Result<String, String>>,
+}
+
+pub enum VaultIndexAction {
+    None,
+    WriteNote,
+    ExportCsv(PathBuf),
+}
+
+impl VaultIndex {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            path_text: default_csv_path().display().to_string(),
+            status: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.status = None;
+        self.show_dialog = true;
+    }
+
+    /// Shows the outcome of the last export below the path field.
+    pub fn set_status(&mut self, status: Result<String, String>) {
+        self.status = Some(status);
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> VaultIndexAction {
+        let mut action = VaultIndexAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(tr("vault-index-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.label(tr("vault-index-note-description"));
+                if ui.button(tr("vault-index-write-note")).clicked() {
+                    action = VaultIndexAction::WriteNote;
+                    close = true;
+                }
+                ui.separator();
+
+                ui.label(tr("vault-index-csv-description"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("vault-index-file"));
+                    ui.add(egui::TextEdit::singleline(&mut self.path_text).desired_width(f32::INFINITY));
+                });
+                match &self.status {
+                    Some(Ok(message)) => {
+                        ui.label(message);
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    None => {}
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let path_text = self.path_text.trim();
+                    if ui.add_enabled(!path_text.is_empty(), egui::Button::new(tr("vault-index-export-csv"))).clicked() {
+                        action = VaultIndexAction::ExportCsv(PathBuf::from(path_text));
+                    }
+                    if ui.button(tr("button-close")).clicked() {
+                        close = true;
+                    }
+                });
+
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
+            });
+
+        if !open || close {
+            self.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+fn default_csv_path() -> PathBuf {
+    std::env::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("NoteSquirrel-notes.csv")
+}
+
+impl Default for VaultIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
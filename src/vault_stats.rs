@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use eframe::egui;
+use crate::file_manager::FileManager;
+use crate::i18n::{tr, tr_args};
+use crate::note_io;
+use crate::tags;
+use crate::writing_activity::WritingActivity;
+
+/// How many weeks of activity the dashboard charts, ending with the current week.
+const ACTIVITY_WEEKS: usize = 12;
+const LARGEST_NOTES: usize = 5;
+const TOP_TAGS: usize = 10;
+/// How many weeks the writing heatmap covers, ending with the current week.
+const HEATMAP_WEEKS: usize = 52;
+
+pub struct WeekActivity {
+    pub week_start: NaiveDate,
+    pub created: usize,
+    pub edited: usize,
+}
+
+/// Counts gathered from every note in the vault.
+pub struct VaultStats {
+    pub note_count: usize,
+    pub total_words: usize,
+    pub weeks: Vec<WeekActivity>,
+    /// Note names with their word counts, largest first.
+    pub largest_notes: Vec<(String, usize)>,
+    /// `#tags` with how many notes use them, most used first.
+    pub top_tags: Vec<(String, usize)>,
+    pub writing: WritingActivity,
+    pub today: NaiveDate,
+}
+
+impl VaultStats {
+    /// Reads every note from disk; slow for large vaults, so run it off the UI thread.
+    pub fn compute(file_manager: &FileManager, today: NaiveDate) -> Self {
+        let current_week = week_start(today);
+        let mut weeks: Vec<WeekActivity> = (0..ACTIVITY_WEEKS)
+            .rev()
+            .map(|weeks_ago| WeekActivity {
+                week_start: current_week - chrono::Days::new(7 * weeks_ago as u64),
+                created: 0,
+                edited: 0,
+            })
+            .collect();
+
+        let names = file_manager.list_note_names();
+        let note_count = names.len();
+        let mut total_words = 0;
+        let mut note_words = Vec::with_capacity(note_count);
+        let mut tag_counts: HashMap<String, usize> = HashMap::new();
+
+        for note in note_io::read_notes(file_manager, names) {
+            let content = note.content.unwrap_or_default();
+            let words = content.split_whitespace().count();
+            total_words += words;
+            note_words.push((note.name.clone(), words));
+
+            let mut note_tags = tags::note_tags(&content);
+            note_tags.sort();
+            note_tags.dedup();
+            for tag in note_tags {
+                *tag_counts.entry(tag).or_default() += 1;
+            }
+
+            let mut count_in_week = |date: Option<NaiveDate>, created: bool| {
+                let Some(date) = date else {
+                    return;
+                };
+                if let Some(week) = weeks.iter_mut().find(|w| w.week_start == week_start(date)) {
+                    if created {
+                        week.created += 1;
+                    } else {
+                        week.edited += 1;
+                    }
+                }
+            };
+            count_in_week(file_manager.get_note_created_time(&note.name).map(local_date), true);
+            count_in_week(note.modified.map(local_date), false);
+        }
+
+        note_words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        note_words.truncate(LARGEST_NOTES);
+
+        let mut top_tags: Vec<(String, usize)> = tag_counts.into_iter().collect();
+        top_tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_tags.truncate(TOP_TAGS);
+
+        Self {
+            note_count,
+            total_words,
+            weeks,
+            largest_notes: note_words,
+            top_tags,
+            writing: WritingActivity::parse(&file_manager.read_writing_activity()),
+            today,
+        }
+    }
+}
+
+fn local_date(time: std::time::SystemTime) -> NaiveDate {
+    DateTime::<Local>::from(time).date_naive()
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - chrono::Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+pub struct StatsDialog {
+    pub show_dialog: bool,
+    stats: Option<VaultStats>,
+    receiver: Option<mpsc::Receiver<VaultStats>>,
+}
+
+pub enum StatsAction {
+    None,
+    Refresh,
+}
+
+impl StatsDialog {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            stats: None,
+            receiver: None,
+        }
+    }
+
+    /// Shows the dialog and starts computing statistics on a background thread.
+    pub fn open(&mut self, file_manager: FileManager, ctx: &egui::Context) {
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let stats = VaultStats::compute(&file_manager, Local::now().date_naive());
+            if sender.send(stats).is_ok() {
+                ctx.request_repaint();
+            }
+        });
+        self.receiver = Some(receiver);
+        self.show_dialog = true;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> StatsAction {
+        let mut action = StatsAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        if let Some(stats) = self.receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.stats = Some(stats);
+            self.receiver = None;
+        }
+
+        let mut open = true;
+        egui::Window::new(tr("stats-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                match &self.stats {
+                    Some(stats) => render_stats(ui, stats),
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(tr("stats-computing"));
+                        });
+                    }
+                }
+
+                ui.separator();
+                let refreshing = self.receiver.is_some();
+                if ui.add_enabled(!refreshing, egui::Button::new(tr("stats-refresh"))).clicked() {
+                    action = StatsAction::Refresh;
+                }
+                if refreshing && self.stats.is_some() {
+                    ui.spinner();
+                }
+            });
+
+        if !open {
+            self.show_dialog = false;
+            self.receiver = None;
+        }
+
+        action
+    }
+}
+
+fn render_stats(ui: &mut egui::Ui, stats: &VaultStats) {
+    egui::Grid::new("stats_totals_grid").num_columns(2).show(ui, |ui| {
+        ui.label(tr("stats-notes"));
+        ui.strong(stats.note_count.to_string());
+        ui.end_row();
+        ui.label(tr("stats-words"));
+        ui.strong(stats.total_words.to_string());
+        ui.end_row();
+    });
+
+    ui.separator();
+    ui.label(egui::RichText::new(tr("stats-activity")).strong());
+    render_activity_chart(ui, &stats.weeks);
+
+    ui.separator();
+    ui.label(egui::RichText::new(tr("stats-writing")).strong());
+    render_writing_heatmap(ui, &stats.writing, stats.today);
+    ui.horizontal(|ui| {
+        let today = stats.writing.words_on(stats.today);
+        ui.label(tr_args("stats-words-today", &[("words", &today)]));
+        ui.separator();
+        ui.label(tr_args("stats-current-streak", &[("days", &stats.writing.current_streak(stats.today))]));
+        ui.separator();
+        ui.label(tr_args("stats-longest-streak", &[("days", &stats.writing.longest_streak())]));
+    });
+
+    ui.separator();
+    ui.horizontal_top(|ui| {
+        ui.vertical(|ui| {
+            ui.set_width(240.0);
+            ui.label(egui::RichText::new(tr("stats-largest-notes")).strong());
+            for (name, words) in &stats.largest_notes {
+                ui.label(tr_args("stats-note-words", &[("note", name), ("words", words)]));
+            }
+        });
+        ui.vertical(|ui| {
+            ui.label(egui::RichText::new(tr("stats-top-tags")).strong());
+            if stats.top_tags.is_empty() {
+                ui.weak(tr("stats-no-tags"));
+            }
+            for (tag, count) in &stats.top_tags {
+                ui.label(tr_args("stats-tag-count", &[("tag", tag), ("count", count)]));
+            }
+        });
+    });
+}
+
+/// Paired bars per week: created notes in the accent color, edited notes dimmed beside them.
+fn render_activity_chart(ui: &mut egui::Ui, weeks: &[WeekActivity]) {
+    let height = 100.0;
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height + 16.0), egui::Sense::hover());
+    if !ui.is_rect_visible(rect) || weeks.is_empty() {
+        return;
+    }
+
+    let painter = ui.painter();
+    let max = weeks.iter().map(|w| w.created.max(w.edited)).max().unwrap_or(0).max(1) as f32;
+    let slot = rect.width() / weeks.len() as f32;
+    let bar_width = (slot / 3.0).min(14.0);
+    let created_color = ui.visuals().selection.bg_fill;
+    let edited_color = ui.visuals().widgets.inactive.bg_fill;
+    let baseline = rect.top() + height;
+
+    for (index, week) in weeks.iter().enumerate() {
+        let center = rect.left() + slot * (index as f32 + 0.5);
+        for (count, color, offset) in [(week.created, created_color, -bar_width), (week.edited, edited_color, 0.0)] {
+            let bar_height = height * count as f32 / max;
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(center + offset, baseline - bar_height),
+                egui::pos2(center + offset + bar_width, baseline),
+            );
+            painter.rect_filled(bar, 2.0, color);
+        }
+        painter.text(
+            egui::pos2(center, baseline + 2.0),
+            egui::Align2::CENTER_TOP,
+            week.week_start.format("%m-%d").to_string(),
+            egui::FontId::proportional(9.0),
+            ui.visuals().weak_text_color(),
+        );
+    }
+
+    ui.horizontal(|ui| {
+        ui.colored_label(created_color, "■");
+        ui.label(tr("stats-created"));
+        ui.colored_label(edited_color, "■");
+        ui.label(tr("stats-edited"));
+    });
+}
+
+/// A square per day, a column per week, shaded by how many words were written that day.
+fn render_writing_heatmap(ui: &mut egui::Ui, writing: &WritingActivity, today: NaiveDate) {
+    let gap = 2.0;
+    let cell = ((ui.available_width() / HEATMAP_WEEKS as f32) - gap).clamp(4.0, 12.0);
+    let step = cell + gap;
+    let size = egui::vec2(step * HEATMAP_WEEKS as f32, step * 7.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    if !ui.is_rect_visible(rect) {
+        return;
+    }
+
+    let first_day = week_start(today) - chrono::Days::new(7 * (HEATMAP_WEEKS as u64 - 1));
+    let max = first_day
+        .iter_days()
+        .take_while(|date| *date <= today)
+        .map(|date| writing.words_on(date))
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+    let empty_color = ui.visuals().widgets.inactive.bg_fill;
+    let written_color = ui.visuals().selection.bg_fill;
+    let day_rect = |index: usize| {
+        let min = rect.min + egui::vec2((index / 7) as f32 * step, (index % 7) as f32 * step);
+        egui::Rect::from_min_size(min, egui::vec2(cell, cell))
+    };
+
+    let painter = ui.painter();
+    let mut hovered = None;
+    for (index, date) in first_day.iter_days().take_while(|date| *date <= today).enumerate() {
+        let words = writing.words_on(date);
+        // Four shades, so a single long day doesn't wash out the rest.
+        let color = if words == 0 {
+            empty_color
+        } else {
+            let level = (words as f32 / max * 4.0).ceil().clamp(1.0, 4.0);
+            empty_color.lerp_to_gamma(written_color, level / 4.0)
+        };
+        let day = day_rect(index);
+        painter.rect_filled(day, 2.0, color);
+        if response.hover_pos().is_some_and(|pos| day.expand(gap / 2.0).contains(pos)) {
+            hovered = Some((date, words));
+        }
+    }
+
+    if let Some((date, words)) = hovered {
+        response.on_hover_text(tr_args("stats-day-words", &[("date", &date.format("%Y-%m-%d")), ("words", &words)]));
+    }
+}
+
+impl Default for StatsDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::config::Config;
+use crate::folder_picker::FolderPicker;
+use crate::i18n::tr;
+
+pub enum VaultAction {
+    None,
+    Open(PathBuf),
+    /// Add a folder to the list and open it.
+    Add(PathBuf),
+    /// Take a folder off the list; its notes are left alone.
+    Remove(PathBuf),
+}
+
+/// Lists the notes folders the app can switch between, to open, add, or forget them.
+pub struct VaultSwitcher {
+    pub show_dialog: bool,
+    new_folder: String,
+    folder_picker: FolderPicker,
+}
+
+impl VaultSwitcher {
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            new_folder: String::new(),
+            folder_picker: FolderPicker::new("vault_folder_picker"),
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, vaults: &[PathBuf], active: &Path) -> VaultAction {
+        let mut action = VaultAction::None;
+
+        if !self.show_dialog {
+            return action;
+        }
+
+        let mut open = true;
+        egui::Window::new(tr("vaults-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("vaults_grid").num_columns(3).striped(true).show(ui, |ui| {
+                    for folder in vaults {
+                        let is_active = folder == active;
+                        ui.vertical(|ui| {
+                            ui.strong(Config::vault_name(folder));
+                            ui.weak(folder.display().to_string());
+                        });
+                        if is_active {
+                            ui.label(tr("vaults-active"));
+                        } else if ui.button(tr("vaults-open")).clicked() {
+                            action = VaultAction::Open(folder.clone());
+                        }
+                        let remove = ui.add_enabled(!is_active, egui::Button::new(tr("vaults-remove")));
+                        if remove.on_hover_text(tr("vaults-remove-hint")).clicked() {
+                            action = VaultAction::Remove(folder.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.separator();
+
+                ui.label(tr("vaults-add-label"));
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_folder)
+                            .hint_text(tr("vaults-add-hint"))
+                            .desired_width(ui.available_width() - 160.0),
+                    );
+                    if ui.button(tr("settings-browse")).clicked() {
+                        self.folder_picker.open(active.parent().unwrap_or(active));
+                    }
+                    let folder = self.new_folder.trim();
+                    if ui.add_enabled(!folder.is_empty(), egui::Button::new(tr("vaults-add"))).clicked() {
+                        action = VaultAction::Add(PathBuf::from(folder));
+                        self.new_folder.clear();
+                    }
+                });
+            });
+
+        if let Some(folder) = self.folder_picker.render(ctx) {
+            self.new_folder = folder.display().to_string();
+        }
+        if !open {
+            self.show_dialog = false;
+            self.folder_picker.show_dialog = false;
+        }
+
+        action
+    }
+}
+
+impl Default for VaultSwitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,236 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::config::GitAutocommit;
+use crate::file_manager::{HISTORY_DIR, NOTE_VIEWS_FILE, SEARCH_INDEX_FILE, TRASH_DIR, WRITING_ACTIVITY_FILE};
+
+/// How long saves have to pause before `AfterSaving` commits them, so a burst of typing ends up
+/// in one commit rather than dozens.
+const SAVE_SETTLE: Duration = Duration::from_secs(30);
+
+/// A commit that changed a note.
+pub struct NoteCommit {
+    pub hash: String,
+    /// When it was committed, as `YYYY-MM-DD HH:MM`.
+    pub date: String,
+    pub message: String,
+    /// The note's file at that commit, from the top of the repository, since notes can be
+    /// renamed.
+    pub path: String,
+}
+
+/// Runs git in `dir` and returns its output, or what went wrong. Paths in the output are left
+/// unquoted so notes with accented names can be found by them.
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "git was not found; install it to use version control".to_string(),
+            _ => format!("could not run git: {}", e),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args.first().unwrap_or(&""), stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether the folder is inside a git working tree.
+pub fn is_repository(dir: &Path) -> bool {
+    git(dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|output| output.trim() == "true")
+}
+
+/// Makes the notes folder a repository and commits the notes as they are.
+fn init(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+    git(dir, &["init", "-q"])?;
+    // The app's caches change constantly, and the history and trash duplicate what git keeps.
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        let ignored = format!(
+            "/{}\n/{}\n/{}\n/{}/\n/{}/\n",
+            SEARCH_INDEX_FILE, NOTE_VIEWS_FILE, WRITING_ACTIVITY_FILE, HISTORY_DIR, TRASH_DIR
+        );
+        fs::write(&gitignore, ignored).map_err(|e| format!("Failed to write '{}': {}", gitignore.display(), e))?;
+    }
+    commit_all(dir, Some("Start tracking notes")).map(|_| ())
+}
+
+/// Commits every change in the notes folder, with `message` or one describing the changes.
+/// Returns the commit's subject, or `None` when there was nothing to commit.
+fn commit_all(dir: &Path, message: Option<&str>) -> Result<Option<String>, String> {
+    git(dir, &["add", "-A", "--", "."])?;
+    let changes = git(dir, &["diff", "--cached", "--name-status", "--", "."])?;
+    if changes.trim().is_empty() {
+        return Ok(None);
+    }
+    let message = message.map_or_else(|| commit_message(&changes), str::to_string);
+    git(dir, &["commit", "-q", "-m", &message, "--", "."])?;
+    Ok(message.lines().next().map(str::to_string))
+}
+
+/// "Update Ideas", or "Update 3 notes" followed by a line per note, from `git diff --name-status`.
+fn commit_message(name_status: &str) -> String {
+    let note = |path: &str| path.strip_suffix(".md").unwrap_or(path).to_string();
+    let changes: Vec<String> = name_status
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let status = fields.next()?;
+            let path = fields.next()?;
+            Some(match status.chars().next()? {
+                'A' => format!("Add {}", note(path)),
+                'D' => format!("Delete {}", note(path)),
+                'R' => format!("Rename {} to {}", note(path), note(fields.next()?)),
+                _ => format!("Update {}", note(path)),
+            })
+        })
+        .collect();
+    match changes.as_slice() {
+        [only] => only.clone(),
+        _ => format!("Update {} notes\n\n{}", changes.len(), changes.join("\n")),
+    }
+}
+
+/// The commits that changed a note's file, given relative to `dir`, newest first, following it
+/// across renames.
+pub fn note_log(dir: &Path, file: &str) -> Result<Vec<NoteCommit>, String> {
+    let output = git(
+        dir,
+        &["log", "--follow", "--format=%x1e%h%x1f%ad%x1f%s", "--date=format:%Y-%m-%d %H:%M", "--name-only", "--", file],
+    )?;
+    Ok(output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut header = lines.next()?.split('\x1f');
+            let (hash, date, message) = (header.next()?, header.next()?, header.next()?);
+            let path = lines.find(|line| !line.is_empty())?;
+            Some(NoteCommit {
+                hash: hash.to_string(),
+                date: date.to_string(),
+                message: message.to_string(),
+                path: path.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// A note's content as of a commit.
+pub fn show(dir: &Path, commit: &NoteCommit) -> Result<String, String> {
+    git(dir, &["show", &format!("{}:{}", commit.hash, commit.path)])
+}
+
+/// Commits the notes folder when `git_autocommit` says to, on a background thread so a slow
+/// repository doesn't stall typing.
+pub struct VersionControl {
+    dir: PathBuf,
+    is_repository: bool,
+    running: Option<Receiver<Result<Option<String>, String>>>,
+    /// The last change to the notes a commit has been started for.
+    committed_change: Option<Instant>,
+    last_commit: Instant,
+    /// The subject and time of the last commit made this session.
+    last_commit_summary: Option<String>,
+}
+
+impl VersionControl {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            is_repository: is_repository(dir),
+            running: None,
+            committed_change: None,
+            last_commit: Instant::now(),
+            last_commit_summary: None,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn is_repository(&self) -> bool {
+        self.is_repository
+    }
+
+    pub fn last_commit_summary(&self) -> Option<&str> {
+        self.last_commit_summary.as_deref()
+    }
+
+    /// Turns the notes folder into a repository with a first commit.
+    pub fn enable(&mut self) -> Result<(), String> {
+        init(&self.dir)?;
+        self.is_repository = true;
+        Ok(())
+    }
+
+    /// Starts a commit of everything changed up to `last_change`, unless one is running.
+    pub fn commit_now(&mut self, last_change: Option<Instant>) {
+        if !self.is_repository || self.running.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let dir = self.dir.clone();
+        thread::spawn(move || {
+            sender.send(commit_all(&dir, None)).ok();
+        });
+        self.running = Some(receiver);
+        self.committed_change = last_change;
+        self.last_commit = Instant::now();
+    }
+
+    /// Collects the result of a finished commit. Returns an error to show, if it failed.
+    pub fn poll(&mut self, ctx: &egui::Context) -> Option<String> {
+        let mut error = None;
+        if let Some(running) = &self.running {
+            match running.try_recv() {
+                Ok(Ok(summary)) => {
+                    if let Some(summary) = summary {
+                        self.last_commit_summary = Some(format!("{} ({})", summary, chrono::Local::now().format("%H:%M")));
+                    }
+                    self.running = None;
+                }
+                Ok(Err(e)) => {
+                    error = Some(format!("Failed to commit the notes: {}", e));
+                    self.running = None;
+                }
+                Err(TryRecvError::Empty) => ctx.request_repaint_after(Duration::from_millis(250)),
+                Err(TryRecvError::Disconnected) => self.running = None,
+            }
+        }
+        error
+    }
+
+    /// Whether `git_autocommit` calls for a commit now, given when the notes last changed.
+    /// Otherwise asks for a repaint when one will be.
+    pub fn commit_due(&self, ctx: &egui::Context, mode: GitAutocommit, minutes: u32, last_change: Option<Instant>) -> bool {
+        let Some(changed) = last_change.filter(|_| self.is_repository && self.running.is_none()) else {
+            return false;
+        };
+        if self.committed_change == Some(changed) {
+            return false;
+        }
+        let wait = match mode {
+            GitAutocommit::Off => return false,
+            GitAutocommit::AfterSaving => SAVE_SETTLE.saturating_sub(changed.elapsed()),
+            GitAutocommit::Interval => {
+                Duration::from_secs(u64::from(minutes.max(1)) * 60).saturating_sub(self.last_commit.elapsed())
+            }
+        };
+        if !wait.is_zero() {
+            ctx.request_repaint_after(wait);
+        }
+        wait.is_zero()
+    }
+}
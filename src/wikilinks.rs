@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::uri_scheme;
+
+/// `[[Note]]` or `[[Note|label]]`.
+fn wikilink_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\[\[([^\[\]|\n]+)(?:\|([^\[\]\n]+))?\]\]").expect("wikilink regex is valid"))
+}
+
+/// `text` with each `[[Note]]` outside code turned into a markdown link to the note, so the
+/// preview shows it as a link that opens the note, or the note with that alias.
+pub fn to_note_links(text: &str) -> Cow<'_, str> {
+    if !text.contains("[[") {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut in_code_block = false;
+    for line in text.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        if in_code_block || !line.contains("[[") {
+            result.push_str(line);
+            continue;
+        }
+        let mut copied = 0;
+        for capture in wikilink_regex().captures_iter(line) {
+            let (Some(link), Some(target)) = (capture.get(0), capture.get(1)) else {
+                continue;
+            };
+            // Inside an inline code span when an odd number of backticks come before it.
+            if line[..link.start()].matches('`').count() % 2 == 1 {
+                continue;
+            }
+            let target = target.as_str().trim();
+            let label = capture.get(2).map_or(target, |label| label.as_str().trim());
+            result.push_str(&line[copied..link.start()]);
+            result.push_str(&format!("[{}]({})", label, uri_scheme::note_link(target)));
+            copied = link.end();
+        }
+        result.push_str(&line[copied..]);
+    }
+    Cow::Owned(result)
+}
@@ -0,0 +1,38 @@
+use crate::front_matter;
+
+/// Front matter key holding a note's word-count goal, e.g. `word_goal: 1500`.
+const GOAL_KEY: &str = "word_goal";
+
+/// The note's word-count goal from its front matter.
+pub fn word_goal(content: &str) -> Option<usize> {
+    front_matter::value(content, GOAL_KEY)?.parse().ok()
+}
+
+/// Words in the note, leaving out the front matter.
+pub fn count_words(content: &str) -> usize {
+    let body = front_matter::block(content).map_or(content, |(_, end)| &content[end..]);
+    body.split_whitespace().count()
+}
+
+/// `content` with its goal set to `goal`, or removed for `None`. Front matter is added when the
+/// note has none, and dropped when the goal was all it held.
+pub fn set_word_goal(content: &str, goal: Option<usize>) -> String {
+    let goal_line = goal.map(|goal| format!("{}: {}\n", GOAL_KEY, goal));
+    let Some((lines, end)) = front_matter::block(content) else {
+        return match goal_line {
+            Some(line) => format!("---\n{}---\n{}", line, content),
+            None => content.to_string(),
+        };
+    };
+
+    let mut kept: Vec<String> = content[lines]
+        .split_inclusive('\n')
+        .filter(|line| line.split_once(':').is_none_or(|(key, _)| key.trim() != GOAL_KEY))
+        .map(str::to_string)
+        .collect();
+    kept.extend(goal_line);
+    if kept.is_empty() {
+        return content[end..].to_string();
+    }
+    format!("---\n{}---\n{}", kept.concat(), &content[end..])
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Default, Serialize, Deserialize)]
+struct WritingActivityFile {
+    /// Words written on each day, keyed by `YYYY-MM-DD`.
+    #[serde(default)]
+    days: BTreeMap<String, usize>,
+}
+
+/// Words written on each day, counted as notes are saved and kept in the notes folder for the
+/// statistics' heatmap and streaks. Only words added count; deleting text doesn't take any away.
+#[derive(Default)]
+pub struct WritingActivity {
+    days: BTreeMap<NaiveDate, usize>,
+    /// Changed since it was loaded or last saved.
+    dirty: bool,
+}
+
+impl WritingActivity {
+    /// Reads activity saved by `serialize`. Anything unreadable just gives no activity.
+    pub fn parse(data: &str) -> Self {
+        let file: WritingActivityFile = toml::from_str(data).unwrap_or_default();
+        let days = file
+            .days
+            .into_iter()
+            .filter_map(|(date, words)| Some((NaiveDate::parse_from_str(&date, DATE_FORMAT).ok()?, words)))
+            .collect();
+        Self { days, dirty: false }
+    }
+
+    pub fn serialize(&self) -> String {
+        let days = self.days.iter().map(|(date, words)| (date.format(DATE_FORMAT).to_string(), *words)).collect();
+        toml::to_string(&WritingActivityFile { days }).unwrap_or_default()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Counts the words a save added to a note, going from `before` to `after`, towards `date`.
+    pub fn record_edit(&mut self, date: NaiveDate, before: &str, after: &str) {
+        let added = after.split_whitespace().count().saturating_sub(before.split_whitespace().count());
+        if added > 0 {
+            *self.days.entry(date).or_default() += added;
+            self.dirty = true;
+        }
+    }
+
+    pub fn words_on(&self, date: NaiveDate) -> usize {
+        self.days.get(&date).copied().unwrap_or(0)
+    }
+
+    /// Days in a row with writing, up to `today`. A day without writing yet doesn't break the
+    /// streak until it's over.
+    pub fn current_streak(&self, today: NaiveDate) -> usize {
+        let start = if self.words_on(today) > 0 { today } else { today.pred_opt().unwrap_or(today) };
+        std::iter::successors(Some(start), |date| date.pred_opt())
+            .take_while(|date| self.words_on(*date) > 0)
+            .count()
+    }
+
+    /// The most days in a row with writing.
+    pub fn longest_streak(&self) -> usize {
+        let mut longest = 0;
+        let mut streak = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for (&date, &words) in &self.days {
+            if words == 0 {
+                continue;
+            }
+            streak = if previous.and_then(|p| p.succ_opt()) == Some(date) { streak + 1 } else { 1 };
+            longest = longest.max(streak);
+            previous = Some(date);
+        }
+        longest
+    }
+}